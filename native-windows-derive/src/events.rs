@@ -107,11 +107,15 @@ pub struct ControlEvents {
 impl ControlEvents {
 
     pub fn with_capacity(partial: bool, cap: usize) -> ControlEvents {
-        let mut cache = HashMap::with_capacity(4);
+        let mut cache = HashMap::with_capacity(8);
         cache.insert(0, syn::parse_str("&evt_ui").unwrap());
         cache.insert(2, syn::parse_str("&_handle").unwrap());
         cache.insert(3, syn::parse_str("_evt").unwrap());
         cache.insert(4, syn::parse_str("&_evt_data").unwrap());
+        cache.insert(6, syn::parse_str("_evt_data.as_key()").unwrap());
+        cache.insert(7, syn::parse_str("_evt_data.as_mouse()").unwrap());
+        cache.insert(8, syn::parse_str("_evt_data.as_resize()").unwrap());
+        cache.insert(9, syn::parse_str("_evt_data.as_scroll()").unwrap());
 
         ControlEvents {
             partial,
@@ -313,7 +317,7 @@ fn find_events_attr(attrs: &[syn::Attribute]) -> Option<&syn::Attribute> {
 
 
 fn top_level_window(field: &syn::Field) -> bool {
-    static TOP_LEVEL: &'static [&'static str] = &["Window", "FancyWindow", "MessageWindow"];
+    static TOP_LEVEL: &'static [&'static str] = &["Window", "FancyWindow", "MessageWindow", "Dialog", "Wizard"];
 
     match &field.ty {
         syn::Type::Path(p) => {
@@ -334,7 +338,7 @@ fn map_callback_args(member: &syn::Ident, args: &Option<Punctuated<syn::Ident, T
         return p;
     }
 
-    let values = ["SELF", "CTRL", "HANDLE", "EVT", "EVT_DATA", "EVT_UI"];
+    let values = ["SELF", "CTRL", "HANDLE", "EVT", "EVT_DATA", "EVT_UI", "KEY_DATA", "MOUSE_DATA", "RESIZE_DATA", "SCROLL_DATA"];
     for a in args.as_ref().unwrap().iter() {
         let pos = values.iter().position(|v| &a == &v );
         match pos {
@@ -346,6 +350,7 @@ fn map_callback_args(member: &syn::Ident, args: &Option<Punctuated<syn::Ident, T
             Some(2) => { p.push(cache[&2].clone()); },
             Some(3) => { p.push(cache[&3].clone()); },
             Some(4) => { p.push(cache[&4].clone()); },
+            Some(i @ 6..=9) => { p.push(cache[&i].clone()); },
             Some(_) => { unreachable!(); }
             None => panic!("Unknown callback argument: {}. Should be one of those values: {:?}", a, values)
         }
@@ -358,7 +363,10 @@ fn map_callback_args(member: &syn::Ident, args: &Option<Punctuated<syn::Ident, T
 fn map_event_enum(ident: &syn::Ident) -> syn::Pat {
     let evt = ident.to_string();
     let pat = match &evt as &str {
-        "MousePressLeftUp" | "MousePressLeftDown" | "MousePressRightUp" | "MousePressRightDown" => {
+        "MousePressLeftUp" | "MousePressLeftDown" | "MousePressRightUp" | "MousePressRightDown" |
+        "MousePressMiddleUp" | "MousePressMiddleDown" | "MousePressX1Up" | "MousePressX1Down" | "MousePressX2Up" | "MousePressX2Down" |
+        "MousePressLeftDoubleClick" | "MousePressRightDoubleClick" | "MousePressMiddleDoubleClick" |
+        "MousePressX1DoubleClick" | "MousePressX2DoubleClick" => {
             format!("Event::OnMousePress(MousePressEvent::{})", evt)
         },
         "OnMousePress" => "Event::OnMousePress(_)".into(),
@@ -1,12 +1,13 @@
 use crate::shared::Parameters;
 
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct GridLayoutChild {
     pub col: u32,
     pub row: u32,
     pub col_span: u32,
-    pub row_span: u32
+    pub row_span: u32,
+    pub min_size: Option<syn::Expr>,
 }
 
 #[derive(Clone, Debug)]
@@ -16,11 +17,20 @@ pub struct FlexboxLayoutChild {
 }
 
 
+#[derive(Clone, Debug)]
+pub struct DockLayoutChild {
+    pub dock: syn::Expr,
+    pub param_names: Vec<syn::Ident>,
+    pub param_values: Vec<syn::Expr>,
+}
+
+
 #[derive(Debug)]
 pub enum LayoutChild {
     Init { field_name: String, params: Parameters },
     Grid(GridLayoutChild),
     Flexbox(FlexboxLayoutChild),
+    Dock(DockLayoutChild),
 }
 
 impl LayoutChild {
@@ -46,6 +56,8 @@ impl LayoutChild {
             *self = Self::parse_grid_layout_params(self);
         } else if parent_type == "FlexboxLayout" {
             *self = Self::parse_flexbox_layout_params(self);
+        } else if parent_type == "DockLayout" {
+            *self = Self::parse_dock_layout_params(self);
         } else {
             panic!("Unknown parent type: {:?}", parent_type);
         }
@@ -72,6 +84,7 @@ impl LayoutChild {
 
     fn parse_grid_layout_params(child: &mut LayoutChild) -> LayoutChild {
         let [mut col, mut row, mut col_span, mut row_span] = [0, 0, 1, 1];
+        let mut min_size = None;
 
         match child {
             LayoutChild::Init{ params: p, .. } => for p in p.params.iter() {
@@ -81,13 +94,14 @@ impl LayoutChild {
                     "row" => { row = Self::int_value(&p.e) },
                     "col_span" => { col_span = Self::int_value(&p.e) },
                     "row_span" => { row_span = Self::int_value(&p.e) },
+                    "min_size" => { min_size = Some(p.e.clone()) },
                     _ => {}
                 }
             },
             _ => panic!("Called parse on a non-Init child layout")
         };
 
-        LayoutChild::Grid( GridLayoutChild { col, col_span, row, row_span } )
+        LayoutChild::Grid( GridLayoutChild { col, col_span, row, row_span, min_size } )
     }
 
     fn parse_flexbox_layout_params(child: &mut LayoutChild) -> LayoutChild {
@@ -110,6 +124,36 @@ impl LayoutChild {
         LayoutChild::Flexbox( FlexboxLayoutChild { param_names, param_values } )
     }
 
+    fn parse_dock_layout_params(child: &mut LayoutChild) -> LayoutChild {
+        let mut dock = None;
+        let mut param_names = Vec::with_capacity(2);
+        let mut param_values = Vec::with_capacity(2);
+
+        match child {
+            LayoutChild::Init{ params: p, field_name } => {
+                for p in p.params.iter() {
+                    if &p.ident == "layout" {
+                        continue;
+                    } else if &p.ident == "dock" {
+                        dock = Some(p.e.clone());
+                        continue;
+                    }
+
+                    let child_name = format!("child_{}", &p.ident);
+                    param_names.push(syn::Ident::new(&child_name, p.ident.span()));
+                    param_values.push(p.e.clone());
+                }
+
+                if dock.is_none() {
+                    panic!("Missing `dock` parameter in the dock layout item of field \"{}\"", field_name);
+                }
+            },
+            _ => panic!("Called parse on a non-Init child layout")
+        }
+
+        LayoutChild::Dock( DockLayoutChild { dock: dock.unwrap(), param_names, param_values } )
+    }
+
     fn int_value(expr: &syn::Expr) -> u32 {
         match expr {
             syn::Expr::Lit(lit) => 
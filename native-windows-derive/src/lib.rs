@@ -164,6 +164,12 @@ That's very limiting. For example, if the same callback is used by two different
  - **HANDLE**: Sends the handle of the control. `&ControlHandle`
  - **EVT**: Sends the event that was triggered. `&Event`
  - **EVT_DATA**: Sends the data of the event that was triggered. `&EventData`
+ - **KEY_DATA**: Sends the key data of a `OnKeyPress` or `OnKeyRelease` event. `Option<&KeyData>`
+ - **MOUSE_DATA**: Sends the mouse data of a `OnMousePress`, `OnMouseMove`, `OnMouseHover`, `OnMouseLeave` or wheel event. `Option<&MouseData>`
+ - **RESIZE_DATA**: Sends the new size of a `OnResize`, `OnWindowMaximize` or `OnWindowMinimize` event. `Option<&ResizeData>`
+ - **SCROLL_DATA**: Sends the scroll request of a `OnHorizontalScroll` or `OnVerticalScroll` event. `Option<&ScrollData>`
+
+The typed data identifiers send `None` if the event does not carry this type of data.
 
 It's also possible to not use any parameters, ex: `TestApp::callback1()`. 
 
@@ -199,6 +205,33 @@ Under the hood, both these attribute work the same way as `nwg_control`. `nwg_la
 
 NWD cannot guess the parent of layout items.
 
+For `GridLayout`, the sizing policy of the columns and rows are set with `column_sizes` and `row_sizes` in `nwg_layout`,
+and the minimum size used by the `GridLayoutSize::Auto` lines is set with `min_size` in `nwg_layout_item`:
+
+```
+#[nwg_layout(parent: window, column_sizes: &[GridLayoutSize::Auto, GridLayoutSize::Stretch(1)])]
+form_layout: nwg::GridLayout,
+
+#[nwg_control(text: "Name")]
+#[nwg_layout_item(layout: form_layout, col: 0, row: 0, min_size: [80, 25])]
+name_label: nwg::Label,
+```
+
+For `DockLayout`, the `dock` parameter of `nwg_layout_item` is required. The other parameters (`size`, `min_size`) set the values of the current child:
+
+```
+#[nwg_layout(parent: window)]
+shell_layout: nwg::DockLayout,
+
+#[nwg_control]
+#[nwg_layout_item(layout: shell_layout, dock: DockStyle::Left, size: 200, min_size: [100, 0])]
+tree: nwg::TreeView,
+
+#[nwg_control]
+#[nwg_layout_item(layout: shell_layout, dock: DockStyle::Fill)]
+content: nwg::TextBox,
+```
+
 ## Partials
 
 Use the `nwg_partial` attribute to instance a partial from a struct field:
@@ -1,14 +1,15 @@
 use quote::{ToTokens};
-use crate::layouts::{LayoutChild, FlexboxLayoutChild, GridLayoutChild, layout_parameters};
+use crate::layouts::{LayoutChild, FlexboxLayoutChild, GridLayoutChild, DockLayoutChild, layout_parameters};
 use crate::events::ControlEvents;
 use crate::shared::Parameters;
 
 const TOP_LEVEL: &'static [&'static str] = &[
-    "Window", "MessageWindow", "ExternCanvas"
+    "Window", "MessageWindow", "ExternCanvas", "Dialog", "Wizard"
 ];
 
 const AUTO_PARENT: &'static [&'static str] = &[
-    "Window", "TabsContainer", "Tab", "MessageWindow", "ExternCanvas"
+    "Window", "TabsContainer", "Tab", "MessageWindow", "ExternCanvas", "Dialog",
+    "Wizard", "WizardPage"
 ];
 
 
@@ -372,15 +373,24 @@ impl<'a> ToTokens for NwgUiLayouts<'a> {
                 let id = &c.id;
 
                 let item_tk = match &c.layout {
-                    Some(LayoutChild::Grid( GridLayoutChild {col, row, col_span, row_span} )) => 
+                    Some(LayoutChild::Grid( GridLayoutChild {col, row, col_span, row_span, min_size: None} )) => 
                         quote! { 
                             child_item(GridLayoutItem::new(&ui.#id, #col, #row, #col_span, #row_span))
                         },
+                    Some(LayoutChild::Grid( GridLayoutChild {col, row, col_span, row_span, min_size: Some(min_size)} )) => 
+                        quote! { 
+                            child_item(GridLayoutItem::new(&ui.#id, #col, #row, #col_span, #row_span).min_size(#min_size))
+                        },
                     Some(LayoutChild::Flexbox( FlexboxLayoutChild { param_names, param_values } )) => 
                         quote! { 
                             child(&ui.#id)
                             #(.#param_names(#param_values))*
                         },
+                    Some(LayoutChild::Dock( DockLayoutChild { dock, param_names, param_values } )) => 
+                        quote! { 
+                            child(#dock, &ui.#id)
+                            #(.#param_names(#param_values))*
+                        },
                     Some(LayoutChild::Init{ field_name, .. }) => panic!("Unmatched layout item for field \"{}\", Did you forget the `layout` parameter?", field_name),
                     None => panic!("Unfiltered layout item")
                 };
@@ -0,0 +1,98 @@
+/*!
+    An application that sorts a ListView when one of its column header is clicked.
+
+    Requires the following features: `cargo run --example list_view_sort_d --features "list-view"`
+*/
+
+
+extern crate native_windows_gui as nwg;
+extern crate native_windows_derive as nwd;
+
+use nwd::NwgUi;
+use nwg::NativeUi;
+use nwg::ListViewColumnSortArrow as Arrow;
+
+
+#[derive(Default, NwgUi)]
+pub struct ListViewSortApp {
+    #[nwg_control(size: (400, 250), position: (300, 300), title: "ListView - Sort by column")]
+    #[nwg_events( OnWindowClose: [ListViewSortApp::exit], OnInit: [ListViewSortApp::load_data])]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window)]
+    layout: nwg::GridLayout,
+
+    #[nwg_control(list_style: nwg::ListViewStyle::Detailed, focus: true, column_header: true,
+        ex_flags: nwg::ListViewExFlags::GRID | nwg::ListViewExFlags::FULL_ROW_SELECT,
+    )]
+    #[nwg_layout_item(layout: layout, col: 0, row: 0)]
+    #[nwg_events( OnListViewColumnClick: [ListViewSortApp::sort(SELF, EVT_DATA)] )]
+    list_view: nwg::ListView,
+}
+
+impl ListViewSortApp {
+
+    fn load_data(&self) {
+        let list = &self.list_view;
+
+        for &column in &["Name", "Price (USD $)", "Quantity"] {
+            list.insert_column(column);
+        }
+
+        let data: &[&[&str]] = &[
+            &["Banana", "10.0", "1000"],
+            &["Apple", "2.0", "345"],
+            &["Kiwi", "5.0", "194"],
+            &["Oranges", "5.0", "15"],
+            &["Lettuce", "1.0", "257"],
+        ];
+
+        for d in data {
+            list.insert_items_row(None, d);
+        }
+    }
+
+    fn sort(&self, data: &nwg::EventData) {
+        let column = match data.as_list_view_column_index() {
+            Some(column) => column,
+            None => return
+        };
+
+        let list = &self.list_view;
+        let order = match list.column_sort_arrow(column) {
+            Some(Arrow::Ascending) => Arrow::Descending,
+            _ => Arrow::Ascending
+        };
+
+        // The name column is sorted by text, the others by value
+        list.sort_items_by_text(column, |a, b| {
+            let ordering = match column {
+                0 => a.cmp(b),
+                _ => a.parse::<f64>().unwrap_or(0.0).partial_cmp(&b.parse::<f64>().unwrap_or(0.0)).unwrap()
+            };
+
+            match order {
+                Arrow::Ascending => ordering,
+                Arrow::Descending => ordering.reverse(),
+            }
+        });
+
+        for c in 0..list.column_len() {
+            list.set_column_sort_arrow(c, if c == column { Some(order) } else { None });
+        }
+    }
+
+    fn exit(&self) {
+        nwg::stop_thread_dispatch();
+    }
+
+}
+
+fn main() {
+    nwg::init().expect("Failed to init Native Windows GUI");
+    nwg::Font::set_global_family("Segoe UI").expect("Failed to set default font");
+
+    let _app = ListViewSortApp::build_ui(Default::default()).expect("Failed to build UI");
+
+    nwg::dispatch_thread_events();
+}
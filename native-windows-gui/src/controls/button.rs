@@ -58,6 +58,9 @@ Button is not behind any features.
   * `MousePress(_)`: Generic mouse press events on the button
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
 
 ```rust
 use native_windows_gui as nwg;
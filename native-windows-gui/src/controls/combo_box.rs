@@ -54,6 +54,9 @@ Requires the `combobox` feature.
   * `MousePress(_)`: Generic mouse press events on the checkbox
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
 
 
 ```rust
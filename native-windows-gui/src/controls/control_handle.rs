@@ -27,7 +27,10 @@ pub enum ControlHandle {
     Timer(HWND, u32),
 
     /// System tray control
-    SystemTray(HWND)
+    SystemTray(HWND),
+
+    /// (Parent window / Hotkey ID). Global hotkey control
+    Hotkey(HWND, u32)
 }
 
 impl ControlHandle {
@@ -99,6 +102,13 @@ impl ControlHandle {
         }
     }
 
+    pub fn hotkey(&self) -> Option<(HWND, u32)> {
+        match self {
+            &ControlHandle::Hotkey(h, i) => Some((h, i)),
+            _ => None,
+        }
+    }
+
 }
 
 
@@ -152,6 +162,11 @@ impl PartialEq for ControlHandle {
             &ControlHandle::SystemTray(hwnd1) => match other {
                 &ControlHandle::SystemTray(hwnd2) => hwnd1 == hwnd2,
                 _ => false
+            },
+            // Global hotkey
+            &ControlHandle::Hotkey(hwnd1, id1) => match other {
+                &ControlHandle::Hotkey(hwnd2, id2) => hwnd1 == hwnd2 && id1 == id2,
+                _ => false
             }
         }
     }
@@ -0,0 +1,446 @@
+/*!
+    A top level window that runs modally over its owner.
+*/
+
+use winapi::um::winuser::{WS_CLIPCHILDREN, WS_CAPTION, WS_SYSMENU, WS_POPUP, WS_EX_DLGMODALFRAME, WS_EX_TOPMOST};
+use winapi::shared::windef::HWND;
+
+use crate::win32::window_helper as wh;
+use crate::win32::base_helper::check_hwnd;
+use crate::{NwgError, Icon, WindowFlags};
+use super::{ControlBase, ControlHandle};
+use std::cell::{Cell, RefCell};
+use std::ptr;
+
+const NOT_BOUND: &'static str = "Dialog is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: Dialog handle is not HWND!";
+
+
+/// The default result type of a `Dialog`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DialogResult {
+    Ok,
+    Cancel,
+}
+
+/**
+    A top level window that runs modally over its owner. The dialog is created hidden and is displayed by `run_modal`.
+
+    `run_modal` disables the owner window, shows the dialog and dispatches the events of the current thread until
+    `end_dialog` is called or until the dialog is closed. The value passed to `end_dialog` is returned to the caller.
+    The result type is a parameter of the dialog (`DialogResult` by default).
+
+    While the dialog is running, the Enter key clicks the default button and the Escape key clicks the cancel button (see
+    `set_default_button` and `set_cancel_button`). Without a cancel button, Escape closes the dialog like the close button.
+
+    Dialogs are top level windows so they accept the same children and raise the same events as `Window`.
+
+    Requires the `dialog` feature.
+
+    **Builder parameters:**
+      * `flags`: The window flags. See `WindowFlags`. Defaults to `WindowFlags::WINDOW`.
+      * `title`: The text in the dialog title bar
+      * `size`: The default size of the dialog
+      * `position`: The default position of the dialog in the desktop
+      * `icon`: The dialog icon
+      * `topmost`: If the dialog should always be on top of other system window
+      * `parent`: The owner of the dialog. The owner is disabled while the dialog is running.
+
+    **Control events:**
+      * `OnInit`: The dialog was created
+      * `OnWindowClose`: The user tries to close the dialog (the close button or the Escape key)
+      * `MousePress(_)`: Generic mouse press events on the dialog
+      * `OnMouseMove`: Generic mouse mouse event
+      * `OnMouseWheel`: Generic mouse wheel event
+      * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+      * `OnMouseHover`: When the mouse rests over the dialog
+      * `OnMouseLeave`: When the mouse leaves the dialog
+      * `OnPaint`: Generic on paint event
+      * `OnKeyPress`: Generic key press
+      * `OnKeyRelease`: Generic ket release
+      * `OnResize`: When the dialog is resized
+      * `OnMove`: When the dialog is moved by the user
+
+```rust
+use native_windows_gui as nwg;
+
+#[derive(Copy, Clone)]
+enum Choice { Save, Discard }
+
+fn ask(dialog: &nwg::Dialog<Choice>, save: &nwg::Button) -> Option<Choice> {
+    dialog.set_default_button(Some(save));
+    dialog.run_modal()
+}
+
+fn on_save(dialog: &nwg::Dialog<Choice>) {
+    dialog.end_dialog(Choice::Save);
+}
+```
+*/
+pub struct Dialog<T: 'static = DialogResult> {
+    pub handle: ControlHandle,
+    default_button: Cell<Option<ControlHandle>>,
+    cancel_button: Cell<Option<ControlHandle>>,
+    result: RefCell<Option<T>>,
+    running: Cell<bool>,
+}
+
+impl Dialog {
+
+    pub fn builder<'a>() -> DialogBuilder<'a> {
+        DialogBuilder {
+            title: "New Dialog",
+            size: (300, 200),
+            position: (300, 300),
+            topmost: false,
+            flags: None,
+            icon: None,
+            parent: None
+        }
+    }
+
+}
+
+impl<T: 'static> Dialog<T> {
+
+    /**
+        Show the dialog and dispatch the events of the current thread until the dialog ends.
+        The owner of the dialog (if any) is disabled until the method returns.
+
+        Returns the value passed to `end_dialog`, or `None` if the dialog was closed without a result.
+        If `stop_thread_dispatch` is called while the dialog is running, the dialog ends and the outer
+        event loop stops as well.
+
+        Panics if the dialog is already running.
+    */
+    pub fn run_modal(&self) -> Option<T> {
+        use crate::win32::dispatch_modal_events;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if self.running.replace(true) {
+            panic!("Dialog is already running");
+        }
+
+        *self.result.borrow_mut() = None;
+
+        let owner = wh::get_window_parent(handle);
+        let disable_owner = !owner.is_null() && unsafe { wh::get_window_enabled(owner) };
+
+        unsafe {
+            if disable_owner {
+                wh::set_window_enabled(owner, false);
+            }
+
+            wh::set_window_visibility(handle, true);
+            wh::set_focus(handle);
+        }
+
+        dispatch_modal_events(
+            |msg| self.translate_dialog_key(handle, msg),
+            || self.result.borrow().is_some() || !wh::window_valid(handle) || unsafe { !wh::get_window_visibility(handle) }
+        );
+
+        // The owner must be enabled before the dialog is hidden, otherwise another application gets activated
+        unsafe {
+            if disable_owner {
+                wh::set_window_enabled(owner, true);
+            }
+
+            if wh::window_valid(handle) {
+                wh::set_window_visibility(handle, false);
+            }
+
+            if !owner.is_null() {
+                wh::set_focus(owner);
+            }
+        }
+
+        self.running.set(false);
+        self.result.borrow_mut().take()
+    }
+
+    /// End the dialog. `run_modal` returns `result` once the current event is processed.
+    /// If the dialog is not running, the result is discarded by the next call to `run_modal`.
+    pub fn end_dialog(&self, result: T) {
+        *self.result.borrow_mut() = Some(result);
+    }
+
+    /// Return true if `run_modal` is currently running for this dialog
+    pub fn running(&self) -> bool {
+        self.running.get()
+    }
+
+    /// Set the button clicked by the Enter key. The button is drawn as the default button.
+    pub fn set_default_button<C: Into<ControlHandle>>(&self, button: Option<C>) {
+        use winapi::um::winuser::{BM_SETSTYLE, BS_PUSHBUTTON, BS_DEFPUSHBUTTON};
+
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        if let Some(hwnd) = self.default_button.get().and_then(|b| b.hwnd()) {
+            wh::send_message(hwnd, BM_SETSTYLE, BS_PUSHBUTTON as usize, 1);
+        }
+
+        let button = button.map(|b| b.into());
+        if let Some(hwnd) = button.and_then(|b| b.hwnd()) {
+            wh::send_message(hwnd, BM_SETSTYLE, BS_DEFPUSHBUTTON as usize, 1);
+        }
+
+        self.default_button.set(button);
+    }
+
+    /// Set the button clicked by the Escape key. Without a cancel button, Escape closes the dialog.
+    pub fn set_cancel_button<C: Into<ControlHandle>>(&self, button: Option<C>) {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.cancel_button.set(button.map(|b| b.into()));
+    }
+
+    /// Close the dialog as if the user clicked the X button.
+    pub fn close(&self) {
+        use winapi::um::winuser::WM_CLOSE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::post_message(handle, WM_CLOSE, 0, 0);
+    }
+
+    /// Return the icon of the dialog
+    pub fn icon(&self) -> Option<Icon> {
+        use winapi::um::winuser::WM_GETICON;
+        use winapi::um::winnt::HANDLE;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let handle = wh::send_message(handle, WM_GETICON, 0, 0);
+        if handle == 0 {
+            None
+        } else {
+            Some(Icon { handle: handle as HANDLE, owned: false })
+        }
+    }
+
+    /// Set the icon in the dialog
+    /// - icon: The new icon. If None, the icon is removed
+    pub fn set_icon(&self, icon: Option<&Icon>) {
+        use winapi::um::winuser::WM_SETICON;
+        use std::mem;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let image_handle = icon.map(|i| i.handle).unwrap_or(ptr::null_mut());
+        unsafe {
+            wh::send_message(handle, WM_SETICON, 0, mem::transmute(image_handle));
+        }
+    }
+
+    /// Return true if the control currently has the keyboard focus
+    pub fn focus(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_focus(handle) }
+    }
+
+    /// Set the keyboard focus on the dialog
+    pub fn set_focus(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_focus(handle); }
+    }
+
+    /// Return true if the dialog is visible to the user
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Return the size of the dialog
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Set the size of the dialog
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, true) }
+    }
+
+    /// Return the position of the dialog in the desktop
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Set the position of the dialog in the desktop
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Return the dialog title
+    pub fn text(&self) -> String {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_text(handle) }
+    }
+
+    /// Set the dialog title
+    pub fn set_text<'a>(&self, v: &'a str) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_text(handle, v) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        "NativeWindowsGuiWindow"
+    }
+
+    // Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_CAPTION | WS_SYSMENU
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        WS_CLIPCHILDREN | WS_POPUP
+    }
+
+    /// Handle the Enter and Escape keys pressed in the dialog. Returns true if the message was consumed.
+    fn translate_dialog_key(&self, handle: HWND, msg: &winapi::um::winuser::MSG) -> bool {
+        use winapi::um::winuser::{WM_KEYDOWN, WM_CLOSE, VK_RETURN, VK_ESCAPE, WM_GETDLGCODE, DLGC_WANTALLKEYS, BM_CLICK, GA_ROOT};
+        use crate::win32::backend::GetAncestor;
+
+        if msg.message != WM_KEYDOWN || msg.hwnd.is_null() || unsafe { GetAncestor(msg.hwnd, GA_ROOT) } != handle {
+            return false;
+        }
+
+        match msg.wParam as i32 {
+            VK_RETURN => {
+                // Multiline text controls use the Enter key
+                let code = wh::send_message(msg.hwnd, WM_GETDLGCODE, msg.wParam, msg as *const _ as isize);
+                if code & (DLGC_WANTALLKEYS as isize) != 0 {
+                    return false;
+                }
+
+                match self.default_button.get().and_then(|b| b.hwnd()) {
+                    Some(button) if unsafe { wh::get_window_enabled(button) } => {
+                        wh::send_message(button, BM_CLICK, 0, 0);
+                        true
+                    },
+                    _ => false
+                }
+            },
+            VK_ESCAPE => {
+                match self.cancel_button.get().and_then(|b| b.hwnd()) {
+                    Some(button) => {
+                        if unsafe { wh::get_window_enabled(button) } {
+                            wh::send_message(button, BM_CLICK, 0, 0);
+                        }
+                    },
+                    None => { wh::send_message(handle, WM_CLOSE, 0, 0); }
+                }
+                true
+            },
+            _ => false
+        }
+    }
+
+}
+
+impl<T: 'static> Default for Dialog<T> {
+
+    fn default() -> Dialog<T> {
+        Dialog {
+            handle: ControlHandle::NoHandle,
+            default_button: Cell::new(None),
+            cancel_button: Cell::new(None),
+            result: RefCell::new(None),
+            running: Cell::new(false),
+        }
+    }
+
+}
+
+impl<T: 'static> PartialEq for Dialog<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl<T: 'static> Drop for Dialog<T> {
+    fn drop(&mut self) {
+        self.handle.destroy();
+    }
+}
+
+pub struct DialogBuilder<'a> {
+    title: &'a str,
+    size: (i32, i32),
+    position: (i32, i32),
+    topmost: bool,
+    flags: Option<WindowFlags>,
+    icon: Option<&'a Icon>,
+    parent: Option<ControlHandle>
+}
+
+impl<'a> DialogBuilder<'a> {
+
+    pub fn flags(mut self, flags: WindowFlags) -> DialogBuilder<'a> {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn title(mut self, text: &'a str) -> DialogBuilder<'a> {
+        self.title = text;
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> DialogBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> DialogBuilder<'a> {
+        self.position = pos;
+        self
+    }
+
+    pub fn icon(mut self, ico: Option<&'a Icon>) -> DialogBuilder<'a> {
+        self.icon = ico;
+        self
+    }
+
+    pub fn topmost(mut self, topmost: bool) -> DialogBuilder<'a> {
+        self.topmost = topmost;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: Option<C>) -> DialogBuilder<'a> {
+        self.parent = p.map(|p2| p2.into());
+        self
+    }
+
+    pub fn build<T: 'static>(self, out: &mut Dialog<T>) -> Result<(), NwgError> {
+        use winapi::um::winuser::WS_VISIBLE;
+
+        // The dialog is displayed by `run_modal`
+        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags()) & !WS_VISIBLE;
+
+        let mut ex_flags = WS_EX_DLGMODALFRAME;
+        if self.topmost { ex_flags |= WS_EX_TOPMOST; }
+
+        *out = Default::default();
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .ex_flags(ex_flags)
+            .flags(flags)
+            .size(self.size)
+            .position(self.position)
+            .text(self.title)
+            .parent(self.parent)
+            .build()?;
+
+        if self.icon.is_some() {
+            out.set_icon(self.icon);
+        }
+
+        Ok(())
+    }
+
+}
@@ -0,0 +1,365 @@
+/*!
+    Fallible versions of the common control accessors.
+
+    The accessors of the controls panic if the control was not built or if the window was destroyed by the OS.
+    The `try_*` methods return a `NwgError::ControlNotBound` or a `NwgError::InvalidHandle` error instead.
+
+    The accessors shared by the window controls have a `try_*` version: visibility, enabled state, size, position, focus and text.
+    Every other accessor can be called with `try_with`, which checks the control before calling the closure.
+    Menus, timers, notices, hotkeys and tray notifications use the same checks for their own handle types.
+
+```rust
+use native_windows_gui as nwg;
+fn update_title(label: &nwg::Label) -> Result<(), nwg::NwgError> {
+    label.try_set_text("Done")?;
+    label.try_set_visible(true)
+}
+
+fn selected_row(list: &nwg::ListView) -> Result<Option<usize>, nwg::NwgError> {
+    list.try_with(|list| list.selected_item())
+}
+```
+*/
+use winapi::shared::windef::HWND;
+use crate::win32::base_helper::try_check_hwnd;
+use crate::NwgError;
+use super::{Window, Button, CheckBox, RadioButton, TextInput, Label, ImageFrame};
+
+#[cfg(any(feature = "combobox", feature = "listbox"))]
+use std::fmt::Display;
+
+#[cfg(any(feature = "menu", feature = "tray-notification", feature = "global-hotkey", feature = "timer", feature = "notice"))]
+use super::ControlHandle;
+
+macro_rules! fallible_control {
+    (@hwnd $control:ident) => {
+        /// Returns the window handle of the control.
+        /// Returns `ControlNotBound` if the control was not built and `InvalidHandle` if the window was destroyed.
+        /// Use it to check the control before calling an accessor that has no `try_*` version.
+        pub fn try_hwnd(&self) -> Result<HWND, NwgError> {
+            try_check_hwnd(
+                &self.handle,
+                concat!(stringify!($control), " is not yet bound to a winapi object"),
+                concat!("INTERNAL ERROR: ", stringify!($control), " handle is not HWND!")
+            )
+        }
+
+        fallible_control!(@with try_hwnd);
+    };
+
+    (@with $check:ident) => {
+        /// Checks the control, then calls `f` with it. Use it to call the accessors that have no `try_*` version.
+        /// Returns `ControlNotBound` if the control was not built and `InvalidHandle` if the handle is no longer usable.
+        pub fn try_with<R, F: FnOnce(&Self) -> R>(&self, f: F) -> Result<R, NwgError> {
+            self.$check().map(|_| f(self))
+        }
+    };
+
+    // Controls that are not windows. `$handle` checks the type of the handle and `$valid` checks that the control is still usable.
+    (@other $control:ident, $handle:expr, $valid:expr) => {
+        fn try_check(&self) -> Result<(), NwgError> {
+            if self.handle.blank() {
+                return Err(NwgError::not_bound(concat!(stringify!($control), " is not yet bound to a winapi object")));
+            }
+
+            if !$handle(&self.handle) {
+                return Err(NwgError::invalid_handle(concat!("INTERNAL ERROR: ", stringify!($control), " handle is not the right type!")));
+            }
+
+            match $valid(self) {
+                true => Ok(()),
+                false => Err(NwgError::invalid_handle(concat!(stringify!($control), " parent window was destroyed")))
+            }
+        }
+
+        fallible_control!(@with try_check);
+    };
+
+    (@window) => {
+        /// Fallible version of `visible`
+        pub fn try_visible(&self) -> Result<bool, NwgError> {
+            self.try_hwnd().map(|_| self.visible())
+        }
+
+        /// Fallible version of `set_visible`
+        pub fn try_set_visible(&self, v: bool) -> Result<(), NwgError> {
+            self.try_hwnd().map(|_| self.set_visible(v))
+        }
+
+        /// Fallible version of `enabled`
+        pub fn try_enabled(&self) -> Result<bool, NwgError> {
+            self.try_hwnd().map(|_| self.enabled())
+        }
+
+        /// Fallible version of `set_enabled`
+        pub fn try_set_enabled(&self, v: bool) -> Result<(), NwgError> {
+            self.try_hwnd().map(|_| self.set_enabled(v))
+        }
+
+        /// Fallible version of `size`
+        pub fn try_size(&self) -> Result<(u32, u32), NwgError> {
+            self.try_hwnd().map(|_| self.size())
+        }
+
+        /// Fallible version of `set_size`
+        pub fn try_set_size(&self, x: u32, y: u32) -> Result<(), NwgError> {
+            self.try_hwnd().map(|_| self.set_size(x, y))
+        }
+
+        /// Fallible version of `position`
+        pub fn try_position(&self) -> Result<(i32, i32), NwgError> {
+            self.try_hwnd().map(|_| self.position())
+        }
+
+        /// Fallible version of `set_position`
+        pub fn try_set_position(&self, x: i32, y: i32) -> Result<(), NwgError> {
+            self.try_hwnd().map(|_| self.set_position(x, y))
+        }
+    };
+
+    (@focus) => {
+        /// Fallible version of `focus`
+        pub fn try_focus(&self) -> Result<bool, NwgError> {
+            self.try_hwnd().map(|_| self.focus())
+        }
+
+        /// Fallible version of `set_focus`
+        pub fn try_set_focus(&self) -> Result<(), NwgError> {
+            self.try_hwnd().map(|_| self.set_focus())
+        }
+    };
+
+    (@text) => {
+        /// Fallible version of `text`
+        pub fn try_text(&self) -> Result<String, NwgError> {
+            self.try_hwnd().map(|_| self.text())
+        }
+
+        /// Fallible version of `set_text`
+        pub fn try_set_text<'a>(&self, v: &'a str) -> Result<(), NwgError> {
+            self.try_hwnd().map(|_| self.set_text(v))
+        }
+    };
+
+    (generic $control:ident, [$($group:ident),*], $($bound:tt)+) => {
+        impl<D: $($bound)+> $control<D> {
+            fallible_control!(@hwnd $control);
+            $( fallible_control!(@$group); )*
+        }
+    };
+
+    ($control:ident, [$($group:ident),*]) => {
+        impl $control {
+            fallible_control!(@hwnd $control);
+            $( fallible_control!(@$group); )*
+        }
+    };
+}
+
+fallible_control!(Window, [window, focus, text]);
+fallible_control!(Button, [window, focus, text]);
+fallible_control!(CheckBox, [window, focus, text]);
+fallible_control!(RadioButton, [window, focus, text]);
+fallible_control!(TextInput, [window, focus, text]);
+fallible_control!(Label, [window, focus, text]);
+fallible_control!(ImageFrame, [window]);
+
+
+#[cfg(feature = "textbox")]
+use super::TextBox;
+
+#[cfg(feature = "textbox")]
+fallible_control!(TextBox, [window, focus, text]);
+
+#[cfg(feature = "rich-textbox")]
+use super::RichTextBox;
+
+#[cfg(feature = "rich-textbox")]
+fallible_control!(RichTextBox, [window, focus, text]);
+
+#[cfg(feature = "trackbar")]
+use super::TrackBar;
+
+#[cfg(feature = "trackbar")]
+fallible_control!(TrackBar, [window, focus]);
+
+#[cfg(feature = "combobox")]
+use super::ComboBox;
+
+#[cfg(feature = "combobox")]
+fallible_control!(generic ComboBox, [window, focus], Display+Default);
+
+#[cfg(feature = "listbox")]
+use super::ListBox;
+
+#[cfg(feature = "listbox")]
+fallible_control!(generic ListBox, [window, focus], Display+Default);
+
+#[cfg(feature = "tabs")]
+use super::TabsContainer;
+
+#[cfg(feature = "tabs")]
+fallible_control!(TabsContainer, [window, focus]);
+
+#[cfg(feature = "datetime-picker")]
+use super::DatePicker;
+
+#[cfg(feature = "datetime-picker")]
+fallible_control!(DatePicker, [window, focus]);
+
+#[cfg(feature = "progress-bar")]
+use super::ProgressBar;
+
+#[cfg(feature = "progress-bar")]
+fallible_control!(ProgressBar, [window, focus]);
+
+#[cfg(feature = "tree-view")]
+use super::TreeView;
+
+#[cfg(feature = "tree-view")]
+fallible_control!(TreeView, [window, focus]);
+
+#[cfg(feature = "list-view")]
+use super::ListView;
+
+#[cfg(feature = "list-view")]
+fallible_control!(ListView, [window, focus]);
+
+#[cfg(feature = "number-select")]
+use super::NumberSelect;
+
+#[cfg(feature = "number-select")]
+fallible_control!(NumberSelect, [window, focus]);
+
+#[cfg(feature = "extern-canvas")]
+use super::ExternCanvas;
+
+#[cfg(feature = "extern-canvas")]
+fallible_control!(ExternCanvas, [window, focus, text]);
+
+#[cfg(feature = "frame")]
+use super::Frame;
+
+#[cfg(feature = "frame")]
+fallible_control!(Frame, [window, focus]);
+
+#[cfg(feature = "scroll-bar")]
+use super::ScrollBar;
+
+#[cfg(feature = "scroll-bar")]
+fallible_control!(ScrollBar, [window, focus]);
+
+#[cfg(feature = "splitter")]
+use super::Splitter;
+
+#[cfg(feature = "splitter")]
+fallible_control!(Splitter, [window, focus]);
+
+#[cfg(feature = "scroll-panel")]
+use super::ScrollPanel;
+
+#[cfg(feature = "scroll-panel")]
+fallible_control!(ScrollPanel, [window, focus]);
+
+#[cfg(feature = "tabs")]
+use super::Tab;
+
+#[cfg(feature = "tabs")]
+fallible_control!(Tab, []);
+
+#[cfg(feature = "status-bar")]
+use super::StatusBar;
+
+#[cfg(feature = "status-bar")]
+fallible_control!(StatusBar, []);
+
+#[cfg(feature = "tooltip")]
+use super::Tooltip;
+
+#[cfg(feature = "tooltip")]
+fallible_control!(Tooltip, []);
+
+#[cfg(feature = "dialog")]
+use super::Dialog;
+
+#[cfg(feature = "dialog")]
+fallible_control!(generic Dialog, [focus, text], 'static);
+
+#[cfg(feature = "wizard")]
+use super::{Wizard, WizardPage};
+
+#[cfg(feature = "wizard")]
+fallible_control!(Wizard, [text]);
+
+#[cfg(feature = "wizard")]
+fallible_control!(WizardPage, []);
+
+#[cfg(feature = "menu")]
+use super::{Menu, MenuItem};
+
+#[cfg(feature = "menu")]
+impl Menu {
+    fallible_control!(@other Menu, |h: &ControlHandle| h.hmenu().is_some() || h.pop_hmenu().is_some(), |_| true);
+}
+
+#[cfg(feature = "menu")]
+impl MenuItem {
+    fallible_control!(@other MenuItem, |h: &ControlHandle| h.hmenu_item().is_some(), |_| true);
+}
+
+#[cfg(feature = "tray-notification")]
+use super::TrayNotification;
+
+#[cfg(feature = "tray-notification")]
+impl TrayNotification {
+    fallible_control!(@other TrayNotification, |h: &ControlHandle| h.tray().is_some(), |_| true);
+}
+
+#[cfg(feature = "global-hotkey")]
+use super::GlobalHotkey;
+
+#[cfg(feature = "global-hotkey")]
+impl GlobalHotkey {
+    fallible_control!(@other GlobalHotkey, |h: &ControlHandle| h.hotkey().is_some(), GlobalHotkey::valid);
+}
+
+#[cfg(feature = "timer")]
+use super::Timer;
+
+#[cfg(feature = "timer")]
+impl Timer {
+    fallible_control!(@other Timer, |h: &ControlHandle| h.timer().is_some(), Timer::valid);
+
+    /// Fallible version of `start`
+    pub fn try_start(&self) -> Result<(), NwgError> {
+        self.try_with(|t| t.start())
+    }
+
+    /// Fallible version of `stop`
+    pub fn try_stop(&self) -> Result<(), NwgError> {
+        self.try_with(|t| t.stop())
+    }
+}
+
+#[cfg(feature = "notice")]
+use super::{Notice, NoticeSender, NoticeChannel, NoticeChannelSender};
+
+#[cfg(feature = "notice")]
+impl Notice {
+    fallible_control!(@other Notice, |h: &ControlHandle| h.notice().is_some(), Notice::valid);
+
+    /// Fallible version of `sender`
+    pub fn try_sender(&self) -> Result<NoticeSender, NwgError> {
+        self.try_with(|n| n.sender())
+    }
+}
+
+#[cfg(feature = "notice")]
+impl<T: Send + 'static> NoticeChannel<T> {
+    fallible_control!(@other NoticeChannel, |h: &ControlHandle| h.notice().is_some(), NoticeChannel::valid);
+
+    /// Fallible version of `sender`
+    pub fn try_sender(&self) -> Result<NoticeChannelSender<T>, NwgError> {
+        self.try_with(|n| n.sender())
+    }
+}
@@ -37,6 +37,9 @@ Requires the `frame` feature.
   * `enabled`:  If the frame children can be used by the user.
   * `flags`:    A combination of the FrameFlags values.
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
 
 **Control events:**
   * `MousePress(_)`: Generic mouse press events on the button
@@ -0,0 +1,161 @@
+use crate::controls::ControlHandle;
+use crate::win32::{window_helper as wh, window::{build_hotkey, register_hotkey, unregister_hotkey}};
+use crate::{KeyCombo, NwgError};
+use std::cell::RefCell;
+
+const NOT_BOUND: &'static str = "GlobalHotkey is not yet bound to a winapi object";
+const UNUSABLE_HOTKEY: &'static str = "GlobalHotkey parent window was freed";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: GlobalHotkey handle is not Hotkey!";
+
+
+/**
+A global hotkey is an invisible UI component that trigger the `OnHotkey` event when its key combination is pressed,
+even if the application does not have the keyboard focus. To handle shortcuts in the application windows, see the `Accelerators` resource.
+
+A key combination can only be registered by one application at a time. Building a hotkey fails with a `ControlCreationError`
+if the combination is already in use (the system error code is `1409`).
+
+A hotkey requires a window parent. If the parent window is destroyed, the hotkey becomes invalid.
+
+Requires the `global-hotkey` feature.
+
+**Builder parameters:**
+    * `parent`:   **Required.** The hotkey parent window. The events are sent to this window.
+    * `combo`:    **Required.** The key combination of the hotkey.
+    * `repeat`:   If `OnHotkey` is raised repeatedly while the user holds the combination down. Defaults to `false`.
+
+**Control events:**
+    * `OnHotkey`: When the key combination is pressed
+
+```rust
+use native_windows_gui as nwg;
+
+fn build_hotkey(hotkey: &mut nwg::GlobalHotkey, parent: &nwg::Window) -> Result<(), nwg::NwgError> {
+    nwg::GlobalHotkey::builder()
+        .parent(parent)
+        .combo("Ctrl+Alt+K".parse()?)
+        .build(hotkey)
+}
+```
+*/
+#[derive(Default)]
+pub struct GlobalHotkey {
+    pub handle: ControlHandle,
+    combo: RefCell<Option<KeyCombo>>,
+    repeat: RefCell<bool>,
+}
+
+impl GlobalHotkey {
+
+    pub fn builder() -> GlobalHotkeyBuilder {
+        GlobalHotkeyBuilder {
+            parent: None,
+            combo: None,
+            repeat: false
+        }
+    }
+
+    /// Checks if the hotkey is still usable. A hotkey becomes unusable when the parent window is destroyed.
+    /// This will also return false if the hotkey is not initialized.
+    pub fn valid(&self) -> bool {
+        if self.handle.blank() { return false; }
+        let (hwnd, _) = self.handle.hotkey().expect(BAD_HANDLE);
+        wh::window_valid(hwnd)
+    }
+
+    /// Returns the key combination of the hotkey. Returns `None` if the hotkey is not initialized.
+    pub fn combo(&self) -> Option<KeyCombo> {
+        *self.combo.borrow()
+    }
+
+    /// Registers a new key combination for the hotkey. If the new combination cannot be registered,
+    /// the hotkey keeps its old combination and the error is returned.
+    pub fn set_combo(&self, combo: KeyCombo) -> Result<(), NwgError> {
+        if self.handle.blank() { return Err(NwgError::not_bound(NOT_BOUND)); }
+        if !self.valid() { return Err(NwgError::invalid_handle(UNUSABLE_HOTKEY)); }
+        let (hwnd, id) = self.handle.hotkey().expect(BAD_HANDLE);
+        let repeat = *self.repeat.borrow();
+
+        unsafe {
+            unregister_hotkey(hwnd, id);
+            if let Err(e) = register_hotkey(hwnd, id, combo, repeat) {
+                if let Some(old) = self.combo() {
+                    register_hotkey(hwnd, id, old, repeat).ok();
+                }
+                return Err(e);
+            }
+        }
+
+        *self.combo.borrow_mut() = Some(combo);
+        Ok(())
+    }
+
+}
+
+impl Drop for GlobalHotkey {
+    fn drop(&mut self) {
+        if let Some((hwnd, id)) = self.handle.hotkey() {
+            unsafe { unregister_hotkey(hwnd, id); }
+        }
+
+        self.handle.destroy();
+    }
+}
+
+pub struct GlobalHotkeyBuilder {
+    parent: Option<ControlHandle>,
+    combo: Option<KeyCombo>,
+    repeat: bool,
+}
+
+impl GlobalHotkeyBuilder {
+
+    pub fn combo(mut self, combo: KeyCombo) -> GlobalHotkeyBuilder {
+        self.combo = Some(combo);
+        self
+    }
+
+    pub fn repeat(mut self, repeat: bool) -> GlobalHotkeyBuilder {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> GlobalHotkeyBuilder {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut GlobalHotkey) -> Result<(), NwgError> {
+        let parent = match self.parent {
+            Some(p) => match p.hwnd() {
+                Some(handle) => Ok(handle),
+                None => Err(NwgError::control_create("Wrong parent type"))
+            },
+            None => Err(NwgError::no_parent("GlobalHotkey"))
+        }?;
+
+        let combo = match self.combo {
+            Some(combo) => combo,
+            None => { return Err(NwgError::control_create("No key combination defined for GlobalHotkey").with_class("GlobalHotkey")); }
+        };
+
+        // Free the previous hotkey if the control is rebuilt
+        if let Some((hwnd, id)) = out.handle.hotkey() {
+            unsafe { unregister_hotkey(hwnd, id); }
+            out.handle = ControlHandle::NoHandle;
+        }
+
+        out.handle = unsafe { build_hotkey(parent, combo, self.repeat)? };
+        *out.combo.borrow_mut() = Some(combo);
+        *out.repeat.borrow_mut() = self.repeat;
+
+        Ok(())
+    }
+
+}
+
+impl PartialEq for GlobalHotkey {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
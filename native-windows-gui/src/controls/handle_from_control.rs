@@ -202,6 +202,28 @@ use super::Notice;
 #[cfg(feature = "notice")]
 handles!(Notice);
 
+#[cfg(feature = "notice")]
+use super::NoticeChannel;
+
+#[cfg(feature = "notice")]
+impl<T: Send + 'static> From<&NoticeChannel<T>> for ControlHandle {
+    fn from(control: &NoticeChannel<T>) -> Self { control.handle }
+}
+
+#[cfg(feature = "notice")]
+impl<T: Send + 'static> PartialEq<ControlHandle> for NoticeChannel<T> {
+    fn eq(&self, other: &ControlHandle) -> bool {
+        self.handle == *other
+    }
+}
+
+#[cfg(feature = "notice")]
+impl<T: Send + 'static> PartialEq<NoticeChannel<T>> for ControlHandle {
+    fn eq(&self, other: &NoticeChannel<T>) -> bool {
+        *self == other.handle
+    }
+}
+
 #[cfg(feature = "list-view")]
 use super::ListView;
 
@@ -234,4 +256,51 @@ use super::ScrollBar;
 #[cfg(feature = "scroll-bar")]
 handles!(ScrollBar);
 
+#[cfg(feature = "splitter")]
+use super::Splitter;
+
+#[cfg(feature = "splitter")]
+handles!(Splitter);
+
+#[cfg(feature = "scroll-panel")]
+use super::ScrollPanel;
+
+#[cfg(feature = "scroll-panel")]
+handles!(ScrollPanel);
+
+#[cfg(feature = "global-hotkey")]
+use super::GlobalHotkey;
+
+#[cfg(feature = "global-hotkey")]
+handles!(GlobalHotkey);
+
+#[cfg(feature = "dialog")]
+use super::Dialog;
+
+#[cfg(feature = "dialog")]
+impl<T: 'static> From<&Dialog<T>> for ControlHandle {
+    fn from(control: &Dialog<T>) -> Self { control.handle }
+}
+
+#[cfg(feature = "dialog")]
+impl<T: 'static> PartialEq<ControlHandle> for Dialog<T> {
+    fn eq(&self, other: &ControlHandle) -> bool {
+        self.handle == *other
+    }
+}
+
+#[cfg(feature = "dialog")]
+impl<T: 'static> PartialEq<Dialog<T>> for ControlHandle {
+    fn eq(&self, other: &Dialog<T>) -> bool {
+        *self == other.handle
+    }
+}
+
+#[cfg(feature = "wizard")]
+use super::{Wizard, WizardPage};
+
+#[cfg(feature = "wizard")]
+handles!(Wizard);
 
+#[cfg(feature = "wizard")]
+handles!(WizardPage);
@@ -49,6 +49,9 @@ Label is not behind any features.
   * `MousePress(_)`: Generic mouse press events on the label
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
 
 ```rust
 use native_windows_gui as nwg;
@@ -1,22 +1,41 @@
+use winapi::shared::minwindef::{LPARAM, LRESULT};
+use winapi::shared::windef::HWND;
+use winapi::ctypes::c_int;
 use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
 use winapi::um::commctrl::{
     LVS_ICON, LVS_SMALLICON, LVS_LIST, LVS_REPORT, LVS_NOCOLUMNHEADER, LVCOLUMNW, LVCFMT_LEFT, LVCFMT_RIGHT, LVCFMT_CENTER, LVCFMT_JUSTIFYMASK,
     LVCFMT_IMAGE, LVCFMT_BITMAP_ON_RIGHT, LVCFMT_COL_HAS_IMAGES, LVITEMW, LVIF_TEXT, LVCF_WIDTH, LVCF_TEXT, LVS_EX_GRIDLINES, LVS_EX_BORDERSELECT,
-    LVS_EX_AUTOSIZECOLUMNS, LVM_SETEXTENDEDLISTVIEWSTYLE, LVS_EX_FULLROWSELECT, LVS_SINGLESEL, LVCF_FMT, LVIF_IMAGE
+    LVS_EX_AUTOSIZECOLUMNS, LVM_SETEXTENDEDLISTVIEWSTYLE, LVS_EX_FULLROWSELECT, LVS_SINGLESEL, LVCF_FMT, LVIF_IMAGE, LVS_OWNERDATA, LVS_EDITLABELS,
+    LVS_EX_CHECKBOXES, LVS_EX_INFOTIP, LVS_EX_DOUBLEBUFFER, LVIS_STATEIMAGEMASK
 };
-use super::{ControlBase, ControlHandle};
-use crate::win32::window_helper as wh;
+use super::{ControlBase, ControlHandle, TextInput};
+use crate::win32::window_helper::{self as wh, NWG_LIST_VIEW_BEGIN_EDIT, NWG_LIST_VIEW_END_EDIT, NWG_LIST_VIEW_EDITOR_DONE,
+    NWG_LIST_VIEW_INFO_TIP, NWG_LIST_VIEW_GROUP_CLICK};
 use crate::win32::base_helper::{to_utf16, from_utf16, check_hwnd};
 use crate::{NwgError, RawEventHandler, unbind_raw_event_handler};
-use std::{mem, cell::RefCell};
+use std::{mem, cell::RefCell, rc::Rc, cmp::Ordering, collections::HashMap};
 
 #[cfg(feature="image-list")]
 use crate::ImageList;
 
+#[cfg(feature="combobox")]
+use super::ComboBox;
+
 
 const NOT_BOUND: &'static str = "ListView is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: ListView handle is not HWND!";
 
+/// The maximum length of the text read by the cell editors
+const EDIT_BUFFER_SIZE: usize = 1024;
+
+/// The indices of the check box images in the state image list
+const UNCHECKED_STATE_IMAGE: u32 = 1;
+pub(crate) const CHECKED_STATE_IMAGE: u32 = 2;
+
+/// The height of the drop down list of the combobox editor
+#[cfg(feature="combobox")]
+const EDIT_COMBO_LIST_HEIGHT: i32 = 150;
+
 
 bitflags! {
     /**
@@ -25,8 +44,10 @@ bitflags! {
         * VISIBLE:  The list view is immediatly visible after creation
         * DISABLED: The list view cannot be interacted with by the user. It also has a grayed out look. The user can drag the items to any location in the list-view window.
         * TAB_STOP: The control can be selected using tab navigation
-        * NO_HEADER: Remove the headers in Detailed view (always ON, see "Windows is Shit" section in ListView docs as of why)
+        * NO_HEADER: Remove the headers in Detailed view (always ON unless `ListViewBuilder::column_header` is used, see "Windows is Shit" section in ListView docs as of why)
         * SINGLE_SELECTION: Only one item can be selected
+        * EDIT_LABELS: The user can edit the text of the items (the first column) by clicking on a selected item. See `OnListViewBeginEdit`.
+        * VIRTUAL: The items are not stored in the list view. They are queried from a `ListViewDataSource`. Set automatically by `ListViewBuilder::data_source`.
     */
     pub struct ListViewFlags: u32 {
         const VISIBLE = WS_VISIBLE;
@@ -34,8 +55,9 @@ bitflags! {
         const TAB_STOP = WS_TABSTOP;
 
         const SINGLE_SELECTION = LVS_SINGLESEL;
+        const EDIT_LABELS = LVS_EDITLABELS;
+        const VIRTUAL = LVS_OWNERDATA;
 
-        // Remove the headers in Detailed view (always ON, see "Windows is Shit" section in ListView docs as of why)
         const NO_HEADER = LVS_NOCOLUMNHEADER;
     }
 }
@@ -49,6 +71,9 @@ bitflags! {
         * BORDER_SELECT: Only highlight the border instead of the full item. COMMCTRL version 4.71 or later
         * AUTO_COLUMN_SIZE: Automatically resize to column
         * FULL_ROW_SELECT: When an item is selected, the item and all its subitems are highlighted. Only in detailed view 
+        * CHECK_BOXES: Display a check box next to the items. See `ListView::check_item`
+        * INFO_TIP: Raise `OnListViewInfoTip` when the mouse rests over an item
+        * DOUBLE_BUFFER: Paint the list view with double buffering. Reduces flicker when the items have custom colors
     */
     pub struct ListViewExFlags: u32 {
         const NONE = 0;
@@ -56,6 +81,9 @@ bitflags! {
         const BORDER_SELECT = LVS_EX_BORDERSELECT;
         const AUTO_COLUMN_SIZE = LVS_EX_AUTOSIZECOLUMNS;
         const FULL_ROW_SELECT = LVS_EX_FULLROWSELECT;
+        const CHECK_BOXES = LVS_EX_CHECKBOXES;
+        const INFO_TIP = LVS_EX_INFOTIP;
+        const DOUBLE_BUFFER = LVS_EX_DOUBLEBUFFER;
     }
 }
 
@@ -93,6 +121,27 @@ pub enum ListViewStyle {
     SmallIcon,
 }
 
+/// The sort indicator displayed in a list view column header. See `ListView::set_column_sort_arrow`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ListViewColumnSortArrow {
+    /// An arrow pointing up. The items are sorted in ascending order.
+    Ascending,
+
+    /// An arrow pointing down. The items are sorted in descending order.
+    Descending,
+}
+
+/// The control displayed over a list view cell by `ListView::edit_item`
+#[derive(Clone, Debug, PartialEq)]
+pub enum ListViewEditor {
+    /// A single line text input initialized with the text of the cell
+    TextInput,
+
+    /// A drop down list with a fixed set of choices. The choice matching the text of the cell is selected.
+    #[cfg(feature="combobox")]
+    ComboBox(Vec<String>),
+}
+
 impl ListViewStyle {
     fn from_bits(bits: u32) -> ListViewStyle {
         let bits = bits & 0b11;
@@ -206,6 +255,81 @@ pub struct ListViewItem {
     pub image: i32,
 }
 
+/// The colors of a list view item or cell. `None` keeps the default color of the list view.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListViewItemColors {
+    /// The color of the text
+    pub text: Option<[u8; 3]>,
+
+    /// The color of the background
+    pub background: Option<[u8; 3]>,
+}
+
+/// Represents a list view group. See `ListView::insert_group`
+#[derive(Default, Clone, Debug)]
+pub struct InsertListViewGroup {
+    /// The id of the group. Used to add items to the group.
+    pub id: i32,
+
+    /// Index of the group. If None, the group is added after the other groups.
+    pub index: Option<i32>,
+
+    /// Text of the group header
+    pub header: String,
+
+    /// If the user can collapse the group
+    pub collapsible: bool,
+
+    /// If the group is collapsed when it is inserted
+    pub collapsed: bool,
+}
+
+/**
+    The items of a virtual list view (a list view built with `ListViewFlags::VIRTUAL`).
+
+    A virtual list view does not store its items. Instead, it asks the data source for the
+    cells that are currently visible. This means that a list with millions of rows can be displayed instantly.
+    The list view only stores the number of items (see `ListView::set_item_count`) and the state of the
+    items (selection and focus), which are tracked by index.
+
+    The data source is called from the GUI thread while the list view is painting, so its methods should be fast.
+    Use `cache_hint` to prepare the items that will be requested next.
+*/
+pub trait ListViewDataSource {
+
+    /// Returns the number of items. Used to set the item count when the data source is assigned to the list view.
+    fn len(&self) -> usize;
+
+    /// Returns the text of the cell at `row_index` and `column_index`
+    fn text(&self, row_index: usize, column_index: usize) -> String;
+
+    /// Returns the index of the cell image in the image list of the list view
+    fn image(&self, _row_index: usize, _column_index: usize) -> Option<i32> {
+        None
+    }
+
+    /// Returns the one-based index of the item image in the state image list of the list view (ex: the check boxes).
+    fn state_image(&self, _row_index: usize) -> Option<u32> {
+        None
+    }
+
+    /// Called before the list view requests the items from `from` to `to` (inclusive)
+    fn cache_hint(&self, _from: usize, _to: usize) {
+    }
+
+    /// Returns the colors of the cell at `row_index` and `column_index`
+    fn colors(&self, _row_index: usize, _column_index: usize) -> Option<ListViewItemColors> {
+        None
+    }
+
+    /// Find the first item after `start` (inclusive) that starts with `text` (or that is equal to `text` if `partial` is false).
+    /// Used by the keyboard navigation. Returns `None` by default.
+    fn find_item(&self, _text: &str, _start: usize, _partial: bool) -> Option<usize> {
+        None
+    }
+
+}
+
 /**
 A list-view control is a window that displays a collection of items.
 List-view controls provide several ways to arrange and display items and are much more flexible than simple ListBox.
@@ -222,22 +346,127 @@ Builder parameters:
     * `item_count`: Number of item to preallocate
     * `list_style`: The default style of the listview
     * `focus`:      The control receive focus after being created
+    * `data_source`: The items of a virtual list view. See `ListViewDataSource`.
+    * `column_header`: Display the column headers in Detailed view. See the "Windows is Shit" section.
 
 **Control events:**
   * `MousePress(_)`: Generic mouse press events on the tree view
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
   * `OnKeyPress`:    Generic key press event
   * `OnKeyRelease`:  Generic key release event
+  * `OnListViewColumnClick`: When the user clicks on a column header. See `EventData::OnListViewColumnIndex`
+  * `OnListViewBeginEdit`: When the user starts editing an item. See `EventData::OnListViewEdit`
+  * `OnListViewEndEdit`: When the user finishes editing an item. See `EventData::OnListViewEdit`
+  * `OnListViewItemChecked`: When the check box of an item is toggled. See `EventData::OnListViewItemChecked`
+  * `OnListViewGroupClick`: When the user clicks on a group header. See `EventData::OnListViewGroupId`
+  * `OnListViewInfoTip`: When the mouse rests over an item. Requires `ListViewExFlags::INFO_TIP`. See `EventData::OnListViewInfoTip`
+
+Editing:
+With the `EDIT_LABELS` flag, the user can edit the text of the items in the first column.
+Any cell can be edited with `edit_item`, which displays a `TextInput` (or a `ComboBox`) over the cell. Enter (or a click
+outside the editor) saves the new text with `update_item`, and Escape cancels the edit.
+In both cases, `OnListViewBeginEdit` and `OnListViewEndEdit` can cancel the edit, and `OnListViewEndEdit` can replace the new text.
+A virtual list view must save the new text in its data source in `OnListViewEndEdit`.
+
+```rust
+use native_windows_gui as nwg;
+
+fn validate_price(evt_data: &nwg::EventData) {
+    let edit = evt_data.as_list_view_edit().unwrap();
+    if let Some(text) = edit.text() {
+        match text.trim().parse::<f64>() {
+            Ok(price) => edit.set_text(&format!("{:.2}", price)),
+            Err(_) => edit.allow(false)
+        }
+    }
+}
+```
+
+Groups:
+The items of a detailed list view can be displayed in groups. Groups are identified by an id chosen by the application.
+
+```rust
+use native_windows_gui as nwg;
+
+fn build_groups(list: &nwg::ListView) {
+    list.set_groups_enabled(true);
+    list.insert_group(nwg::InsertListViewGroup { id: 1, header: "Online".into(), collapsible: true, ..Default::default() });
+    list.insert_group(nwg::InsertListViewGroup { id: 2, header: "Offline".into(), collapsible: true, ..Default::default() });
+
+    list.insert_items_row(None, &["Server A", "Running"]);
+    list.set_item_group(0, 1);
+    list.set_item_colors(0, Some(1), Some(nwg::ListViewItemColors { text: Some([0, 128, 0]), background: None }));
+}
+```
+
+Virtual list view:
+A list view created with a `data_source` (or the `VIRTUAL` flag) does not store its items. The text and the images are
+queried from a `ListViewDataSource` when the items are displayed. The methods that modify the items (ex: `insert_item`)
+do nothing in this mode. Use `set_item_count` when the number of items changes and `redraw_items` when the items are modified.
+
+```rust
+use native_windows_gui as nwg;
+use std::rc::Rc;
+
+struct LogLines;
+
+impl nwg::ListViewDataSource for LogLines {
+    fn len(&self) -> usize { 1_000_000 }
+    fn text(&self, row: usize, column: usize) -> String { format!("Line {} column {}", row, column) }
+}
+
+fn build_list(list: &mut nwg::ListView, window: &nwg::Window) -> Result<(), nwg::NwgError> {
+    nwg::ListView::builder()
+        .list_style(nwg::ListViewStyle::Detailed)
+        .data_source(Rc::new(LogLines))
+        .parent(window)
+        .build(list)
+}
+```
+
+Sorting:
+The list view does not sort its items on its own. Handle `OnListViewColumnClick` and call `sort_items` (or `sort_items_by_text`).
+Use `set_column_sort_arrow` to display the sort order in the column header. The column headers must be enabled with `column_header`.
+
+```rust
+use native_windows_gui as nwg;
+
+fn sort_by_column(list: &nwg::ListView, column: usize) {
+    use nwg::ListViewColumnSortArrow as Arrow;
+
+    let order = match list.column_sort_arrow(column) {
+        Some(Arrow::Ascending) => Arrow::Descending,
+        _ => Arrow::Ascending
+    };
+
+    list.sort_items_by_text(column, |a, b| match order {
+        Arrow::Ascending => a.cmp(b),
+        Arrow::Descending => b.cmp(a),
+    });
+
+    for c in 0..list.column_len() {
+        list.set_column_sort_arrow(c, if c == column { Some(order) } else { None });
+    }
+}
+```
 
 Windows is Shit:
-- The win32 header controls leaks megabytes of memory per seconds because it is shit. As such, NO_HEADER is always ON.
+- The win32 header controls leaks megabytes of memory per seconds because it is shit. As such, NO_HEADER is always ON
+unless the list view is built with `column_header(true)`. The column click events and the sort arrows need the headers.
 
 */
 #[derive(Default)]
 pub struct ListView {
     pub handle: ControlHandle,
+    data_source: Rc<RefCell<Option<Rc<dyn ListViewDataSource>>>>,
+    editor: Rc<RefCell<Option<CellEditor>>>,
+    colors: Rc<RefCell<ListViewColors>>,
     handler0: RefCell<Option<RawEventHandler>>,
+    handler1: RefCell<Option<RawEventHandler>>,
 }
 
 impl ListView {
@@ -251,10 +480,233 @@ impl ListView {
             ex_flags: None,
             style: ListViewStyle::Simple,
             parent: None,
-            item_count: 0
+            item_count: 0,
+            data_source: None,
+            column_header: false,
         }
     }
 
+    /**
+        Displays an editor over the cell at `row_index` and `column_index`. The editor is initialized with the text of the cell.
+        Enter or a click outside the editor saves the text with `update_item`. Escape cancels the edit.
+
+        Returns `false` if the cell does not exist or if the edit was cancelled by `OnListViewBeginEdit`.
+        The edit in progress (if any) is saved first.
+    */
+    pub fn edit_item(&self, row_index: usize, column_index: usize, editor: ListViewEditor) -> bool {
+        use winapi::um::commctrl::{LVM_GETSUBITEMRECT, LVIR_BOUNDS, LVIR_LABEL};
+        use winapi::shared::windef::RECT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.end_edit(true);
+
+        if !self.has_item(row_index, column_index) {
+            return false;
+        }
+
+        let mut rect = RECT { left: if column_index == 0 { LVIR_LABEL } else { LVIR_BOUNDS }, top: column_index as _, right: 0, bottom: 0 };
+        if wh::send_message(handle, LVM_GETSUBITEMRECT, row_index as _, &mut rect as *mut RECT as _) == 0 {
+            return false;
+        }
+
+        if !send_edit_event(handle, NWG_LIST_VIEW_BEGIN_EDIT, row_index, column_index, None).0 {
+            return false;
+        }
+
+        let text = self.item(row_index, column_index, EDIT_BUFFER_SIZE).map(|i| i.text).unwrap_or_default();
+        let position = (rect.left, rect.top);
+        let size = (rect.right - rect.left, rect.bottom - rect.top);
+
+        let mut cell = CellEditor { row_index, column_index, ..Default::default() };
+        let built = match editor {
+            ListViewEditor::TextInput => {
+                TextInput::builder()
+                    .text(&text)
+                    .position(position)
+                    .size(size)
+                    .parent(&self.handle)
+                    .build(&mut cell.input)
+            },
+            #[cfg(feature="combobox")]
+            ListViewEditor::ComboBox(choices) => {
+                let selected = choices.iter().position(|c| c == &text);
+                ComboBox::builder()
+                    .collection(choices)
+                    .selected_index(selected)
+                    .position(position)
+                    .size((size.0, size.1 + EDIT_COMBO_LIST_HEIGHT))
+                    .parent(&self.handle)
+                    .build(&mut cell.combo)
+            },
+        };
+
+        if built.is_err() {
+            return false;
+        }
+
+        cell.hook_keys(handle);
+        cell.set_focus();
+        *self.editor.borrow_mut() = Some(cell);
+
+        true
+    }
+
+    /// Closes the editor opened by `edit_item`. If `save` is true, the text is saved like if the user pressed Enter.
+    /// Does nothing if no cell is being edited.
+    pub fn end_edit(&self, save: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        finish_cell_edit(handle, &self.editor, save);
+    }
+
+    /// Returns the (row_index, column_index) of the cell being edited with `edit_item`
+    pub fn editing(&self) -> Option<(usize, usize)> {
+        self.editor.borrow().as_ref().map(|e| (e.row_index, e.column_index))
+    }
+
+    /// Starts editing the label (the text in the first column) of an item. The list view must have the `EDIT_LABELS` flag.
+    /// Returns `false` if the edit could not start.
+    pub fn edit_label(&self, row_index: usize) -> bool {
+        use winapi::um::commctrl::LVM_EDITLABELW;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.set_focus();
+        wh::send_message(handle, LVM_EDITLABELW, row_index as _, 0) != 0
+    }
+
+    /// Sets the sort indicator of a column header. `None` removes the indicator.
+    /// Does nothing if the list view has no header or if the column does not exist.
+    pub fn set_column_sort_arrow(&self, column_index: usize, arrow: Option<ListViewColumnSortArrow>) {
+        use winapi::um::commctrl::{HDM_GETITEMW, HDM_SETITEMW, HDITEMW, HDI_FORMAT, HDF_SORTUP, HDF_SORTDOWN};
+
+        let header = match self.header() {
+            Some(h) => h,
+            None => { return; }
+        };
+
+        let mut item: HDITEMW = unsafe { mem::zeroed() };
+        item.mask = HDI_FORMAT;
+
+        if wh::send_message(header, HDM_GETITEMW, column_index as _, &mut item as *mut HDITEMW as _) == 0 {
+            return;
+        }
+
+        item.fmt &= !(HDF_SORTUP | HDF_SORTDOWN);
+        item.fmt |= match arrow {
+            Some(ListViewColumnSortArrow::Ascending) => HDF_SORTUP,
+            Some(ListViewColumnSortArrow::Descending) => HDF_SORTDOWN,
+            None => 0
+        };
+
+        wh::send_message(header, HDM_SETITEMW, column_index as _, &mut item as *mut HDITEMW as _);
+    }
+
+    /// Returns the sort indicator of a column header
+    pub fn column_sort_arrow(&self, column_index: usize) -> Option<ListViewColumnSortArrow> {
+        use winapi::um::commctrl::{HDM_GETITEMW, HDITEMW, HDI_FORMAT, HDF_SORTUP, HDF_SORTDOWN};
+
+        let header = self.header()?;
+
+        let mut item: HDITEMW = unsafe { mem::zeroed() };
+        item.mask = HDI_FORMAT;
+
+        if wh::send_message(header, HDM_GETITEMW, column_index as _, &mut item as *mut HDITEMW as _) == 0 {
+            return None;
+        }
+
+        if item.fmt & HDF_SORTUP == HDF_SORTUP {
+            Some(ListViewColumnSortArrow::Ascending)
+        } else if item.fmt & HDF_SORTDOWN == HDF_SORTDOWN {
+            Some(ListViewColumnSortArrow::Descending)
+        } else {
+            None
+        }
+    }
+
+    /**
+        Sorts the items of the list view. `compare` receives the row indices of two items (as they were before the sort started)
+        and returns their order. Use `item` to read the values of the rows inside the comparator.
+
+        Does nothing on a virtual list view: sort the data source instead and call `redraw_items`.
+    */
+    pub fn sort_items<F: FnMut(usize, usize) -> Ordering>(&self, mut compare: F) {
+        use winapi::um::commctrl::LVM_SORTITEMSEX;
+
+        if self.is_virtual() {
+            return;
+        }
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut compare: &mut dyn FnMut(usize, usize) -> Ordering = &mut compare;
+        let compare_ptr = &mut compare as *mut &mut dyn FnMut(usize, usize) -> Ordering;
+
+        wh::send_message(handle, LVM_SORTITEMSEX, compare_ptr as _, sort_items_proc as usize as _);
+    }
+
+    /**
+        Sorts the items of the list view using the text of a column. `compare` receives the text of the
+        cells in `column_index` of two items and returns their order.
+
+        Does nothing on a virtual list view.
+    */
+    pub fn sort_items_by_text<F: FnMut(&str, &str) -> Ordering>(&self, column_index: usize, mut compare: F) {
+        const TEXT_BUFFER_SIZE: usize = 512;
+
+        if self.is_virtual() {
+            return;
+        }
+
+        let texts: Vec<String> = (0..self.len())
+            .map(|row| self.item(row, column_index, TEXT_BUFFER_SIZE).map(|i| i.text).unwrap_or_default())
+            .collect();
+
+        self.sort_items(|a, b| compare(&texts[a], &texts[b]));
+    }
+
+    /// Returns the handle of the header control of the list view
+    fn header(&self) -> Option<HWND> {
+        use winapi::um::commctrl::LVM_GETHEADER;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        match wh::send_message(handle, LVM_GETHEADER, 0, 0) {
+            0 => None,
+            header => Some(header as HWND)
+        }
+    }
+
+    /// Returns `true` if the list view was created with the `VIRTUAL` flag
+    pub fn is_virtual(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::get_style(handle) & LVS_OWNERDATA == LVS_OWNERDATA
+    }
+
+    /// Returns the data source of a virtual list view
+    pub fn data_source(&self) -> Option<Rc<dyn ListViewDataSource>> {
+        self.data_source.borrow().clone()
+    }
+
+    /// Sets the data source of a virtual list view and sets the item count to the length of the data source.
+    /// Does nothing if the list view is not virtual.
+    pub fn set_data_source(&self, source: Option<Rc<dyn ListViewDataSource>>) {
+        if !self.is_virtual() {
+            return;
+        }
+
+        let len = source.as_ref().map(|s| s.len()).unwrap_or(0);
+        *self.data_source.borrow_mut() = source;
+
+        self.set_item_count(len as u32);
+        self.invalidate();
+    }
+
+    /// Redraws the items from `first` to `last` (inclusive). In a virtual list view, the items are queried again from the data source.
+    pub fn redraw_items(&self, first: usize, last: usize) {
+        use winapi::um::commctrl::LVM_REDRAWITEMS;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_REDRAWITEMS, first as _, last as _);
+    }
+
     /// Sets the image list of the listview
     /// A listview can accept different kinds of image list. See `ListViewImageListType`
     #[cfg(feature="image-list")]
@@ -316,6 +768,92 @@ impl ListView {
         ]
     }
 
+    /**
+        Sets the colors of the item at `row_index`. If `column_index` is `None`, the colors are used for the whole row,
+        otherwise they are only used for the cell. `None` removes the colors.
+        The colors follow the item when the items are sorted or when other items are removed.
+
+        Does nothing if the index is out of bounds or if the list view is virtual (see `ListViewDataSource::colors`).
+    */
+    pub fn set_item_colors(&self, row_index: usize, column_index: Option<usize>, colors: Option<ListViewItemColors>) {
+        if self.is_virtual() || !self.has_item(row_index, 0) {
+            return;
+        }
+
+        let item_id = match (self.item_id(row_index), colors.is_some()) {
+            (0, false) => { return; },
+            (0, true) => self.set_item_id(row_index),
+            (id, _) => id
+        };
+
+        {
+            let mut all_colors = self.colors.borrow_mut();
+            let item_colors = all_colors.items.entry(item_id).or_default();
+            match column_index {
+                Some(column) => match colors {
+                    Some(c) => { item_colors.cells.insert(column, c); },
+                    None => { item_colors.cells.remove(&column); }
+                },
+                None => { item_colors.row = colors; }
+            }
+        }
+
+        self.redraw_items(row_index, row_index);
+    }
+
+    /// Returns the colors of the item at `row_index` set by `set_item_colors`.
+    /// If `column_index` is `None`, returns the colors of the whole row.
+    pub fn item_colors(&self, row_index: usize, column_index: Option<usize>) -> Option<ListViewItemColors> {
+        if let Some(source) = self.data_source() {
+            return source.colors(row_index, column_index.unwrap_or(0));
+        }
+
+        let item_id = self.item_id(row_index);
+        let all_colors = self.colors.borrow();
+        let item_colors = all_colors.items.get(&item_id)?;
+        match column_index {
+            Some(column) => item_colors.cells.get(&column).cloned(),
+            None => item_colors.row
+        }
+    }
+
+    /// Returns the id used to find the colors of an item (stored in the lParam of the item). 0 if the item has no id.
+    fn item_id(&self, row_index: usize) -> LPARAM {
+        use winapi::um::commctrl::{LVM_GETITEMW, LVIF_PARAM};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.mask = LVIF_PARAM;
+        item.iItem = row_index as _;
+
+        match wh::send_message(handle, LVM_GETITEMW, 0, &mut item as *mut LVITEMW as _) {
+            0 => 0,
+            _ => item.lParam
+        }
+    }
+
+    fn set_item_id(&self, row_index: usize) -> LPARAM {
+        use winapi::um::commctrl::{LVM_SETITEMW, LVIF_PARAM};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let id = {
+            let mut colors = self.colors.borrow_mut();
+            colors.next_id += 1;
+            colors.next_id
+        };
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.mask = LVIF_PARAM;
+        item.iItem = row_index as _;
+        item.lParam = id;
+
+        wh::send_message(handle, LVM_SETITEMW, 0, &mut item as *mut LVITEMW as _);
+
+        id
+    }
+
     /// Returns the index of the selected column. Only available if Comclt32.dll version is >= 6.0.
     pub fn selected_column(&self) -> usize {
         use winapi::um::commctrl::LVM_GETSELECTEDCOLUMN;
@@ -459,7 +997,7 @@ impl ListView {
 
     /// Select or unselect an item at `row_index`. Does nothing if the index is out of bounds.
     pub fn select_item(&self, row_index: usize, selected: bool) {
-        use winapi::um::commctrl::{LVM_SETITEMW, LVIF_STATE, LVIS_SELECTED};
+        use winapi::um::commctrl::{LVM_SETITEMSTATE, LVIF_STATE, LVIS_SELECTED};
 
         if !self.has_item(row_index, 0) {
             return;
@@ -468,12 +1006,11 @@ impl ListView {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
         let mut item: LVITEMW = unsafe { mem::zeroed() };
-        item.iItem = row_index as _;
         item.mask = LVIF_STATE;
         item.state = match selected { true => LVIS_SELECTED, false => 0 };
         item.stateMask = LVIS_SELECTED;
 
-        wh::send_message(handle, LVM_SETITEMW , 0, &mut item as *mut LVITEMW as _);
+        wh::send_message(handle, LVM_SETITEMSTATE, row_index as _, &mut item as *mut LVITEMW as _);
     }
 
     /// Returns the index of the first selected item.
@@ -498,7 +1035,7 @@ impl ListView {
         use winapi::um::commctrl::{LVM_GETNEXTITEMINDEX, LVNI_SELECTED, LVITEMINDEX};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
-        let mut indices = Vec::with_capacity(self.len());
+        let mut indices = Vec::with_capacity(self.selected_count());
 
         let mut i_data = LVITEMINDEX { iItem: -1, iGroup: -1 };
         
@@ -509,10 +1046,179 @@ impl ListView {
         indices
     }
 
+    /// Checks or unchecks the item at `row_index`. The list view must have the `CHECK_BOXES` extended flag.
+    /// Does nothing if the index is out of bounds or if the list view is virtual (see `ListViewDataSource::state_image`).
+    pub fn check_item(&self, row_index: usize, checked: bool) {
+        use winapi::um::commctrl::{LVM_SETITEMSTATE, LVIF_STATE};
+
+        if self.is_virtual() || !self.has_item(row_index, 0) {
+            return;
+        }
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let image = match checked { true => CHECKED_STATE_IMAGE, false => UNCHECKED_STATE_IMAGE };
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.mask = LVIF_STATE;
+        item.state = image << 12;
+        item.stateMask = LVIS_STATEIMAGEMASK;
+
+        wh::send_message(handle, LVM_SETITEMSTATE, row_index as _, &mut item as *mut LVITEMW as _);
+    }
+
+    /// Returns true if the item at `row_index` is checked
+    pub fn item_checked(&self, row_index: usize) -> bool {
+        use winapi::um::commctrl::LVM_GETITEMSTATE;
+
+        if let Some(source) = self.data_source() {
+            return source.state_image(row_index) == Some(CHECKED_STATE_IMAGE);
+        }
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let state = wh::send_message(handle, LVM_GETITEMSTATE, row_index as _, LVIS_STATEIMAGEMASK as _) as u32;
+        (state & LVIS_STATEIMAGEMASK) >> 12 == CHECKED_STATE_IMAGE
+    }
+
+    /// Returns the indices of every checked items
+    pub fn checked_items(&self) -> Vec<usize> {
+        (0..self.len()).filter(|&i| self.item_checked(i)).collect()
+    }
+
+    /// Displays the items in groups. Only the items added to a group are visible in group view.
+    pub fn set_groups_enabled(&self, enabled: bool) {
+        use winapi::um::commctrl::LVM_ENABLEGROUPVIEW;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_ENABLEGROUPVIEW, enabled as _, 0);
+    }
+
+    /// Returns true if the items are displayed in groups
+    pub fn groups_enabled(&self) -> bool {
+        use winapi::um::commctrl::LVM_ISGROUPVIEWENABLED;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_ISGROUPVIEWENABLED, 0, 0) != 0
+    }
+
+    /// Inserts a group in the list view. Does nothing if a group with the same id already exists.
+    pub fn insert_group<I: Into<InsertListViewGroup>>(&self, insert: I) {
+        use winapi::um::commctrl::{LVM_INSERTGROUP, LVGROUP, LVGF_HEADER, LVGF_GROUPID, LVGF_STATE, LVGS_COLLAPSIBLE, LVGS_COLLAPSED};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let insert = insert.into();
+
+        let mut state = 0;
+        if insert.collapsible { state |= LVGS_COLLAPSIBLE; }
+        if insert.collapsed { state |= LVGS_COLLAPSED; }
+
+        let mut header = to_utf16(&insert.header);
+
+        let mut group: LVGROUP = unsafe { mem::zeroed() };
+        group.cbSize = mem::size_of::<LVGROUP>() as u32;
+        group.mask = LVGF_HEADER | LVGF_GROUPID | LVGF_STATE;
+        group.pszHeader = header.as_mut_ptr();
+        group.cchHeader = header.len() as i32;
+        group.iGroupId = insert.id;
+        group.stateMask = LVGS_COLLAPSIBLE | LVGS_COLLAPSED;
+        group.state = state;
+
+        let index = insert.index.unwrap_or(-1);
+        wh::send_message(handle, LVM_INSERTGROUP, index as _, &mut group as *mut LVGROUP as _);
+    }
+
+    /// Returns true if the list view has a group with the id `group_id`
+    pub fn has_group(&self, group_id: i32) -> bool {
+        use winapi::um::commctrl::LVM_HASGROUP;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_HASGROUP, group_id as _, 0) != 0
+    }
+
+    /// Removes a group. The items of the group are not removed, but they are not displayed in group view.
+    pub fn remove_group(&self, group_id: i32) {
+        use winapi::um::commctrl::LVM_REMOVEGROUP;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_REMOVEGROUP, group_id as _, 0);
+    }
+
+    /// Returns the number of groups in the list view
+    pub fn group_len(&self) -> usize {
+        use winapi::um::commctrl::LVM_GETGROUPCOUNT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, LVM_GETGROUPCOUNT, 0, 0) as usize
+    }
+
+    /// Collapses or expands a group
+    pub fn set_group_collapsed(&self, group_id: i32, collapsed: bool) {
+        use winapi::um::commctrl::{LVM_SETGROUPINFO, LVGROUP, LVGF_STATE, LVGS_COLLAPSED};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut group: LVGROUP = unsafe { mem::zeroed() };
+        group.cbSize = mem::size_of::<LVGROUP>() as u32;
+        group.mask = LVGF_STATE;
+        group.stateMask = LVGS_COLLAPSED;
+        group.state = match collapsed { true => LVGS_COLLAPSED, false => 0 };
+
+        wh::send_message(handle, LVM_SETGROUPINFO, group_id as _, &mut group as *mut LVGROUP as _);
+    }
+
+    /// Returns true if the group is collapsed
+    pub fn group_collapsed(&self, group_id: i32) -> bool {
+        use winapi::um::commctrl::{LVM_GETGROUPSTATE, LVGS_COLLAPSED};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let state = wh::send_message(handle, LVM_GETGROUPSTATE, group_id as _, LVGS_COLLAPSED as _) as u32;
+        state & LVGS_COLLAPSED == LVGS_COLLAPSED
+    }
+
+    /// Moves the item at `row_index` into the group `group_id`. Does nothing if the index is out of bounds.
+    pub fn set_item_group(&self, row_index: usize, group_id: i32) {
+        use winapi::um::commctrl::{LVM_SETITEMW, LVIF_GROUPID};
+
+        if !self.has_item(row_index, 0) {
+            return;
+        }
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.mask = LVIF_GROUPID;
+        item.iItem = row_index as _;
+        item.iGroupId = group_id;
+
+        wh::send_message(handle, LVM_SETITEMW, 0, &mut item as *mut LVITEMW as _);
+    }
+
+    /// Returns the id of the group of the item at `row_index` or `None` if the item is not in a group
+    pub fn item_group(&self, row_index: usize) -> Option<i32> {
+        use winapi::um::commctrl::{LVM_GETITEMW, LVIF_GROUPID};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.mask = LVIF_GROUPID;
+        item.iItem = row_index as _;
+
+        match wh::send_message(handle, LVM_GETITEMW, 0, &mut item as *mut LVITEMW as _) {
+            0 => None,
+            _ if item.iGroupId < 0 => None,
+            _ => Some(item.iGroupId)
+        }
+    }
+
     /// Inserts a new item into the list view
+    /// Does nothing if the list view is virtual
     pub fn insert_item<I: Into<InsertListViewItem>>(&self, insert: I) {
         use winapi::um::commctrl::{LVM_INSERTITEMW, LVM_SETITEMW};
 
+        if self.is_virtual() {
+            return;
+        }
+
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let insert = insert.into();
 
@@ -562,6 +1268,9 @@ impl ListView {
         use winapi::um::commctrl::LVM_GETITEMW;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if self.is_virtual() {
+            return row_index < self.len() && column_index < self.column_len().max(1);
+        }
 
         let mut item: LVITEMW = unsafe { mem::zeroed() };
         item.iItem = row_index as _;
@@ -598,47 +1307,26 @@ impl ListView {
     }
 
     /// Updates the item at the selected position
-    /// Does nothing if there is no item at the selected position
+    /// Does nothing if there is no item at the selected position or if the list view is virtual
     pub fn update_item<I: Into<InsertListViewItem>>(&self, row_index: usize, data: I) {
-        use winapi::um::commctrl::LVM_SETITEMW;
-
-        if !self.has_item(row_index, 0) {
+        if self.is_virtual() || !self.has_item(row_index, 0) {
             return;
         }
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
-        let insert = data.into();
-
-        let mut mask = check_image_mask(&insert);
-        if insert.text.is_some() {
-            mask |= LVIF_TEXT;
-        }
-
-        let image = check_image(&insert);
-
-        let use_text = insert.text.is_some();
-        let text = insert.text.unwrap_or("".to_string());
-        let mut text = to_utf16(&text);
-
-        let mut item: LVITEMW = unsafe { mem::zeroed() };
-        item.mask = mask;
-        item.iItem = row_index as _;
-        item.iImage = image;
-        item.iSubItem = insert.column_index as _;
-
-        if use_text {
-            item.pszText = text.as_mut_ptr();
-            item.cchTextMax = text.len() as i32;
-        }
-        
-        wh::send_message(handle, LVM_SETITEMW , 0, &mut item as *mut LVITEMW as _);
+        update_list_item(handle, row_index, data.into());
     }
 
     /// Remove all items on the seleted row. Returns `true` if an item was removed or false otherwise.
     /// To "remove" an item without deleting the row, use `update_item` and set the text to "".
+    /// Always returns `false` if the list view is virtual.
     pub fn remove_item(&self, row_index: usize) -> bool {
         use winapi::um::commctrl::LVM_DELETEITEM;
 
+        if self.is_virtual() {
+            return false;
+        }
+
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LVM_DELETEITEM , row_index as _, 0) == 1
     }
@@ -707,6 +1395,7 @@ impl ListView {
 
     /// Preallocate space for n number of item in the whole control.
     /// For example calling this method with n=1000 while the list has 500 items will add space for 500 new items.
+    /// In a virtual list view, this sets the number of items.
     pub fn set_item_count(&self, n: u32) {
         use winapi::um::commctrl::LVM_SETITEMCOUNT;
 
@@ -749,9 +1438,14 @@ impl ListView {
     }
 
     /// Removes all item from the listview
+    /// Does nothing if the list view is virtual. Use `set_item_count` instead.
     pub fn clear(&self) {
         use winapi::um::commctrl::LVM_DELETEALLITEMS;
 
+        if self.is_virtual() {
+            return;
+        }
+
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LVM_DELETEALLITEMS, 0, 0);
     }
@@ -817,6 +1511,77 @@ impl ListView {
         unsafe { wh::set_window_position(handle, x, y) }
     }
 
+    /// Handles the notifications sent to the parent (virtual items and label edits) and the messages of the cell editor
+    fn hook_list_view(&self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::winuser::{NMHDR, WM_NOTIFY, WM_VSCROLL, WM_HSCROLL, WM_MOUSEWHEEL};
+        use winapi::um::commctrl::{LVM_SETCALLBACKMASK, LVN_BEGINLABELEDITW, LVN_ENDLABELEDITW, LVN_GETINFOTIPW,
+            LVN_DELETEITEM, LVN_DELETEALLITEMS, NM_CUSTOMDRAW, NM_CLICK, NMLISTVIEW};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let data_source = self.data_source.clone();
+        let colors = self.colors.clone();
+
+        if self.is_virtual() {
+            // The state images are provided by the data source
+            wh::send_message(handle, LVM_SETCALLBACKMASK, LVIS_STATEIMAGEMASK as _, 0);
+        }
+
+        let handler0 = bind_raw_event_handler_inner(&parent_handle, handle as usize, move |_hwnd, msg, _w, l| {
+            if msg != WM_NOTIFY {
+                return None;
+            }
+
+            let nmhdr: &NMHDR = unsafe { &*(l as *const NMHDR) };
+            if nmhdr.hwndFrom != handle {
+                return None;
+            }
+
+            match nmhdr.code {
+                LVN_BEGINLABELEDITW | LVN_ENDLABELEDITW => unsafe { Some(label_edit_notify(handle, nmhdr.code, l)) },
+                LVN_GETINFOTIPW => unsafe { Some(info_tip_notify(handle, l)) },
+                NM_CLICK => unsafe { group_click_notify(handle, l) },
+                NM_CUSTOMDRAW => unsafe { custom_draw_notify(&colors.borrow(), data_source.borrow().as_ref(), l) },
+                LVN_DELETEITEM => {
+                    let data: &NMLISTVIEW = unsafe { &*(l as *const NMLISTVIEW) };
+                    colors.borrow_mut().items.remove(&data.lParam);
+                    None
+                },
+                LVN_DELETEALLITEMS => {
+                    colors.borrow_mut().items.clear();
+                    None
+                },
+                code => match data_source.borrow().clone() {
+                    Some(source) => unsafe { virtual_items_notify(&*source, code, l) },
+                    None => None
+                }
+            }
+        });
+
+        let editor = self.editor.clone();
+        let handler1 = bind_raw_event_handler_inner(&self.handle, handle as usize, move |hwnd, msg, w, l| {
+            match msg {
+                NWG_LIST_VIEW_EDITOR_DONE => {
+                    let current = editor.borrow().as_ref().and_then(|e| e.handle().hwnd()) == Some(l as HWND);
+                    if current {
+                        finish_cell_edit(hwnd, &editor, w != 0);
+                    }
+                    Some(0)
+                },
+                WM_VSCROLL | WM_HSCROLL | WM_MOUSEWHEEL => {
+                    // The editor does not follow the cell
+                    finish_cell_edit(hwnd, &editor, true);
+                    None
+                },
+                _ => None
+            }
+        });
+
+        *self.handler0.borrow_mut() = Some(handler0.unwrap());
+        *self.handler1.borrow_mut() = Some(handler1.unwrap());
+    }
+
     /// Winapi class name used during control creation
     pub fn class_name(&self) -> &'static str {
         ::winapi::um::commctrl::WC_LISTVIEW
@@ -843,6 +1608,15 @@ impl Drop for ListView {
             drop(unbind_raw_event_handler(h));
         }
 
+        let handler = self.handler1.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        if let Some(cell) = self.editor.borrow_mut().take() {
+            cell.close();
+        }
+
         self.handle.destroy();
     }
 }
@@ -855,6 +1629,8 @@ pub struct ListViewBuilder {
     ex_flags: Option<ListViewExFlags>,
     style: ListViewStyle,
     item_count: u32,
+    data_source: Option<Rc<dyn ListViewDataSource>>,
+    column_header: bool,
     parent: Option<ControlHandle>
 }
 
@@ -900,10 +1676,24 @@ impl ListViewBuilder {
         self
     }
 
+    pub fn data_source(mut self, source: Rc<dyn ListViewDataSource>) -> ListViewBuilder {
+        self.data_source = Some(source);
+        self
+    }
+
+    pub fn column_header(mut self, header: bool) -> ListViewBuilder {
+        self.column_header = header;
+        self
+    }
+
     pub fn build(self, out: &mut ListView) -> Result<(), NwgError> {
         let mut flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
         flags |= self.style.bits();
 
+        if self.data_source.is_some() {
+            flags |= LVS_OWNERDATA;
+        }
+
         let parent = match self.parent {
             Some(p) => Ok(p),
             None => Err(NwgError::no_parent("ListView"))
@@ -919,6 +1709,17 @@ impl ListViewBuilder {
             .parent(Some(parent))
             .build()?;
 
+        if self.column_header {
+            let handle = out.handle.hwnd().unwrap();
+            wh::set_style(handle, wh::get_style(handle) & !LVS_NOCOLUMNHEADER);
+        }
+
+        out.hook_list_view();
+
+        if self.data_source.is_some() {
+            out.set_data_source(self.data_source);
+        }
+
         if self.item_count > 0 {
             out.set_item_count(self.item_count);
         }
@@ -1029,3 +1830,398 @@ fn build_list_view_image(row_index: usize, column_index: usize, state: u32, text
         selected: state & LVIS_SELECTED == LVIS_SELECTED,
     }
 }
+
+/// The custom colors of the items of a regular list view, by item id
+#[derive(Default)]
+struct ListViewColors {
+    next_id: LPARAM,
+    items: HashMap<LPARAM, ListViewRowColors>,
+}
+
+#[derive(Default)]
+struct ListViewRowColors {
+    row: Option<ListViewItemColors>,
+    cells: HashMap<usize, ListViewItemColors>,
+}
+
+/// Paints the items with their custom colors. Does nothing if no item has custom colors.
+unsafe fn custom_draw_notify(colors: &ListViewColors, source: Option<&Rc<dyn ListViewDataSource>>, l: LPARAM) -> Option<LRESULT> {
+    use winapi::um::commctrl::{NMLVCUSTOMDRAW, CDDS_PREPAINT, CDDS_ITEMPREPAINT, CDDS_SUBITEM, CDRF_NOTIFYITEMDRAW,
+        CDRF_NOTIFYSUBITEMDRAW, CDRF_NEWFONT, CDRF_DODEFAULT, CLR_DEFAULT};
+    use winapi::um::wingdi::RGB;
+
+    fn apply(draw: &mut NMLVCUSTOMDRAW, colors: Option<ListViewItemColors>) {
+        let colors = colors.unwrap_or_default();
+        draw.clrText = colors.text.map(|[r, g, b]| RGB(r, g, b)).unwrap_or(CLR_DEFAULT);
+        draw.clrTextBk = colors.background.map(|[r, g, b]| RGB(r, g, b)).unwrap_or(CLR_DEFAULT);
+    }
+
+    if source.is_none() && colors.items.is_empty() {
+        return None;
+    }
+
+    let draw = &mut *(l as *mut NMLVCUSTOMDRAW);
+    let stage = draw.nmcd.dwDrawStage;
+
+    if stage == CDDS_PREPAINT {
+        Some(CDRF_NOTIFYITEMDRAW as LRESULT)
+    } else if stage == CDDS_ITEMPREPAINT {
+        // Each cell must be painted separately if the colors of the cells are not the same
+        let row_colors = match source {
+            Some(_) => { return Some(CDRF_NOTIFYSUBITEMDRAW as LRESULT); },
+            None => colors.items.get(&draw.nmcd.lItemlParam)
+        };
+
+        match row_colors {
+            Some(row) if !row.cells.is_empty() => Some(CDRF_NOTIFYSUBITEMDRAW as LRESULT),
+            Some(row) => {
+                apply(draw, row.row);
+                Some(CDRF_NEWFONT as LRESULT)
+            },
+            None => Some(CDRF_DODEFAULT as LRESULT)
+        }
+    } else if stage == CDDS_ITEMPREPAINT | CDDS_SUBITEM {
+        let (row_index, column_index) = (draw.nmcd.dwItemSpec as usize, draw.iSubItem as usize);
+        let cell_colors = match source {
+            Some(source) => source.colors(row_index, column_index),
+            None => colors.items.get(&draw.nmcd.lItemlParam).and_then(|row| row.cells.get(&column_index).cloned().or(row.row))
+        };
+
+        apply(draw, cell_colors);
+        Some(CDRF_NEWFONT as LRESULT)
+    } else {
+        None
+    }
+}
+
+/// Raises `OnListViewInfoTip` and copies the text set by the event handlers in the info tip buffer.
+/// If no text is set, the default info tip is displayed (the full text of a truncated item).
+unsafe fn info_tip_notify(list: HWND, l: LPARAM) -> LRESULT {
+    use winapi::um::commctrl::NMLVGETINFOTIPW;
+    use crate::ListViewInfoTipData;
+    use std::ptr;
+
+    let info = &mut *(l as *mut NMLVGETINFOTIPW);
+
+    let mut text: Option<String> = None;
+    let data = ListViewInfoTipData { row_index: info.iItem as usize, text: &mut text as *mut Option<String> };
+    wh::send_message(list, NWG_LIST_VIEW_INFO_TIP, 0, &data as *const ListViewInfoTipData as LPARAM);
+
+    if let Some(text) = text {
+        if !info.pszText.is_null() && info.cchTextMax > 0 {
+            let text = to_utf16(&text);
+            let count = text.len().min(info.cchTextMax as usize);
+            ptr::copy_nonoverlapping(text.as_ptr(), info.pszText, count);
+            *info.pszText.add(count - 1) = 0;
+        }
+    }
+
+    0
+}
+
+/// Raises `OnListViewGroupClick` if the user clicked on a group header
+unsafe fn group_click_notify(list: HWND, l: LPARAM) -> Option<LRESULT> {
+    use winapi::um::commctrl::{NMITEMACTIVATE, LVHITTESTINFO, LVM_HITTEST, LVHT_EX_GROUP_HEADER, LVM_GETGROUPINFOBYINDEX,
+        LVGROUP, LVGF_GROUPID};
+
+    let click = &*(l as *const NMITEMACTIVATE);
+
+    let mut hit: LVHITTESTINFO = mem::zeroed();
+    hit.pt = click.ptAction;
+
+    // A wparam of -1 also tests the groups
+    wh::send_message(list, LVM_HITTEST, -1isize as _, &mut hit as *mut LVHITTESTINFO as _);
+    if hit.flags & LVHT_EX_GROUP_HEADER != LVHT_EX_GROUP_HEADER {
+        return None;
+    }
+
+    // On a group header, `iItem` is the index of the group
+    let mut group: LVGROUP = mem::zeroed();
+    group.cbSize = mem::size_of::<LVGROUP>() as u32;
+    group.mask = LVGF_GROUPID;
+    if wh::send_message(list, LVM_GETGROUPINFOBYINDEX, hit.iItem as _, &mut group as *mut LVGROUP as _) == 0 {
+        return None;
+    }
+
+    wh::send_message(list, NWG_LIST_VIEW_GROUP_CLICK, group.iGroupId as _, 0);
+
+    None
+}
+
+/// A cell editor opened by `ListView::edit_item`
+#[derive(Default)]
+struct CellEditor {
+    row_index: usize,
+    column_index: usize,
+    input: TextInput,
+    #[cfg(feature="combobox")]
+    combo: ComboBox<String>,
+    handler: Option<RawEventHandler>,
+}
+
+impl CellEditor {
+
+    fn handle(&self) -> ControlHandle {
+        #[cfg(feature="combobox")]
+        {
+            if !self.combo.handle.blank() {
+                return self.combo.handle;
+            }
+        }
+
+        self.input.handle
+    }
+
+    fn text(&self) -> String {
+        #[cfg(feature="combobox")]
+        {
+            if !self.combo.handle.blank() {
+                return self.combo.selection_string().unwrap_or_default();
+            }
+        }
+
+        self.input.text()
+    }
+
+    fn set_focus(&self) {
+        if let Some(hwnd) = self.handle().hwnd() {
+            unsafe { wh::set_focus(hwnd); }
+        }
+    }
+
+    /// Enter saves the edit, Escape cancels it. Leaving the editor saves the edit.
+    /// The editor cannot be destroyed while it is processing its own messages, so the list view is notified with a posted message.
+    /// The handle of the editor is sent with the message because the editor may be replaced before the message is received.
+    fn hook_keys(&mut self, list: HWND) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::winuser::{WM_KEYDOWN, WM_CHAR, WM_KILLFOCUS, WM_GETDLGCODE, VK_RETURN, VK_ESCAPE, DLGC_WANTALLKEYS};
+
+        let handle = self.handle();
+        let hwnd = match handle.hwnd() {
+            Some(h) => h,
+            None => { return; }
+        };
+
+        let handler = bind_raw_event_handler_inner(&handle, hwnd as usize, move |_hwnd, msg, w, _l| {
+            match msg {
+                WM_GETDLGCODE => Some(DLGC_WANTALLKEYS as LRESULT),
+                WM_KEYDOWN if w as i32 == VK_RETURN => {
+                    wh::post_message(list, NWG_LIST_VIEW_EDITOR_DONE, 1, hwnd as LPARAM);
+                    Some(0)
+                },
+                WM_KEYDOWN if w as i32 == VK_ESCAPE => {
+                    wh::post_message(list, NWG_LIST_VIEW_EDITOR_DONE, 0, hwnd as LPARAM);
+                    Some(0)
+                },
+                // Single line edits beep on Enter and Escape
+                WM_CHAR if w == 0x0D || w == 0x1B => Some(0),
+                WM_KILLFOCUS => {
+                    wh::post_message(list, NWG_LIST_VIEW_EDITOR_DONE, 1, hwnd as LPARAM);
+                    None
+                },
+                _ => None
+            }
+        });
+
+        self.handler = handler.ok();
+    }
+
+    /// Unbinds the key handler and destroys the editor controls
+    fn close(mut self) {
+        if let Some(h) = self.handler.take() {
+            drop(unbind_raw_event_handler(&h));
+        }
+    }
+
+}
+
+
+/// Closes the cell editor of a list view. If `save` is true, raise `OnListViewEndEdit` and save the text of the editor.
+fn finish_cell_edit(list: HWND, editor: &RefCell<Option<CellEditor>>, save: bool) {
+    let cell = match editor.borrow_mut().take() {
+        Some(cell) => cell,
+        None => { return; }
+    };
+
+    let (row_index, column_index) = (cell.row_index, cell.column_index);
+    let text = cell.text();
+
+    // Give the focus back to the list view before the editor is destroyed
+    if cell.handle().hwnd().map(|h| unsafe { wh::get_focus(h) }).unwrap_or(false) {
+        unsafe { wh::set_focus(list); }
+    }
+
+    cell.close();
+
+    let text = match save {
+        true => Some(text),
+        false => None
+    };
+
+    let (allowed, replace) = send_edit_event(list, NWG_LIST_VIEW_END_EDIT, row_index, column_index, text.clone());
+    if !allowed {
+        return;
+    }
+
+    if let Some(text) = replace.or(text) {
+        update_list_item(list, row_index, InsertListViewItem {
+            index: Some(row_index as _),
+            column_index: column_index as _,
+            text: Some(text),
+
+            #[cfg(feature="image-list")]
+            image: None,
+        });
+
+        wh::send_message(list, winapi::um::commctrl::LVM_REDRAWITEMS, row_index, row_index as _);
+    }
+}
+
+/// Sends a `OnListViewBeginEdit` or `OnListViewEndEdit` event to the list view. Returns if the edit was allowed and the replacement text.
+fn send_edit_event(list: HWND, msg: u32, row_index: usize, column_index: usize, text: Option<String>) -> (bool, Option<String>) {
+    use crate::ListViewEditData;
+
+    let cancelled = msg == NWG_LIST_VIEW_END_EDIT && text.is_none();
+
+    let mut allow = true;
+    let mut replace = None;
+    let data = ListViewEditData { row_index, column_index, text, allow: &mut allow as *mut bool, replace: &mut replace as *mut Option<String> };
+    wh::send_message(list, msg, 0, &data as *const ListViewEditData as LPARAM);
+
+    (allow && !cancelled, replace)
+}
+
+/// Handles the label edit notifications. `LVN_BEGINLABELEDITW` returns `TRUE` to cancel the edit
+/// and `LVN_ENDLABELEDITW` returns `TRUE` to accept the new text.
+unsafe fn label_edit_notify(list: HWND, code: u32, l: LPARAM) -> LRESULT {
+    use winapi::um::commctrl::{LVN_BEGINLABELEDITW, NMLVDISPINFOW};
+    use crate::win32::base_helper::from_wide_ptr;
+
+    let info = &*(l as *const NMLVDISPINFOW);
+    let row_index = info.item.iItem as usize;
+
+    if code == LVN_BEGINLABELEDITW {
+        let (allowed, _) = send_edit_event(list, NWG_LIST_VIEW_BEGIN_EDIT, row_index, 0, None);
+        return (!allowed) as LRESULT;
+    }
+
+    let text = match info.item.pszText.is_null() {
+        true => None,
+        false => Some(from_wide_ptr(info.item.pszText, None))
+    };
+
+    match send_edit_event(list, NWG_LIST_VIEW_END_EDIT, row_index, 0, text) {
+        (true, Some(replace)) => {
+            // The list view only saves the text of the label edit control. Save the replacement and reject the edit.
+            update_list_item(list, row_index, InsertListViewItem {
+                index: Some(row_index as _),
+                column_index: 0,
+                text: Some(replace),
+
+                #[cfg(feature="image-list")]
+                image: None,
+            });
+            0
+        },
+        (allowed, None) => allowed as LRESULT,
+        (false, Some(_)) => 0,
+    }
+}
+
+/// Sets the text (and the image) of a list view item. Used by `ListView::update_item`.
+fn update_list_item(handle: HWND, row_index: usize, insert: InsertListViewItem) {
+    use winapi::um::commctrl::LVM_SETITEMW;
+
+    let mut mask = check_image_mask(&insert);
+    if insert.text.is_some() {
+        mask |= LVIF_TEXT;
+    }
+
+    let image = check_image(&insert);
+
+    let use_text = insert.text.is_some();
+    let text = insert.text.unwrap_or("".to_string());
+    let mut text = to_utf16(&text);
+
+    let mut item: LVITEMW = unsafe { mem::zeroed() };
+    item.mask = mask;
+    item.iItem = row_index as _;
+    item.iImage = image;
+    item.iSubItem = insert.column_index as _;
+
+    if use_text {
+        item.pszText = text.as_mut_ptr();
+        item.cchTextMax = text.len() as i32;
+    }
+
+    wh::send_message(handle, LVM_SETITEMW , 0, &mut item as *mut LVITEMW as _);
+}
+
+/// Comparison callback of `LVM_SORTITEMSEX`. `sort` is a pointer to the comparator of `ListView::sort_items`
+unsafe extern "system" fn sort_items_proc(row1: LPARAM, row2: LPARAM, sort: LPARAM) -> c_int {
+    let compare = &mut *(sort as *mut &mut dyn FnMut(usize, usize) -> Ordering);
+    match compare(row1 as usize, row2 as usize) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+/// Handles the notifications sent by a virtual list view to its parent
+unsafe fn virtual_items_notify(source: &dyn ListViewDataSource, code: u32, l: LPARAM) -> Option<LRESULT> {
+    use winapi::um::commctrl::{LVN_GETDISPINFOW, LVN_ODCACHEHINT, LVN_ODFINDITEMW, NMLVDISPINFOW, NMLVCACHEHINT, NMLVFINDITEMW,
+        LVIF_STATE, LVIS_STATEIMAGEMASK, LVFI_STRING, LVFI_PARTIAL};
+    use crate::win32::base_helper::from_wide_ptr;
+    use std::ptr;
+
+    match code {
+        LVN_GETDISPINFOW => {
+            let info = &mut *(l as *mut NMLVDISPINFOW);
+            let item = &mut info.item;
+            let (row, column) = (item.iItem as usize, item.iSubItem as usize);
+
+            if item.mask & LVIF_TEXT == LVIF_TEXT && !item.pszText.is_null() && item.cchTextMax > 0 {
+                let text = to_utf16(&source.text(row, column));
+                let count = (text.len() - 1).min(item.cchTextMax as usize - 1);
+                ptr::copy_nonoverlapping(text.as_ptr(), item.pszText, count);
+                *item.pszText.add(count) = 0;
+            }
+
+            if item.mask & LVIF_IMAGE == LVIF_IMAGE {
+                if let Some(image) = source.image(row, column) {
+                    item.iImage = image;
+                }
+            }
+
+            if item.mask & LVIF_STATE == LVIF_STATE {
+                if let Some(index) = source.state_image(row) {
+                    item.state = (item.state & !LVIS_STATEIMAGEMASK) | ((index << 12) & LVIS_STATEIMAGEMASK);
+                    item.stateMask |= LVIS_STATEIMAGEMASK;
+                }
+            }
+
+            Some(0)
+        },
+        LVN_ODCACHEHINT => {
+            let hint = &*(l as *const NMLVCACHEHINT);
+            source.cache_hint(hint.iFrom as usize, hint.iTo as usize);
+            Some(0)
+        },
+        LVN_ODFINDITEMW => {
+            let find = &*(l as *const NMLVFINDITEMW);
+            if find.lvfi.flags & (LVFI_STRING | LVFI_PARTIAL) == 0 || find.lvfi.psz.is_null() {
+                return Some(-1);
+            }
+
+            let text = from_wide_ptr(find.lvfi.psz as *mut u16, None);
+            let partial = find.lvfi.flags & LVFI_PARTIAL == LVFI_PARTIAL;
+            let start = find.iStart.max(0) as usize;
+
+            match source.find_item(&text, start, partial) {
+                Some(index) => Some(index as LRESULT),
+                None => Some(-1)
+            }
+        },
+        _ => None
+    }
+}
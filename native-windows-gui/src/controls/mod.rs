@@ -74,7 +74,23 @@ mod frame;
 #[cfg(feature = "scroll-bar")]
 mod scroll_bar;
 
+#[cfg(feature = "splitter")]
+mod splitter;
+
+#[cfg(feature = "scroll-panel")]
+mod scroll_panel;
+
+#[cfg(feature = "global-hotkey")]
+mod global_hotkey;
+
+#[cfg(feature = "dialog")]
+mod dialog;
+
+#[cfg(feature = "wizard")]
+mod wizard;
+
 mod handle_from_control;
+mod fallible_control;
 
 pub use control_handle::ControlHandle;
 pub use control_base::{ControlBase, HwndBuilder, TimerBuilder as BaseTimerBuilder, OtherBuilder};
@@ -111,7 +127,10 @@ pub use control_base::HmenuBuilder;
 pub use timer::{Timer, TimerBuilder};
 
 #[cfg(feature = "notice")]
-pub use notice::{Notice, NoticeSender, NoticeBuilder};
+pub use notice::{Notice, NoticeSender, NoticeBuilder, NoticeChannel, NoticeChannelSender, NoticeChannelBuilder};
+
+#[cfg(feature = "notice")]
+pub(crate) use notice::NoticeQueue;
 
 #[cfg(feature = "combobox")]
 pub use combo_box::{ComboBox, ComboBoxFlags, ComboBoxBuilder};
@@ -141,11 +160,14 @@ pub use tray_notification::{TrayNotificationFlags, TrayNotification, TrayNotific
 pub use message_window::{MessageWindow, MessageWindowBuilder};
 
 #[cfg(feature = "list-view")]
-pub use list_view::{ListView, ListViewStyle, ListViewBuilder, ListViewFlags, ListViewExFlags, InsertListViewItem, ListViewItem, InsertListViewColumn, ListViewColumn};
+pub use list_view::{ListView, ListViewStyle, ListViewBuilder, ListViewFlags, ListViewExFlags, InsertListViewItem, ListViewItem, InsertListViewColumn, ListViewColumn, ListViewDataSource, ListViewColumnSortArrow, ListViewEditor, ListViewItemColors, InsertListViewGroup};
 
 #[cfg(all(feature="list-view", feature="image-list"))]
 pub use list_view::ListViewImageListType;
 
+#[cfg(feature = "list-view")]
+pub(crate) use list_view::CHECKED_STATE_IMAGE;
+
 #[cfg(feature = "number-select")]
 pub use number_select::{NumberSelect, NumberSelectBuilder, NumberSelectFlags};
 
@@ -158,4 +180,19 @@ pub use frame::{Frame, FrameBuilder, FrameFlags};
 #[cfg(feature = "scroll-bar")]
 pub use scroll_bar::{ScrollBar, ScrollBarBuilder, ScrollBarFlags};
 
+#[cfg(feature = "splitter")]
+pub use splitter::{Splitter, SplitterBuilder, SplitterFlags, SplitterOrientation, SplitterPane};
+
+#[cfg(feature = "scroll-panel")]
+pub use scroll_panel::{ScrollPanel, ScrollPanelBuilder, ScrollPanelFlags};
+
+#[cfg(feature = "global-hotkey")]
+pub use global_hotkey::{GlobalHotkey, GlobalHotkeyBuilder};
+
+#[cfg(feature = "dialog")]
+pub use dialog::{Dialog, DialogBuilder, DialogResult};
+
+#[cfg(feature = "wizard")]
+pub use wizard::{Wizard, WizardBuilder, WizardPage, WizardPageBuilder, WizardStyle, WizardAction};
+
 pub use handle_from_control::*;
@@ -1,17 +1,21 @@
 use super::control_handle::ControlHandle;
-use crate::win32::{window_helper as wh, window::build_notice};
+use crate::win32::{window_helper as wh, window::{build_notice, register_notice_queue, unregister_notice_queue}};
 use crate::NwgError;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 
 
 const NOT_BOUND: &'static str = "Notice is not yet bound to a winapi object";
 const UNUSABLE_NOTICE: &'static str = "Notice parent window was freed";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: Notice handle is not Notice!";
+const CHANNEL_NOT_BOUND: &'static str = "NoticeChannel is not yet bound to a winapi object";
+const UNUSABLE_CHANNEL: &'static str = "NoticeChannel parent window was freed";
 
 /**
 An invisible component that can be triggered by other thread.
 
 A notice object do not send data between threads. Rust has already plenty of way to do this.
-The notice object only serve to "wake up" the GUI thread.
+The notice object only serve to "wake up" the GUI thread. To send values with the notice, use a `NoticeChannel`.
 
 A notice must have a parent window. If the parent is destroyed before the notice, the notice becomes invalid.
 
@@ -128,7 +132,7 @@ impl NoticeSender {
 
     /// Send a message to the thread of the parent `Notice` 
     pub fn notice(&self) {
-        use winapi::um::winuser::SendNotifyMessageW;
+        use crate::win32::backend::SendNotifyMessageW;
         use winapi::shared::minwindef::{WPARAM, LPARAM};
         use winapi::shared::windef::HWND;
 
@@ -166,3 +170,246 @@ impl NoticeBuilder {
     }
 
 }
+
+
+/// The values sent to a `NoticeChannel`. Shared between the channel, its senders and the `OnNotice` events data.
+pub(crate) struct NoticeQueue<T> {
+    state: Mutex<NoticeQueueState<T>>,
+}
+
+struct NoticeQueueState<T> {
+    values: VecDeque<T>,
+    closed: bool,
+}
+
+impl<T> NoticeQueue<T> {
+
+    fn new() -> NoticeQueue<T> {
+        NoticeQueue {
+            state: Mutex::new(NoticeQueueState { values: VecDeque::new(), closed: false })
+        }
+    }
+
+    pub(crate) fn drain(&self) -> Vec<T> {
+        self.state.lock().unwrap().values.drain(..).collect()
+    }
+
+    fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+        state.values.clear();
+    }
+
+}
+
+/**
+A notice that carries values from other threads to the GUI thread.
+
+Each value sent with a `NoticeChannelSender` is queued and a `OnNotice` event is raised on the GUI thread.
+The queued values can be read from the event data with `EventData::as_notice` or with `NoticeChannel::drain`.
+Values are received in the order they were sent. Because the values are queued before the event is raised,
+one event can receive several values and the next events may find the queue empty.
+
+A notice channel must have a parent window. If the parent window is destroyed, the values that were not received
+yet are kept until the channel is drained or dropped, and the senders start to return their value as an error.
+Dropping the channel also closes the senders.
+
+Requires the `notice` feature.
+
+## Example
+
+```rust
+use native_windows_gui as nwg;
+use std::thread;
+
+fn build_channel(channel: &mut nwg::NoticeChannel<String>, window: &nwg::Window) {
+    nwg::NoticeChannel::builder()
+        .parent(window)
+        .build(channel)
+        .expect("Failed to build notice channel");
+
+    let sender = channel.sender();
+    thread::spawn(move || {
+        sender.send("Work done".to_string()).ok();
+    });
+}
+
+fn on_notice(data: &nwg::EventData) {
+    if let Some(notice) = data.as_notice() {
+        for message in notice.drain::<String>() {
+            println!("{}", message);
+        }
+    }
+}
+```
+*/
+pub struct NoticeChannel<T: Send + 'static> {
+    pub handle: ControlHandle,
+    queue: Arc<NoticeQueue<T>>,
+}
+
+impl<T: Send + 'static> NoticeChannel<T> {
+
+    pub fn builder() -> NoticeChannelBuilder {
+        NoticeChannelBuilder {
+            parent: None
+        }
+    }
+
+    /// A shortcut over the builder API for the notice channel
+    pub fn create<C: Into<ControlHandle>>(parent: C) -> Result<NoticeChannel<T>, NwgError> {
+        let mut channel = Self::default();
+        Self::builder()
+            .parent(parent)
+            .build(&mut channel)?;
+
+        Ok(channel)
+    }
+
+    /// Checks if the channel is still usable. A channel becomes unusable when the parent window is destroyed.
+    /// This will also return false if the channel is not initialized.
+    pub fn valid(&self) -> bool {
+        if self.handle.blank() { return false; }
+        let (hwnd, _) = self.handle.notice().expect(BAD_HANDLE);
+        wh::window_valid(hwnd)
+    }
+
+    /// Return an handle to the channel window or `None` if the window was destroyed.
+    pub fn window_handle(&self) -> Option<ControlHandle> {
+        match self.valid() {
+            true => Some(ControlHandle::Hwnd(self.handle.notice().unwrap().0)),
+            false => None
+        }
+    }
+
+    /// Create a new `NoticeChannelSender` bound to this channel.
+    /// Panics if the channel was not initialized or if the parent window was destroyed.
+    pub fn sender(&self) -> NoticeChannelSender<T> {
+        if self.handle.blank() { panic!(CHANNEL_NOT_BOUND); }
+        if !self.valid() { panic!(UNUSABLE_CHANNEL); }
+        let (hwnd, id) = self.handle.notice().expect(BAD_HANDLE);
+
+        NoticeChannelSender {
+            hwnd: hwnd as usize,
+            id,
+            queue: self.queue.clone(),
+        }
+    }
+
+    /// Remove and return the values that were not received yet, oldest first
+    pub fn drain(&self) -> Vec<T> {
+        self.queue.drain()
+    }
+
+}
+
+impl<T: Send + 'static> Default for NoticeChannel<T> {
+    fn default() -> NoticeChannel<T> {
+        NoticeChannel {
+            handle: ControlHandle::NoHandle,
+            queue: Arc::new(NoticeQueue::new()),
+        }
+    }
+}
+
+impl<T: Send + 'static> PartialEq for NoticeChannel<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl<T: Send + 'static> Drop for NoticeChannel<T> {
+    fn drop(&mut self) {
+        if let Some((hwnd, id)) = self.handle.notice() {
+            unregister_notice_queue(hwnd, id);
+        }
+
+        self.queue.close();
+        self.handle.destroy();
+    }
+}
+
+/// NoticeChannelSender sends values to its parent `NoticeChannel` from another thread
+pub struct NoticeChannelSender<T: Send + 'static> {
+    hwnd: usize,
+    id: u32,
+    queue: Arc<NoticeQueue<T>>,
+}
+
+impl<T: Send + 'static> NoticeChannelSender<T> {
+
+    /// Queue a value and raise `OnNotice` on the thread of the parent `NoticeChannel`.
+    /// If the channel was dropped or if its parent window was destroyed, the value is returned as an error.
+    pub fn send(&self, value: T) -> Result<(), T> {
+        use crate::win32::backend::PostMessageW;
+        use winapi::shared::minwindef::{WPARAM, LPARAM};
+        use winapi::shared::windef::HWND;
+
+        let mut state = self.queue.state.lock().unwrap();
+        if state.closed {
+            return Err(value);
+        }
+
+        // The message is posted while the queue is locked so that the value can be taken back if the window is gone.
+        // Unlike `SendNotifyMessageW`, `PostMessageW` never runs the window procedure from here.
+        state.values.push_back(value);
+        let posted = unsafe { PostMessageW(self.hwnd as HWND, wh::NOTICE_MESSAGE, self.id as WPARAM, self.hwnd as LPARAM) != 0 };
+        if !posted {
+            state.closed = true;
+            return Err(state.values.pop_back().unwrap());
+        }
+
+        Ok(())
+    }
+
+}
+
+impl<T: Send + 'static> Clone for NoticeChannelSender<T> {
+    fn clone(&self) -> Self {
+        NoticeChannelSender {
+            hwnd: self.hwnd,
+            id: self.id,
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+
+pub struct NoticeChannelBuilder {
+    parent: Option<ControlHandle>
+}
+
+impl NoticeChannelBuilder {
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> NoticeChannelBuilder {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build<T: Send + 'static>(self, out: &mut NoticeChannel<T>) -> Result<(), NwgError> {
+        let parent = match self.parent {
+            Some(p) => match p.hwnd() {
+                Some(handle) => Ok(handle),
+                None => Err(NwgError::control_create("Wrong parent type"))
+            },
+            None => Err(NwgError::no_parent("NoticeChannel"))
+        }?;
+
+        // Close the senders of the previous channel if the control is rebuilt
+        if let Some((hwnd, id)) = out.handle.notice() {
+            unregister_notice_queue(hwnd, id);
+            out.queue.close();
+        }
+
+        let queue = Arc::new(NoticeQueue::new());
+        let handle = build_notice(parent);
+        let (hwnd, id) = handle.notice().unwrap();
+        register_notice_queue(hwnd, id, queue.clone());
+
+        out.handle = handle;
+        out.queue = queue;
+
+        Ok(())
+    }
+
+}
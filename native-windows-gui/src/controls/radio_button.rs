@@ -57,6 +57,9 @@ Note: Internally, radio buttons are `Button` and as such, they trigger the same
   * `MousePress(_)`: Generic mouse press events on the adio button
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
 
 
 ```rust
@@ -54,6 +54,9 @@ Note: Use `\r\n` to input a new line not just `\n`.
 **Control events:**
   * `OnMouseMove`:   Generic mouse mouse event
   * `OnMouseWheel`:  Generic mouse wheel event
+  * `OnMouseHorizontalWheel`:  Generic horizontal mouse wheel event
+  * `OnMouseHover`:  When the mouse rests over the control
+  * `OnMouseLeave`:  When the mouse leaves the control
   * `MousePress(_)`: Generic mouse press events on the button
   * `OnKeyPress`:    Generic key press event
   * `OnKeyRelease`:  Generic key release event
@@ -51,6 +51,9 @@ Requires the `scroll-bar` feature.
   * `MousePress(_)`: Generic mouse press events on the button
   * `OnMouseMove`: Generic mouse event
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
 
 ```rust
 use native_windows_gui as nwg;
@@ -0,0 +1,551 @@
+use winapi::shared::minwindef::{UINT, WPARAM, LPARAM, LRESULT};
+use winapi::shared::windef::HWND;
+use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_BORDER, WS_TABSTOP, WS_CHILD, WS_CLIPCHILDREN, WS_HSCROLL, WS_VSCROLL, WS_EX_CONTROLPARENT};
+use crate::win32::window_helper as wh;
+use crate::win32::base_helper::check_hwnd;
+use crate::win32::window::bind_raw_event_handler_inner;
+use crate::{NwgError, RawEventHandler, unbind_raw_event_handler, Layout};
+use super::{ControlBase, ControlHandle};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const NOT_BOUND: &'static str = "ScrollPanel is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: ScrollPanel handle is not HWND!";
+
+
+bitflags! {
+    /**
+        The scroll panel flags
+
+        * NONE:     No flags. Equivalent to a invisible panel without borders.
+        * VISIBLE:  The panel is immediatly visible after creation
+        * DISABLED: The panel chidlren cannot be interacted with by the user.
+        * BORDER:   The panel has a thin black border
+        * TAB_STOP: The panel can be selected using tab navigation. The content can then be scrolled with the keyboard.
+    */
+    pub struct ScrollPanelFlags: u32 {
+        const NONE = 0;
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const BORDER = WS_BORDER;
+        const TAB_STOP = WS_TABSTOP;
+    }
+}
+
+
+/// The state shared between the panel and its window handler
+struct ScrollPanelState {
+    /// The content point displayed at the top left of the panel
+    offset: (u32, u32),
+
+    /// How many pixels are scrolled by the arrow keys and the scrollbar arrows
+    line_step: u32,
+
+    /// The layout that places the children. If None, the children are moved by the panel.
+    layout: Option<Box<dyn Layout>>,
+
+    /// Set while the scrollbars are updated. Showing or hiding a scrollbar resizes the panel.
+    updating: bool,
+}
+
+impl ScrollPanelState {
+
+    /// Returns the size of the content. Either the layout minimum size or the space used by the children.
+    fn content_size(&self, hwnd: HWND) -> (u32, u32) {
+        if let Some(layout) = self.layout.as_ref() {
+            let [w, h] = layout.minimum_size();
+            return (w, h);
+        }
+
+        let (offset_x, offset_y) = self.offset;
+        let mut size = (0, 0);
+        wh::iterate_window_children(hwnd, |child| {
+            let (x, y) = unsafe { wh::get_window_position(child) };
+            let (w, h) = unsafe { wh::get_window_size(child) };
+            let right = (x + offset_x as i32).max(0) as u32 + w;
+            let bottom = (y + offset_y as i32).max(0) as u32 + h;
+            size = (size.0.max(right), size.1.max(bottom));
+        });
+
+        size
+    }
+
+    /// Moves the content so that `offset` is displayed at the top left of the panel
+    fn move_content(&mut self, hwnd: HWND, offset: (u32, u32)) {
+        let dx = offset.0 as i32 - self.offset.0 as i32;
+        let dy = offset.1 as i32 - self.offset.1 as i32;
+        self.offset = offset;
+
+        match self.layout.as_ref() {
+            Some(layout) => {
+                let (w, h) = unsafe { wh::get_window_size(hwnd) };
+                let [min_w, min_h] = layout.minimum_size();
+                layout.layout_in(-(offset.0 as i32), -(offset.1 as i32), w.max(min_w), h.max(min_h));
+            },
+            None => {
+                if dx == 0 && dy == 0 {
+                    return;
+                }
+
+                wh::iterate_window_children(hwnd, |child| unsafe {
+                    let (x, y) = wh::get_window_position(child);
+                    wh::set_window_position(child, x - dx, y - dy);
+                });
+            }
+        }
+    }
+
+}
+
+
+/**
+A scroll panel is a container that scrolls its children when they do not fit in it.
+
+The size of the content is computed from the space used by the children of the panel. If a layout is set with `set_layout`,
+the content size is the layout minimum size (ex: `GridLayout::min_size`) and the layout fills the panel if the panel is bigger.
+Call `fit_content` after the children were added, moved, or resized.
+
+The scrollbars are only displayed when the content does not fit in the panel. The content is scrolled with the scrollbars, the mouse wheel
+and with the keyboard (arrows, page up, page down, home, end) when the panel has the focus.
+
+Requires the `scroll-panel` feature.
+
+**Builder parameters:**
+  * `parent`:     **Required.** The panel parent container.
+  * `size`:       The panel size.
+  * `position`:   The panel position.
+  * `enabled`:    If the panel children can be used by the user.
+  * `flags`:      A combination of the ScrollPanelFlags values.
+  * `line_step`:  How many pixels are scrolled by the arrow keys and the scrollbar arrows.
+
+**Control events:**
+  * `MousePress(_)`: Generic mouse press events on the panel
+  * `OnMouseMove`: Generic mouse mouse event
+  * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
+
+```rust
+use native_windows_gui as nwg;
+fn build_form(panel: &mut nwg::ScrollPanel, layout: &nwg::GridLayout, window: &nwg::Window) {
+    nwg::ScrollPanel::builder()
+        .size((300, 300))
+        .parent(window)
+        .build(panel);
+
+    // Fields are added to `layout` with `panel` as their parent
+    nwg::GridLayout::builder()
+        .parent(&*panel)
+        .min_size([280, 1000])
+        .build(layout);
+
+    panel.set_layout(layout);
+}
+```
+*/
+#[derive(Default)]
+pub struct ScrollPanel {
+    pub handle: ControlHandle,
+    state: Rc<RefCell<ScrollPanelState>>,
+    handler0: RefCell<Option<RawEventHandler>>,
+}
+
+impl ScrollPanel {
+
+    pub fn builder() -> ScrollPanelBuilder {
+        ScrollPanelBuilder {
+            size: (100, 100),
+            position: (0, 0),
+            enabled: true,
+            flags: None,
+            parent: None,
+            line_step: 20,
+        }
+    }
+
+    /**
+        Sets the layout that places the children of the panel. The parent of the layout must be the panel.
+        The layout is resized by the panel and does not react to the panel resize events anymore.
+    */
+    pub fn set_layout<L: Layout + Clone + 'static>(&self, layout: &L) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        layout.set_nested(true);
+        self.state.borrow_mut().layout = Some(Box::new(layout.clone()));
+        update_panel(&self.state, handle, None);
+    }
+
+    /// Computes the content size again and updates the scrollbars.
+    /// Call this after children were added, moved, or resized.
+    pub fn fit_content(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        update_panel(&self.state, handle, None);
+    }
+
+    /// Returns the size of the content of the panel
+    pub fn content_size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().content_size(handle)
+    }
+
+    /// Returns the content point displayed at the top left of the panel
+    pub fn scroll_position(&self) -> (u32, u32) {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().offset
+    }
+
+    /// Scrolls the content so that the point (`x`, `y`) is displayed at the top left of the panel.
+    /// The position is kept inside the content.
+    pub fn scroll_to(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        update_panel(&self.state, handle, Some((x as i32, y as i32)));
+    }
+
+    /**
+        Scrolls the content until the control is fully visible. Does nothing if the control is already visible.
+        The control can be a child of the panel or a child of a child of the panel.
+
+        Panics if the control is not window-like or if it is not in the panel.
+    */
+    pub fn ensure_visible<C: Into<ControlHandle>>(&self, control: C) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let control = control.into().hwnd().expect("Control must be a window-like control (HWND handle)");
+
+        // Position of the control in the panel
+        let (mut x, mut y) = unsafe { wh::get_window_position(control) };
+        let mut parent = wh::get_window_parent(control);
+        while parent != handle {
+            if parent.is_null() {
+                panic!("Control is not in the scroll panel");
+            }
+
+            let (px, py) = unsafe { wh::get_window_position(parent) };
+            x += px;
+            y += py;
+            parent = wh::get_window_parent(parent);
+        }
+
+        let (w, h) = unsafe { wh::get_window_size(control) };
+        let (view_w, view_h) = unsafe { wh::get_window_size(handle) };
+        let (offset_x, offset_y) = self.state.borrow().offset;
+
+        let visible_range = |pos: i32, size: u32, offset: u32, view: u32| -> i32 {
+            let start = pos + offset as i32;
+            let end = start + size as i32;
+            if start < offset as i32 {
+                start
+            } else if end > (offset + view) as i32 {
+                (end - view as i32).min(start)
+            } else {
+                offset as i32
+            }
+        };
+
+        let target = (visible_range(x, w, offset_x, view_w), visible_range(y, h, offset_y, view_h));
+        update_panel(&self.state, handle, Some(target));
+    }
+
+    /// Returns true if the control currently has the keyboard focus
+    pub fn focus(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_focus(handle) }
+    }
+
+    /// Sets the keyboard focus on the panel.
+    pub fn set_focus(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_focus(handle); }
+    }
+
+    /// Returns true if the control user can interact with the control, return false otherwise
+    pub fn enabled(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_enabled(handle) }
+    }
+
+    /// Enable or disable the control
+    pub fn set_enabled(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_enabled(handle, v) }
+    }
+
+    /// Returns true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Returns the size of the panel in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Sets the size of the panel in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Returns the position of the panel in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Sets the position of the panel in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        crate::win32::scroll_panel::SCROLL_PANEL_CLASS_ID
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        WS_CHILD | WS_CLIPCHILDREN | WS_HSCROLL | WS_VSCROLL
+    }
+
+    /// Scrolls the content on the scrollbars, mouse wheel, and keyboard events
+    fn hook_panel_events(&self) {
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        let state = self.state.clone();
+
+        let handler = bind_raw_event_handler_inner(&self.handle, handle as usize, move |hwnd, msg, w, l| {
+            panel_events(&state, hwnd, msg, w, l)
+        });
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+
+}
+
+/**
+    Scrolls the content to `target` (or keep the current position if None) and updates the scrollbars.
+    The position is kept inside the content.
+*/
+fn update_panel(state: &Rc<RefCell<ScrollPanelState>>, hwnd: HWND, target: Option<(i32, i32)>) {
+    use winapi::um::winuser::{SB_HORZ, SB_VERT};
+
+    {
+        let mut state = state.borrow_mut();
+        if state.updating {
+            return;
+        }
+        state.updating = true;
+    }
+
+    // Showing or hiding a scrollbar changes the panel size, so the scrollbars are updated until the size is stable
+    for _ in 0..3 {
+        let (view_w, view_h) = unsafe { wh::get_window_size(hwnd) };
+
+        let (content_w, content_h, offset) = {
+            let mut state = state.borrow_mut();
+            let (content_w, content_h) = state.content_size(hwnd);
+            let (x, y) = target.unwrap_or((state.offset.0 as i32, state.offset.1 as i32));
+            let offset = (
+                (x.max(0) as u32).min(content_w.saturating_sub(view_w)),
+                (y.max(0) as u32).min(content_h.saturating_sub(view_h)),
+            );
+
+            state.move_content(hwnd, offset);
+            (content_w, content_h, offset)
+        };
+
+        unsafe {
+            wh::set_window_scroll(hwnd, SB_HORZ, content_w, view_w, offset.0);
+            wh::set_window_scroll(hwnd, SB_VERT, content_h, view_h, offset.1);
+        }
+
+        if unsafe { wh::get_window_size(hwnd) } == (view_w, view_h) {
+            break;
+        }
+    }
+
+    state.borrow_mut().updating = false;
+}
+
+fn panel_events(state: &Rc<RefCell<ScrollPanelState>>, hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> Option<LRESULT> {
+    use winapi::um::winuser::{WM_SIZE, WM_HSCROLL, WM_VSCROLL, WM_MOUSEWHEEL, WM_KEYDOWN, WM_LBUTTONDOWN, WHEEL_DELTA, GET_WHEEL_DELTA_WPARAM};
+    use winapi::um::winuser::{SB_LINEUP, SB_LINEDOWN, SB_PAGEUP, SB_PAGEDOWN, SB_THUMBTRACK, SB_THUMBPOSITION, SB_TOP, SB_BOTTOM, SB_HORZ, SB_VERT};
+    use winapi::um::winuser::{VK_LEFT, VK_UP, VK_RIGHT, VK_DOWN, VK_PRIOR, VK_NEXT, VK_HOME, VK_END};
+    use winapi::shared::minwindef::LOWORD;
+
+    let (offset_x, offset_y) = {
+        let (x, y) = state.borrow().offset;
+        (x as i32, y as i32)
+    };
+
+    let line = state.borrow().line_step as i32;
+    let (view_w, view_h) = unsafe { wh::get_window_size(hwnd) };
+    let (view_w, view_h) = (view_w as i32, view_h as i32);
+
+    let target = match msg {
+        WM_SIZE => Some((offset_x, offset_y)),
+        WM_HSCROLL | WM_VSCROLL if l == 0 => {
+            let (bar, offset, page) = match msg == WM_HSCROLL {
+                true => (SB_HORZ, offset_x, view_w),
+                false => (SB_VERT, offset_y, view_h),
+            };
+
+            let pos = match LOWORD(w as u32) as isize {
+                SB_LINEUP => Some(offset - line),
+                SB_LINEDOWN => Some(offset + line),
+                SB_PAGEUP => Some(offset - page),
+                SB_PAGEDOWN => Some(offset + page),
+                SB_THUMBTRACK | SB_THUMBPOSITION => Some(unsafe { wh::get_window_scroll_track(hwnd, bar) } as i32),
+                SB_TOP => Some(0),
+                SB_BOTTOM => Some(i32::max_value()),
+                _ => None
+            };
+
+            pos.map(|pos| match msg == WM_HSCROLL {
+                true => (pos, offset_y),
+                false => (offset_x, pos),
+            })
+        },
+        WM_MOUSEWHEEL => {
+            let delta = GET_WHEEL_DELTA_WPARAM(w) as i32;
+            let lines = -(delta * 3) / WHEEL_DELTA as i32;
+            update_panel(state, hwnd, Some((offset_x, offset_y + lines * line)));
+
+            // Do not forward the wheel to the parent window
+            return Some(0);
+        },
+        WM_KEYDOWN => match w as i32 {
+            VK_LEFT => Some((offset_x - line, offset_y)),
+            VK_RIGHT => Some((offset_x + line, offset_y)),
+            VK_UP => Some((offset_x, offset_y - line)),
+            VK_DOWN => Some((offset_x, offset_y + line)),
+            VK_PRIOR => Some((offset_x, offset_y - view_h)),
+            VK_NEXT => Some((offset_x, offset_y + view_h)),
+            VK_HOME => Some((offset_x, 0)),
+            VK_END => Some((offset_x, i32::max_value())),
+            _ => None
+        },
+        WM_LBUTTONDOWN => {
+            unsafe { wh::set_focus(hwnd); }
+            None
+        },
+        _ => None
+    };
+
+    if let Some(target) = target {
+        update_panel(state, hwnd, Some(target));
+    }
+
+    None
+}
+
+impl Drop for ScrollPanel {
+    fn drop(&mut self) {
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+        self.handle.destroy();
+    }
+}
+
+pub struct ScrollPanelBuilder {
+    size: (i32, i32),
+    position: (i32, i32),
+    enabled: bool,
+    flags: Option<ScrollPanelFlags>,
+    parent: Option<ControlHandle>,
+    line_step: u32,
+}
+
+impl ScrollPanelBuilder {
+
+    pub fn flags(mut self, flags: ScrollPanelFlags) -> ScrollPanelBuilder {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> ScrollPanelBuilder {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> ScrollPanelBuilder {
+        self.position = pos;
+        self
+    }
+
+    pub fn enabled(mut self, e: bool) -> ScrollPanelBuilder {
+        self.enabled = e;
+        self
+    }
+
+    pub fn line_step(mut self, step: u32) -> ScrollPanelBuilder {
+        self.line_step = step;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> ScrollPanelBuilder {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut ScrollPanel) -> Result<(), NwgError> {
+        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("ScrollPanel"))
+        }?;
+
+        *out = Default::default();
+        out.state.borrow_mut().line_step = self.line_step;
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .flags(flags)
+            .ex_flags(WS_EX_CONTROLPARENT)
+            .size(self.size)
+            .position(self.position)
+            .parent(Some(parent))
+            .build()?;
+
+        out.set_enabled(self.enabled);
+        out.hook_panel_events();
+        out.fit_content();
+
+        Ok(())
+    }
+
+}
+
+impl Default for ScrollPanelState {
+    fn default() -> ScrollPanelState {
+        ScrollPanelState {
+            offset: (0, 0),
+            line_step: 20,
+            layout: None,
+            updating: false,
+        }
+    }
+}
+
+impl PartialEq for ScrollPanel {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
@@ -0,0 +1,606 @@
+use winapi::shared::minwindef::{UINT, WPARAM, LPARAM, LRESULT};
+use winapi::shared::windef::HWND;
+use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP, WS_CHILD, WS_CLIPCHILDREN, WS_EX_CONTROLPARENT};
+use crate::win32::window_helper::{self as wh, NWG_SPLITTER_MOVED};
+use crate::win32::base_helper::check_hwnd;
+use crate::win32::window::bind_raw_event_handler_inner;
+use crate::{NwgError, RawEventHandler, unbind_raw_event_handler};
+use super::{ControlBase, ControlHandle};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const NOT_BOUND: &'static str = "Splitter is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: Splitter handle is not HWND!";
+
+
+bitflags! {
+    /**
+        The splitter flags
+
+        * NONE:     No flags. Equivalent to a invisible splitter.
+        * VISIBLE:  The splitter is immediatly visible after creation
+        * DISABLED: The splitter bar cannot be moved by the user
+        * TAB_STOP: The splitter bar can be selected using tab navigation
+    */
+    pub struct SplitterFlags: u32 {
+        const NONE = 0;
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const TAB_STOP = WS_TABSTOP;
+    }
+}
+
+/// How the panes of a splitter are placed
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SplitterOrientation {
+    /// The panes are placed side by side. The splitter bar is vertical and moves horizontally.
+    Horizontal,
+
+    /// The panes are placed on top of each other. The splitter bar is horizontal and moves vertically.
+    Vertical,
+}
+
+/// Identifies a pane of a splitter
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SplitterPane {
+    /// The left pane of an horizontal splitter or the top pane of a vertical splitter
+    First,
+
+    /// The right pane of an horizontal splitter or the bottom pane of a vertical splitter
+    Second,
+}
+
+
+/// The state shared between the splitter and its window handler
+struct SplitterState {
+    orientation: SplitterOrientation,
+    position: u32,
+    min_sizes: [u32; 2],
+    bar_size: u32,
+    keyboard_step: u32,
+    collapsed: Option<SplitterPane>,
+    panes: [Option<HWND>; 2],
+
+    /// Distance between the mouse and the start of the bar while the bar is dragged
+    drag_offset: Option<i32>,
+}
+
+impl SplitterState {
+
+    /// Returns the size of the splitter along the orientation axis
+    fn total(&self, hwnd: HWND) -> u32 {
+        let (w, h) = unsafe { wh::get_window_size(hwnd) };
+        match self.orientation {
+            SplitterOrientation::Horizontal => w,
+            SplitterOrientation::Vertical => h,
+        }
+    }
+
+    /// Keeps a position inside the minimum sizes of the panes. The first pane minimum size has the priority.
+    fn clamp(&self, position: u32, total: u32) -> u32 {
+        let [min_first, min_second] = self.min_sizes;
+        let max = total.saturating_sub(self.bar_size + min_second);
+        position.min(max).max(min_first)
+    }
+
+    /// Returns where the bar is displayed, including the collapsed state
+    fn bar_position(&self, total: u32) -> u32 {
+        match self.collapsed {
+            Some(SplitterPane::First) => 0,
+            Some(SplitterPane::Second) => total.saturating_sub(self.bar_size),
+            None => self.clamp(self.position, total),
+        }
+    }
+
+    fn layout(&self, hwnd: HWND) {
+        let (w, h) = unsafe { wh::get_window_size(hwnd) };
+        let total = self.total(hwnd);
+        let first_size = self.bar_position(total);
+        let second_start = first_size + self.bar_size;
+        let second_size = total.saturating_sub(second_start);
+
+        let rects = match self.orientation {
+            SplitterOrientation::Horizontal => [(0, 0, first_size, h), (second_start as i32, 0, second_size, h)],
+            SplitterOrientation::Vertical => [(0, 0, w, first_size), (0, second_start as i32, w, second_size)],
+        };
+
+        let panes = [SplitterPane::First, SplitterPane::Second];
+        for ((pane, handle), (x, y, width, height)) in panes.iter().zip(self.panes.iter()).zip(rects.iter()) {
+            let handle = match handle {
+                Some(h) => *h,
+                None => continue
+            };
+
+            let visible = self.collapsed != Some(*pane);
+            unsafe {
+                wh::set_window_visibility(handle, visible);
+                if visible {
+                    wh::set_window_position(handle, *x, *y);
+                    wh::set_window_size(handle, *width, *height, false);
+                }
+            }
+        }
+    }
+
+    /// Returns the mouse position along the orientation axis from a mouse message `LPARAM`
+    fn mouse_position(&self, l: LPARAM) -> i32 {
+        let x = (l & 0xFFFF) as i16 as i32;
+        let y = ((l >> 16) & 0xFFFF) as i16 as i32;
+        let (x, y) = unsafe { crate::win32::high_dpi::physical_to_logical(x, y) };
+        match self.orientation {
+            SplitterOrientation::Horizontal => x,
+            SplitterOrientation::Vertical => y,
+        }
+    }
+
+}
+
+impl Default for SplitterState {
+    fn default() -> SplitterState {
+        SplitterState {
+            orientation: SplitterOrientation::Horizontal,
+            position: 100,
+            min_sizes: [0, 0],
+            bar_size: 5,
+            keyboard_step: 10,
+            collapsed: None,
+            panes: [None, None],
+            drag_offset: None,
+        }
+    }
+}
+
+
+/**
+A splitter is a container with two panes separated by a bar that the user can drag to resize the panes.
+The bar can also be moved with the arrow keys when the splitter has the keyboard focus. Home and End move the bar to its minimum and maximum position.
+
+The panes are window-like controls that must be children of the splitter. Set them with `set_panes` after they are created.
+A pane can be a `Frame` to hold more than one control. The splitter itself can be placed in a layout like any other control.
+
+A pane can be collapsed. The other pane then takes all the space and the bar is moved to the side of the collapsed pane.
+Moving the bar restores the collapsed pane.
+
+`split_position` returns the position of the bar. It can be saved and restored later with `set_split_position`.
+
+Requires the `splitter` feature.
+
+**Builder parameters:**
+  * `parent`:         **Required.** The splitter parent container.
+  * `size`:           The splitter size.
+  * `position`:       The splitter position.
+  * `enabled`:        If the splitter bar can be moved by the user.
+  * `flags`:          A combination of the SplitterFlags values.
+  * `orientation`:    How the panes are placed. See `SplitterOrientation`.
+  * `split_position`: The size of the first pane, in pixels.
+  * `min_sizes`:      The minimum size of the first and the second pane.
+  * `bar_size`:       The thickness of the splitter bar.
+  * `keyboard_step`:  How many pixels the bar moves when an arrow key is pressed.
+  * `collapsed`:      The pane collapsed after the splitter creation.
+
+**Control events:**
+  * `OnSplitterMoved`: When the bar is moved by the user
+  * `MousePress(_)`: Generic mouse press events on the splitter bar
+  * `OnMouseMove`: Generic mouse mouse event
+  * `OnKeyPress`: Generic key press event
+
+```rust
+use native_windows_gui as nwg;
+fn build_splitter(splitter: &mut nwg::Splitter, window: &nwg::Window) {
+    nwg::Splitter::builder()
+        .orientation(nwg::SplitterOrientation::Horizontal)
+        .split_position(200)
+        .min_sizes([100, 100])
+        .parent(window)
+        .build(splitter);
+}
+
+fn set_panes(splitter: &nwg::Splitter, tree: &nwg::TreeView, list: &nwg::ListView) {
+    // `tree` and `list` were built with `splitter` as their parent
+    splitter.set_panes(tree, list);
+}
+```
+*/
+#[derive(Default)]
+pub struct Splitter {
+    pub handle: ControlHandle,
+    state: Rc<RefCell<SplitterState>>,
+    handler0: RefCell<Option<RawEventHandler>>,
+}
+
+impl Splitter {
+
+    pub fn builder() -> SplitterBuilder {
+        SplitterBuilder {
+            size: (300, 200),
+            position: (0, 0),
+            enabled: true,
+            flags: None,
+            parent: None,
+            orientation: SplitterOrientation::Horizontal,
+            split_position: 100,
+            min_sizes: [0, 0],
+            bar_size: 5,
+            keyboard_step: 10,
+            collapsed: None,
+        }
+    }
+
+    /// Sets the panes of the splitter. The panes must be children of the splitter.
+    /// Panics if the panes are not window-like controls.
+    pub fn set_panes<A: Into<ControlHandle>, B: Into<ControlHandle>>(&self, first: A, second: B) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let first = first.into().hwnd().expect("Splitter pane must be a window-like control (HWND handle)");
+        let second = second.into().hwnd().expect("Splitter pane must be a window-like control (HWND handle)");
+
+        let mut state = self.state.borrow_mut();
+        state.panes = [Some(first), Some(second)];
+        state.layout(handle);
+    }
+
+    /// Returns the orientation of the splitter
+    pub fn orientation(&self) -> SplitterOrientation {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().orientation
+    }
+
+    /// Returns the position of the bar, which is the size of the first pane, in pixels.
+    /// If a pane is collapsed, returns the position the bar will have once the pane is restored.
+    pub fn split_position(&self) -> u32 {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().position
+    }
+
+    /// Sets the position of the bar, which is the size of the first pane, in pixels.
+    /// The position is kept inside the minimum sizes of the panes. Does not restore a collapsed pane.
+    pub fn set_split_position(&self, position: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let mut state = self.state.borrow_mut();
+        let total = state.total(handle);
+        state.position = state.clamp(position, total);
+        state.layout(handle);
+    }
+
+    /// Returns the collapsed pane, if any
+    pub fn collapsed(&self) -> Option<SplitterPane> {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().collapsed
+    }
+
+    /// Collapse a pane. The other pane takes all the space. Use `None` to restore the collapsed pane.
+    pub fn collapse(&self, pane: Option<SplitterPane>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let mut state = self.state.borrow_mut();
+        state.collapsed = pane;
+        state.layout(handle);
+    }
+
+    /// Returns the minimum size of the first and the second pane
+    pub fn min_sizes(&self) -> [u32; 2] {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state.borrow().min_sizes
+    }
+
+    /// Sets the minimum size of the first and the second pane
+    pub fn set_min_sizes(&self, sizes: [u32; 2]) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let mut state = self.state.borrow_mut();
+        state.min_sizes = sizes;
+        state.layout(handle);
+    }
+
+    /// Returns true if the control currently has the keyboard focus
+    pub fn focus(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_focus(handle) }
+    }
+
+    /// Sets the keyboard focus on the splitter bar.
+    pub fn set_focus(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_focus(handle); }
+    }
+
+    /// Returns true if the user can move the splitter bar, return false otherwise
+    pub fn enabled(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_enabled(handle) }
+    }
+
+    /// Enable or disable the control
+    pub fn set_enabled(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_enabled(handle, v) }
+    }
+
+    /// Returns true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Returns the size of the splitter in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Sets the size of the splitter in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Returns the position of the splitter in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Sets the position of the splitter in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        crate::win32::splitter::SPLITTER_CLASS_ID
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE | WS_TABSTOP
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        WS_CHILD | WS_CLIPCHILDREN
+    }
+
+    /// Moves and resizes the panes when the splitter is resized or when the user moves the bar
+    fn hook_splitter_events(&self) {
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        let state = self.state.clone();
+
+        let handler = bind_raw_event_handler_inner(&self.handle, handle as usize, move |hwnd, msg, w, l| {
+            splitter_events(&state, hwnd, msg, w, l)
+        });
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+
+}
+
+/// Moves the bar following a user action. Sends `OnSplitterMoved` if the bar was moved.
+fn user_move(state: &Rc<RefCell<SplitterState>>, hwnd: HWND, position: i32) {
+    let moved = {
+        let mut state = state.borrow_mut();
+        let total = state.total(hwnd);
+        let position = state.clamp(position.max(0) as u32, total);
+
+        if state.collapsed.is_none() && state.position == position {
+            false
+        } else {
+            state.position = position;
+            state.collapsed = None;
+            state.layout(hwnd);
+            true
+        }
+    };
+
+    // The state must not be borrowed while the event handlers are called
+    if moved {
+        wh::send_message(hwnd, NWG_SPLITTER_MOVED, 0, 0);
+    }
+}
+
+fn splitter_events(state: &Rc<RefCell<SplitterState>>, hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> Option<LRESULT> {
+    use winapi::um::winuser::{WM_SIZE, WM_SETCURSOR, WM_LBUTTONDOWN, WM_MOUSEMOVE, WM_LBUTTONUP, WM_CAPTURECHANGED, WM_KEYDOWN, WM_GETDLGCODE};
+    use winapi::um::winuser::{VK_LEFT, VK_UP, VK_RIGHT, VK_DOWN, VK_HOME, VK_END, IDC_SIZEWE, IDC_SIZENS, DLGC_WANTARROWS};
+
+    match msg {
+        WM_SIZE => {
+            state.borrow().layout(hwnd);
+        },
+        WM_SETCURSOR => {
+            // Only the bar is not covered by the panes
+            if w as HWND == hwnd {
+                let cursor = match state.borrow().orientation {
+                    SplitterOrientation::Horizontal => IDC_SIZEWE,
+                    SplitterOrientation::Vertical => IDC_SIZENS,
+                };
+                unsafe { wh::set_system_cursor(cursor); }
+                return Some(1);
+            }
+        },
+        WM_GETDLGCODE => {
+            return Some(DLGC_WANTARROWS as LRESULT);
+        },
+        WM_LBUTTONDOWN => {
+            let mut state = state.borrow_mut();
+            let mouse = state.mouse_position(l);
+            let bar = state.bar_position(state.total(hwnd)) as i32;
+            if mouse >= bar && mouse < bar + state.bar_size as i32 {
+                state.drag_offset = Some(mouse - bar);
+                unsafe {
+                    wh::set_focus(hwnd);
+                    wh::set_capture(hwnd);
+                }
+            }
+        },
+        WM_MOUSEMOVE => {
+            let target = {
+                let state = state.borrow();
+                state.drag_offset.map(|offset| state.mouse_position(l) - offset)
+            };
+
+            if let Some(position) = target {
+                user_move(state, hwnd, position);
+            }
+        },
+        WM_LBUTTONUP => {
+            let dragging = state.borrow_mut().drag_offset.take().is_some();
+            if dragging {
+                unsafe { wh::release_capture(); }
+            }
+        },
+        WM_CAPTURECHANGED => {
+            state.borrow_mut().drag_offset = None;
+        },
+        WM_KEYDOWN => {
+            let target = {
+                let state = state.borrow();
+                let total = state.total(hwnd);
+                let current = state.bar_position(total) as i32;
+                let step = state.keyboard_step as i32;
+                match w as i32 {
+                    VK_LEFT | VK_UP => Some(current - step),
+                    VK_RIGHT | VK_DOWN => Some(current + step),
+                    VK_HOME => Some(0),
+                    VK_END => Some(total as i32),
+                    _ => None
+                }
+            };
+
+            if let Some(position) = target {
+                user_move(state, hwnd, position);
+            }
+        },
+        _ => {}
+    }
+
+    None
+}
+
+impl Drop for Splitter {
+    fn drop(&mut self) {
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+        self.handle.destroy();
+    }
+}
+
+pub struct SplitterBuilder {
+    size: (i32, i32),
+    position: (i32, i32),
+    enabled: bool,
+    flags: Option<SplitterFlags>,
+    parent: Option<ControlHandle>,
+    orientation: SplitterOrientation,
+    split_position: u32,
+    min_sizes: [u32; 2],
+    bar_size: u32,
+    keyboard_step: u32,
+    collapsed: Option<SplitterPane>,
+}
+
+impl SplitterBuilder {
+
+    pub fn flags(mut self, flags: SplitterFlags) -> SplitterBuilder {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> SplitterBuilder {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> SplitterBuilder {
+        self.position = pos;
+        self
+    }
+
+    pub fn enabled(mut self, e: bool) -> SplitterBuilder {
+        self.enabled = e;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: SplitterOrientation) -> SplitterBuilder {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn split_position(mut self, position: u32) -> SplitterBuilder {
+        self.split_position = position;
+        self
+    }
+
+    pub fn min_sizes(mut self, sizes: [u32; 2]) -> SplitterBuilder {
+        self.min_sizes = sizes;
+        self
+    }
+
+    pub fn bar_size(mut self, size: u32) -> SplitterBuilder {
+        self.bar_size = size;
+        self
+    }
+
+    pub fn keyboard_step(mut self, step: u32) -> SplitterBuilder {
+        self.keyboard_step = step;
+        self
+    }
+
+    pub fn collapsed(mut self, pane: Option<SplitterPane>) -> SplitterBuilder {
+        self.collapsed = pane;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> SplitterBuilder {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut Splitter) -> Result<(), NwgError> {
+        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("Splitter"))
+        }?;
+
+        *out = Default::default();
+
+        {
+            let mut state = out.state.borrow_mut();
+            state.orientation = self.orientation;
+            state.position = self.split_position;
+            state.min_sizes = self.min_sizes;
+            state.bar_size = self.bar_size;
+            state.keyboard_step = self.keyboard_step;
+            state.collapsed = self.collapsed;
+        }
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .flags(flags)
+            .ex_flags(WS_EX_CONTROLPARENT)
+            .size(self.size)
+            .position(self.position)
+            .parent(Some(parent))
+            .build()?;
+
+        out.set_enabled(self.enabled);
+        out.hook_splitter_events();
+
+        Ok(())
+    }
+
+}
+
+impl PartialEq for Splitter {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
@@ -56,6 +56,9 @@ Note: Use `\r\n` to input a new line not just `\n`.
   * `MousePress(_)`: Generic mouse press events on the button
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
   * `OnKeyPress`:    Generic key press event
   * `OnKeyRelease`:  Generic key release event
 
@@ -2,14 +2,15 @@
 A tree-view control is a window that displays a hierarchical list of items
 */
 
-use winapi::shared::minwindef::{WPARAM, LPARAM};
+use winapi::shared::minwindef::{WPARAM, LPARAM, LRESULT};
+use winapi::shared::windef::HWND;
 use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
 use winapi::um::commctrl::{HIMAGELIST, HTREEITEM, TVIS_EXPANDED, TVIS_SELECTED, TVITEMW};
-use crate::win32::window_helper as wh;
+use crate::win32::window_helper::{self as wh, NWG_TREE_ITEM_EXPANDING};
 use crate::win32::base_helper::{check_hwnd, to_utf16, from_utf16};
-use crate::{Font, NwgError};
+use crate::{Font, NwgError, RawEventHandler, unbind_raw_event_handler};
 use super::{ControlBase, ControlHandle};
-use std::{mem, ptr};
+use std::{mem, ptr, any::Any, cell::RefCell, rc::Rc, collections::HashMap};
 
 #[cfg(feature="image-list")]
 use crate::ImageList;
@@ -68,7 +69,7 @@ pub enum TreeInsert {
 }
 
 /// Possible state of a tree item regarding the "expanded/collapsed" state
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum ExpandState {
     Collapse,
@@ -124,20 +125,65 @@ Requires the `tree-view` feature
   * `MousePress(_)`: Generic mouse press events on the tree view
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+  * `OnMouseHover`: When the mouse rests over the control
+  * `OnMouseLeave`: When the mouse leaves the control
   * `OnTreeViewClick`: When the user has clicked the left mouse button within the control.
   * `OnTreeViewDoubleClick`: When the user has clicked the left mouse button within the control twice rapidly.
   * `OnTreeViewRightClick`: When the user has clicked the right mouse button within the control.
   * `OnTreeFocusLost`: When the control has lost the input focus
   * `OnTreeFocus`: When the control has acquired the input focus
   * `OnTreeItemDelete`: Just before an item is deleted. Also sent for all the children.
+  * `OnTreeItemExpanding`: Before an item is expanded or collapsed. Can be cancelled. Sends a `EventData::OnTreeItemExpanding`.
   * `OnTreeItemExpanded`: After an item was expanded or collapsed. Sends a `EventData::OnTreeItemUpdate`.
   * `OnTreeItemChanged`: After the state of an item was changed. Sends a `EventData::OnTreeItemUpdate`.
   * `OnTreeItemSelectionChanged`: After the current selection was changed. Sends a `EventData::OnTreeItemChanged`.
+
+**Item data:**
+Any value can be associated with an item with `set_item_data` (or `insert_item_with_data`) and read back with `item_data`.
+The value is dropped when the item is removed from the tree view.
+
+**Lazy loading:**
+With `set_lazy_children`, an item displays an expand button before its children are inserted.
+The children can then be inserted in `OnTreeItemExpanding`. If no children are inserted, the button is removed.
+
+```rust
+use native_windows_gui as nwg;
+use std::path::PathBuf;
+
+fn add_folder(tree: &nwg::TreeView, parent: Option<&nwg::TreeItem>, path: PathBuf) {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let item = tree.insert_item_with_data(&name, parent, nwg::TreeInsert::Last, path);
+    tree.set_lazy_children(&item, true);
+}
+
+fn load_children(tree: &nwg::TreeView, evt_data: &nwg::EventData) {
+    let expanding = evt_data.as_tree_item_expanding().unwrap();
+    let item = expanding.item();
+    if expanding.action() != nwg::ExpandState::Expand || tree.first_child(&item).is_some() {
+        return;
+    }
+
+    let path = match tree.item_data::<PathBuf>(&item) {
+        Some(path) => path,
+        None => { return; }
+    };
+
+    match std::fs::read_dir(&*path) {
+        Ok(entries) => for entry in entries.flatten() {
+            add_folder(tree, Some(&item), entry.path());
+        },
+        Err(_) => expanding.allow(false)
+    }
+}
+```
 */
-#[derive(Default, PartialEq, Eq)]
+#[derive(Default)]
 pub struct TreeView {
-    pub handle: ControlHandle
-} 
+    pub handle: ControlHandle,
+    data: Rc<RefCell<TreeViewItemData>>,
+    handler0: RefCell<Option<RawEventHandler>>,
+}
 
 
 impl TreeView {
@@ -351,6 +397,132 @@ impl TreeView {
         TreeItem { handle }
     }
 
+    /// Insert a new item into the TreeView with a value attached to it. See `set_item_data`.
+    pub fn insert_item_with_data<'a, D: Any>(&self, new: &'a str, parent: Option<&TreeItem>, position: TreeInsert, data: D) -> TreeItem {
+        let item = self.insert_item(new, parent, position);
+        self.set_item_data(&item, data);
+        item
+    }
+
+    /// Associates a value with an item. The previous value of the item is replaced.
+    /// The value is dropped when the item is removed from the tree view. Does nothing if the item is not in the tree view.
+    pub fn set_item_data<D: Any>(&self, item: &TreeItem, data: D) {
+        let item_id = match self.item_id(item) {
+            Some(0) => self.set_item_id(item),
+            Some(id) => id,
+            None => { return; }
+        };
+
+        self.data.borrow_mut().values.insert(item_id, Rc::new(data));
+    }
+
+    /// Returns the value associated with an item. Returns `None` if the item has no value or if the value is not a `D`.
+    pub fn item_data<D: Any>(&self, item: &TreeItem) -> Option<Rc<D>> {
+        let item_id = self.item_id(item)?;
+        let value = self.data.borrow().values.get(&item_id).cloned()?;
+        value.downcast::<D>().ok()
+    }
+
+    /// Removes the value associated with an item
+    pub fn remove_item_data(&self, item: &TreeItem) {
+        if let Some(item_id) = self.item_id(item) {
+            self.data.borrow_mut().values.remove(&item_id);
+        }
+    }
+
+    /**
+        Displays an expand button next to the item even if the item has no children.
+        The children can be inserted when the user expands the item for the first time (see `OnTreeItemExpanding`).
+        If the item still has no children after being expanded, the button is removed.
+    */
+    pub fn set_lazy_children(&self, item: &TreeItem, lazy: bool) {
+        use winapi::um::commctrl::{TVM_SETITEMW, TVIF_CHILDREN, TVIF_HANDLE, I_CHILDRENCALLBACK};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut tree_item = blank_item();
+        tree_item.mask = TVIF_CHILDREN | TVIF_HANDLE;
+        tree_item.hItem = item.handle;
+        tree_item.cChildren = match lazy {
+            true => I_CHILDRENCALLBACK,
+            false => self.first_child(item).is_some() as i32
+        };
+
+        wh::send_message(handle, TVM_SETITEMW, 0, &mut tree_item as *mut TVITEMW as LPARAM);
+    }
+
+    /// Returns the id used to find the value of an item (stored in the lParam of the item). `None` if the item is not in the tree view.
+    fn item_id(&self, tree_item: &TreeItem) -> Option<LPARAM> {
+        use winapi::um::commctrl::{TVM_GETITEMW, TVIF_PARAM, TVIF_HANDLE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut item: TVITEMW = blank_item();
+        item.mask = TVIF_PARAM | TVIF_HANDLE;
+        item.hItem = tree_item.handle;
+
+        match wh::send_message(handle, TVM_GETITEMW, 0, &mut item as *mut TVITEMW as LPARAM) {
+            0 => None,
+            _ => Some(item.lParam)
+        }
+    }
+
+    fn set_item_id(&self, tree_item: &TreeItem) -> LPARAM {
+        use winapi::um::commctrl::{TVM_SETITEMW, TVIF_PARAM, TVIF_HANDLE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let id = {
+            let mut data = self.data.borrow_mut();
+            data.next_id += 1;
+            data.next_id
+        };
+
+        let mut item: TVITEMW = blank_item();
+        item.mask = TVIF_PARAM | TVIF_HANDLE;
+        item.hItem = tree_item.handle;
+        item.lParam = id;
+
+        wh::send_message(handle, TVM_SETITEMW, 0, &mut item as *mut TVITEMW as LPARAM);
+
+        id
+    }
+
+    /// Removes the values of the deleted items, answers the children queries of the lazy items and raises `OnTreeItemExpanding`
+    fn hook_tree_view(&self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+        use winapi::um::commctrl::{TVN_DELETEITEMW, TVN_GETDISPINFOW, TVN_ITEMEXPANDINGW, NMTREEVIEWW};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let data = self.data.clone();
+
+        let handler = bind_raw_event_handler_inner(&parent_handle, handle as usize, move |_hwnd, msg, _w, l| {
+            if msg != WM_NOTIFY {
+                return None;
+            }
+
+            let nmhdr: &NMHDR = unsafe { &*(l as *const NMHDR) };
+            if nmhdr.hwndFrom != handle {
+                return None;
+            }
+
+            match nmhdr.code {
+                TVN_DELETEITEMW => {
+                    let info: &NMTREEVIEWW = unsafe { &*(l as *const NMTREEVIEWW) };
+                    data.borrow_mut().values.remove(&info.itemOld.lParam);
+                    None
+                },
+                TVN_GETDISPINFOW => unsafe { lazy_children_notify(handle, l) },
+                TVN_ITEMEXPANDINGW => unsafe { Some(item_expanding_notify(handle, l)) },
+                _ => None
+            }
+        });
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+
     /// Remove an item and its children from the tree view
     pub fn remove_item(&self, item: &TreeItem) {
         use winapi::um::commctrl::{TVM_DELETEITEM};
@@ -612,8 +784,21 @@ impl TreeView {
     }
 }
 
+impl PartialEq for TreeView {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Eq for TreeView {}
+
 impl Drop for TreeView {
     fn drop(&mut self) {
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
         self.handle.destroy();
     }
 }
@@ -694,6 +879,8 @@ impl<'a> TreeViewBuilder<'a> {
             .parent(Some(parent))
             .build()?;
 
+        out.hook_tree_view();
+
         if self.font.is_some() {
             out.set_font(self.font);
         } else {
@@ -737,6 +924,49 @@ fn next_treeview_item(handle: &ControlHandle, action: usize, item: HTREEITEM) ->
     }
 }
 
+/// The values associated with the items of a tree view, by item id
+#[derive(Default)]
+struct TreeViewItemData {
+    next_id: LPARAM,
+    values: HashMap<LPARAM, Rc<dyn Any>>,
+}
+
+/// Answers the children query of a lazy item: the item has children until it was expanded once.
+unsafe fn lazy_children_notify(tree: HWND, l: LPARAM) -> Option<LRESULT> {
+    use winapi::um::commctrl::{NMTVDISPINFOW, TVIF_CHILDREN, TVM_GETNEXTITEM, TVM_GETITEMSTATE, TVGN_CHILD, TVIS_EXPANDEDONCE};
+
+    let info = &mut *(l as *mut NMTVDISPINFOW);
+    if info.item.mask & TVIF_CHILDREN != TVIF_CHILDREN {
+        return None;
+    }
+
+    let item = info.item.hItem;
+    let has_children = wh::send_message(tree, TVM_GETNEXTITEM, TVGN_CHILD, item as LPARAM) != 0;
+    let expanded_once = wh::send_message(tree, TVM_GETITEMSTATE, item as WPARAM, TVIS_EXPANDEDONCE as LPARAM) as u32 & TVIS_EXPANDEDONCE != 0;
+    info.item.cChildren = (has_children || !expanded_once) as i32;
+
+    Some(0)
+}
+
+/// Raises `OnTreeItemExpanding`. Returns `TRUE` to prevent the item from expanding or collapsing.
+unsafe fn item_expanding_notify(tree: HWND, l: LPARAM) -> LRESULT {
+    use winapi::um::commctrl::{NMTREEVIEWW, TVE_COLLAPSE, TVE_EXPAND};
+    use crate::TreeItemExpandingData;
+
+    let info = &*(l as *const NMTREEVIEWW);
+    let action = match info.action as usize & (TVE_COLLAPSE | TVE_EXPAND) {
+        TVE_COLLAPSE => ExpandState::Collapse,
+        TVE_EXPAND => ExpandState::Expand,
+        _ => ExpandState::Toggle
+    };
+
+    let mut allow = true;
+    let data = TreeItemExpandingData { item: info.itemNew.hItem, action, allow: &mut allow as *mut bool };
+    wh::send_message(tree, NWG_TREE_ITEM_EXPANDING, 0, &data as *const TreeItemExpandingData as LPARAM);
+
+    (!allow) as LRESULT
+}
+
 #[cfg(feature="image-list")]
 fn builder_set_image_list(builder: &TreeViewBuilder, out: &TreeView) {
     if builder.image_list.is_some() {
@@ -69,6 +69,9 @@ bitflags! {
       * `MousePress(_)`: Generic mouse press events on the button
       * `OnMouseMove`: Generic mouse mouse event
       * `OnMouseWheel`: Generic mouse wheel event
+      * `OnMouseHorizontalWheel`: Generic horizontal mouse wheel event
+      * `OnMouseHover`: When the mouse rests over the window
+      * `OnMouseLeave`: When the mouse leaves the window
       * `OnPaint`: Generic on paint event
       * `OnKeyPress`: Generic key press
       * `OnKeyRelease`: Generic ket release
@@ -103,7 +106,7 @@ impl Window {
 
     /// Force the window to refraw iteself and all its children
     pub fn invalidate(&self) {
-        use winapi::um::winuser::InvalidateRect;
+        use crate::win32::backend::InvalidateRect;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         unsafe { InvalidateRect(handle, ::std::ptr::null(), 1); }
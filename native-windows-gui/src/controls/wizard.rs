@@ -0,0 +1,720 @@
+/*!
+    Multi-page dialogs: wizards and property sheets.
+*/
+
+use winapi::shared::minwindef::{UINT, WPARAM, LPARAM, LRESULT};
+use winapi::shared::windef::HWND;
+
+use crate::win32::window_helper::{self as wh, NWG_WIZARD_NAVIGATE, NWG_WIZARD_PAGE_CHANGED};
+use crate::win32::window::bind_raw_event_handler_inner;
+use crate::win32::base_helper::check_hwnd;
+use crate::{NwgError, Icon, RawEventHandler, unbind_raw_event_handler, WizardNavigateData};
+use super::{ControlHandle, Dialog, DialogResult, Label, Button, Frame, FrameFlags, TabsContainer, Tab};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+const NOT_BOUND: &'static str = "Wizard is not yet bound to a winapi object";
+const BAD_HANDLE: &'static str = "INTERNAL ERROR: Wizard handle is not HWND!";
+
+const BACK_TEXT: &'static str = "< Back";
+const NEXT_TEXT: &'static str = "Next >";
+const FINISH_TEXT: &'static str = "Finish";
+const CANCEL_TEXT: &'static str = "Cancel";
+const OK_TEXT: &'static str = "OK";
+const APPLY_TEXT: &'static str = "Apply";
+
+thread_local! {
+    /// The state of the wizards created in this thread. Used by the pages to find their wizard.
+    static WIZARDS: RefCell<HashMap<usize, Weak<RefCell<Option<WizardState>>>>> = RefCell::new(HashMap::new());
+}
+
+
+/// The presentation of a `Wizard`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WizardStyle {
+    /// One page at a time with a title and a subtitle. The user moves between the pages with the Back and Next buttons.
+    Wizard,
+
+    /// The pages are displayed in tabs. The dialog has the OK, Cancel and Apply buttons.
+    PropertySheet,
+}
+
+/// The navigation buttons of a `Wizard`. Sent with the `OnWizardNavigate` event.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WizardAction {
+    /// Display the previous page (wizard)
+    Back,
+
+    /// Display the next page (wizard)
+    Next,
+
+    /// End the wizard with `DialogResult::Ok`. Sent instead of `Next` on the last page.
+    Finish,
+
+    /// End the dialog with `DialogResult::Cancel`. Also sent by the Escape key and the close button.
+    Cancel,
+
+    /// End the property sheet with `DialogResult::Ok`. The changes should be applied.
+    Ok,
+
+    /// Apply the changes without closing the property sheet. The Apply button is disabled after the event.
+    Apply,
+}
+
+struct WizardPageInfo {
+    handle: HWND,
+    title: String,
+    subtitle: String,
+}
+
+struct WizardState {
+    style: WizardStyle,
+    pages: Vec<WizardPageInfo>,
+    current: Option<usize>,
+    result: Option<DialogResult>,
+    page_position: (i32, i32),
+    page_size: (i32, i32),
+    title: ControlHandle,
+    subtitle: ControlHandle,
+    tabs: ControlHandle,
+    back: ControlHandle,
+    next: ControlHandle,
+    cancel: ControlHandle,
+    apply: ControlHandle,
+}
+
+impl WizardState {
+
+    /// The page displayed by the wizard, or the selected tab of a property sheet
+    fn current_page(&self) -> Option<usize> {
+        use winapi::um::commctrl::TCM_GETCURSEL;
+
+        match self.style {
+            WizardStyle::Wizard => self.current,
+            WizardStyle::PropertySheet => match self.tabs.hwnd() {
+                Some(tabs) => match wh::send_message(tabs, TCM_GETCURSEL, 0, 0) {
+                    -1 => None,
+                    i => Some(i as usize)
+                },
+                None => None
+            }
+        }
+    }
+
+    fn button_action(&self, button: HWND) -> Option<WizardAction> {
+        let is = |handle: &ControlHandle| handle.hwnd() == Some(button);
+
+        match self.style {
+            WizardStyle::Wizard if is(&self.back) => Some(WizardAction::Back),
+            WizardStyle::Wizard if is(&self.next) => match self.current {
+                Some(i) if i + 1 < self.pages.len() => Some(WizardAction::Next),
+                _ => Some(WizardAction::Finish)
+            },
+            WizardStyle::PropertySheet if is(&self.next) => Some(WizardAction::Ok),
+            WizardStyle::PropertySheet if is(&self.apply) => Some(WizardAction::Apply),
+            _ if is(&self.cancel) => Some(WizardAction::Cancel),
+            _ => None
+        }
+    }
+
+    fn button(&self, action: WizardAction) -> Option<HWND> {
+        match action {
+            WizardAction::Back => self.back.hwnd(),
+            WizardAction::Next | WizardAction::Finish | WizardAction::Ok => self.next.hwnd(),
+            WizardAction::Cancel => self.cancel.hwnd(),
+            WizardAction::Apply => self.apply.hwnd(),
+        }
+    }
+
+}
+
+
+/**
+    A modal dialog that displays its content over multiple pages. A wizard can be displayed in two styles (see `WizardStyle`):
+
+      * `Wizard`: The pages are displayed one at a time, with their title and subtitle in the header.
+        The Back, Next (Finish on the last page) and Cancel buttons are at the bottom of the dialog.
+      * `PropertySheet`: The pages are displayed in tabs. The OK, Cancel and Apply buttons are at the bottom of the dialog.
+
+    The pages are `WizardPage` controls. Each page is a container, so a page is usually filled by a `PartialUi`.
+
+    Every navigation button raises a `OnWizardNavigate` event on the wizard before doing anything. The event data
+    (`EventData::OnWizardNavigate`) can cancel the navigation, for example if the inputs of the current page are not valid.
+    `OnWizardPageChanged` is raised after a new page is displayed.
+
+    The wizard is displayed by `run_modal`. Like `Dialog`, the owner is disabled until the wizard ends.
+    The Enter key presses Next/Finish (or OK). The Escape key and the close button press Cancel.
+
+    Note that the event handlers must be bound after the pages are created.
+
+    Requires the `wizard` feature.
+
+    **Builder parameters:**
+      * `style`:    The wizard style. See `WizardStyle`
+      * `title`:    The text in the dialog title bar
+      * `size`:     The size of the dialog
+      * `position`: The position of the dialog in the desktop
+      * `icon`:     The dialog icon
+      * `parent`:   The owner of the wizard. The owner is disabled while the wizard is running.
+
+    **Control events:**
+      * `OnWizardNavigate`: When the user presses a navigation button. See `WizardAction`.
+      * `OnWizardPageChanged`: When a new page is displayed (wizard style only)
+      * `OnInit`: The dialog was created
+
+```rust
+use native_windows_gui as nwg;
+
+fn build_wizard(wizard: &mut nwg::Wizard, welcome: &mut nwg::WizardPage, options: &mut nwg::WizardPage) -> Result<(), nwg::NwgError> {
+    nwg::Wizard::builder()
+        .title("Import")
+        .build(wizard)?;
+
+    nwg::WizardPage::builder()
+        .title("Welcome")
+        .subtitle("This wizard imports your data")
+        .parent(&*wizard)
+        .build(welcome)?;
+
+    nwg::WizardPage::builder()
+        .title("Options")
+        .subtitle("Choose what to import")
+        .parent(&*wizard)
+        .build(options)?;
+
+    Ok(())
+}
+
+fn validate(evt_data: &nwg::EventData, options_valid: bool) {
+    let nav = evt_data.as_wizard_navigate().unwrap();
+    if nav.action() == nwg::WizardAction::Finish && !options_valid {
+        nav.allow(false);
+    }
+}
+```
+*/
+#[derive(Default)]
+pub struct Wizard {
+    pub handle: ControlHandle,
+    state: Rc<RefCell<Option<WizardState>>>,
+    title: Label,
+    subtitle: Label,
+    tabs: TabsContainer,
+    back: Button,
+    next: Button,
+    cancel: Button,
+    apply: Button,
+    handler0: RefCell<Option<RawEventHandler>>,
+    dialog: Dialog<DialogResult>,
+}
+
+impl Wizard {
+
+    pub fn builder<'a>() -> WizardBuilder<'a> {
+        WizardBuilder {
+            style: WizardStyle::Wizard,
+            title: "Wizard",
+            size: (500, 380),
+            position: (300, 300),
+            icon: None,
+            parent: None
+        }
+    }
+
+    /**
+        Show the wizard and dispatch the events of the current thread until the wizard ends.
+        The wizard style always starts on the first page.
+
+        Returns `DialogResult::Ok` if the user pressed Finish (or OK) and `DialogResult::Cancel` otherwise.
+    */
+    pub fn run_modal(&self) -> DialogResult {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let style = self.state().style;
+        self.state_mut().result = None;
+
+        if style == WizardStyle::Wizard {
+            show_page(&self.state, handle, 0);
+        }
+
+        self.dialog.run_modal();
+
+        self.state_mut().result.take().unwrap_or(DialogResult::Cancel)
+    }
+
+    /// Return the style of the wizard
+    pub fn style(&self) -> WizardStyle {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state().style
+    }
+
+    /// Return the index of the page displayed by the wizard, or the selected tab of a property sheet.
+    /// Returns `None` if the wizard has no pages or if a wizard was never displayed.
+    pub fn current_page(&self) -> Option<usize> {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state().current_page()
+    }
+
+    /// Return the number of pages in the wizard
+    pub fn page_count(&self) -> usize {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.state().pages.len()
+    }
+
+    /// Display the page at `index` without raising `OnWizardNavigate`. Returns false if the page does not exist.
+    pub fn set_page(&self, index: usize) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let style = self.state().style;
+        match style {
+            WizardStyle::Wizard => show_page(&self.state, handle, index),
+            WizardStyle::PropertySheet if index < self.page_count() => {
+                self.tabs.set_selected_tab(index);
+                true
+            },
+            WizardStyle::PropertySheet => false
+        }
+    }
+
+    /// Return true if the button of a navigation action is enabled
+    pub fn button_enabled(&self, action: WizardAction) -> bool {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        match self.state().button(action) {
+            Some(button) => unsafe { wh::get_window_enabled(button) },
+            None => false
+        }
+    }
+
+    /// Enable or disable the button of a navigation action. Ex: disable Next until the page is filled.
+    /// Changing the page of a wizard enables or disables the Back button.
+    pub fn set_button_enabled(&self, action: WizardAction, enabled: bool) {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if let Some(button) = self.state().button(action) {
+            unsafe { wh::set_window_enabled(button, enabled); }
+        }
+    }
+
+    /// Enable the Apply button of a property sheet. Call this when the user modifies a page.
+    pub fn set_changed(&self, changed: bool) {
+        self.set_button_enabled(WizardAction::Apply, changed);
+    }
+
+    /// Return true if the wizard is visible to the user
+    pub fn visible(&self) -> bool {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.dialog.visible()
+    }
+
+    /// Return the wizard title
+    pub fn text(&self) -> String {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.dialog.text()
+    }
+
+    /// Set the wizard title
+    pub fn set_text<'a>(&self, v: &'a str) {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.dialog.set_text(v)
+    }
+
+    fn state(&self) -> std::cell::Ref<WizardState> {
+        std::cell::Ref::map(self.state.borrow(), |s| s.as_ref().expect(NOT_BOUND))
+    }
+
+    fn state_mut(&self) -> std::cell::RefMut<WizardState> {
+        std::cell::RefMut::map(self.state.borrow_mut(), |s| s.as_mut().expect(NOT_BOUND))
+    }
+
+    /// Handles the navigation buttons and the close button
+    fn hook_wizard_events(&self) {
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+        let state = self.state.clone();
+
+        let handler = bind_raw_event_handler_inner(&self.handle, handle as usize, move |hwnd, msg, w, l| {
+            wizard_events(&state, hwnd, msg, w, l)
+        });
+
+        *self.handler0.borrow_mut() = Some(handler.unwrap());
+    }
+
+}
+
+impl Drop for Wizard {
+    fn drop(&mut self) {
+        let handler = self.handler0.borrow();
+        if let Some(h) = handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        if let Some(hwnd) = self.handle.hwnd() {
+            WIZARDS.with(|wizards| wizards.borrow_mut().remove(&(hwnd as usize)));
+        }
+    }
+}
+
+fn wizard_events(state: &Rc<RefCell<Option<WizardState>>>, hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> Option<LRESULT> {
+    use winapi::um::winuser::{WM_COMMAND, WM_CLOSE, BN_CLICKED};
+    use winapi::shared::minwindef::HIWORD;
+
+    match msg {
+        WM_COMMAND if HIWORD(w as u32) == BN_CLICKED && l != 0 => {
+            let action = state.borrow().as_ref().and_then(|s| s.button_action(l as HWND));
+            if let Some(action) = action {
+                navigate(state, hwnd, action);
+            }
+        },
+        WM_CLOSE => {
+            // The close button cancels the wizard
+            navigate(state, hwnd, WizardAction::Cancel);
+            return Some(0);
+        },
+        _ => {}
+    }
+
+    None
+}
+
+/// Raise `OnWizardNavigate` and execute the action if the event handlers allow it.
+/// The state must not be borrowed while the event handlers are called.
+fn navigate(state: &Rc<RefCell<Option<WizardState>>>, hwnd: HWND, action: WizardAction) {
+    let page = match state.borrow().as_ref() {
+        Some(s) => s.current_page(),
+        None => { return; }
+    };
+
+    let mut allow = true;
+    let data = WizardNavigateData { action, page, data: &mut allow as *mut bool };
+    wh::send_message(hwnd, NWG_WIZARD_NAVIGATE, 0, &data as *const WizardNavigateData as LPARAM);
+
+    if !allow {
+        return;
+    }
+
+    match (action, page) {
+        (WizardAction::Back, Some(i)) if i > 0 => { show_page(state, hwnd, i - 1); },
+        (WizardAction::Next, Some(i)) => { show_page(state, hwnd, i + 1); },
+        (WizardAction::Finish, _) | (WizardAction::Ok, _) => end_wizard(state, hwnd, DialogResult::Ok),
+        (WizardAction::Cancel, _) => end_wizard(state, hwnd, DialogResult::Cancel),
+        (WizardAction::Apply, _) => {
+            if let Some(apply) = state.borrow().as_ref().and_then(|s| s.apply.hwnd()) {
+                unsafe { wh::set_window_enabled(apply, false); }
+            }
+        },
+        _ => {}
+    }
+}
+
+/// Hiding the dialog ends the modal loop of `Dialog::run_modal`
+fn end_wizard(state: &Rc<RefCell<Option<WizardState>>>, hwnd: HWND, result: DialogResult) {
+    if let Some(state) = state.borrow_mut().as_mut() {
+        state.result = Some(result);
+    }
+
+    unsafe { wh::set_window_visibility(hwnd, false); }
+}
+
+/// Display a page of a wizard and update the header and the buttons. Raise `OnWizardPageChanged`.
+fn show_page(state: &Rc<RefCell<Option<WizardState>>>, hwnd: HWND, index: usize) -> bool {
+    {
+        let mut state = state.borrow_mut();
+        let state = match state.as_mut() {
+            Some(s) => s,
+            None => { return false; }
+        };
+
+        if index >= state.pages.len() {
+            return false;
+        }
+
+        unsafe {
+            for (i, page) in state.pages.iter().enumerate() {
+                wh::set_window_visibility(page.handle, i == index);
+            }
+
+            let page = &state.pages[index];
+            if let (Some(title), Some(subtitle)) = (state.title.hwnd(), state.subtitle.hwnd()) {
+                wh::set_window_text(title, &page.title);
+                wh::set_window_text(subtitle, &page.subtitle);
+            }
+
+            if let Some(back) = state.back.hwnd() {
+                wh::set_window_enabled(back, index > 0);
+            }
+
+            if let Some(next) = state.next.hwnd() {
+                let last = index + 1 == state.pages.len();
+                wh::set_window_text(next, if last { FINISH_TEXT } else { NEXT_TEXT });
+            }
+        }
+
+        state.current = Some(index);
+    }
+
+    wh::send_message(hwnd, NWG_WIZARD_PAGE_CHANGED, 0, 0);
+
+    true
+}
+
+/// Find the wizard of a window by looking at its ancestors
+fn find_wizard(mut hwnd: HWND) -> Option<(HWND, Rc<RefCell<Option<WizardState>>>)> {
+    while !hwnd.is_null() {
+        let state = WIZARDS.with(|wizards| wizards.borrow().get(&(hwnd as usize)).and_then(|s| s.upgrade()));
+        if let Some(state) = state {
+            return Some((hwnd, state));
+        }
+
+        hwnd = wh::get_window_parent(hwnd);
+    }
+
+    None
+}
+
+
+pub struct WizardBuilder<'a> {
+    style: WizardStyle,
+    title: &'a str,
+    size: (i32, i32),
+    position: (i32, i32),
+    icon: Option<&'a Icon>,
+    parent: Option<ControlHandle>
+}
+
+impl<'a> WizardBuilder<'a> {
+
+    pub fn style(mut self, style: WizardStyle) -> WizardBuilder<'a> {
+        self.style = style;
+        self
+    }
+
+    pub fn title(mut self, text: &'a str) -> WizardBuilder<'a> {
+        self.title = text;
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> WizardBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> WizardBuilder<'a> {
+        self.position = pos;
+        self
+    }
+
+    pub fn icon(mut self, ico: Option<&'a Icon>) -> WizardBuilder<'a> {
+        self.icon = ico;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: Option<C>) -> WizardBuilder<'a> {
+        self.parent = p.map(|p2| p2.into());
+        self
+    }
+
+    pub fn build(self, out: &mut Wizard) -> Result<(), NwgError> {
+        *out = Default::default();
+
+        Dialog::builder()
+            .title(self.title)
+            .size(self.size)
+            .position(self.position)
+            .icon(self.icon)
+            .parent(self.parent)
+            .build(&mut out.dialog)?;
+
+        out.handle = out.dialog.handle;
+
+        let (w, h) = self.size;
+        let (button_y, button_size) = (h - 38, (80, 26));
+        let (page_position, page_size);
+
+        match self.style {
+            WizardStyle::Wizard => {
+                Label::builder().text("").position((15, 10)).size((w - 30, 20)).parent(&out.dialog).build(&mut out.title)?;
+                Label::builder().text("").position((25, 32)).size((w - 40, 20)).parent(&out.dialog).build(&mut out.subtitle)?;
+                Button::builder().text(BACK_TEXT).position((w - 270, button_y)).size(button_size).parent(&out.dialog).build(&mut out.back)?;
+                Button::builder().text(NEXT_TEXT).position((w - 185, button_y)).size(button_size).parent(&out.dialog).build(&mut out.next)?;
+                Button::builder().text(CANCEL_TEXT).position((w - 90, button_y)).size(button_size).parent(&out.dialog).build(&mut out.cancel)?;
+
+                page_position = (10, 62);
+                page_size = (w - 20, h - 112);
+            },
+            WizardStyle::PropertySheet => {
+                TabsContainer::builder().position((10, 10)).size((w - 20, h - 60)).parent(&out.dialog).build(&mut out.tabs)?;
+                Button::builder().text(OK_TEXT).position((w - 270, button_y)).size(button_size).parent(&out.dialog).build(&mut out.next)?;
+                Button::builder().text(CANCEL_TEXT).position((w - 180, button_y)).size(button_size).parent(&out.dialog).build(&mut out.cancel)?;
+                Button::builder().text(APPLY_TEXT).position((w - 90, button_y)).size(button_size).enabled(false).parent(&out.dialog).build(&mut out.apply)?;
+
+                page_position = (0, 0);
+                page_size = (w - 20, h - 60);
+            }
+        }
+
+        out.dialog.set_default_button(Some(&out.next));
+        out.dialog.set_cancel_button(Some(&out.cancel));
+
+        *out.state.borrow_mut() = Some(WizardState {
+            style: self.style,
+            pages: Vec::new(),
+            current: None,
+            result: None,
+            page_position,
+            page_size,
+            title: out.title.handle,
+            subtitle: out.subtitle.handle,
+            tabs: out.tabs.handle,
+            back: out.back.handle,
+            next: out.next.handle,
+            cancel: out.cancel.handle,
+            apply: out.apply.handle,
+        });
+
+        let hwnd = out.handle.hwnd().expect(BAD_HANDLE);
+        WIZARDS.with(|wizards| wizards.borrow_mut().insert(hwnd as usize, Rc::downgrade(&out.state)));
+
+        out.hook_wizard_events();
+
+        Ok(())
+    }
+
+}
+
+
+/**
+    A page of a `Wizard`. The page is a container for the controls of the page, usually a `PartialUi`.
+    In a property sheet, the page is a tab.
+
+    The pages are displayed in the order they were created.
+
+    Requires the `wizard` feature.
+
+    **Builder parameters:**
+      * `parent`:   **Required.** The wizard of the page (or another page of the same wizard)
+      * `title`:    The title of the page. In a property sheet, the text of the tab.
+      * `subtitle`: The subtitle of the page. Not displayed in a property sheet.
+
+    **Control events:**
+      * `MousePress(_)`: Generic mouse press events on the page
+      * `OnMouseMove`: Generic mouse mouse event
+*/
+#[derive(Default)]
+pub struct WizardPage {
+    pub handle: ControlHandle,
+    wizard: Weak<RefCell<Option<WizardState>>>,
+    frame: Frame,
+    tab: Tab,
+}
+
+impl WizardPage {
+
+    pub fn builder<'a>() -> WizardPageBuilder<'a> {
+        WizardPageBuilder {
+            title: "",
+            subtitle: "",
+            parent: None
+        }
+    }
+
+    /// Return the index of the page in its wizard
+    pub fn index(&self) -> Option<usize> {
+        let handle = self.handle.hwnd()?;
+        let state = self.wizard.upgrade()?;
+        let state = state.borrow();
+        state.as_ref()?.pages.iter().position(|p| p.handle == handle)
+    }
+
+    /// Return true if the page is currently displayed
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, "WizardPage is not yet bound to a winapi object", "INTERNAL ERROR: WizardPage handle is not HWND!");
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+}
+
+impl Drop for WizardPage {
+    fn drop(&mut self) {
+        if let (Some(handle), Some(state)) = (self.handle.hwnd(), self.wizard.upgrade()) {
+            if let Some(state) = state.borrow_mut().as_mut() {
+                state.pages.retain(|p| p.handle != handle);
+            }
+        }
+    }
+}
+
+pub struct WizardPageBuilder<'a> {
+    title: &'a str,
+    subtitle: &'a str,
+    parent: Option<ControlHandle>
+}
+
+impl<'a> WizardPageBuilder<'a> {
+
+    pub fn title(mut self, text: &'a str) -> WizardPageBuilder<'a> {
+        self.title = text;
+        self
+    }
+
+    pub fn subtitle(mut self, text: &'a str) -> WizardPageBuilder<'a> {
+        self.subtitle = text;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> WizardPageBuilder<'a> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut WizardPage) -> Result<(), NwgError> {
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("WizardPage"))
+        }?;
+
+        let (wizard, state) = match parent.hwnd().and_then(find_wizard) {
+            Some(w) => w,
+            None => { return Err(NwgError::control_create("WizardPage requires a Wizard parent.")); }
+        };
+
+        let (style, position, size, tabs) = match state.borrow().as_ref() {
+            Some(s) => (s.style, s.page_position, s.page_size, s.tabs),
+            None => { return Err(NwgError::control_create("WizardPage requires a Wizard parent.")); }
+        };
+
+        *out = Default::default();
+
+        match style {
+            WizardStyle::Wizard => {
+                Frame::builder()
+                    .flags(FrameFlags::NONE)
+                    .position(position)
+                    .size(size)
+                    .parent(ControlHandle::Hwnd(wizard))
+                    .build(&mut out.frame)?;
+
+                out.handle = out.frame.handle;
+            },
+            WizardStyle::PropertySheet => {
+                Tab::builder()
+                    .text(self.title)
+                    .parent(tabs)
+                    .build(&mut out.tab)?;
+
+                out.handle = out.tab.handle;
+            }
+        }
+
+        if let Some(state) = state.borrow_mut().as_mut() {
+            state.pages.push(WizardPageInfo {
+                handle: out.handle.hwnd().expect(BAD_HANDLE),
+                title: self.title.to_string(),
+                subtitle: self.subtitle.to_string(),
+            });
+        }
+
+        out.wizard = Rc::downgrade(&state);
+
+        Ok(())
+    }
+
+}
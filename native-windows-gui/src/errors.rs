@@ -1,91 +1,326 @@
 use std::fmt;
 use std::error::Error;
 
+/// HRESULT returned by COM functions when the user cancelled an operation. Same as `HRESULT_FROM_WIN32(ERROR_CANCELLED)`.
+const HRESULT_CANCELLED: i32 = 0x800704C7u32 as i32;
+
+/// `GetLastError` value when the user cancelled an operation
+const ERROR_CANCELLED: u32 = 1223;
+
+
+/**
+    The raw code returned by a failed system function
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemErrorCode {
+    /// A value returned by `GetLastError`
+    Win32(u32),
+
+    /// A COM HRESULT
+    HResult(i32),
+}
+
+impl fmt::Display for SystemErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SystemErrorCode::Win32(code) => write!(f, "system error {}", code),
+            SystemErrorCode::HResult(hr) => write!(f, "HRESULT 0x{:08X}", *hr as u32),
+        }
+    }
+}
+
+
+/**
+    The details of an error.
+
+    * message:   A description of the error
+    * code:      The raw system error code if a system function failed
+    * operation: The system function or the action that failed (ex: `CreateWindowExW`)
+    * class:     The class name of the control that failed (ex: `BUTTON`)
+    * source:    The error that caused this error. Returned by `Error::source`.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct ErrorInfo {
+    pub message: String,
+    pub code: Option<SystemErrorCode>,
+    pub operation: Option<&'static str>,
+    pub class: Option<String>,
+    pub source: Option<Box<NwgError>>,
+}
+
+impl ErrorInfo {
+
+    pub fn new<S: Into<String>>(message: S) -> ErrorInfo {
+        ErrorInfo {
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+
+}
+
+impl fmt::Display for ErrorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.message)?;
+
+        match (self.operation, self.code) {
+            (Some(op), Some(code)) => write!(f, " ({} failed with {})", op, code)?,
+            (Some(op), None) => write!(f, " ({} failed)", op)?,
+            (None, Some(code)) => write!(f, " ({})", code)?,
+            (None, None) => {}
+        }
+
+        if let Some(class) = self.class.as_ref() {
+            write!(f, " [class: {}]", class)?;
+        }
+
+        Ok(())
+    }
+}
+
+
 /**
     Error enums used in the native window gui crate
+
+    Every variant (except `Unknown`) holds an `ErrorInfo` with the raw system error code, the failed operation,
+    the control class, and the error source when they are known. Use `NwgError::info` or the shortcuts `code`,
+    `operation` and `class` to read them.
+
+```rust
+use native_windows_gui as nwg;
+
+fn pick_file(dialog: &nwg::FileDialog) -> Result<(), nwg::NwgError> {
+    match dialog.try_run(None::<&nwg::Window>) {
+        Err(e) if e.is_cancelled() => Ok(()),
+        Err(e) => {
+            println!("{} (code: {:?})", e, e.code());
+            Err(e)
+        },
+        Ok(()) => Ok(())
+    }
+}
+```
 */
 #[derive(Debug, Clone)]
 pub enum NwgError {
     Unknown,
-    
+
     /// Fatal error raised when calling low level winapi functionalities
-    InitializationError(String),
+    InitializationError(ErrorInfo),
 
     /// Error raised when creating a control.
-    ControlCreationError(String),
+    ControlCreationError(ErrorInfo),
 
     /// Error raised when creating a menu.
-    MenuCreationError(String),
+    MenuCreationError(ErrorInfo),
 
     /// Error raised when creating a resource.
-    ResourceCreationError(String),
+    ResourceCreationError(ErrorInfo),
 
     /// Error raised when the creation of a layout failed
-    LayoutCreationError(String),
+    LayoutCreationError(ErrorInfo),
 
     /// Error raised when an event handler could not be bound
-    EventsBinding(String),
+    EventsBinding(ErrorInfo),
+
+    /// Error raised when a control, a layout, or a resource is used before being built
+    ControlNotBound(ErrorInfo),
+
+    /// Error raised when the handle of a control is not of the expected type or when the window was freed by the OS
+    InvalidHandle(ErrorInfo),
+
+    /// Error raised when a control is not a child of a layout
+    ChildNotFound(ErrorInfo),
 
     /// Error raised by the FileDialog object
     #[cfg(feature = "file-dialog")]
-    FileDialogError(String),
+    FileDialogError(ErrorInfo),
 
-    /// Error raised by the ImageDecoder feature
+    /// Error raised by the ImageDecoder feature. The HRESULT is in the error code.
     #[cfg(feature = "image-decoder")]
-    ImageDecoderError(i32, String),
+    ImageDecoderError(ErrorInfo),
 
     /// Error raised by one of the locale functions
     #[cfg(feature = "winnls")]
-    BadLocale(String),
+    BadLocale(ErrorInfo),
+
+    /// Error raised when a key combination could not be parsed
+    BadKeyCombo(ErrorInfo),
 }
 
 impl NwgError {
 
     pub fn initialization<S: Into<String>>(e: S) -> NwgError {
-        NwgError::InitializationError(e.into())
+        NwgError::InitializationError(ErrorInfo::new(e))
     }
 
     pub fn control_create<S: Into<String>>(e: S) -> NwgError {
-        NwgError::ControlCreationError(e.into())
+        NwgError::ControlCreationError(ErrorInfo::new(e))
     }
 
     pub fn menu_create<S: Into<String>>(e: S) -> NwgError {
-        NwgError::MenuCreationError(e.into())
+        NwgError::MenuCreationError(ErrorInfo::new(e))
     }
 
     pub fn resource_create<S: Into<String>>(e: S) -> NwgError {
-        NwgError::ResourceCreationError(e.into())
+        NwgError::ResourceCreationError(ErrorInfo::new(e))
     }
 
     pub fn layout_create<S: Into<String>>(e: S) -> NwgError {
-        NwgError::LayoutCreationError(e.into())
+        NwgError::LayoutCreationError(ErrorInfo::new(e))
     }
 
     pub fn events_binding<S: Into<String>>(e: S) -> NwgError {
-        NwgError::EventsBinding(e.into())
+        NwgError::EventsBinding(ErrorInfo::new(e))
+    }
+
+    pub fn not_bound<S: Into<String>>(e: S) -> NwgError {
+        NwgError::ControlNotBound(ErrorInfo::new(e))
+    }
+
+    pub fn invalid_handle<S: Into<String>>(e: S) -> NwgError {
+        NwgError::InvalidHandle(ErrorInfo::new(e))
+    }
+
+    pub fn child_not_found<S: Into<String>>(e: S) -> NwgError {
+        NwgError::ChildNotFound(ErrorInfo::new(e))
     }
 
     #[cfg(feature = "file-dialog")]
     pub fn file_dialog<S: Into<String>>(e: S) -> NwgError {
-        NwgError::FileDialogError(e.into())
+        NwgError::FileDialogError(ErrorInfo::new(e))
     }
 
     #[cfg(feature = "winnls")]
     pub fn bad_locale<S: Into<String>>(e: S) -> NwgError {
-        NwgError::BadLocale(e.into())
+        NwgError::BadLocale(ErrorInfo::new(e))
+    }
+
+    pub fn bad_key_combo<S: Into<String>>(e: S) -> NwgError {
+        NwgError::BadKeyCombo(ErrorInfo::new(e))
     }
 
     #[cfg(feature = "image-decoder")]
     pub fn image_decoder<S: Into<String>>(code: i32, e: S) -> NwgError {
-        NwgError::ImageDecoderError(code, e.into())
+        NwgError::ImageDecoderError(ErrorInfo::new(e)).with_code(SystemErrorCode::HResult(code))
     }
 
     pub fn no_parent(name: &'static str) -> NwgError {
-        NwgError::ControlCreationError(format!("No parent defined for {:?} control", name))
+        NwgError::control_create(format!("No parent defined for {:?} control", name)).with_class(name)
     }
 
     pub fn no_parent_menu() -> NwgError {
-        NwgError::MenuCreationError("No parent defined for menu".to_string())
+        NwgError::menu_create("No parent defined for menu")
+    }
+
+    /// Returns the details of the error. Returns `None` for `NwgError::Unknown`.
+    pub fn info(&self) -> Option<&ErrorInfo> {
+        use NwgError::*;
+
+        match self {
+            Unknown => None,
+            InitializationError(i) | ControlCreationError(i) | MenuCreationError(i) | ResourceCreationError(i) |
+            LayoutCreationError(i) | EventsBinding(i) | ControlNotBound(i) | InvalidHandle(i) | ChildNotFound(i) |
+            BadKeyCombo(i) => Some(i),
+
+            #[cfg(feature = "file-dialog")]
+            FileDialogError(i) => Some(i),
+
+            #[cfg(feature = "image-decoder")]
+            ImageDecoderError(i) => Some(i),
+
+            #[cfg(feature = "winnls")]
+            BadLocale(i) => Some(i),
+        }
+    }
+
+    fn info_mut(&mut self) -> Option<&mut ErrorInfo> {
+        use NwgError::*;
+
+        match self {
+            Unknown => None,
+            InitializationError(i) | ControlCreationError(i) | MenuCreationError(i) | ResourceCreationError(i) |
+            LayoutCreationError(i) | EventsBinding(i) | ControlNotBound(i) | InvalidHandle(i) | ChildNotFound(i) |
+            BadKeyCombo(i) => Some(i),
+
+            #[cfg(feature = "file-dialog")]
+            FileDialogError(i) => Some(i),
+
+            #[cfg(feature = "image-decoder")]
+            ImageDecoderError(i) => Some(i),
+
+            #[cfg(feature = "winnls")]
+            BadLocale(i) => Some(i),
+        }
+    }
+
+    /// Returns the description of the error
+    pub fn message(&self) -> &str {
+        self.info().map(|i| i.message.as_str()).unwrap_or("")
+    }
+
+    /// Returns the raw system error code of the error
+    pub fn code(&self) -> Option<SystemErrorCode> {
+        self.info().and_then(|i| i.code)
+    }
+
+    /// Returns the system function or the action that failed
+    pub fn operation(&self) -> Option<&'static str> {
+        self.info().and_then(|i| i.operation)
+    }
+
+    /// Returns the class name of the control that failed
+    pub fn class(&self) -> Option<&str> {
+        self.info().and_then(|i| i.class.as_ref().map(|c| c.as_str()))
+    }
+
+    /// Returns true if the error (or one of its sources) was raised because the user cancelled the operation
+    pub fn is_cancelled(&self) -> bool {
+        match self.code() {
+            Some(SystemErrorCode::HResult(HRESULT_CANCELLED)) | Some(SystemErrorCode::Win32(ERROR_CANCELLED)) => true,
+            _ => match self.info().and_then(|i| i.source.as_ref()) {
+                Some(source) => source.is_cancelled(),
+                None => false
+            }
+        }
+    }
+
+    /// Sets the raw system error code of the error
+    pub fn with_code(mut self, code: SystemErrorCode) -> NwgError {
+        if let Some(i) = self.info_mut() { i.code = Some(code); }
+        self
+    }
+
+    /// Sets the system function or the action that failed
+    pub fn with_operation(mut self, operation: &'static str) -> NwgError {
+        if let Some(i) = self.info_mut() { i.operation = Some(operation); }
+        self
+    }
+
+    /// Sets the class name of the control that failed
+    pub fn with_class<S: Into<String>>(mut self, class: S) -> NwgError {
+        if let Some(i) = self.info_mut() { i.class = Some(class.into()); }
+        self
+    }
+
+    /// Sets the error that caused this error
+    pub fn with_source(mut self, source: NwgError) -> NwgError {
+        if let Some(i) = self.info_mut() { i.source = Some(Box::new(source)); }
+        self
+    }
+
+    /// Sets `operation` as the failed operation and the value of `GetLastError` as the error code.
+    /// Must be called right after the system function failed.
+    pub fn with_last_error(self, operation: &'static str) -> NwgError {
+        use crate::win32::backend::GetLastError;
+
+        let code = unsafe { GetLastError() };
+        self.with_operation(operation).with_code(SystemErrorCode::Win32(code))
+    }
+
+    /// Sets `operation` as the failed operation and `hr` as the error code
+    pub fn with_hresult(self, operation: &'static str, hr: i32) -> NwgError {
+        self.with_operation(operation).with_code(SystemErrorCode::HResult(hr))
     }
 
 }
@@ -96,24 +331,36 @@ impl fmt::Display for NwgError {
 
         match self {
             Unknown => write!(f, "Unknown error. This should never happen"),
-            InitializationError(reason) => write!(f, "Failed to initialize NWG: {:?}", reason),
-            ControlCreationError(reason) => write!(f, "Failed to create a control: {:?}", reason),
-            MenuCreationError(reason) => write!(f, "Failed to create a menu: {:?}", reason),
-            ResourceCreationError(reason) => write!(f, "Failed to create a resource: {:?}", reason),
-            LayoutCreationError(reason) => write!(f, "Failed to create a layout: {:?}", reason),
-            EventsBinding(reason) => write!(f, "Failed to bind events: {:?}", reason),
-            
+            InitializationError(info) => write!(f, "Failed to initialize NWG: {}", info),
+            ControlCreationError(info) => write!(f, "Failed to create a control: {}", info),
+            MenuCreationError(info) => write!(f, "Failed to create a menu: {}", info),
+            ResourceCreationError(info) => write!(f, "Failed to create a resource: {}", info),
+            LayoutCreationError(info) => write!(f, "Failed to create a layout: {}", info),
+            EventsBinding(info) => write!(f, "Failed to bind events: {}", info),
+            ControlNotBound(info) => write!(f, "{}", info.message),
+            InvalidHandle(info) => write!(f, "{}", info.message),
+            ChildNotFound(info) => write!(f, "{}", info.message),
+            BadKeyCombo(info) => write!(f, "{}", info.message),
+
             #[cfg(feature = "file-dialog")]
-            FileDialogError(reason) => write!(f, "File dialog actions failed: {:?}", reason),
+            FileDialogError(info) => write!(f, "File dialog actions failed: {}", info),
 
             #[cfg(feature = "image-decoder")]
-            ImageDecoderError(_id, reason) => write!(f, "Image decoder failed: {:?}", reason),
+            ImageDecoderError(info) => write!(f, "Image decoder failed: {}", info),
 
             #[cfg(feature = "winnls")]
-            BadLocale(reason) => write!(f, "Windows locale functions failed: {:?}", reason),
+            BadLocale(info) => write!(f, "Windows locale functions failed: {}", info),
         }
-        
+
     }
 }
 
-impl Error for NwgError {}
+impl Error for NwgError {
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.info()
+            .and_then(|i| i.source.as_ref())
+            .map(|source| source.as_ref() as &(dyn Error + 'static))
+    }
+
+}
@@ -7,7 +7,20 @@ pub enum MousePressEvent {
     MousePressLeftUp,
     MousePressLeftDown,
     MousePressRightUp,
-    MousePressRightDown
+    MousePressRightDown,
+    MousePressMiddleUp,
+    MousePressMiddleDown,
+    MousePressX1Up,
+    MousePressX1Down,
+    MousePressX2Up,
+    MousePressX2Down,
+
+    /// Double clicks are sent on the second click of a double click, after its `Down` event
+    MousePressLeftDoubleClick,
+    MousePressRightDoubleClick,
+    MousePressMiddleDoubleClick,
+    MousePressX1DoubleClick,
+    MousePressX2DoubleClick,
 }
 
 /// Events are identifier that are sent by controls on user interaction
@@ -18,16 +31,30 @@ pub enum Event {
     /// Undefined / not implemented event. This can be dispatched by the bigger controls such as ListView and TreeView
     Unknown,
 
-    /// Generic mouse press events that can be generated by most window controls
+    /// Generic mouse press events that can be generated by most window controls.
+    /// Read the position of the mouse with `EventData::as_mouse` (not available for the tray notification).
     OnMousePress(MousePressEvent),
 
     /// Generic mouse move event that can be generated by most window controls
+    /// Read the position of the mouse with `EventData::as_mouse` (not available for the tray notification).
     OnMouseMove,
 
     /// Generic mouse wheel event that be generated by most window controls
-    /// Read the delta value with `EventData::OnMouseWheel` to check which key.
+    /// Read the delta value with `EventData::as_mouse_wheel` and the position of the mouse with `EventData::as_mouse`.
     OnMouseWheel,
 
+    /// Generic horizontal mouse wheel event that be generated by most window controls
+    /// Read the delta value with `EventData::as_mouse_wheel` and the position of the mouse with `EventData::as_mouse`.
+    OnMouseHorizontalWheel,
+
+    /// When the mouse rests over a control for a short time. Raised again after the mouse moves and rests again.
+    /// Read the position of the mouse with `EventData::as_mouse`.
+    OnMouseHover,
+
+    /// When the mouse leaves the client area of a control. The position of the mouse is outside of the control.
+    /// Read the position of the mouse with `EventData::as_mouse`.
+    OnMouseLeave,
+
     /// Generic window event when the user right click a window
     OnContextMenu,
 
@@ -40,14 +67,15 @@ pub enum Event {
     /// When a key is pressed on a keyboard. Unlike OnKeyDown, this returns a char (ex: 'c') in a EventData::OnChar.
     OnChar,
 
-    /// When a key is pressed on a keyboard. Use `EventData::OnKey` to check which key.
+    /// When a key is pressed on a keyboard. Use `EventData::as_key` to check which key.
     OnKeyPress,
 
-    /// When a key is pressed on a keyboard.Use EventData::OnKey to check which key.
+    /// When a key is released on a keyboard. Use `EventData::as_key` to check which key.
     OnKeyRelease,
     
     /// When a control is resized by the user. 
     /// This is typically applied to top level windows but it also applies to children when layouts are used.
+    /// Read the new size with `EventData::as_resize`.
     OnResize,
 
     /// When a control is about to be resized by the user. 
@@ -58,20 +86,20 @@ pub enum Event {
     /// This does not triggers on maximize
     OnResizeEnd,
 
-    // When a window control is maximized
+    /// When a window control is maximized. Read the new size with `EventData::as_resize`.
     OnWindowMaximize,
 
-    // When a window control is minimized
+    /// When a window control is minimized. Read the new size with `EventData::as_resize`.
     OnWindowMinimize,
 
     /// When a control is moved by the user. This is typically applied to top level windows.
     /// This is typically applied to top level windows but it also applies to children when layouts are used.
     OnMove,
 
-    /// When a bar like control value is changed.
+    /// When a bar like control value is changed. Read the scroll request with `EventData::as_scroll`.
     OnVerticalScroll,
 
-    /// When a bar like control value is changed.
+    /// When a bar like control value is changed. Read the scroll request with `EventData::as_scroll`.
     OnHorizontalScroll,
 
     /// When a file is dropped into a a control
@@ -137,9 +165,16 @@ pub enum Event {
     /// When a menu is hovered (either through mouse or keyboard)
     OnMenuHover,
 
-    /// When the user selects on a menu item
+    /// When the user selects on a menu item. Also raised when the key combination of the menu item in an `Accelerators` table is pressed.
     OnMenuItemSelected,
 
+    /// When a key combination of an `Accelerators` table mapped to a custom id is pressed.
+    /// The handle is the window of the accelerators. Read the id with `EventData::as_accelerator`.
+    OnAccelerator,
+
+    /// When the key combination of a `GlobalHotkey` is pressed, even if the application does not have the keyboard focus.
+    OnHotkey,
+
     /// When the user hovers over a callback tooltip
     /// The callback will also receive a `EventData::OnTooltipText`
     OnTooltipText,
@@ -165,6 +200,10 @@ pub enum Event {
     /// When an item is expanded. Generates a `EventData::OnTreeItemDelete`
     OnTreeItemExpanded,
 
+    /// Before an item is expanded or collapsed. The children of a lazy item can be inserted here.
+    /// The expansion can be cancelled with `EventData::OnTreeItemExpanding`
+    OnTreeItemExpanding,
+
     /// When the state of a tree item is changed.
     OnTreeItemChanged,
 
@@ -193,12 +232,46 @@ pub enum Event {
     /// See `EventData::OnListViewItemChanged` to differentiate the two
     OnListViewItemChanged,
 
+    /// When the user clicks on a column header of the list view
+    /// Generates a `EventData::OnListViewColumnIndex`
+    OnListViewColumnClick,
+
+    /// When the user starts editing the label of a list view item, or when `ListView::edit_item` opens a cell editor.
+    /// The edit can be cancelled with `EventData::OnListViewEdit`.
+    OnListViewBeginEdit,
+
+    /// When the user finishes editing a list view cell. The new text can be rejected or replaced with `EventData::OnListViewEdit`.
+    OnListViewEndEdit,
+
+    /// When the check box of a list view item is checked or unchecked. Raised after `OnListViewItemChanged`.
+    /// Generates a `EventData::OnListViewItemChecked`
+    OnListViewItemChecked,
+
+    /// When the user clicks on the header of a list view group
+    /// Generates a `EventData::OnListViewGroupId`
+    OnListViewGroupClick,
+
+    /// When the mouse rests over a list view item. Requires `ListViewExFlags::INFO_TIP`.
+    /// The text of the info tip is set with `EventData::OnListViewInfoTip`
+    OnListViewInfoTip,
+
     /// When the control has acquired the input focus
     OnListViewFocus,
 
     /// When the control has lost the input focus
     OnListViewFocusLost,
 
+    /// When the user moves the bar of a splitter with the mouse or the keyboard.
+    /// Read the new position with `Splitter::split_position`.
+    OnSplitterMoved,
+
+    /// When the user presses a navigation button of a wizard or a property sheet (ex: Next, Finish, Apply).
+    /// The navigation can be cancelled with `EventData::OnWizardNavigate`.
+    OnWizardNavigate,
+
+    /// When a wizard displays a new page. Read the page index with `Wizard::current_page`.
+    OnWizardPageChanged,
+
     /// When a TrayNotification info popup (not the tooltip) is shown 
     OnTrayNotificationShow,
 
@@ -214,7 +287,7 @@ pub enum Event {
     /// When a timer delay is elapsed
     OnTimerTick,
 
-    /// When a notice is... noticed
+    /// When a notice is... noticed. For a `NoticeChannel`, the queued values are in `EventData::OnNotice`.
     OnNotice,
 
     /// When a user click on the X button of a window
@@ -223,6 +296,9 @@ pub enum Event {
 
 
 /// Events data sent by the controls. 
+///
+/// The `as_*` methods return `None` if the data is not of the requested type.
+/// The `on_*` methods panic instead.
 #[derive(Debug)]
 pub enum EventData {
     /// The event has no data
@@ -238,19 +314,46 @@ pub enum EventData {
     /// The character inputted by a user by a `OnChar` event
     OnChar(char),
 
-    /// The windows key code inputted by a user. See the `nwg::keys` module
-    OnKey(u32),
+    /// The key inputted by a user with the state of the modifier keys. Sent by `OnKeyPress` and `OnKeyRelease`.
+    OnKey(KeyData),
+
+    /// The position of the mouse and the state of the mouse buttons. Sent by `OnMousePress`, `OnMouseMove`, `OnMouseHover` and `OnMouseLeave`.
+    OnMouse(MouseData),
+
+    /// The new size of a control. Sent by `OnResize`, `OnWindowMaximize` and `OnWindowMinimize`.
+    OnResize(ResizeData),
+
+    /// The scroll request of a bar like control. Sent by `OnHorizontalScroll` and `OnVerticalScroll`.
+    OnScroll(ScrollData),
+
+    /// The custom id of an accelerator. Sent by `OnAccelerator`.
+    OnAccelerator(u16),
 
     /// Hold resources that will most likely be used during painting. 
     OnPaint(PaintData),
 
-    /// The delta value of a mouse wheel event. A positive value indicates that the wheel was rotated to the right; 
-    /// a negative value indicates that the wheel was rotated to the left.
-    OnMouseWheel(i32),
+    /// The delta value and the mouse position of a mouse wheel event. Sent by `OnMouseWheel` and `OnMouseHorizontalWheel`.
+    OnMouseWheel(WheelData),
 
     /// The path to one or more files that were dropped in the application
     OnFileDrop(DropFiles),
 
+    /// The values queued in a `NoticeChannel`. Sent by `OnNotice`.
+    #[cfg(feature="notice")]
+    OnNotice(NoticeData),
+
+    /// The navigation requested by the user. Sent by `OnWizardNavigate`.
+    #[cfg(feature="wizard")]
+    OnWizardNavigate(WizardNavigateData),
+
+    /// The list view cell being edited. Sent by `OnListViewBeginEdit` and `OnListViewEndEdit`.
+    #[cfg(feature="list-view")]
+    OnListViewEdit(ListViewEditData),
+
+    /// The list view item under the mouse. Sent by `OnListViewInfoTip`.
+    #[cfg(feature="list-view")]
+    OnListViewInfoTip(ListViewInfoTipData),
+
     /// The handle to the item being deleted. The item is still valid.
     #[cfg(feature="tree-view")]
     OnTreeItemDelete(crate::TreeItem),
@@ -263,6 +366,10 @@ pub enum EventData {
     #[cfg(feature="tree-view")]
     OnTreeItemSelectionChanged{ old: crate::TreeItem, new: crate::TreeItem },
 
+    /// The tree item about to be expanded or collapsed. Sent by `OnTreeItemExpanding`.
+    #[cfg(feature="tree-view")]
+    OnTreeItemExpanding(TreeItemExpandingData),
+
     /// Row index and column index of the list view item that raised the event
     #[cfg(feature="list-view")]
     OnListViewItemIndex { row_index: usize, column_index: usize },
@@ -270,92 +377,282 @@ pub enum EventData {
     /// Row index, column index, and selected state of the list view item that raised the event
     #[cfg(feature="list-view")]
     OnListViewItemChanged { row_index: usize, column_index: usize, selected: bool },
+
+    /// Index of the list view column that raised the event
+    #[cfg(feature="list-view")]
+    OnListViewColumnIndex { column_index: usize },
+
+    /// Row index and new check state of the list view item that raised the event
+    #[cfg(feature="list-view")]
+    OnListViewItemChecked { row_index: usize, checked: bool },
+
+    /// Id of the list view group that raised the event
+    #[cfg(feature="list-view")]
+    OnListViewGroupId { group_id: i32 },
 }
 
 impl EventData {
 
     /// Unwraps event data into a `&PaintData`. Panics if it's not the right type.
     pub fn on_paint(&self) -> &PaintData {
-        match self {
-            EventData::OnPaint(p) => p,
-            d => panic!("Wrong data type: {:?}", d)
-        }
+        self.as_paint().unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
     }
 
     /// Unwraps event data into a `char`. Panics if it's not the right type.
     pub fn on_char(&self) -> char {
-        match self {
-            EventData::OnChar(c) => *c,
-            d => panic!("Wrong data type: {:?}", d)
-        }
+        self.as_char().unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
     }
 
     /// Unwraps event data into a `&ToolTipTextData`. Panics if it's not the right type.
     pub fn on_tooltip_text(&self) -> &ToolTipTextData {
-        match self {
-            EventData::OnTooltipText(d) => d,
-            d => panic!("Wrong data type: {:?}", d)
-        }
+        self.as_tooltip_text().unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
     }
 
     /// Unwraps event data into a `&DragData`. Panics if it's not the right type.
     pub fn on_file_drop(&self) -> &DropFiles {
-        match self {
-            EventData::OnFileDrop(d) => d,
-            d => panic!("Wrong data type: {:?}", d)
-        }
+        self.as_file_drop().unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
     }
 
     /// Unwraps event data into the virtual key code for `OnKeyPress` and `OnKeyRelease`
     pub fn on_key(&self) -> u32 {
-        match self {
-            EventData::OnKey(key) => *key,
-            d => panic!("Wrong data type: {:?}", d)
-        }
+        self.as_key().map(|k| k.key.code()).unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
     }
 
     /// uwraps event data into the removed tree item
     #[cfg(feature="tree-view")]
     pub fn on_tree_item_delete(&self) -> &crate::TreeItem {
-        match self {
-            EventData::OnTreeItemDelete(item) => item,
-            d => panic!("Wrong data type: {:?}", d)
-        }
+        self.as_tree_item_delete().unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
     }
 
     /// uwraps event data into the update tree view item and the action
     #[cfg(feature="tree-view")]
     pub fn on_tree_item_update(&self) -> (&crate::TreeItem, crate::TreeItemAction) {
-        match self {
-            EventData::OnTreeItemUpdate { item, action } => (item, *action),
-            d => panic!("Wrong data type: {:?}", d)
-        }
+        self.as_tree_item_update().unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
     }
 
     /// unwraps event data into the removed tree item
     #[cfg(feature="tree-view")]
     pub fn on_tree_item_selection_changed(&self) -> (&crate::TreeItem, &crate::TreeItem) {
-        match self {
-            EventData::OnTreeItemSelectionChanged { old, new } => (old, new),
-            d => panic!("Wrong data type: {:?}", d)
-        }
+        self.as_tree_item_selection_changed().unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
     }
 
     /// unwraps event data into the indices of a list view index (row_index, column_index)
     #[cfg(feature="list-view")]
     pub fn on_list_view_item_index(&self) -> (usize, usize) {
-        match self {
-            &EventData::OnListViewItemIndex { row_index, column_index } => (row_index, column_index),
-            d => panic!("Wrong data type: {:?}", d)
-        }
+        self.as_list_view_item_index().unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
     }
 
     /// unwraps event data into the indices of a list view index (row_index, column_index, selected)
     #[cfg(feature="list-view")]
     pub fn on_list_view_item_changed(&self) -> (usize, usize, bool) {
+        self.as_list_view_item_changed().unwrap_or_else(|| panic!("Wrong data type: {:?}", self))
+    }
+
+    /// Returns the paint data of a `OnPaint` event or `None` if the data is not the right type.
+    pub fn as_paint(&self) -> Option<&PaintData> {
+        match self {
+            EventData::OnPaint(p) => Some(p),
+            _ => None
+        }
+    }
+
+    /// Returns the character of a `OnChar` event or `None` if the data is not the right type.
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            EventData::OnChar(c) => Some(*c),
+            _ => None
+        }
+    }
+
+    /// Returns the tooltip data of a `OnTooltipText` event or `None` if the data is not the right type.
+    pub fn as_tooltip_text(&self) -> Option<&ToolTipTextData> {
+        match self {
+            EventData::OnTooltipText(d) => Some(d),
+            _ => None
+        }
+    }
+
+    /// Returns the dropped files of a `OnFileDrop` event or `None` if the data is not the right type.
+    pub fn as_file_drop(&self) -> Option<&DropFiles> {
+        match self {
+            EventData::OnFileDrop(d) => Some(d),
+            _ => None
+        }
+    }
+
+    /// Returns the close data of a `OnWindowClose` event or `None` if the data is not the right type.
+    pub fn as_window_close(&self) -> Option<&WindowCloseData> {
+        match self {
+            EventData::OnWindowClose(d) => Some(d),
+            _ => None
+        }
+    }
+
+    /// Returns the key data of a `OnKeyPress` or `OnKeyRelease` event or `None` if the data is not the right type.
+    pub fn as_key(&self) -> Option<&KeyData> {
         match self {
-            &EventData::OnListViewItemChanged { row_index, column_index, selected} => (row_index, column_index, selected),
-            d => panic!("Wrong data type: {:?}", d)
+            EventData::OnKey(k) => Some(k),
+            _ => None
+        }
+    }
+
+    /// Returns the mouse data of a mouse event (ex: `OnMousePress`, `OnMouseMove`, `OnMouseWheel`, `OnMouseLeave`)
+    /// or `None` if the data is not the right type.
+    pub fn as_mouse(&self) -> Option<&MouseData> {
+        match self {
+            EventData::OnMouse(m) => Some(m),
+            EventData::OnMouseWheel(w) => Some(&w.mouse),
+            _ => None
+        }
+    }
+
+    /// Returns the delta of a `OnMouseWheel` or a `OnMouseHorizontalWheel` event or `None` if the data is not the right type.
+    pub fn as_mouse_wheel(&self) -> Option<i32> {
+        match self {
+            EventData::OnMouseWheel(w) => Some(w.delta),
+            _ => None
+        }
+    }
+
+    /// Returns the new size of a `OnResize` event or `None` if the data is not the right type.
+    pub fn as_resize(&self) -> Option<&ResizeData> {
+        match self {
+            EventData::OnResize(r) => Some(r),
+            _ => None
+        }
+    }
+
+    /// Returns the scroll data of a `OnHorizontalScroll` or `OnVerticalScroll` event or `None` if the data is not the right type.
+    pub fn as_scroll(&self) -> Option<&ScrollData> {
+        match self {
+            EventData::OnScroll(s) => Some(s),
+            _ => None
+        }
+    }
+
+    /// Returns the custom id of a `OnAccelerator` event or `None` if the data is not the right type.
+    pub fn as_accelerator(&self) -> Option<u16> {
+        match self {
+            EventData::OnAccelerator(id) => Some(*id),
+            _ => None
+        }
+    }
+
+    /// Returns the channel data of a `OnNotice` event raised by a `NoticeChannel` or `None` if the data is not the right type.
+    #[cfg(feature="notice")]
+    pub fn as_notice(&self) -> Option<&NoticeData> {
+        match self {
+            EventData::OnNotice(n) => Some(n),
+            _ => None
+        }
+    }
+
+    /// Returns the navigation data of a `OnWizardNavigate` event or `None` if the data is not the right type.
+    #[cfg(feature="wizard")]
+    pub fn as_wizard_navigate(&self) -> Option<&WizardNavigateData> {
+        match self {
+            EventData::OnWizardNavigate(n) => Some(n),
+            _ => None
+        }
+    }
+
+    /// Returns the edit data of a `OnListViewBeginEdit` or `OnListViewEndEdit` event or `None` if the data is not the right type.
+    #[cfg(feature="list-view")]
+    pub fn as_list_view_edit(&self) -> Option<&ListViewEditData> {
+        match self {
+            EventData::OnListViewEdit(e) => Some(e),
+            _ => None
+        }
+    }
+
+    /// Returns the info tip data of a `OnListViewInfoTip` event or `None` if the data is not the right type.
+    #[cfg(feature="list-view")]
+    pub fn as_list_view_info_tip(&self) -> Option<&ListViewInfoTipData> {
+        match self {
+            EventData::OnListViewInfoTip(i) => Some(i),
+            _ => None
+        }
+    }
+
+    /// Returns the item being deleted or `None` if the data is not the right type.
+    #[cfg(feature="tree-view")]
+    pub fn as_tree_item_delete(&self) -> Option<&crate::TreeItem> {
+        match self {
+            EventData::OnTreeItemDelete(item) => Some(item),
+            _ => None
+        }
+    }
+
+    /// Returns the updated tree view item and the action or `None` if the data is not the right type.
+    #[cfg(feature="tree-view")]
+    pub fn as_tree_item_update(&self) -> Option<(&crate::TreeItem, crate::TreeItemAction)> {
+        match self {
+            EventData::OnTreeItemUpdate { item, action } => Some((item, *action)),
+            _ => None
+        }
+    }
+
+    /// Returns the expansion data of a `OnTreeItemExpanding` event or `None` if the data is not the right type.
+    #[cfg(feature="tree-view")]
+    pub fn as_tree_item_expanding(&self) -> Option<&TreeItemExpandingData> {
+        match self {
+            EventData::OnTreeItemExpanding(e) => Some(e),
+            _ => None
+        }
+    }
+
+    /// Returns the old and the new selected tree items or `None` if the data is not the right type.
+    #[cfg(feature="tree-view")]
+    pub fn as_tree_item_selection_changed(&self) -> Option<(&crate::TreeItem, &crate::TreeItem)> {
+        match self {
+            EventData::OnTreeItemSelectionChanged { old, new } => Some((old, new)),
+            _ => None
+        }
+    }
+
+    /// Returns the indices of a list view item (row_index, column_index) or `None` if the data is not the right type.
+    #[cfg(feature="list-view")]
+    pub fn as_list_view_item_index(&self) -> Option<(usize, usize)> {
+        match self {
+            &EventData::OnListViewItemIndex { row_index, column_index } => Some((row_index, column_index)),
+            _ => None
+        }
+    }
+
+    /// Returns the indices and the selected state of a list view item (row_index, column_index, selected)
+    /// or `None` if the data is not the right type.
+    #[cfg(feature="list-view")]
+    pub fn as_list_view_item_changed(&self) -> Option<(usize, usize, bool)> {
+        match self {
+            &EventData::OnListViewItemChanged { row_index, column_index, selected } => Some((row_index, column_index, selected)),
+            _ => None
+        }
+    }
+
+    /// Returns the index of a list view column or `None` if the data is not the right type.
+    #[cfg(feature="list-view")]
+    pub fn as_list_view_column_index(&self) -> Option<usize> {
+        match self {
+            &EventData::OnListViewColumnIndex { column_index } => Some(column_index),
+            _ => None
+        }
+    }
+
+    /// Returns the row index and the check state of a list view item (row_index, checked) or `None` if the data is not the right type.
+    #[cfg(feature="list-view")]
+    pub fn as_list_view_item_checked(&self) -> Option<(usize, bool)> {
+        match self {
+            &EventData::OnListViewItemChecked { row_index, checked } => Some((row_index, checked)),
+            _ => None
+        }
+    }
+
+    /// Returns the id of a list view group or `None` if the data is not the right type.
+    #[cfg(feature="list-view")]
+    pub fn as_list_view_group_id(&self) -> Option<i32> {
+        match self {
+            &EventData::OnListViewGroupId { group_id } => Some(group_id),
+            _ => None
         }
     }
 
@@ -369,8 +666,161 @@ use winapi::um::commctrl::NMTTDISPINFOW;
 use winapi::um::winuser::{PAINTSTRUCT, BeginPaint, EndPaint};
 use winapi::um::shellapi::{HDROP, DragFinish};
 use winapi::shared::windef::HWND;
+use crate::{Key, KeyCombo, Modifiers};
 use std::fmt;
 
+bitflags! {
+    /**
+        The mouse buttons held down during a mouse event
+
+        * LEFT:   The left mouse button
+        * RIGHT:  The right mouse button
+        * MIDDLE: The middle mouse button
+        * X1:     The first X button
+        * X2:     The second X button
+    */
+    pub struct MouseButtons: u8 {
+        const NONE = 0;
+        const LEFT = 0b00001;
+        const RIGHT = 0b00010;
+        const MIDDLE = 0b00100;
+        const X1 = 0b01000;
+        const X2 = 0b10000;
+    }
+}
+
+/// The data of a keyboard event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyData {
+    /// The key pressed or released. Use `Key::code` to get the windows virtual key code.
+    pub key: Key,
+
+    /// The modifier keys held down when the key was pressed or released
+    pub modifiers: Modifiers,
+
+    /// The number of times the keystroke is repeated because the user is holding down the key
+    pub repeat_count: u16,
+
+    /// If the key was already down before the message was sent. Always true for `OnKeyRelease`.
+    pub repeated: bool,
+
+    /// If the key is an extended key, such as the right-hand ALT and CTRL keys or the arrows outside the numeric keypad
+    pub extended: bool,
+}
+
+impl KeyData {
+
+    /// Returns the key combination of the event. Ex: `Ctrl+Shift+S`
+    pub fn combo(&self) -> KeyCombo {
+        KeyCombo::new(self.modifiers, self.key)
+    }
+
+    /// Returns true if the event key and the modifiers held down are exactly the ones of the combination
+    pub fn matches(&self, combo: &KeyCombo) -> bool {
+        self.key == combo.key && self.modifiers == combo.modifiers
+    }
+
+}
+
+/// The data of a mouse event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseData {
+    /// The position of the mouse in the client area of the control. Ex: (0, 0) is the top left corner of the control.
+    pub position: (i32, i32),
+
+    /// The mouse buttons held down during the event
+    pub buttons: MouseButtons,
+
+    /// The modifier keys held down during the event
+    pub modifiers: Modifiers,
+}
+
+/// The data of a mouse wheel event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WheelData {
+    /// The distance the wheel is rotated, in multiples of 120 (`WHEEL_DELTA`).
+    /// For `OnMouseWheel`, a positive value means the wheel was rotated forward, away from the user.
+    /// For `OnMouseHorizontalWheel`, a positive value means the wheel was tilted to the right.
+    pub delta: i32,
+
+    /// The position of the mouse in the client area of the control that received the event
+    pub mouse: MouseData,
+}
+
+/// The reason of a resize event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeKind {
+    /// The control was resized, but not minimized or maximized
+    Restored,
+
+    /// The window was minimized
+    Minimized,
+
+    /// The window was maximized
+    Maximized,
+
+    /// Sent to all the popup windows when another window is restored
+    MaxShow,
+
+    /// Sent to all the popup windows when another window is maximized
+    MaxHide,
+}
+
+/// The data of a resize event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResizeData {
+    /// The new size of the client area of the control
+    pub size: (u32, u32),
+
+    /// The reason of the resize
+    pub kind: ResizeKind,
+}
+
+/// The scroll request of a bar like control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollCode {
+    /// Scroll one line up or left
+    LineUp,
+
+    /// Scroll one line down or right
+    LineDown,
+
+    /// Scroll one page up or left
+    PageUp,
+
+    /// Scroll one page down or right
+    PageDown,
+
+    /// The user released the thumb at `ScrollData::position`
+    ThumbPosition,
+
+    /// The user is dragging the thumb. The thumb is at `ScrollData::position`
+    ThumbTrack,
+
+    /// Scroll to the top or to the left
+    Top,
+
+    /// Scroll to the bottom or to the right
+    Bottom,
+
+    /// The scrolling ended
+    EndScroll,
+
+    /// A scroll code sent by a control that is not listed here
+    Other(u16),
+}
+
+/// The data of a scroll event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollData {
+    /// The scroll request
+    pub code: ScrollCode,
+
+    /// The position of the thumb. Only meaningful with `ScrollCode::ThumbPosition` and `ScrollCode::ThumbTrack`
+    pub position: u32,
+}
+
+
 /// A wrapper structure that set the tooltip text on a `OnTooltipText` callback
 pub struct ToolTipTextData {
     pub(crate) data: *mut NMTTDISPINFOW
@@ -454,6 +904,46 @@ impl fmt::Debug for WindowCloseData {
 }
 
 
+/// Opaque type that manage if a tree item should be expanded or collapsed after a `OnTreeItemExpanding` event
+#[cfg(feature="tree-view")]
+pub struct TreeItemExpandingData {
+    pub(crate) item: winapi::um::commctrl::HTREEITEM,
+    pub(crate) action: crate::ExpandState,
+    pub(crate) allow: *mut bool,
+}
+
+#[cfg(feature="tree-view")]
+impl TreeItemExpandingData {
+
+    /// Returns the item being expanded or collapsed
+    pub fn item(&self) -> crate::TreeItem {
+        crate::TreeItem { handle: self.item }
+    }
+
+    /// Returns `ExpandState::Expand` if the item is being expanded or `ExpandState::Collapse` if it is being collapsed
+    pub fn action(&self) -> crate::ExpandState {
+        self.action
+    }
+
+    /// Sets if the item should be expanded (or collapsed) after the event
+    pub fn allow(&self, value: bool) {
+        unsafe{ *self.allow = value; }
+    }
+
+    /// Returns true if the item will be expanded (or collapsed) after the event or false otherwise
+    pub fn allowed(&self) -> bool {
+        unsafe{ *self.allow }
+    }
+}
+
+#[cfg(feature="tree-view")]
+impl fmt::Debug for TreeItemExpandingData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TreeItemExpandingData({:?}, {:?}, {})", self.item, self.action, self.allowed())
+    }
+}
+
+
 /// Opaque type over a paint event data
 #[derive(Debug)]
 pub struct PaintData {
@@ -553,3 +1043,159 @@ impl Drop for DropFiles {
     }
 
 }
+
+
+/// Opaque type over the queue of a `NoticeChannel`
+#[cfg(feature="notice")]
+pub struct NoticeData {
+    pub(crate) queue: std::sync::Arc<dyn std::any::Any + Send + Sync>,
+}
+
+#[cfg(feature="notice")]
+impl NoticeData {
+
+    /// Remove and return the values queued in the channel, oldest first.
+    /// Returns an empty vector if `T` is not the type of the channel values.
+    pub fn drain<T: Send + 'static>(&self) -> Vec<T> {
+        match self.queue.downcast_ref::<crate::controls::NoticeQueue<T>>() {
+            Some(queue) => queue.drain(),
+            None => Vec::new()
+        }
+    }
+
+}
+
+#[cfg(feature="notice")]
+impl fmt::Debug for NoticeData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NoticeData")
+    }
+}
+
+
+/// Opaque type that manage if the navigation of a wizard should happen after a `OnWizardNavigate` event
+#[cfg(feature="wizard")]
+pub struct WizardNavigateData {
+    pub(crate) action: crate::WizardAction,
+    pub(crate) page: Option<usize>,
+    pub(crate) data: *mut bool
+}
+
+#[cfg(feature="wizard")]
+impl WizardNavigateData {
+
+    /// Returns the navigation button pressed by the user
+    pub fn action(&self) -> crate::WizardAction {
+        self.action
+    }
+
+    /// Returns the index of the page displayed when the button was pressed
+    pub fn page(&self) -> Option<usize> {
+        self.page
+    }
+
+    /// Sets if the navigation should happen after the event. Use `allow(false)` to keep the current page
+    /// (ex: if the page input is not valid) or to keep the wizard open.
+    pub fn allow(&self, value: bool) {
+        unsafe{ *self.data = value; }
+    }
+
+    /// Returns true if the navigation will happen after the event or false otherwise
+    pub fn allowed(&self) -> bool {
+        unsafe{ *self.data }
+    }
+}
+
+#[cfg(feature="wizard")]
+impl fmt::Debug for WizardNavigateData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WizardNavigateData({:?}, {:?}, {})", self.action, self.page, self.allowed())
+    }
+}
+
+
+/// Opaque type that manage the edition of a list view cell in the `OnListViewBeginEdit` and `OnListViewEndEdit` events
+#[cfg(feature="list-view")]
+pub struct ListViewEditData {
+    pub(crate) row_index: usize,
+    pub(crate) column_index: usize,
+    pub(crate) text: Option<String>,
+    pub(crate) allow: *mut bool,
+    pub(crate) replace: *mut Option<String>,
+}
+
+#[cfg(feature="list-view")]
+impl ListViewEditData {
+
+    /// Returns the row index of the edited cell
+    pub fn row_index(&self) -> usize {
+        self.row_index
+    }
+
+    /// Returns the column index of the edited cell
+    pub fn column_index(&self) -> usize {
+        self.column_index
+    }
+
+    /// Returns the text entered by the user. `None` in `OnListViewBeginEdit` or if the user cancelled the edit.
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_ref().map(|t| t as &str)
+    }
+
+    /// Sets if the edit should happen. In `OnListViewBeginEdit`, `allow(false)` keeps the cell from being edited.
+    /// In `OnListViewEndEdit`, `allow(false)` rejects the new text.
+    pub fn allow(&self, value: bool) {
+        unsafe{ *self.allow = value; }
+    }
+
+    /// Returns true if the edit will happen after the event or false otherwise
+    pub fn allowed(&self) -> bool {
+        unsafe{ *self.allow }
+    }
+
+    /// Replaces the text saved in the cell at the end of the edit. Only used by `OnListViewEndEdit`.
+    pub fn set_text<'a>(&self, text: &'a str) {
+        unsafe{ *self.replace = Some(text.to_string()); }
+    }
+}
+
+#[cfg(feature="list-view")]
+impl fmt::Debug for ListViewEditData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ListViewEditData({}, {}, {:?}, {})", self.row_index, self.column_index, self.text, self.allowed())
+    }
+}
+
+
+/// Opaque type that sets the text of a list view info tip in the `OnListViewInfoTip` event
+#[cfg(feature="list-view")]
+pub struct ListViewInfoTipData {
+    pub(crate) row_index: usize,
+    pub(crate) text: *mut Option<String>,
+}
+
+#[cfg(feature="list-view")]
+impl ListViewInfoTipData {
+
+    /// Returns the row index of the item under the mouse
+    pub fn row_index(&self) -> usize {
+        self.row_index
+    }
+
+    /// Sets the text of the info tip. No info tip is displayed if the text is not set.
+    pub fn set_text<'a>(&self, text: &'a str) {
+        unsafe{ *self.text = Some(text.to_string()); }
+    }
+
+    /// Returns the text of the info tip
+    pub fn text(&self) -> Option<&str> {
+        unsafe{ (*self.text).as_ref().map(|t| t as &str) }
+    }
+}
+
+#[cfg(feature="list-view")]
+impl fmt::Debug for ListViewInfoTipData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ListViewInfoTipData({}, {:?})", self.row_index, self.text())
+    }
+}
@@ -0,0 +1,345 @@
+/*!
+    Keyboard keys, modifiers and key combinations.
+
+    `Key` wraps the windows virtual key codes of the `keys` module and `KeyCombo` a key with the modifier keys
+    that must be held down. Key combinations can be parsed from strings:
+
+```rust
+use native_windows_gui as nwg;
+
+fn on_key(data: &nwg::EventData) {
+    let save: nwg::KeyCombo = "Ctrl+S".parse().unwrap();
+    if data.as_key().map(|k| k.matches(&save)).unwrap_or(false) {
+        println!("Saving...");
+    }
+}
+```
+*/
+use crate::{keys, NwgError};
+use std::{fmt, str::FromStr};
+
+
+bitflags! {
+    /**
+        The modifier keys held down during a keyboard or a mouse event
+
+        * SHIFT:   A shift key is down
+        * CONTROL: A control key is down
+        * ALT:     An alt key is down
+        * WIN:     A windows key is down
+    */
+    pub struct Modifiers: u8 {
+        const NONE = 0;
+        const SHIFT = 0b0001;
+        const CONTROL = 0b0010;
+        const ALT = 0b0100;
+        const WIN = 0b1000;
+    }
+}
+
+impl Modifiers {
+
+    /// Parse a modifier name. Names are case insensitive.
+    fn from_name(name: &str) -> Option<Modifiers> {
+        match &name.to_lowercase() as &str {
+            "ctrl" | "control" => Some(Modifiers::CONTROL),
+            "shift" => Some(Modifiers::SHIFT),
+            "alt" => Some(Modifiers::ALT),
+            "win" | "windows" => Some(Modifiers::WIN),
+            _ => None
+        }
+    }
+
+}
+
+
+macro_rules! define_keys {
+    ($($name:ident = $code:path, $label:expr;)*) => {
+        /**
+            A keyboard key. Keys without a name in this enum are kept in `Key::Other` with their virtual key code.
+            Use `Key::from(code)` and `Key::code` to convert from/to the virtual key codes of the `keys` module.
+        */
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Key {
+            $( $name, )*
+            Other(u32),
+        }
+
+        impl Key {
+
+            /// Returns the windows virtual key code of the key
+            pub fn code(&self) -> u32 {
+                match self {
+                    $( Key::$name => $code, )*
+                    Key::Other(code) => *code,
+                }
+            }
+
+            /// Returns the name of the key. Returns `None` for `Key::Other`.
+            pub fn name(&self) -> Option<&'static str> {
+                match self {
+                    $( Key::$name => Some($label), )*
+                    Key::Other(_) => None,
+                }
+            }
+
+            fn from_code(code: u32) -> Key {
+                match code {
+                    $( $code => Key::$name, )*
+                    code => Key::Other(code),
+                }
+            }
+
+            fn from_label(label: &str) -> Option<Key> {
+                $( if label.eq_ignore_ascii_case($label) { return Some(Key::$name); } )*
+                None
+            }
+
+        }
+    };
+}
+
+define_keys! {
+    Back = keys::BACK, "Backspace";
+    Tab = keys::TAB, "Tab";
+    Clear = keys::CLEAR, "Clear";
+    Return = keys::RETURN, "Enter";
+    Shift = keys::SHIFT, "Shift";
+    Control = keys::CONTROL, "Ctrl";
+    Alt = keys::ALT, "Alt";
+    Pause = keys::PAUSE, "Pause";
+    CapsLock = keys::CAPITAL, "CapsLock";
+    Escape = keys::ESCAPE, "Esc";
+    Space = keys::SPACE, "Space";
+    PageUp = keys::PRIOR, "PageUp";
+    PageDown = keys::NEXT, "PageDown";
+    End = keys::END, "End";
+    Home = keys::HOME, "Home";
+    Left = keys::LEFT, "Left";
+    Up = keys::UP, "Up";
+    Right = keys::RIGHT, "Right";
+    Down = keys::DOWN, "Down";
+    PrintScreen = keys::SNAPSHOT, "PrintScreen";
+    Insert = keys::INSERT, "Insert";
+    Delete = keys::DELETE, "Delete";
+    Num0 = keys::_0, "0";
+    Num1 = keys::_1, "1";
+    Num2 = keys::_2, "2";
+    Num3 = keys::_3, "3";
+    Num4 = keys::_4, "4";
+    Num5 = keys::_5, "5";
+    Num6 = keys::_6, "6";
+    Num7 = keys::_7, "7";
+    Num8 = keys::_8, "8";
+    Num9 = keys::_9, "9";
+    A = keys::_A, "A";
+    B = keys::_B, "B";
+    C = keys::_C, "C";
+    D = keys::_D, "D";
+    E = keys::_E, "E";
+    F = keys::_F, "F";
+    G = keys::_G, "G";
+    H = keys::_H, "H";
+    I = keys::_I, "I";
+    J = keys::_J, "J";
+    K = keys::_K, "K";
+    L = keys::_L, "L";
+    M = keys::_M, "M";
+    N = keys::_N, "N";
+    O = keys::_O, "O";
+    P = keys::_P, "P";
+    Q = keys::_Q, "Q";
+    R = keys::_R, "R";
+    S = keys::_S, "S";
+    T = keys::_T, "T";
+    U = keys::_U, "U";
+    V = keys::_V, "V";
+    W = keys::_W, "W";
+    X = keys::_X, "X";
+    Y = keys::_Y, "Y";
+    Z = keys::_Z, "Z";
+    LWin = keys::LWIN, "LWin";
+    RWin = keys::RWIN, "RWin";
+    Apps = keys::APPS, "Apps";
+    Numpad0 = keys::NUMPAD0, "Numpad0";
+    Numpad1 = keys::NUMPAD1, "Numpad1";
+    Numpad2 = keys::NUMPAD2, "Numpad2";
+    Numpad3 = keys::NUMPAD3, "Numpad3";
+    Numpad4 = keys::NUMPAD4, "Numpad4";
+    Numpad5 = keys::NUMPAD5, "Numpad5";
+    Numpad6 = keys::NUMPAD6, "Numpad6";
+    Numpad7 = keys::NUMPAD7, "Numpad7";
+    Numpad8 = keys::NUMPAD8, "Numpad8";
+    Numpad9 = keys::NUMPAD9, "Numpad9";
+    Multiply = keys::MULTIPLY, "Multiply";
+    Add = keys::ADD, "Add";
+    Subtract = keys::SUBTRACT, "Subtract";
+    Decimal = keys::DECIMAL, "Decimal";
+    Divide = keys::DIVIDE, "Divide";
+    F1 = keys::F1, "F1";
+    F2 = keys::F2, "F2";
+    F3 = keys::F3, "F3";
+    F4 = keys::F4, "F4";
+    F5 = keys::F5, "F5";
+    F6 = keys::F6, "F6";
+    F7 = keys::F7, "F7";
+    F8 = keys::F8, "F8";
+    F9 = keys::F9, "F9";
+    F10 = keys::F10, "F10";
+    F11 = keys::F11, "F11";
+    F12 = keys::F12, "F12";
+    F13 = keys::F13, "F13";
+    F14 = keys::F14, "F14";
+    F15 = keys::F15, "F15";
+    F16 = keys::F16, "F16";
+    F17 = keys::F17, "F17";
+    F18 = keys::F18, "F18";
+    F19 = keys::F19, "F19";
+    F20 = keys::F20, "F20";
+    F21 = keys::F21, "F21";
+    F22 = keys::F22, "F22";
+    F23 = keys::F23, "F23";
+    F24 = keys::F24, "F24";
+    NumLock = keys::NUMLOCK, "NumLock";
+    ScrollLock = keys::SCROLL, "ScrollLock";
+    LShift = keys::LSHIFT, "LShift";
+    RShift = keys::RSHIFT, "RShift";
+    LControl = keys::LCONTROL, "LCtrl";
+    RControl = keys::RCONTROL, "RCtrl";
+    LAlt = keys::LMENU, "LAlt";
+    RAlt = keys::RMENU, "RAlt";
+    Plus = keys::OEM_PLUS, "Plus";
+    Comma = keys::OEM_COMMA, "Comma";
+    Minus = keys::OEM_MINUS, "Minus";
+    Period = keys::OEM_PERIOD, "Period";
+}
+
+impl Key {
+
+    /// Parse a key name. Names are case insensitive and the usual abbreviations are accepted. Ex: `Esc`, `Del`, `PgUp`
+    pub fn from_name(name: &str) -> Option<Key> {
+        if let Some(key) = Key::from_label(name) {
+            return Some(key);
+        }
+
+        let key = match &name.to_lowercase() as &str {
+            "back" => Key::Back,
+            "return" => Key::Return,
+            "control" => Key::Control,
+            "escape" => Key::Escape,
+            "pgup" => Key::PageUp,
+            "pgdn" => Key::PageDown,
+            "ins" => Key::Insert,
+            "del" => Key::Delete,
+            "win" => Key::LWin,
+            "+" => Key::Plus,
+            "," => Key::Comma,
+            "-" => Key::Minus,
+            "." => Key::Period,
+            _ => { return None; }
+        };
+
+        Some(key)
+    }
+
+}
+
+impl From<u32> for Key {
+    fn from(code: u32) -> Key {
+        Key::from_code(code)
+    }
+}
+
+impl From<Key> for u32 {
+    fn from(key: Key) -> u32 {
+        key.code()
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "0x{:02X}", self.code())
+        }
+    }
+}
+
+
+/**
+    A key with the modifier keys that must be held down. Ex: `Ctrl+Shift+S`
+
+    A combination can be parsed from a string with `str::parse`. The modifiers (`Ctrl`, `Shift`, `Alt`, `Win`) and the key
+    are separated by `+` and are case insensitive. Use `Plus` or `+` for the plus key (ex: `Ctrl++`).
+    Parsing fails with a `NwgError::BadKeyCombo` error.
+
+    A `KeyCombo` is displayed with the modifiers in the `Ctrl+Shift+Alt+Win` order.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl KeyCombo {
+
+    pub fn new(modifiers: Modifiers, key: Key) -> KeyCombo {
+        KeyCombo { modifiers, key }
+    }
+
+}
+
+impl FromStr for KeyCombo {
+    type Err = NwgError;
+
+    fn from_str(s: &str) -> Result<KeyCombo, NwgError> {
+        let s = s.trim();
+
+        // The plus key is also the separator
+        let (modifiers_part, key_part) = if s == "+" {
+            ("", "+")
+        } else if s.ends_with("++") {
+            (&s[..s.len()-2], "+")
+        } else {
+            match s.rfind('+') {
+                Some(i) => (&s[..i], &s[i+1..]),
+                None => ("", s)
+            }
+        };
+
+        let mut modifiers = Modifiers::NONE;
+        if !modifiers_part.is_empty() {
+            for name in modifiers_part.split('+').map(|n| n.trim()) {
+                match Modifiers::from_name(name) {
+                    Some(m) => { modifiers |= m; },
+                    None => { return Err(NwgError::bad_key_combo(format!("Unknown modifier {:?} in {:?}", name, s))); }
+                }
+            }
+        }
+
+        let key_part = key_part.trim();
+        match Key::from_name(key_part) {
+            Some(key) => Ok(KeyCombo::new(modifiers, key)),
+            None => Err(NwgError::bad_key_combo(format!("Unknown key {:?} in {:?}", key_part, s)))
+        }
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = [
+            (Modifiers::CONTROL, "Ctrl"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::WIN, "Win"),
+        ];
+
+        for &(modifier, name) in names.iter() {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+
+        write!(f, "{}", self.key)
+    }
+}
@@ -0,0 +1,523 @@
+use crate::controls::ControlHandle;
+use crate::win32::window::bind_raw_event_handler_inner;
+use crate::win32::window_helper as wh;
+use crate::NwgError;
+use super::layout::Layout;
+use winapi::shared::windef::HWND;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::ptr;
+
+
+/// The side of the remaining space a `DockLayout` child is attached to
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DockStyle {
+    /// The child is placed at the top of the remaining space and takes its whole width
+    Top,
+
+    /// The child is placed at the bottom of the remaining space and takes its whole width
+    Bottom,
+
+    /// The child is placed at the left of the remaining space and takes its whole height
+    Left,
+
+    /// The child is placed at the right of the remaining space and takes its whole height
+    Right,
+
+    /// The child takes all the remaining space
+    Fill,
+}
+
+/// A control item in a DockLayout
+#[derive(Debug)]
+pub struct DockLayoutItem {
+    /// The handle to the control in the item
+    control: HWND,
+
+    /// The side where the control is docked
+    pub dock: DockStyle,
+
+    /// The height of a `Top` or `Bottom` item, or the width of a `Left` or `Right` item. Unused by `Fill` items.
+    pub size: u32,
+
+    /// The minimum width and height of the control
+    pub min_size: [u32; 2],
+}
+
+impl DockLayoutItem {
+
+    /// Initialize a new dock layout item. The size of the item is the current size of the control.
+    pub fn new<W: Into<ControlHandle>>(c: W, dock: DockStyle) -> DockLayoutItem {
+        let control = c.into().hwnd().expect("Child must be a window-like control (HWND handle)");
+        let (w, h) = unsafe { wh::get_window_size(control) };
+
+        let size = match dock {
+            DockStyle::Top | DockStyle::Bottom => h,
+            DockStyle::Left | DockStyle::Right => w,
+            DockStyle::Fill => 0,
+        };
+
+        DockLayoutItem { control, dock, size, min_size: [0, 0] }
+    }
+
+    /// Sets the height of a `Top` or `Bottom` item, or the width of a `Left` or `Right` item
+    pub fn size(mut self, size: u32) -> DockLayoutItem {
+        self.size = size;
+        self
+    }
+
+    /// Sets the minimum size of the item. The item keeps its minimum size even if there is not enough space left.
+    pub fn min_size(mut self, sz: [u32; 2]) -> DockLayoutItem {
+        self.min_size = sz;
+        self
+    }
+
+}
+
+
+/// The computed position and size of a docked control
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct DockRect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+
+/// This is the inner data shared between the callback and the application
+pub struct DockLayoutInner {
+    /// The control that holds the layout
+    base: HWND,
+
+    /// The children of the control that fit in the layout
+    children: Vec<DockLayoutItem>,
+
+    /// The top, right, bottom, left space around the layout
+    margins: [u32; 4],
+
+    /// The spacing between controls
+    spacing: u32,
+
+    /// If the layout is the child of another layout
+    nested: bool,
+}
+
+impl DockLayoutInner {
+
+    fn try_base(&self) -> Result<HWND, NwgError> {
+        match self.base.is_null() {
+            true => Err(NwgError::not_bound("DockLayout is not initialized")),
+            false => Ok(self.base)
+        }
+    }
+
+    /**
+        Docks the children in order inside the rectangle. Each child takes its size from the remaining space,
+        and a child that does not fit is shrunk down to its minimum size.
+    */
+    fn dock(&self, x: i32, y: i32, width: u32, height: u32) -> Vec<DockRect> {
+        let [m_top, m_right, m_bottom, m_left] = self.margins;
+        let sp = self.spacing as i32;
+
+        let mut left = x + m_left as i32;
+        let mut top = y + m_top as i32;
+        let mut right = x + width as i32 - m_right as i32;
+        let mut bottom = y + height as i32 - m_bottom as i32;
+
+        let mut rects = Vec::with_capacity(self.children.len());
+        for item in self.children.iter() {
+            let available_w = (right - left).max(0) as u32;
+            let available_h = (bottom - top).max(0) as u32;
+            let [min_w, min_h] = item.min_size;
+
+            let rect = match item.dock {
+                DockStyle::Top => {
+                    let height = item.size.min(available_h).max(min_h);
+                    let rect = DockRect { x: left, y: top, width: available_w.max(min_w), height };
+                    top += height as i32 + sp;
+                    rect
+                },
+                DockStyle::Bottom => {
+                    let height = item.size.min(available_h).max(min_h);
+                    let rect = DockRect { x: left, y: bottom - height as i32, width: available_w.max(min_w), height };
+                    bottom -= height as i32 + sp;
+                    rect
+                },
+                DockStyle::Left => {
+                    let width = item.size.min(available_w).max(min_w);
+                    let rect = DockRect { x: left, y: top, width, height: available_h.max(min_h) };
+                    left += width as i32 + sp;
+                    rect
+                },
+                DockStyle::Right => {
+                    let width = item.size.min(available_w).max(min_w);
+                    let rect = DockRect { x: right - width as i32, y: top, width, height: available_h.max(min_h) };
+                    right -= width as i32 + sp;
+                    rect
+                },
+                DockStyle::Fill => {
+                    let rect = DockRect { x: left, y: top, width: available_w.max(min_w), height: available_h.max(min_h) };
+                    left = right;
+                    top = bottom;
+                    rect
+                }
+            };
+
+            rects.push(rect);
+        }
+
+        rects
+    }
+
+}
+
+
+/**
+A layout that docks its children on the sides of the parent, in the order they were added.
+Each `Top`/`Bottom`/`Left`/`Right` child takes a band of the space left by the previous children and
+a `Fill` child takes everything that remains. This is the usual toolbar/sidebar/status/content application shell.
+
+A `StatusBar` resizes itself, but it can be docked at the bottom so that the other children do not overlap it.
+
+NWG layouts use interior mutability to manage their controls.
+
+A DockLayouts has the following properties:
+* margin - The top, right, bottom, left margins of the layout - (default: [0, 0, 0, 0])
+* spacing - The spacing between children controls - (default: 0)
+
+The items have the following properties:
+* dock - The side where the item is docked. See `DockStyle`.
+* size - The height of `Top`/`Bottom` items or the width of `Left`/`Right` items - (default: the control size when it is added)
+* min_size - The minimum size of the item - (default: [0, 0])
+
+```rust
+    use native_windows_gui as nwg;
+    fn layout(layout: &nwg::DockLayout, window: &nwg::Window, toolbar: &nwg::Frame, tree: &nwg::TreeView, status: &nwg::StatusBar, content: &nwg::TextBox) {
+        nwg::DockLayout::builder()
+            .parent(window)
+            .child(nwg::DockStyle::Top, toolbar)
+            .child_size(30)
+            .child(nwg::DockStyle::Bottom, status)
+            .child(nwg::DockStyle::Left, tree)
+            .child_size(200)
+            .child_min_size([100, 0])
+            .child(nwg::DockStyle::Fill, content)
+            .build(&layout);
+    }
+```
+*/
+#[derive(Clone)]
+pub struct DockLayout {
+    inner: Rc<RefCell<DockLayoutInner>>
+}
+
+impl DockLayout {
+
+    pub fn builder() -> DockLayoutBuilder {
+        let layout = DockLayoutInner {
+            base: ptr::null_mut(),
+            children: Vec::new(),
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            nested: false,
+        };
+
+        DockLayoutBuilder { layout, current_index: None }
+    }
+
+    /**
+        Dock a control after the last child of the layout.
+        This is a simplified interface over `add_child_item`
+
+        Panic:
+        - If the layout is not initialized
+        - If the control is not window-like (HWND handle)
+    */
+    pub fn add_child<W: Into<ControlHandle>>(&self, dock: DockStyle, c: W) {
+        self.try_add_child(dock, c).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /**
+        Fallible version of `add_child`.
+        Returns `ControlNotBound` if the layout is not initialized and `InvalidHandle` if the control is not window-like.
+    */
+    pub fn try_add_child<W: Into<ControlHandle>>(&self, dock: DockStyle, c: W) -> Result<(), NwgError> {
+        let handle = c.into();
+        if handle.hwnd().is_none() {
+            return Err(NwgError::invalid_handle("Child must be a window-like control (HWND handle)"));
+        }
+
+        self.try_add_child_item(DockLayoutItem::new(handle, dock))
+    }
+
+    /**
+        Dock a control after the last child of the layout.
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn add_child_item(&self, item: DockLayoutItem) {
+        self.try_add_child_item(item).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /// Fallible version of `add_child_item`. Returns `ControlNotBound` if the layout is not initialized.
+    pub fn try_add_child_item(&self, item: DockLayoutItem) -> Result<(), NwgError> {
+        let base = {
+            let mut inner = self.inner.borrow_mut();
+            let base = inner.try_base()?;
+
+            inner.children.push(item);
+            base
+        };
+
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        self.update_layout(w, h);
+
+        Ok(())
+    }
+
+    /**
+        Remove a control from the layout. The remaining children are docked again.
+
+        Panic:
+        - If the layout is not initialized
+        - If the control is not window-like (HWND handle)
+        - If the control is not in the layout (see `has_child`)
+    */
+    pub fn remove_child<W: Into<ControlHandle>>(&self, c: W) {
+        self.try_remove_child(c).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /**
+        Fallible version of `remove_child`.
+        Returns `ControlNotBound` if the layout is not initialized, `InvalidHandle` if the control is not window-like
+        and `ChildNotFound` if the control is not in the layout.
+    */
+    pub fn try_remove_child<W: Into<ControlHandle>>(&self, c: W) -> Result<(), NwgError> {
+        let base = {
+            let mut inner = self.inner.borrow_mut();
+            let base = inner.try_base()?;
+
+            let handle = c.into().hwnd().ok_or_else(|| NwgError::invalid_handle("Control must be window-like (HWND handle)"))?;
+            let index = inner.children.iter().position(|item| item.control == handle);
+            match index {
+                Some(i) => { inner.children.remove(i); },
+                None => { return Err(NwgError::child_not_found("Control is not in the layout")); }
+            }
+
+            base
+        };
+
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        self.update_layout(w, h);
+
+        Ok(())
+    }
+
+    /**
+        Check if a window control is a children of the layout
+
+        Panic:
+        - If the layout is not initialized
+        - If the child is not a window-like control
+    */
+    pub fn has_child<W: Into<ControlHandle>>(&self, c: W) -> bool {
+        self.try_has_child(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /**
+        Fallible version of `has_child`.
+        Returns `ControlNotBound` if the layout is not initialized and `InvalidHandle` if the control is not window-like.
+    */
+    pub fn try_has_child<W: Into<ControlHandle>>(&self, c: W) -> Result<bool, NwgError> {
+        let inner = self.inner.borrow();
+        inner.try_base()?;
+
+        let handle = c.into().hwnd().ok_or_else(|| NwgError::invalid_handle("Children is not a window-like control (HWND handle)"))?;
+        Ok(inner.children.iter().any(|c| c.control == handle))
+    }
+
+    /// Resize the layout to fit the parent window size
+    ///
+    /// Panic:
+    ///   - The layout must have been successfully built otherwise this function will panic.
+    pub fn fit(&self) {
+        self.try_fit().unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /// Fallible version of `fit`. Returns `ControlNotBound` if the layout is not initialized.
+    pub fn try_fit(&self) -> Result<(), NwgError> {
+        let base = self.inner.borrow().try_base()?;
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        self.update_layout(w, h);
+
+        Ok(())
+    }
+
+    /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
+    pub fn margin(&self, m: [u32; 4]) {
+        let mut inner = self.inner.borrow_mut();
+        inner.margins = m;
+    }
+
+    /// Set the size of the space between the children in the layout. Default value is 0.
+    pub fn spacing(&self, sp: u32) {
+        let mut inner = self.inner.borrow_mut();
+        inner.spacing = sp;
+    }
+
+    fn update_layout(&self, width: u32, height: u32) {
+        if self.inner.borrow().nested {
+            return;
+        }
+
+        self.layout_in(0, 0, width, height);
+    }
+
+}
+
+impl Layout for DockLayout {
+
+    fn layout_in(&self, x: i32, y: i32, width: u32, height: u32) {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() || inner.children.len() == 0 {
+            return;
+        }
+
+        let rects = inner.dock(x, y, width, height);
+        for (item, rect) in inner.children.iter().zip(rects.iter()) {
+            unsafe {
+                wh::set_window_position(item.control, rect.x, rect.y);
+                wh::set_window_size(item.control, rect.width, rect.height, false);
+            }
+        }
+    }
+
+    fn set_nested(&self, nested: bool) {
+        self.inner.borrow_mut().nested = nested;
+    }
+
+}
+
+impl Default for DockLayout {
+
+    fn default() -> DockLayout {
+        let inner = DockLayoutInner {
+            base: ptr::null_mut(),
+            children: Vec::new(),
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            nested: false,
+        };
+
+        DockLayout {
+            inner: Rc::new(RefCell::new(inner))
+        }
+    }
+
+}
+
+
+/// Builder for a `DockLayout` struct
+pub struct DockLayoutBuilder {
+    layout: DockLayoutInner,
+    current_index: Option<usize>,
+}
+
+impl DockLayoutBuilder {
+
+    /// Set the layout parent. The handle must be a window object otherwise the function will panic
+    pub fn parent<W: Into<ControlHandle>>(mut self, p: W) -> DockLayoutBuilder {
+        self.layout.base = p.into().hwnd().expect("Parent must be HWND");
+        self
+    }
+
+    /// Dock a control after the previous children. The size of the child is the current size of the control.
+    /// The handle must be a window object otherwise the function will panic
+    pub fn child<W: Into<ControlHandle>>(self, dock: DockStyle, c: W) -> DockLayoutBuilder {
+        self.child_item(DockLayoutItem::new(c, dock))
+    }
+
+    /// Dock an item after the previous children
+    pub fn child_item(mut self, item: DockLayoutItem) -> DockLayoutBuilder {
+        self.current_index = Some(self.layout.children.len());
+        self.layout.children.push(item);
+        self
+    }
+
+    /// Set the height of the current `Top`/`Bottom` child, or the width of the current `Left`/`Right` child.
+    /// Panics if `child` was not called before.
+    pub fn child_size(mut self, size: u32) -> DockLayoutBuilder {
+        self.current_child_item().size = size;
+        self
+    }
+
+    /// Set the minimum size of the current child.
+    /// Panics if `child` was not called before.
+    pub fn child_min_size(mut self, sz: [u32; 2]) -> DockLayoutBuilder {
+        self.current_child_item().min_size = sz;
+        self
+    }
+
+    /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
+    pub fn margin(mut self, m: [u32; 4]) -> DockLayoutBuilder {
+        self.layout.margins = m;
+        self
+    }
+
+    /// Set the size of the space between the children in the layout. Default value is 0.
+    pub fn spacing(mut self, sp: u32) -> DockLayoutBuilder {
+        self.layout.spacing = sp;
+        self
+    }
+
+    fn current_child_item(&mut self) -> &mut DockLayoutItem {
+        let index = self.current_index.expect("No current children");
+        &mut self.layout.children[index]
+    }
+
+    /// Build the layout object and bind the callback.
+    pub fn build(self, layout: &DockLayout) -> Result<(), NwgError> {
+        use winapi::um::winuser::WM_SIZE;
+        use winapi::shared::minwindef::{HIWORD, LOWORD};
+
+        if self.layout.base.is_null() {
+            return Err(NwgError::layout_create("Docklayout does not have a parent."));
+        }
+
+        let (w, h) = unsafe { wh::get_window_size(self.layout.base) };
+        let base_handle = ControlHandle::Hwnd(self.layout.base);
+
+        // The layout may already be the child of another layout
+        {
+            let mut layout_inner = layout.inner.borrow_mut();
+            let nested = layout_inner.nested;
+            *layout_inner = self.layout;
+            layout_inner.nested = nested;
+        }
+
+        // Initial layout update
+        layout.update_layout(w, h);
+
+        // Bind the event handler
+        let event_layout = layout.clone();
+        let cb = move |_h, msg, _w, l| {
+            if msg == WM_SIZE {
+                let size = l as u32;
+                let width = LOWORD(size) as i32;
+                let height = HIWORD(size) as i32;
+                let (w, h) = unsafe { crate::win32::high_dpi::physical_to_logical(width, height) };
+                DockLayout::update_layout(&event_layout, w as u32, h as u32);
+            }
+            None
+        };
+
+        /// Keep generating ids so that multiple layouts can be applied to the same parent
+        static mut DOCK_LAYOUT_ID: usize = 0xAFFF;
+        bind_raw_event_handler_inner(&base_handle, unsafe { DOCK_LAYOUT_ID += 1; DOCK_LAYOUT_ID }, cb).unwrap();
+
+        Ok(())
+    }
+
+}
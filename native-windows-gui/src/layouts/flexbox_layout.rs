@@ -2,6 +2,7 @@ use crate::controls::ControlHandle;
 use crate::win32::window_helper as wh;
 use crate::win32::window::{RawEventHandler, unbind_raw_event_handler, bind_raw_event_handler_inner};
 use crate::NwgError;
+use super::{GridLayout, layout::Layout};
 use winapi::shared::windef::HWND;
 use std::{ptr, rc::Rc, cell::{RefCell, RefMut, Ref} };
 
@@ -20,9 +21,16 @@ pub struct FlexboxLayoutItem {
     style: Style,
 }
 
+/// A child of a flexbox layout
 pub enum FlexboxLayoutChild {
+    /// A window-like control
     Item(FlexboxLayoutItem),
-    Flexbox(FlexboxLayout)
+
+    /// A flexbox layout. The style of the layout is used as the child style.
+    Flexbox(FlexboxLayout),
+
+    /// A grid layout and its child style
+    Grid(GridLayout, Style),
 }
 
 /// This is the inner data shared between the callback and the application
@@ -31,9 +39,23 @@ struct FlexboxLayoutInner {
     handler: Option<RawEventHandler>,
     style: Style,
     children: Vec<FlexboxLayoutChild>,
+
+    /// If the layout is the child of another layout
+    nested: bool,
 }
 
 
+impl FlexboxLayoutInner {
+
+    fn try_base(&self) -> Result<HWND, NwgError> {
+        match self.base.is_null() {
+            true => Err(NwgError::not_bound("Flexbox layout is not yet initialized!")),
+            false => Ok(self.base)
+        }
+    }
+
+}
+
 /**
     A flexbox layout that organizes the children control in a parent control.
     Flexbox uses the stretch library internally ( https://github.com/vislyhq/stretch ).
@@ -52,7 +74,8 @@ impl FlexboxLayout {
             base: ptr::null_mut(),
             handler: None,
             style: Default::default(),
-            children: Vec::new()
+            children: Vec::new(),
+            nested: false,
         };
 
         FlexboxLayoutBuilder { layout, current_index: None, auto_size: true, auto_spacing: Some(5) }
@@ -65,12 +88,15 @@ impl FlexboxLayout {
         - The layout must have been successfully built otherwise this function will panic.
     */
     pub fn style(&self) -> Style {
+        self.try_style().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fallible version of `style`. Returns `ControlNotBound` if the layout was not initialized.
+    pub fn try_style(&self) -> Result<Style, NwgError> {
         let inner = self.inner.borrow();
-        if inner.base.is_null() {
-            panic!("Flexbox layout is not yet initialized!");
-        }
+        inner.try_base()?;
 
-        inner.style.clone()
+        Ok(inner.style.clone())
     }
 
     /**
@@ -116,6 +142,60 @@ impl FlexboxLayout {
         self.update_layout(w, h)
     }
 
+    /**
+        Fallible version of `add_child`. Returns `ControlNotBound` if the layout was not initialized,
+        `InvalidHandle` if the control is not window-like and `LayoutCreationError` if stretch failed to compute the layout.
+    */
+    pub fn try_add_child<W: Into<ControlHandle>>(&self, c: W, style: Style) -> Result<(), NwgError> {
+        let handle = c.into();
+        if handle.hwnd().is_none() {
+            return Err(NwgError::invalid_handle("Control must be window like (HWND handle)"));
+        }
+
+        self.inner.borrow().try_base()?;
+        self.add_child(handle, style)
+            .map_err(|e| NwgError::layout_create(format!("{:?}", e)))
+    }
+
+    /**
+        Add a grid layout in the flexbox layout with the stretch style.
+        The children of the grid layout must be children of the flexbox layout parent.
+
+        Panic:
+        * If the layout was not initialized
+    */
+    pub fn add_child_grid(&self, layout: &GridLayout, style: Style) -> Result<(), stretch::Error> {
+        self.add_child_layout(FlexboxLayoutChild::Grid(layout.clone(), style))
+    }
+
+    /**
+        Add a flexbox layout in the flexbox layout. The style of `layout` is used as the child style.
+        The children of `layout` must be children of the flexbox layout parent.
+
+        Panic:
+        * If the layout was not initialized
+    */
+    pub fn add_child_flexbox(&self, layout: &FlexboxLayout) -> Result<(), stretch::Error> {
+        self.add_child_layout(FlexboxLayoutChild::Flexbox(layout.clone()))
+    }
+
+    fn add_child_layout(&self, child: FlexboxLayoutChild) -> Result<(), stretch::Error> {
+        let base = {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("Flexbox layout is not yet initialized!");
+            }
+
+            child.set_nested(true);
+            inner.children.push(child);
+
+            inner.base
+        };
+
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        self.update_layout(w, h)
+    }
+
     /**
         Remove a children from the layout
         
@@ -125,18 +205,25 @@ impl FlexboxLayout {
         * If the layout was not initialized
     */
     pub fn remove_child<W: Into<ControlHandle>>(&self, c: W) {
+        self.try_remove_child(c).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /**
+        Fallible version of `remove_child`.
+        Returns `ControlNotBound` if the layout was not initialized, `InvalidHandle` if the control is not window-like
+        and `ChildNotFound` if the control is not in the layout.
+    */
+    pub fn try_remove_child<W: Into<ControlHandle>>(&self, c: W) -> Result<(), NwgError> {
         let mut inner = self.inner.borrow_mut();
-        if inner.base.is_null() {
-            panic!("Flexbox layout is not yet initialized!");
-        }
+        inner.try_base()?;
 
-        let handle = c.into().hwnd().expect("Control must be window like (HWND handle)");
+        let handle = c.into().hwnd().ok_or_else(|| NwgError::invalid_handle("Control must be window like (HWND handle)"))?;
         let index = inner.children.iter()
             .position(|child| child.is_item() && child.as_item().control == handle);
 
         match index {
-            Some(i) => { inner.children.remove(i); },
-            None => { panic!("Control was not found in layout"); }
+            Some(i) => { inner.children.remove(i); Ok(()) },
+            None => Err(NwgError::child_not_found("Control was not found in layout"))
         }
     }
 
@@ -149,13 +236,19 @@ impl FlexboxLayout {
         * If the layout was not initialized
     */
     pub fn has_child<W: Into<ControlHandle>>(&self, c: W) -> bool {
+        self.try_has_child(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /**
+        Fallible version of `has_child`.
+        Returns `ControlNotBound` if the layout was not initialized and `InvalidHandle` if the control is not window-like.
+    */
+    pub fn try_has_child<W: Into<ControlHandle>>(&self, c: W) -> Result<bool, NwgError> {
         let inner = self.inner.borrow();
-        if inner.base.is_null() {
-            panic!("Flexbox layout is not yet initialized!");
-        }
+        inner.try_base()?;
 
-        let handle = c.into().hwnd().expect("Control must be window like (HWND handle)");
-        inner.children.iter().any(|child| child.is_item() && child.as_item().control == handle)
+        let handle = c.into().hwnd().ok_or_else(|| NwgError::invalid_handle("Control must be window like (HWND handle)"))?;
+        Ok(inner.children.iter().any(|child| child.is_item() && child.as_item().control == handle))
     }
 
     /**
@@ -212,7 +305,26 @@ impl FlexboxLayout {
         self.update_layout(w, h)
     }
 
+    /**
+        Fallible version of `fit`. Returns `ControlNotBound` if the layout was not initialized
+        and `LayoutCreationError` if stretch failed to compute the layout.
+    */
+    pub fn try_fit(&self) -> Result<(), NwgError> {
+        let base = self.inner.borrow().try_base()?;
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        self.update_layout(w, h)
+            .map_err(|e| NwgError::layout_create(format!("{:?}", e)))
+    }
+
     fn update_layout(&self, width: u32, height: u32) -> Result<(), stretch::Error> {
+        if self.inner.borrow().nested {
+            return Ok(());
+        }
+
+        self.compute_layout(0, 0, width, height)
+    }
+
+    fn compute_layout(&self, x: i32, y: i32, width: u32, height: u32) -> Result<(), stretch::Error> {
         use FlexboxLayoutChild as Child;
         
         let inner = self.inner.borrow();
@@ -224,31 +336,34 @@ impl FlexboxLayout {
         let mut children: Vec<Node> = Vec::with_capacity(inner.children.len());
 
         for child in inner.children.iter() {
-            let style = match child {
-                Child::Item(child) => child.style,
-                Child::Flexbox(_child) => todo!(),
-            };
-
-            children.push(stretch.new_node(style, Vec::new())?);
+            children.push(stretch.new_node(child.style(), Vec::new())?);
         }
 
         let mut style = inner.style.clone();
         style.size = Size { width: Dimension::Points(width as f32), height: Dimension::Points(height as f32) };
+
+        // The margin of a nested layout is already applied by its parent
+        if inner.nested {
+            style.margin = Default::default();
+        }
+
         let node = stretch.new_node(style, children.clone())?;
 
         stretch.compute_layout(node, Size::undefined())?;
 
         for (node, child) in children.into_iter().zip(inner.children.iter()) {
             let layout = stretch.layout(node)?;
-            let Point { x, y } = layout.location;
+            let Point { x: child_x, y: child_y } = layout.location;
             let Size { width, height } = layout.size;
+            let (child_x, child_y) = (x + child_x as i32, y + child_y as i32);
             
             match child {
                 Child::Item(child) => unsafe {
-                    wh::set_window_position(child.control, x as i32, y as i32);
+                    wh::set_window_position(child.control, child_x, child_y);
                     wh::set_window_size(child.control, width as u32, height as u32, false);
                 },
-                Child::Flexbox(_child) => todo!()
+                Child::Flexbox(child) => child.compute_layout(child_x, child_y, width as u32, height as u32)?,
+                Child::Grid(child, _) => child.layout_in(child_x, child_y, width as u32, height as u32),
             }
             
         }
@@ -258,6 +373,28 @@ impl FlexboxLayout {
 
 }
 
+impl Layout for FlexboxLayout {
+
+    fn layout_in(&self, x: i32, y: i32, width: u32, height: u32) {
+        self.compute_layout(x, y, width, height).expect("Failed to compute layout!");
+    }
+
+    fn set_nested(&self, nested: bool) {
+        self.inner.borrow_mut().nested = nested;
+    }
+
+    fn minimum_size(&self) -> [u32; 2] {
+        let points = |d: Dimension| match d {
+            Dimension::Points(p) => p.max(0.0) as u32,
+            _ => 0
+        };
+
+        let min_size = self.inner.borrow().style.min_size;
+        [points(min_size.width), points(min_size.height)]
+    }
+
+}
+
 pub struct FlexboxLayoutBuilder {
     layout: FlexboxLayoutInner,
     current_index: Option<usize>,
@@ -288,6 +425,23 @@ impl FlexboxLayoutBuilder {
         self
     }
 
+    /// Add a flexbox layout to the layout build. The style of the nested layout is used as the child style,
+    /// so the `child_*` methods modify the nested layout style.
+    /// The nested layout must be built before this layout. Its children must be children of the same parent.
+    pub fn child_flexbox(mut self, layout: &FlexboxLayout) -> FlexboxLayoutBuilder {
+        self.current_index = Some(self.layout.children.len());
+        self.layout.children.push(FlexboxLayoutChild::Flexbox(layout.clone()));
+        self
+    }
+
+    /// Add a grid layout to the layout build.
+    /// The nested layout must be built before this layout. Its children must be children of the same parent.
+    pub fn child_grid(mut self, layout: &GridLayout) -> FlexboxLayoutBuilder {
+        self.current_index = Some(self.layout.children.len());
+        self.layout.children.push(FlexboxLayoutChild::Grid(layout.clone(), Style::default()));
+        self
+    }
+
     /// Make it so that the children of the layout all have equal size
     /// This flags is erased when `size`, `max_size`, or `min_size` is set on the children.
     pub fn auto_size(mut self, auto: bool) -> FlexboxLayoutBuilder {
@@ -374,7 +528,7 @@ impl FlexboxLayoutBuilder {
     /// Set the size of of the current child.
     /// Panics if `child` was not called before.
     pub fn child_size(mut self, size: Size<Dimension>) -> FlexboxLayoutBuilder {
-        self.current_child_item().style.size = size;
+        self.current_child_style(|style| style.size = size);
         self.auto_size = false;
         self
     }
@@ -382,14 +536,14 @@ impl FlexboxLayoutBuilder {
     /// Set the position of the current child.
     /// Panics if `child` was not called before.
     pub fn child_position(mut self, position: Rect<Dimension>) -> FlexboxLayoutBuilder {
-        self.current_child_item().style.position = position;
+        self.current_child_style(|style| style.position = position);
         self
     }
 
     /// Set the margin of the current child.
     /// Panics if `child` was not called before.
     pub fn child_margin(mut self, value: Rect<Dimension>) -> FlexboxLayoutBuilder {
-        self.current_child_item().style.margin = value;
+        self.current_child_style(|style| style.margin = value);
         self.auto_spacing = None;
         self
     }
@@ -397,7 +551,7 @@ impl FlexboxLayoutBuilder {
     /// Set the min size of the current child.
     /// Panics if `child` was not called before.
     pub fn child_min_size(mut self, value: Size<Dimension>) -> FlexboxLayoutBuilder {
-        self.current_child_item().style.min_size = value;
+        self.current_child_style(|style| style.min_size = value);
         self.auto_size = false;
         self
     }
@@ -405,35 +559,35 @@ impl FlexboxLayoutBuilder {
     /// Set the max size of the current child.
     /// Panics if `child` was not called before.
     pub fn child_max_size(mut self, value: Size<Dimension>) -> FlexboxLayoutBuilder {
-        self.current_child_item().style.max_size = value;
+        self.current_child_style(|style| style.max_size = value);
         self.auto_size = false;
         self
     }
 
     /// Panics if `child` was not called before.
     pub fn child_flex_grow(mut self, value: f32) -> FlexboxLayoutBuilder {
-        self.current_child_item().style.flex_grow = value;
+        self.current_child_style(|style| style.flex_grow = value);
         self.auto_size = false;
         self
     }
 
     /// Panics if `child` was not called before.
     pub fn child_flex_shrink(mut self, value: f32) -> FlexboxLayoutBuilder {
-        self.current_child_item().style.flex_shrink = value;
+        self.current_child_style(|style| style.flex_shrink = value);
         self.auto_size = false;
         self
     }
 
     /// Panics if `child` was not called before.
     pub fn child_flex_basis(mut self, value: Dimension) -> FlexboxLayoutBuilder {
-        self.current_child_item().style.flex_basis = value;
+        self.current_child_style(|style| style.flex_basis = value);
         self.auto_size = false;
         self
     }
 
     /// Panics if `child` was not called before.
     pub fn child_align_self(mut self, value: AlignSelf) -> FlexboxLayoutBuilder {
-        self.current_child_item().style.align_self = value;
+        self.current_child_style(|style| style.align_self = value);
         self
     }
 
@@ -444,25 +598,22 @@ impl FlexboxLayoutBuilder {
         If defining style is too verbose, other method such as `size` can be used.
     */
     pub fn style(mut self, style: Style) -> FlexboxLayoutBuilder {
-        self.current_child_item().style = style;
+        self.current_child_style(|s| *s = style);
         self
     }
 
-    fn current_child_item(&mut self) -> &mut FlexboxLayoutItem {
+    fn current_child_style<F: FnOnce(&mut Style)>(&mut self, f: F) {
         assert!(self.current_index.is_some(), "No current children");
 
         let index = self.current_index.unwrap();
-
-        assert!(self.layout.children[index].is_item(), "Current item must be a FlexboxLayoutItem (found children layout)");
-        self.layout.children[index].as_item_mut()
+        self.layout.children[index].with_style_mut(f);
     }
 
     /// Build the layout object and bind the callback.
-    /// Children must only contains window object otherwise this method will panic.
+    /// Children must only contains window object or layouts otherwise this method will panic.
     pub fn build(mut self, layout: &FlexboxLayout) -> Result<(), NwgError> {
         use winapi::um::winuser::WM_SIZE;
         use winapi::shared::minwindef::{HIWORD, LOWORD};
-        use FlexboxLayoutChild as Child;
 
         if self.layout.base.is_null() {
             return Err(NwgError::layout_create("Flexboxlayout does not have a parent."));
@@ -475,20 +626,13 @@ impl FlexboxLayoutBuilder {
         if self.auto_size {
             let children_count = self.layout.children.len();
             let size = 1.0f32 / (children_count as f32);
+            let child_size = match &self.layout.style.flex_direction {
+                FlexDirection::Row | FlexDirection::RowReverse => Size { width: Dimension::Percent(size), height: Dimension::Auto },
+                FlexDirection::Column | FlexDirection::ColumnReverse => Size { width: Dimension::Auto, height: Dimension::Percent(size) },
+            };
+
             for child in self.layout.children.iter_mut() {
-                let style = match child {
-                    Child::Item(item) => &mut item.style,
-                    Child::Flexbox(_item) => todo!(),
-                };
-                
-                match &self.layout.style.flex_direction {
-                    FlexDirection::Row | FlexDirection::RowReverse => {
-                        style.size = Size { width: Dimension::Percent(size), height: Dimension::Auto };
-                    },
-                    FlexDirection::Column | FlexDirection::ColumnReverse => {
-                        style.size = Size { width: Dimension::Auto, height: Dimension::Percent(size) };
-                    }
-                }
+                child.with_style_mut(|style| style.size = child_size);
             }
         }
 
@@ -498,13 +642,14 @@ impl FlexboxLayoutBuilder {
             let spacing = Rect { start: spacing, end: spacing, top: spacing, bottom: spacing};
             self.layout.style.padding = spacing;
             for child in self.layout.children.iter_mut() {
-                match child {
-                    Child::Item(item) => { item.style.margin = spacing; },
-                    Child::Flexbox(_layout) => todo!()
-                }
+                child.with_style_mut(|style| style.margin = spacing);
             }
         }
 
+        for child in self.layout.children.iter() {
+            child.set_nested(true);
+        }
+
         // Saves the new layout. Free the old layout (if there is one)
         {
             let mut layout_inner = layout.inner.borrow_mut();
@@ -512,6 +657,7 @@ impl FlexboxLayoutBuilder {
                 drop(unbind_raw_event_handler(layout_inner.handler.as_ref().unwrap()));
             }
             
+            self.layout.nested = layout_inner.nested;
             *layout_inner = self.layout;        
         }
 
@@ -552,6 +698,7 @@ impl Default for FlexboxLayout {
             handler: None,
             children: Vec::new(),
             style: Default::default(),
+            nested: false,
         };
 
         FlexboxLayout {
@@ -592,6 +739,38 @@ impl FlexboxLayoutChild {
         }
     }
 
+    pub fn is_grid(&self) -> bool {
+        match self {
+            FlexboxLayoutChild::Grid(_, _) => true,
+            _ => false
+        }
+    }
+
+    /// Returns the stretch style of the child
+    pub fn style(&self) -> Style {
+        match self {
+            FlexboxLayoutChild::Item(item) => item.style.clone(),
+            FlexboxLayoutChild::Flexbox(layout) => layout.inner.borrow().style.clone(),
+            FlexboxLayoutChild::Grid(_, style) => style.clone(),
+        }
+    }
+
+    fn with_style_mut<F: FnOnce(&mut Style)>(&mut self, f: F) {
+        match self {
+            FlexboxLayoutChild::Item(item) => f(&mut item.style),
+            FlexboxLayoutChild::Flexbox(layout) => f(&mut layout.inner.borrow_mut().style),
+            FlexboxLayoutChild::Grid(_, style) => f(style),
+        }
+    }
+
+    fn set_nested(&self, nested: bool) {
+        match self {
+            FlexboxLayoutChild::Item(_) => {},
+            FlexboxLayoutChild::Flexbox(layout) => layout.set_nested(nested),
+            FlexboxLayoutChild::Grid(layout, _) => layout.set_nested(nested),
+        }
+    }
+
 }
 
 
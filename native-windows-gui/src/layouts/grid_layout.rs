@@ -2,17 +2,26 @@ use crate::controls::ControlHandle;
 use crate::win32::window::bind_raw_event_handler_inner;
 use crate::win32::window_helper as wh;
 use crate::NwgError;
+use super::grid_layout_solver::{GridLayoutSolver, GridLayoutCell, GridLayoutSize};
+use super::layout::Layout;
 use winapi::shared::windef::{HWND};
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::ptr;
 
 
+/// The content of a grid layout cell
+#[derive(Debug)]
+enum GridLayoutContent {
+    Control(HWND),
+    Layout(Box<dyn Layout>),
+}
+
 /// A control item in a GridLayout
 #[derive(Debug)]
 pub struct GridLayoutItem {
-    /// The handle to the control in the item
-    control: HWND,
+    /// The control or the layout in the item
+    content: GridLayoutContent,
 
     /// The column position of the control in the layout
     pub col: u32,
@@ -24,7 +33,10 @@ pub struct GridLayoutItem {
     pub col_span: u32,
 
     /// The number row this item should span. Should be 1 for single row item.
-    pub row_span: u32
+    pub row_span: u32,
+
+    /// The minimum width and height of the control. Used to size the `GridLayoutSize::Auto` columns and rows.
+    pub min_size: [u32; 2],
 }
 
 impl GridLayoutItem {
@@ -34,14 +46,44 @@ impl GridLayoutItem {
         let control = c.into().hwnd().expect("Child must be a window-like control (HWND handle)");
 
         GridLayoutItem {
-            control,
+            content: GridLayoutContent::Control(control),
+            col,
+            row,
+            col_span,
+            row_span,
+            min_size: [0, 0],
+        }
+    }
+
+    /// Initialize a new grid layout item that holds another layout
+    pub fn new_layout<L: Layout + Clone + 'static>(layout: &L, col: u32, row: u32, col_span: u32, row_span: u32) -> GridLayoutItem {
+        GridLayoutItem {
+            content: GridLayoutContent::Layout(Box::new(layout.clone())),
             col,
             row,
             col_span,
-            row_span
+            row_span,
+            min_size: [0, 0],
+        }
+    }
+
+    fn has_control(&self, handle: HWND) -> bool {
+        match &self.content {
+            GridLayoutContent::Control(control) => *control == handle,
+            GridLayoutContent::Layout(_) => false
         }
     }
 
+    /// Sets the minimum size of the item. Used to size the `GridLayoutSize::Auto` columns and rows.
+    pub fn min_size(mut self, sz: [u32; 2]) -> GridLayoutItem {
+        self.min_size = sz;
+        self
+    }
+
+    fn cell(&self) -> GridLayoutCell {
+        GridLayoutCell { col: self.col, row: self.row, col_span: self.col_span, row_span: self.row_span, min_size: self.min_size }
+    }
+
 }
 
 
@@ -70,7 +112,40 @@ pub struct GridLayoutInner {
     row_count: Option<u32>, 
 
     /// The spacing between controls
-    spacing: u32
+    spacing: u32,
+
+    /// The sizing policy of each column
+    column_sizes: Vec<GridLayoutSize>,
+
+    /// The sizing policy of each row
+    row_sizes: Vec<GridLayoutSize>,
+
+    /// If the layout is the child of another layout
+    nested: bool,
+}
+
+impl GridLayoutInner {
+
+    fn solver(&self) -> GridLayoutSolver {
+        GridLayoutSolver {
+            margins: self.margins,
+            spacing: self.spacing,
+            min_size: self.min_size,
+            max_size: self.max_size,
+            column_count: self.column_count,
+            row_count: self.row_count,
+            column_sizes: self.column_sizes.clone(),
+            row_sizes: self.row_sizes.clone(),
+        }
+    }
+
+    fn try_base(&self) -> Result<HWND, NwgError> {
+        match self.base.is_null() {
+            true => Err(NwgError::not_bound("GridLayout is not initialized")),
+            false => Ok(self.base)
+        }
+    }
+
 }
 
 /** 
@@ -84,6 +159,8 @@ A GridLayouts has the following properties:
 * max_size - The maximum size of the layout - (default: [u32::max_value(), u32::max_value()])
 * max_column - Number of columns - (default: None),
 * max_row - Number of rows - (default: None),
+* column_sizes - The sizing policy of each column. See `GridLayoutSize` - (default: [])
+* row_sizes - The sizing policy of each row. See `GridLayoutSize` - (default: [])
 
 ```rust
     use native_windows_gui as nwg;
@@ -115,7 +192,10 @@ impl GridLayout {
             min_size: [0, 0],
             max_size: [u32::max_value(), u32::max_value()],
             column_count: None,
-            row_count: None
+            row_count: None,
+            column_sizes: Vec::new(),
+            row_sizes: Vec::new(),
+            nested: false,
         };
 
         GridLayoutBuilder { layout }
@@ -130,16 +210,36 @@ impl GridLayout {
         - If the control is not window-like (HWND handle)
     */
     pub fn add_child<W: Into<ControlHandle>>(&self, col: u32, row: u32, c: W) {
-        let h = c.into().hwnd().expect("Child must be a window-like control (HWND handle)");
+        self.try_add_child(col, row, c).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /**
+        Fallible version of `add_child`.
+        Returns `ControlNotBound` if the layout was not initialized and `InvalidHandle` if the control is not window-like.
+    */
+    pub fn try_add_child<W: Into<ControlHandle>>(&self, col: u32, row: u32, c: W) -> Result<(), NwgError> {
+        let h = c.into().hwnd().ok_or_else(|| NwgError::invalid_handle("Child must be a window-like control (HWND handle)"))?;
         let item = GridLayoutItem {
-            control: h,
+            content: GridLayoutContent::Control(h),
             col,
             row,
             col_span: 1,
             row_span: 1,
+            min_size: [0, 0],
         };
 
-        self.add_child_item(item);
+        self.try_add_child_item(item)
+    }
+
+    /**
+        Add a layout in a cell of the grid layout. The children of the layout must be children of the grid layout parent.
+        This is a simplified interface over `add_child_item` and `GridLayoutItem::new_layout`
+
+        Panic:
+        - If the layout was not yet initialized
+    */
+    pub fn add_child_layout<L: Layout + Clone + 'static>(&self, col: u32, row: u32, layout: &L) {
+        self.add_child_item(GridLayoutItem::new_layout(layout, col, row, 1, 1));
     }
     
     /** 
@@ -150,21 +250,30 @@ impl GridLayout {
         - If the control is not window-like (HWND handle)
     */
     pub fn add_child_item(&self, i: GridLayoutItem) {
+        self.try_add_child_item(i).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /// Fallible version of `add_child_item`. Returns `ControlNotBound` if the layout was not initialized.
+    pub fn try_add_child_item(&self, i: GridLayoutItem) -> Result<(), NwgError> {
         let base = {
             let mut inner = self.inner.borrow_mut();
-            if inner.base.is_null() {
-                panic!("GridLayout is not initialized");
-            }
+            let base = inner.try_base()?;
 
             // No need to check the layout item control because it's checked in `GridLayoutItem::new`
 
+            if let GridLayoutContent::Layout(layout) = &i.content {
+                layout.set_nested(true);
+            }
+
             inner.children.push(i);
-            inner.base
+            base
         };
         
 
         let (w, h) = unsafe { wh::get_window_size(base) };
         self.update_layout(w as u32, h as u32);
+
+        Ok(())
     }
 
     /**
@@ -176,25 +285,34 @@ impl GridLayout {
         - If the control is not in the layout (see `has_child`)
     */
     pub fn remove_child<W: Into<ControlHandle>>(&self, c: W) {
+        self.try_remove_child(c).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /**
+        Fallible version of `remove_child`.
+        Returns `ControlNotBound` if the layout was not initialized, `InvalidHandle` if the control is not window-like
+        and `ChildNotFound` if the control is not in the layout.
+    */
+    pub fn try_remove_child<W: Into<ControlHandle>>(&self, c: W) -> Result<(), NwgError> {
         let base = {
             let mut inner = self.inner.borrow_mut();
-            if inner.base.is_null() {
-                panic!("GridLayout is not initialized");
-            }
+            let base = inner.try_base()?;
 
-            let handle = c.into().hwnd().expect("Control must be window-like (HWND handle)");
-            let index = inner.children.iter().position(|item| item.control == handle);
+            let handle = c.into().hwnd().ok_or_else(|| NwgError::invalid_handle("Control must be window-like (HWND handle)"))?;
+            let index = inner.children.iter().position(|item| item.has_control(handle));
             match index {
                 Some(i) => { inner.children.remove(i); },
-                None => { panic!("Control is not in the layout"); }
+                None => { return Err(NwgError::child_not_found("Control is not in the layout")); }
             }
             
-            inner.base
+            base
         };
         
 
         let (w, h) = unsafe { wh::get_window_size(base) };
         self.update_layout(w as u32, h as u32);
+
+        Ok(())
     }
 
     /**
@@ -215,7 +333,12 @@ impl GridLayout {
 
             let index = inner.children.iter().position(|item| item.col == col && item.row == row);
             match index {
-                Some(i) => { inner.children.remove(i); },
+                Some(i) => {
+                    let item = inner.children.remove(i);
+                    if let GridLayoutContent::Layout(layout) = &item.content {
+                        layout.set_nested(false);
+                    }
+                },
                 None => {}
             }
             
@@ -235,13 +358,19 @@ impl GridLayout {
         - If the control is not in the layout (see `has_child`)
     */
     pub fn has_child<W: Into<ControlHandle>>(&self, c: W) -> bool {
+        self.try_has_child(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /**
+        Fallible version of `has_child`.
+        Returns `ControlNotBound` if the layout was not initialized and `InvalidHandle` if the control is not window-like.
+    */
+    pub fn try_has_child<W: Into<ControlHandle>>(&self, c: W) -> Result<bool, NwgError> {
         let inner = self.inner.borrow();
-        if inner.base.is_null() {
-            panic!("GridLayout is not initialized");
-        }
+        inner.try_base()?;
 
-        let handle = c.into().hwnd().expect("Children is not a window-like control (HWND handle)");
-        inner.children.iter().any(|c| c.control == handle )
+        let handle = c.into().hwnd().ok_or_else(|| NwgError::invalid_handle("Children is not a window-like control (HWND handle)"))?;
+        Ok(inner.children.iter().any(|c| c.has_control(handle) ))
     }
 
     /// Resize the layout as if the parent window had the specified size.
@@ -265,13 +394,16 @@ impl GridLayout {
     /// Panic:
     ///   - The layout must have been successfully built otherwise this function will panic.
     pub fn fit(&self) {
-        let inner = self.inner.borrow();
-        if inner.base.is_null() {
-            panic!("Grid layout is not bound to a parent control.")
-        }
+        self.try_fit().unwrap_or_else(|e| panic!("{}", e));
+    }
 
-        let (w, h) = unsafe { wh::get_window_size(inner.base) };
+    /// Fallible version of `fit`. Returns `ControlNotBound` if the layout was not initialized.
+    pub fn try_fit(&self) -> Result<(), NwgError> {
+        let base = self.inner.borrow().try_base()?;
+        let (w, h) = unsafe { wh::get_window_size(base) };
         self.update_layout(w, h);
+
+        Ok(())
     }
 
     /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
@@ -310,83 +442,60 @@ impl GridLayout {
         inner.row_count = count;
     }
 
-    fn update_layout(&self, mut width: u32, mut height: u32) -> () {
-        let inner = self.inner.borrow();
-        if inner.base.is_null() || inner.children.len() == 0 {
-            return;
-        }
-
-        let [m_top, m_right, m_bottom, m_left] = inner.margins;
-        let sp = inner.spacing;
-
-        let children = &inner.children;
-
-        let [min_w, min_h] = inner.min_size;
-        if width < min_w { width = min_w; }
-        if height < min_h { height = min_h; }
-
-        let [max_w, max_h] = inner.max_size;
-        if width > max_w { width = max_w; }
-        if height > max_h { height = max_h; }
-
-        let column_count = match inner.column_count {
-            Some(c) => c,
-            None => children.iter().map(|item| item.col + item.col_span).max().unwrap_or(1)
-        };
-
-        let row_count = match inner.row_count {
-            Some(c) => c,
-            None => children.iter().map(|item| item.row + item.row_span).max().unwrap_or(1)
-        };
+    /// Set the sizing policy of the columns. Columns without a policy use `GridLayoutSize::Stretch(1)`.
+    pub fn column_sizes(&self, sizes: &[GridLayoutSize]) {
+        let mut inner = self.inner.borrow_mut();
+        inner.column_sizes = sizes.to_vec();
+    }
 
-        if width < (m_right + m_left) + ((sp * 2) * column_count) {
-            return;
-        }
+    /// Set the sizing policy of the rows. Rows without a policy use `GridLayoutSize::Stretch(1)`.
+    pub fn row_sizes(&self, sizes: &[GridLayoutSize]) {
+        let mut inner = self.inner.borrow_mut();
+        inner.row_sizes = sizes.to_vec();
+    }
 
-        if height < (m_top + m_bottom) + ((sp * 2) * row_count) {
+    fn update_layout(&self, width: u32, height: u32) -> () {
+        if self.inner.borrow().nested {
             return;
         }
 
-        // Apply margins
-        width = width - m_right - m_left;
-        height = height - m_top - m_bottom;
-
-        // Apply spacing
-        width = width - ((sp * 2) * column_count);
-        height = height - ((sp * 2) * row_count);
+        self.layout_in(0, 0, width, height);
+    }
+}
 
-        let item_width = width / column_count;
-        let item_height = height / row_count;
-        let sp2 = sp * 2;
+impl Layout for GridLayout {
 
-        let mut columns = vec![item_width; column_count as usize];
-        let mut rows = vec![item_height; row_count as usize];
-        let extra_width = width - item_width * column_count;
-        if extra_width > 0 {
-            for x in &mut columns[0..(extra_width as usize)] {
-                *x += 1;
-            }
+    fn layout_in(&self, x: i32, y: i32, width: u32, height: u32) {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() || inner.children.len() == 0 {
+            return;
         }
-        let extra_height = height - item_height * row_count;
-        if extra_height > 0 {
-            for x in &mut rows[0..(extra_height as usize)] {
-                *x += 1;
+
+        let cells: Vec<GridLayoutCell> = inner.children.iter().map(|item| item.cell()).collect();
+        let rects = inner.solver().solve(&cells, width, height);
+
+        for (item, rect) in inner.children.iter().zip(rects.iter()) {
+            let (item_x, item_y) = (x + rect.x as i32, y + rect.y as i32);
+            match &item.content {
+                GridLayoutContent::Control(control) => unsafe {
+                    wh::set_window_position(*control, item_x, item_y);
+                    wh::set_window_size(*control, rect.width, rect.height, false);
+                },
+                GridLayoutContent::Layout(layout) => {
+                    layout.layout_in(item_x, item_y, rect.width, rect.height);
+                }
             }
         }
+    }
 
-        for item in inner.children.iter() {
-            let x = m_left + (sp + (sp2 * item.col)) + &columns[0..(item.col as usize)].iter().sum();
-            let y = m_top + (sp + (sp2 * item.row)) + &rows[0..(item.row as usize)].iter().sum();
-
-            let local_width = &columns[(item.col as usize)..((item.col + item.col_span) as usize)].iter().sum() + (sp2 * (item.col_span - 1));
-            let local_height = &rows[(item.row as usize)..((item.row + item.row_span) as usize)].iter().sum() + (sp2 * (item.row_span - 1));
+    fn set_nested(&self, nested: bool) {
+        self.inner.borrow_mut().nested = nested;
+    }
 
-            unsafe {
-                wh::set_window_position(item.control, x as i32, y as i32);
-                wh::set_window_size(item.control, local_width, local_height, false);
-            }
-        }
+    fn minimum_size(&self) -> [u32; 2] {
+        self.inner.borrow().min_size
     }
+
 }
 
 impl Default for GridLayout {
@@ -401,6 +510,9 @@ impl Default for GridLayout {
             column_count: None,
             row_count: None,
             spacing: 5,
+            column_sizes: Vec::new(),
+            row_sizes: Vec::new(),
+            nested: false,
         };
 
         GridLayout {
@@ -430,16 +542,25 @@ impl GridLayoutBuilder {
     pub fn child<W: Into<ControlHandle>>(mut self, col: u32, row: u32, c: W) -> GridLayoutBuilder {
         let h = c.into().hwnd().expect("Child must be HWND");
         self.layout.children.push(GridLayoutItem {
-            control: h,
+            content: GridLayoutContent::Control(h),
             col,
             row,
             col_span: 1,
             row_span: 1,
+            min_size: [0, 0],
         });
 
         self
     }
 
+    /// Add a layout to the layout at the position `col` and `row`.
+    /// This is a shortcut over `child_item` and `GridLayoutItem::new_layout` for item with default span.
+    /// The children of the layout must be children of the grid layout parent.
+    pub fn child_layout<L: Layout + Clone + 'static>(mut self, col: u32, row: u32, layout: &L) -> GridLayoutBuilder {
+        self.layout.children.push(GridLayoutItem::new_layout(layout, col, row, 1, 1));
+        self
+    }
+
     /// Add a children to the layout
     /// The handle must be a window object otherwise the function will panic
     pub fn child_item(mut self, item: GridLayoutItem) -> GridLayoutBuilder {
@@ -483,6 +604,18 @@ impl GridLayoutBuilder {
         self
     }
 
+    /// Set the sizing policy of the columns. Columns without a policy use `GridLayoutSize::Stretch(1)`.
+    pub fn column_sizes(mut self, sizes: &[GridLayoutSize]) -> GridLayoutBuilder {
+        self.layout.column_sizes = sizes.to_vec();
+        self
+    }
+
+    /// Set the sizing policy of the rows. Rows without a policy use `GridLayoutSize::Stretch(1)`.
+    pub fn row_sizes(mut self, sizes: &[GridLayoutSize]) -> GridLayoutBuilder {
+        self.layout.row_sizes = sizes.to_vec();
+        self
+    }
+
     /// Build the layout object and bind the callback.
     /// Children must only contains window object otherwise this method will panic.
     pub fn build(self, layout: &GridLayout) -> Result<(), NwgError> {
@@ -509,10 +642,19 @@ impl GridLayoutBuilder {
         let (w, h) = unsafe { wh::get_window_size(self.layout.base) };
         let base_handle = ControlHandle::Hwnd(self.layout.base);
 
+        for item in self.layout.children.iter() {
+            if let GridLayoutContent::Layout(child_layout) = &item.content {
+                child_layout.set_nested(true);
+            }
+        }
+
         // Saves the new layout. TODO: should free the old one too (if any)
+        // The layout may already be the child of another layout
         {
             let mut layout_inner = layout.inner.borrow_mut();
-            *layout_inner = self.layout;        
+            let nested = layout_inner.nested;
+            *layout_inner = self.layout;
+            layout_inner.nested = nested;
         }
 
         // Initial layout update
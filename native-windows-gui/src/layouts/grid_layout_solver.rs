@@ -0,0 +1,264 @@
+/*!
+    The cell computations of `GridLayout`, without any window involved.
+
+    The solver takes the layout properties and the position/span of the children and returns
+    where each children should be placed. `GridLayout` applies the rectangles to its controls.
+*/
+
+
+/**
+    How the size of a column or a row of a `GridLayout` is computed.
+    Lines without an explicit size use `GridLayoutSize::Stretch(1)`.
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GridLayoutSize {
+    /// The line has a fixed size in pixels
+    Fixed(u32),
+
+    /// The line shares the space left by the fixed and auto lines with the other stretch lines, proportionally to the factor.
+    Stretch(u32),
+
+    /// The line is as big as the biggest minimum size of the single span children it contains
+    Auto,
+}
+
+impl Default for GridLayoutSize {
+    fn default() -> GridLayoutSize {
+        GridLayoutSize::Stretch(1)
+    }
+}
+
+
+/// The position of a child in a grid. Same fields as `GridLayoutItem`, without the control.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GridLayoutCell {
+    /// The column position of the child in the layout
+    pub col: u32,
+
+    /// The row position of the child in the layout
+    pub row: u32,
+
+    /// The number column this child should span. Should be 1 for single column item.
+    pub col_span: u32,
+
+    /// The number row this child should span. Should be 1 for single row item.
+    pub row_span: u32,
+
+    /// The minimum width and height of the child. Used by `GridLayoutSize::Auto` lines.
+    pub min_size: [u32; 2],
+}
+
+impl GridLayoutCell {
+
+    /// Initialize a new grid layout cell
+    pub fn new(col: u32, row: u32, col_span: u32, row_span: u32) -> GridLayoutCell {
+        GridLayoutCell { col, row, col_span, row_span, min_size: [0, 0] }
+    }
+
+}
+
+/// The computed position and size of a child, relative to the layout parent
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GridLayoutRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl GridLayoutRect {
+
+    /// Returns true if the two rectangles share at least one pixel
+    pub fn intersects(&self, other: &GridLayoutRect) -> bool {
+        self.x < other.x + other.width && other.x < self.x + self.width &&
+        self.y < other.y + other.height && other.y < self.y + self.height
+    }
+
+}
+
+/**
+    Computes the cells of a grid layout. See `GridLayout` for the meaning of the properties.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let solver = nwg::GridLayoutSolver { spacing: 0, margins: [0, 0, 0, 0], ..Default::default() };
+    let rects = solver.solve(&[nwg::GridLayoutCell::new(0, 0, 1, 1), nwg::GridLayoutCell::new(1, 0, 1, 1)], 100, 50);
+    assert_eq!(rects[1], nwg::GridLayoutRect { x: 50, y: 0, width: 50, height: 50 });
+    ```
+*/
+#[derive(Clone, Debug)]
+pub struct GridLayoutSolver {
+    /// The top, right, bottom, left space around the layout
+    pub margins: [u32; 4],
+
+    /// The spacing between children
+    pub spacing: u32,
+
+    /// The minimum size of the layout. Used if the parent is smaller than `min_size`.
+    pub min_size: [u32; 2],
+
+    /// The maximum size of the layout. Used if the parent is bigger than `max_size`.
+    pub max_size: [u32; 2],
+
+    /// The number of column. If None, compute the value from children.
+    pub column_count: Option<u32>,
+
+    /// The number of row. If None, compute the value from children.
+    pub row_count: Option<u32>,
+
+    /// The size of each column. Missing values are `GridLayoutSize::Stretch(1)`.
+    pub column_sizes: Vec<GridLayoutSize>,
+
+    /// The size of each row. Missing values are `GridLayoutSize::Stretch(1)`.
+    pub row_sizes: Vec<GridLayoutSize>,
+}
+
+impl GridLayoutSolver {
+
+    /// Returns the number of columns and rows of the grid
+    pub fn grid_size(&self, cells: &[GridLayoutCell]) -> (u32, u32) {
+        let column_count = match self.column_count {
+            Some(c) => c,
+            None => cells.iter().map(|item| item.col + item.col_span).max().unwrap_or(1).max(self.column_sizes.len() as u32)
+        };
+
+        let row_count = match self.row_count {
+            Some(c) => c,
+            None => cells.iter().map(|item| item.row + item.row_span).max().unwrap_or(1).max(self.row_sizes.len() as u32)
+        };
+
+        (column_count.max(1), row_count.max(1))
+    }
+
+    /**
+        Computes the rectangle of every cell for a parent of size `width`x`height`.
+        The rectangles are returned in the same order as `cells`.
+
+        Returns an empty list if there is not enough space to fit the margins and the spacing.
+        Spans that go over the last column or row are clamped to the grid.
+    */
+    pub fn solve(&self, cells: &[GridLayoutCell], mut width: u32, mut height: u32) -> Vec<GridLayoutRect> {
+        if cells.len() == 0 {
+            return Vec::new();
+        }
+
+        let [m_top, m_right, m_bottom, m_left] = self.margins;
+        let sp = self.spacing;
+        let sp2 = sp * 2;
+
+        let [min_w, min_h] = self.min_size;
+        if width < min_w { width = min_w; }
+        if height < min_h { height = min_h; }
+
+        let [max_w, max_h] = self.max_size;
+        if width > max_w { width = max_w; }
+        if height > max_h { height = max_h; }
+
+        let (column_count, row_count) = self.grid_size(cells);
+
+        if width < (m_right + m_left) + (sp2 * column_count) {
+            return Vec::new();
+        }
+
+        if height < (m_top + m_bottom) + (sp2 * row_count) {
+            return Vec::new();
+        }
+
+        // Apply margins and spacing
+        width = width - m_right - m_left - (sp2 * column_count);
+        height = height - m_top - m_bottom - (sp2 * row_count);
+
+        let column_mins: Vec<(u32, u32)> = cells.iter().filter(|c| c.col_span == 1).map(|c| (c.col, c.min_size[0])).collect();
+        let row_mins: Vec<(u32, u32)> = cells.iter().filter(|c| c.row_span == 1).map(|c| (c.row, c.min_size[1])).collect();
+        let columns = split_lines(width, column_count, &self.column_sizes, &column_mins);
+        let rows = split_lines(height, row_count, &self.row_sizes, &row_mins);
+
+        cells.iter()
+            .map(|cell| {
+                let (col, col_span) = clamp_span(cell.col, cell.col_span, column_count);
+                let (row, row_span) = clamp_span(cell.row, cell.row_span, row_count);
+
+                let x = m_left + sp + (sp2 * col) + columns[0..(col as usize)].iter().sum::<u32>();
+                let y = m_top + sp + (sp2 * row) + rows[0..(row as usize)].iter().sum::<u32>();
+
+                let width = columns[(col as usize)..((col + col_span) as usize)].iter().sum::<u32>() + (sp2 * (col_span - 1));
+                let height = rows[(row as usize)..((row + row_span) as usize)].iter().sum::<u32>() + (sp2 * (row_span - 1));
+
+                GridLayoutRect { x, y, width, height }
+            })
+            .collect()
+    }
+
+}
+
+impl Default for GridLayoutSolver {
+
+    fn default() -> GridLayoutSolver {
+        GridLayoutSolver {
+            margins: [5, 5, 5, 5],
+            spacing: 5,
+            min_size: [0, 0],
+            max_size: [u32::max_value(), u32::max_value()],
+            column_count: None,
+            row_count: None,
+            column_sizes: Vec::new(),
+            row_sizes: Vec::new(),
+        }
+    }
+
+}
+
+/**
+    Split `total` in `count` lines. Fixed and auto lines are sized first, then the remaining space is shared between the stretch lines.
+    The remainder of the division goes to the first stretch lines.
+
+    `mins` is the list of (line, minimum size) of the single span children.
+*/
+fn split_lines(total: u32, count: u32, sizes: &[GridLayoutSize], mins: &[(u32, u32)]) -> Vec<u32> {
+    use GridLayoutSize::*;
+
+    let policy = |i: usize| sizes.get(i).copied().unwrap_or_default();
+
+    let mut parts: Vec<u32> = (0..count as usize)
+        .map(|i| match policy(i) {
+            Fixed(size) => size,
+            Auto => mins.iter().filter(|(line, _)| *line as usize == i).map(|(_, min)| *min).max().unwrap_or(0),
+            Stretch(_) => 0,
+        })
+        .collect();
+
+    let used: u32 = parts.iter().sum();
+    let remaining = total.saturating_sub(used);
+    let weights: Vec<u32> = (0..count as usize).map(|i| match policy(i) { Stretch(w) => w, _ => 0 }).collect();
+    let total_weight: u64 = weights.iter().map(|&w| w as u64).sum();
+    if total_weight == 0 {
+        return parts;
+    }
+
+    let mut shared = 0;
+    for (part, &weight) in parts.iter_mut().zip(weights.iter()) {
+        if weight > 0 {
+            *part = ((remaining as u64 * weight as u64) / total_weight) as u32;
+            shared += *part;
+        }
+    }
+
+    let mut extra = remaining - shared;
+    for (part, &weight) in parts.iter_mut().zip(weights.iter()) {
+        if extra == 0 { break; }
+        if weight > 0 {
+            *part += 1;
+            extra -= 1;
+        }
+    }
+
+    parts
+}
+
+/// Keeps a position and a span inside a grid of `count` lines
+fn clamp_span(pos: u32, span: u32, count: u32) -> (u32, u32) {
+    let pos = pos.min(count - 1);
+    let span = span.max(1).min(count - pos);
+    (pos, span)
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+
+/**
+    A layout that can be placed inside another layout.
+
+    `GridLayout` cells and `FlexboxLayout` children can host a layout implementing this trait. The nested layout
+    is resized by its parent, so only the top level layout reacts to the parent window resize events.
+    The children of a nested layout must be children of the same parent window as the top level layout.
+
+    ```rust
+    use native_windows_gui as nwg;
+    fn layout(window: &nwg::Window, outer: &nwg::GridLayout, inner: &nwg::GridLayout, b1: &nwg::Button, b2: &nwg::Button, b3: &nwg::Button) {
+        nwg::GridLayout::builder()
+            .parent(window)
+            .child(0, 0, b1)
+            .child(0, 1, b2)
+            .build(inner);
+
+        nwg::GridLayout::builder()
+            .parent(window)
+            .child_layout(0, 0, inner)
+            .child(1, 0, b3)
+            .build(outer);
+    }
+    ```
+*/
+pub trait Layout {
+
+    /**
+        Places the children of the layout inside a rectangle of the parent window.
+        The position and the size are in logical pixels.
+    */
+    fn layout_in(&self, x: i32, y: i32, width: u32, height: u32);
+
+    /**
+        Marks the layout as the child of another layout. Nested layouts ignore the resize events of their parent window.
+        Called by the parent layout when the layout is added or removed.
+    */
+    fn set_nested(&self, nested: bool);
+
+    /**
+        Returns the minimum width and height of the layout. Used by the containers that scroll their content, like `ScrollPanel`.
+        By default, a layout has no minimum size.
+    */
+    fn minimum_size(&self) -> [u32; 2] {
+        [0, 0]
+    }
+
+}
+
+impl fmt::Debug for dyn Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Layout")
+    }
+}
@@ -1,9 +1,21 @@
+#[cfg(windows)]
+mod layout;
+#[cfg(windows)]
 mod grid_layout;
+mod grid_layout_solver;
+#[cfg(windows)]
+mod dock_layout;
 
-#[cfg(feature = "flexbox")]
+#[cfg(all(windows, feature = "flexbox"))]
 mod flexbox_layout;
 
+#[cfg(windows)]
+pub use self::layout::Layout;
+#[cfg(windows)]
 pub use self::grid_layout::{GridLayout, GridLayoutInner, GridLayoutBuilder, GridLayoutItem};
+pub use self::grid_layout_solver::{GridLayoutSolver, GridLayoutCell, GridLayoutRect, GridLayoutSize};
+#[cfg(windows)]
+pub use self::dock_layout::{DockLayout, DockLayoutInner, DockLayoutBuilder, DockLayoutItem, DockStyle};
 
-#[cfg(feature = "flexbox")]
-pub use self::flexbox_layout::{FlexboxLayout, FlexboxLayoutBuilder, FlexboxLayoutItem, FlexboxLayoutChildrenMut, FlexboxLayoutChildren};
+#[cfg(all(windows, feature = "flexbox"))]
+pub use self::flexbox_layout::{FlexboxLayout, FlexboxLayoutBuilder, FlexboxLayoutItem, FlexboxLayoutChild, FlexboxLayoutChildrenMut, FlexboxLayoutChildren};
@@ -1,28 +1,48 @@
+// NWG only works on Windows. On other targets, only the platform independent parts are compiled (the grid layout solver).
+
+#[cfg(windows)]
 #[macro_use]
 extern crate bitflags;
 
+#[cfg(windows)]
 #[macro_use]
 extern crate lazy_static;
 
+#[cfg(windows)]
 extern crate winapi;
 
+#[cfg(all(feature = "headless", not(windows)))]
+compile_error!("The `headless` backend needs a Windows target. winapi only exports its types on Windows.");
+
 #[cfg(feature="flexbox")]
 pub extern crate stretch;
 
-#[cfg(feature="all")]
 #[cfg(test)]
 mod tests;
 
+#[cfg(windows)]
 mod errors;
-pub use errors::{NwgError};
+#[cfg(windows)]
+pub use errors::{NwgError, ErrorInfo, SystemErrorCode};
 
+#[cfg(windows)]
 mod events;
+#[cfg(windows)]
 pub use events::*;
 
+#[cfg(windows)]
 mod common_types;
+#[cfg(windows)]
 pub use common_types::*;
 
+#[cfg(windows)]
+mod keyboard;
+#[cfg(windows)]
+pub use keyboard::{Key, KeyCombo, Modifiers};
+
+#[cfg(windows)]
 pub(crate) mod win32;
+#[cfg(windows)]
 pub use win32::{
  dispatch_thread_events, dispatch_thread_events_with_callback, stop_thread_dispatch, enable_visual_styles, init_common_controls, 
  window::{
@@ -33,30 +53,42 @@ pub use win32::{
  message_box::*
 };
 
+#[cfg(windows)]
 pub(crate) use win32::window::bind_raw_event_handler_inner;
 
+#[cfg(windows)]
 #[allow(deprecated)]
 pub use win32::high_dpi::{set_dpi_awareness, scale_factor, dpi};
 
-#[cfg(feature="cursor")]
+#[cfg(all(windows, feature="cursor"))]
 pub use win32::cursor::GlobalCursor;
 
-#[cfg(feature="clipboard")]
+#[cfg(all(windows, feature="clipboard"))]
 pub use win32::clipboard::{Clipboard, ClipboardFormat, ClipboardData};
 
+#[cfg(all(windows, feature="event-injection"))]
+pub use win32::event_injection::{inject_event, inject_event_to, inject_window_close};
+
+#[cfg(all(windows, feature="async"))]
+pub use win32::executor::{spawn_local, spawn_background, LocalTask, BackgroundTask, ModalFuture};
+
+#[cfg(windows)]
 mod resources;
+#[cfg(windows)]
 pub use resources::*;
 
+#[cfg(windows)]
 mod controls;
+#[cfg(windows)]
 pub use controls::*;
 
 mod layouts;
 pub use layouts::*;
 
-#[cfg(feature = "winnls")]
+#[cfg(all(windows, feature = "winnls"))]
 mod winnls;
 
-#[cfg(feature = "winnls")]
+#[cfg(all(windows, feature = "winnls"))]
 pub use winnls::*;
 
 /**
@@ -70,6 +102,7 @@ pub use winnls::*;
 
     For an example on how to implement this trait, see the **Small application layout** section in the NWG documentation.
 */
+#[cfg(windows)]
 pub trait PartialUi {
     /**
         Should initializes the GUI components. Similar to `NativeUi::build_ui` except it doesn't handle events binding.
@@ -106,6 +139,7 @@ pub trait PartialUi {
 
     For an example on how to implement this trait, see the **Small application layout** section in the NWG documentation.
 */
+#[cfg(windows)]
 pub trait NativeUi<UI> {
 
     /**
@@ -120,6 +154,7 @@ pub trait NativeUi<UI> {
 
 /// Initialize some application wide GUI settings.
 /// This includes default styling and common controls resources.
+#[cfg(windows)]
 pub fn init() -> std::result::Result<(), errors::NwgError> {
     if cfg!(not(feature="no-styling")) {
         enable_visual_styles();
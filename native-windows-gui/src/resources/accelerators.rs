@@ -0,0 +1,176 @@
+use winapi::shared::windef::{HACCEL, HWND, HMENU};
+use crate::win32::accelerators::{build_accelerators, destroy_accelerators};
+use crate::win32::base_helper::CUSTOM_ID_BEGIN;
+use crate::{ControlHandle, KeyCombo, NwgError};
+use std::ptr;
+
+#[cfg(feature = "menu")]
+use crate::MenuItem;
+
+
+/**
+An accelerators table maps key combinations to menu items or to custom ids for a top level window.
+The accelerators are translated by the dispatch loops (`dispatch_thread_events` and `dispatch_thread_events_with_callback`)
+when a key is pressed in the window or in one of its children.
+
+  * A combination mapped to a menu item raises `OnMenuItemSelected` for the menu item. Disabled menu items are ignored.
+  * A combination mapped to a custom id raises `OnAccelerator` on the parent window. The id is in `EventData::OnAccelerator`.
+
+Custom ids must be lower than 10000. The `Win` modifier is not supported by accelerators, use `GlobalHotkey` instead.
+The accelerators stop working when the resource is dropped.
+
+Accelerators are behind the "accelerators" feature.
+
+**Builder parameters:**
+  * `parent`:      **Required.** The top level window of the accelerators
+  * `accelerator`: Add a key combination that raises `OnAccelerator` with a custom id
+  * `menu_item`:   Add a key combination that raises `OnMenuItemSelected` for a menu item. Requires the "menu" feature.
+
+```rust
+use native_windows_gui as nwg;
+
+const SAVE_ALL: u16 = 1;
+
+fn build_accelerators(accels: &mut nwg::Accelerators, window: &nwg::Window, save: &nwg::MenuItem) -> Result<(), nwg::NwgError> {
+    nwg::Accelerators::builder()
+        .parent(window)
+        .menu_item("Ctrl+S".parse()?, save)
+        .accelerator("Ctrl+Shift+S".parse()?, SAVE_ALL)
+        .build(accels)
+}
+```
+*/
+pub struct Accelerators {
+    pub handle: HACCEL,
+    window: HWND,
+    entries: Vec<(KeyCombo, u16)>,
+}
+
+impl Accelerators {
+
+    pub fn builder() -> AcceleratorsBuilder {
+        AcceleratorsBuilder {
+            parent: None,
+            entries: Vec::new(),
+            menu_items: Vec::new(),
+        }
+    }
+
+    /// Returns the window handle of the accelerators. Returns `None` if the resource is not initialized.
+    pub fn window_handle(&self) -> Option<ControlHandle> {
+        match self.handle.is_null() {
+            true => None,
+            false => Some(ControlHandle::Hwnd(self.window))
+        }
+    }
+
+    /// Returns the key combination mapped to a custom id or to the id of a menu item
+    pub fn combo(&self, id: u16) -> Option<KeyCombo> {
+        self.entries.iter().find(|(_, i)| *i == id).map(|(combo, _)| *combo)
+    }
+
+    /// Returns the number of key combinations in the table
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+}
+
+impl Drop for Accelerators {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            unsafe { destroy_accelerators(self.handle); }
+        }
+    }
+}
+
+impl Default for Accelerators {
+
+    fn default() -> Accelerators {
+        Accelerators {
+            handle: ptr::null_mut(),
+            window: ptr::null_mut(),
+            entries: Vec::new(),
+        }
+    }
+
+}
+
+impl PartialEq for Accelerators {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+
+pub struct AcceleratorsBuilder {
+    parent: Option<ControlHandle>,
+    entries: Vec<(KeyCombo, u16)>,
+    menu_items: Vec<(KeyCombo, ControlHandle)>,
+}
+
+impl AcceleratorsBuilder {
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> AcceleratorsBuilder {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn accelerator(mut self, combo: KeyCombo, id: u16) -> AcceleratorsBuilder {
+        self.entries.push((combo, id));
+        self
+    }
+
+    #[cfg(feature = "menu")]
+    pub fn menu_item(mut self, combo: KeyCombo, item: &MenuItem) -> AcceleratorsBuilder {
+        self.menu_items.push((combo, item.handle));
+        self
+    }
+
+    pub fn build(self, out: &mut Accelerators) -> Result<(), NwgError> {
+        let window = match self.parent {
+            Some(p) => match p.hwnd() {
+                Some(handle) => Ok(handle),
+                None => Err(NwgError::resource_create("Wrong parent type"))
+            },
+            None => Err(NwgError::resource_create("No parent defined for accelerators"))
+        }?;
+
+        let mut entries = self.entries;
+        for &(combo, id) in entries.iter() {
+            if id as u32 >= CUSTOM_ID_BEGIN {
+                return Err(NwgError::resource_create(format!("Accelerator id {} of {} must be lower than {}", id, combo, CUSTOM_ID_BEGIN)));
+            }
+        }
+
+        // Menu items ids are used as the accelerators commands
+        let mut menu_items: Vec<(u16, HMENU)> = Vec::with_capacity(self.menu_items.len());
+        for &(combo, handle) in self.menu_items.iter() {
+            let (parent, id) = match handle.hmenu_item() {
+                Some(item) => item,
+                None => { return Err(NwgError::resource_create(format!("The menu item of {} is not yet bound to a winapi object", combo))); }
+            };
+
+            if id > u16::max_value() as u32 {
+                return Err(NwgError::resource_create(format!("The menu item id of {} is too big for an accelerator", combo)));
+            }
+
+            entries.push((combo, id as u16));
+            menu_items.push((id as u16, parent));
+        }
+
+        let handle = unsafe { build_accelerators(window, &entries, menu_items)? };
+
+        // Free the previous table if the resource is rebuilt
+        if !out.handle.is_null() {
+            unsafe { destroy_accelerators(out.handle); }
+        }
+
+        out.handle = handle;
+        out.window = window;
+        out.entries = entries;
+
+        Ok(())
+    }
+
+}
@@ -7,6 +7,9 @@ use std::cell::{RefCell};
 use std::{ptr, mem};
 use std::pin::Pin;
 
+#[cfg(feature = "async")]
+use crate::win32::executor::{ModalFuture, modal_future};
+
 
 struct InnerColorDialog {
     custom_colors: Pin<Box<[COLORREF; 16]>>,
@@ -45,6 +48,28 @@ impl ColorDialog {
         }
     }
 
+    /// Fallible version of `run`. Returns `InvalidHandle` if the owner is not a window control.
+    pub fn try_run<C: Into<ControlHandle>>(&self, owner: Option<C>) -> Result<bool, NwgError> {
+        let owner: Option<ControlHandle> = owner.map(|o| o.into());
+        if owner.map(|o| o.hwnd().is_none()).unwrap_or(false) {
+            return Err(NwgError::invalid_handle("Color dialog owner must be a window control"));
+        }
+
+        Ok(self.run(owner))
+    }
+
+    /**
+    Execute the color dialog from the message loop. Same as `run`, but returns a future that resolves when the dialog is closed.
+    See `ModalFuture` for the re-entrancy guarantees.
+
+    Requires the `async` feature.
+    */
+    #[cfg(feature = "async")]
+    pub fn run_async<'a, C: Into<ControlHandle>>(&'a self, owner: Option<C>) -> ModalFuture<'a, bool> {
+        let owner: Option<ControlHandle> = owner.map(|o| o.into());
+        modal_future(move || self.run(owner))
+    }
+
     /**
     Return the color choosen by the user. The returned color is a [r, g, b] array.
     If the dialog was never executed, this returns `[0, 0, 0]` (black);
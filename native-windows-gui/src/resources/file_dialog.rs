@@ -5,6 +5,9 @@ use crate::win32::base_helper::to_utf16;
 use crate::{ControlHandle, NwgError};
 use std::{fmt, ptr, mem};
 
+#[cfg(feature = "async")]
+use crate::win32::executor::{ModalFuture, modal_future};
+
 
 /**
     A enum that dictates how a file dialog should behave
@@ -83,6 +86,44 @@ impl FileDialog {
         unsafe { (&mut *self.handle).Show(parent_handle) == S_OK }
     }
 
+    /**
+        Display the dialog from the message loop. Same as `run`, but returns a future that resolves when the dialog is closed.
+        See `ModalFuture` for the re-entrancy guarantees.
+
+        The parent argument must be a window control otherwise the future will panic.
+
+        Requires the `async` feature.
+    */
+    #[cfg(feature = "async")]
+    pub fn run_async<'a, C: Into<ControlHandle>>(&'a self, parent: Option<C>) -> ModalFuture<'a, bool> {
+        let parent: Option<ControlHandle> = parent.map(|p| p.into());
+        modal_future(move || self.run(parent))
+    }
+
+    /**
+        Display the dialog. Same as `run`, but returns the reason why no file was selected.
+
+        If the user cancelled the dialog, the error `is_cancelled`. Otherwise the error holds the HRESULT returned by the dialog.
+        Returns `InvalidHandle` if the parent is not a window control.
+    */
+    pub fn try_run<C: Into<ControlHandle>>(&self, parent: Option<C>) -> Result<(), NwgError> {
+        use winapi::shared::winerror::S_OK;
+
+        if self.handle.is_null() {
+            return Err(NwgError::not_bound("FileDialog is not yet bound to a winapi object"));
+        }
+
+        let parent_handle = match parent {
+            Some(p) => p.into().hwnd().ok_or_else(|| NwgError::invalid_handle("File dialog parent must be a window control"))?,
+            None => ptr::null_mut()
+        };
+
+        match unsafe { (&mut *self.handle).Show(parent_handle) } {
+            S_OK => Ok(()),
+            hr => Err(NwgError::file_dialog("The file dialog was closed without a selection").with_hresult("IFileDialog::Show", hr))
+        }
+    }
+
     /**
         Return the item selected in the dialog by the user. 
         
@@ -97,7 +97,7 @@ impl Font {
 
     /// Set the default (application global!) font that will be used when creating controls and return the old one
     pub fn set_global_default(font: Option<Font>) -> Option<Font> {
-        let mut global_font = DEFAULT_FONT.lock().unwrap();
+        let mut global_font = DEFAULT_FONT.lock().unwrap_or_else(|e| e.into_inner());
         let old = global_font.take();
         *global_font = font;
         old
@@ -118,7 +118,7 @@ impl Font {
     /// Return the default font that was previously set using `Font::set_default`
     pub fn global_default() -> Option<Font> {
         DEFAULT_FONT.lock()
-            .unwrap()
+            .unwrap_or_else(|e| e.into_inner())
             .as_ref()
             .map(|f| Font { handle: f.handle } )
     }
@@ -8,6 +8,9 @@ use std::cell::{RefCell};
 use std::{ptr, mem};
 use std::pin::Pin;
 
+#[cfg(feature = "async")]
+use crate::win32::executor::{ModalFuture, modal_future};
+
 
 struct InnerFontDialog {
     font: Pin<Box<LOGFONTW>>,
@@ -43,6 +46,26 @@ impl FontDialog {
         }
     }
 
+    /// Fallible version of `run`. Returns `InvalidHandle` if the owner is not a window control.
+    pub fn try_run<C: Into<ControlHandle>>(&self, owner: Option<C>) -> Result<bool, NwgError> {
+        let owner: Option<ControlHandle> = owner.map(|o| o.into());
+        if owner.map(|o| o.hwnd().is_none()).unwrap_or(false) {
+            return Err(NwgError::invalid_handle("Font dialog owner must be a window control"));
+        }
+
+        Ok(self.run(owner))
+    }
+
+    /// Execute the font dialog from the message loop. Same as `run`, but returns a future that resolves when the dialog is closed.
+    /// See `ModalFuture` for the re-entrancy guarantees.
+    ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub fn run_async<'a, C: Into<ControlHandle>>(&'a self, owner: Option<C>) -> ModalFuture<'a, bool> {
+        let owner: Option<ControlHandle> = owner.map(|o| o.into());
+        modal_future(move || self.run(owner))
+    }
+
     /// Return a `FontInfo` structure that describe the font selected by the user.
     pub fn font(&self) -> FontInfo {
         let data: &InnerFontDialog = &self.data.borrow();
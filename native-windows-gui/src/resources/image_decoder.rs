@@ -52,13 +52,13 @@ impl ImageDecoder {
         The file type can be any of the native WIC codecs (https://docs.microsoft.com/en-us/windows/win32/wic/native-wic-codecs)
 
         * If there is an error during the decoding, returns a NwgError.
-        * If the image decoder was not initialized, this method panics
+        * If the image decoder was not initialized, returns a `ControlNotBound` error
 
         This method returns a ImageSource object.
     */
     pub fn from_filename<'a>(&self, path: &'a str) -> Result<ImageSource, NwgError> {
         if self.factory.is_null() {
-            panic!("ImageDecoder is not yet bound to a winapi object");
+            return Err(NwgError::not_bound("ImageDecoder is not yet bound to a winapi object"));
         }
 
         let decoder = unsafe { img::create_decoder_from_file(&*self.factory, path) }?;
@@ -70,6 +70,10 @@ impl ImageDecoder {
         Resize an image, returning the new resized image. The pixel format might change.
     */
     pub fn resize_image(&self, image: &ImageData, new_size: [u32;2]) -> Result<ImageData, NwgError> {
+        if self.factory.is_null() {
+            return Err(NwgError::not_bound("ImageDecoder is not yet bound to a winapi object"));
+        }
+
         unsafe { img::resize_bitmap(&*self.factory, image, new_size) }
     }
 
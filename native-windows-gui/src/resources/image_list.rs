@@ -58,6 +58,12 @@ impl ImageList {
         size
     }
 
+    /// Fallible version of `size`. Returns `ControlNotBound` if the image list was not built.
+    pub fn try_size(&self) -> Result<(i32, i32), NwgError> {
+        if self.handle.is_null() { return Err(NwgError::not_bound(NOT_BOUND)); }
+        Ok(self.size())
+    }
+
     /// Sets the size of the image list. This clears all current image data.
     pub fn set_size(&self, size: (i32, i32)) {
         use winapi::um::commctrl::ImageList_SetIconSize;
@@ -77,6 +83,12 @@ impl ImageList {
         unsafe { ImageList_GetImageCount(self.handle) as u32 }
     }
 
+    /// Fallible version of `len`. Returns `ControlNotBound` if the image list was not built.
+    pub fn try_len(&self) -> Result<u32, NwgError> {
+        if self.handle.is_null() { return Err(NwgError::not_bound(NOT_BOUND)); }
+        Ok(self.len())
+    }
+
     /// Adds a new bitmap to the image list. Returns the index to the image. Panics if the bitmap was not initialized
     pub fn add_bitmap(&self, bitmap: &Bitmap) -> i32 {
         if self.handle.is_null() { panic!(NOT_BOUND); }
@@ -85,12 +97,19 @@ impl ImageList {
         unsafe { ImageList_AddMasked(self.handle, bitmap.handle as HBITMAP, 0) }
     }
 
+    /// Fallible version of `add_bitmap`. Returns `ControlNotBound` if the image list or the bitmap was not built.
+    pub fn try_add_bitmap(&self, bitmap: &Bitmap) -> Result<i32, NwgError> {
+        if self.handle.is_null() { return Err(NwgError::not_bound(NOT_BOUND)); }
+        if bitmap.handle.is_null() { return Err(NwgError::not_bound("Bitmap was not initialized")); }
+        Ok(self.add_bitmap(bitmap))
+    }
+
     /**
         Adds a bitmap directly from a filename. The image is resized to the image list size.
         Returns the index to the image or an error if the image could not be loaded
     */
     pub fn add_bitmap_from_filename(&self, filename: &str) -> Result<i32, NwgError> {
-        if self.handle.is_null() { panic!(NOT_BOUND); }
+        if self.handle.is_null() { return Err(NwgError::not_bound(NOT_BOUND)); }
 
         let (w, h) = self.size();
         let mut bitmap = Bitmap::default();
@@ -126,12 +145,19 @@ impl ImageList {
         }
     }
 
+    /// Fallible version of `add_icon`. Returns `ControlNotBound` if the image list or the icon was not built.
+    pub fn try_add_icon(&self, icon: &Icon) -> Result<i32, NwgError> {
+        if self.handle.is_null() { return Err(NwgError::not_bound(NOT_BOUND)); }
+        if icon.handle.is_null() { return Err(NwgError::not_bound("Icon was not initialized")); }
+        Ok(self.add_icon(icon))
+    }
+
     /**
         Adds a icon directly from a filename. The image is resized to the image list size.
         Returns the index to the image or an error if the image could not be loaded
     */
     pub fn add_icon_from_filename(&self, filename: &str) -> Result<i32, NwgError> {
-        if self.handle.is_null() { panic!(NOT_BOUND); }
+        if self.handle.is_null() { return Err(NwgError::not_bound(NOT_BOUND)); }
 
         let (w, h) = self.size();
         let mut icon = Icon::default();
@@ -159,6 +185,12 @@ impl ImageList {
         unsafe { ImageList_Remove(self.handle, index); }
     }
 
+    /// Fallible version of `remove`. Returns `ControlNotBound` if the image list was not built.
+    pub fn try_remove(&self, index: i32) -> Result<(), NwgError> {
+        if self.handle.is_null() { return Err(NwgError::not_bound(NOT_BOUND)); }
+        Ok(self.remove(index))
+    }
+
     /// Replaces an image in the image list. Panics if the bitmap was not initialized
     pub fn replace_bitmap(&self, index: i32, bitmap: &Bitmap) {
         use winapi::um::commctrl::ImageList_Replace;
@@ -22,6 +22,12 @@ mod image_list;
 #[cfg(feature = "embed-resource")]
 mod embed;
 
+#[cfg(feature = "accelerators")]
+mod accelerators;
+
+#[cfg(feature = "task-dialog")]
+mod task_dialog;
+
 pub use font::{Font, MemFont, FontInfo, FontBuilder};
 pub use system_images::*;
 pub use icon::{Icon, IconBuilder};
@@ -46,3 +52,8 @@ pub use image_list::{ImageList, ImageListBuilder};
 #[cfg(feature = "embed-resource")]
 pub use embed::*;
 
+#[cfg(feature = "accelerators")]
+pub use accelerators::{Accelerators, AcceleratorsBuilder};
+
+#[cfg(feature = "task-dialog")]
+pub use task_dialog::{TaskDialog, TaskDialogBuilder, TaskDialogButton, TaskDialogButtons, TaskDialogIcon, TaskDialogEvent, TaskDialogControl, TaskDialogResult};
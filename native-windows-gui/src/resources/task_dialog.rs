@@ -0,0 +1,735 @@
+use winapi::shared::minwindef::{UINT, BOOL, WPARAM, LPARAM};
+use winapi::shared::windef::HWND;
+use winapi::shared::basetsd::LONG_PTR;
+use winapi::shared::ntdef::{HRESULT, LPCWSTR};
+use winapi::um::commctrl::{TASKDIALOGCONFIG, TASKDIALOG_BUTTON};
+use winapi::ctypes::c_int;
+use crate::win32::base_helper::{to_utf16, from_utf16, from_wide_ptr};
+use crate::win32::window_helper as wh;
+use crate::{ControlHandle, NwgError};
+use std::cell::Cell;
+use std::{fmt, ptr, mem};
+
+#[cfg(feature = "async")]
+use crate::win32::executor::{ModalFuture, modal_future};
+
+
+bitflags! {
+    /**
+        The common buttons of a task dialog. If no buttons are defined, the dialog has a `OK` button.
+
+        * OK:     An "OK" button
+        * YES:    A "Yes" button
+        * NO:     A "No" button
+        * CANCEL: A "Cancel" button
+        * RETRY:  A "Retry" button
+        * CLOSE:  A "Close" button
+    */
+    pub struct TaskDialogButtons: u32 {
+        const NONE = 0;
+        const OK = 0x01;
+        const YES = 0x02;
+        const NO = 0x04;
+        const CANCEL = 0x08;
+        const RETRY = 0x10;
+        const CLOSE = 0x20;
+    }
+}
+
+/**
+    A button of a task dialog. Custom buttons are identified by the id used in `TaskDialogBuilder::button`.
+    The ids from 1 to 8 are reserved for the common buttons.
+*/
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TaskDialogButton {
+    Ok,
+    Cancel,
+    Retry,
+    Yes,
+    No,
+    Close,
+    Custom(i32),
+}
+
+impl TaskDialogButton {
+
+    /// Returns the dialog id of the button
+    pub fn id(&self) -> i32 {
+        use winapi::um::winuser::{IDOK, IDCANCEL, IDRETRY, IDYES, IDNO, IDCLOSE};
+
+        match *self {
+            TaskDialogButton::Ok => IDOK,
+            TaskDialogButton::Cancel => IDCANCEL,
+            TaskDialogButton::Retry => IDRETRY,
+            TaskDialogButton::Yes => IDYES,
+            TaskDialogButton::No => IDNO,
+            TaskDialogButton::Close => IDCLOSE,
+            TaskDialogButton::Custom(id) => id,
+        }
+    }
+
+    /// Returns the button with the dialog id `id`
+    pub fn from_id(id: i32) -> TaskDialogButton {
+        use winapi::um::winuser::{IDOK, IDCANCEL, IDRETRY, IDYES, IDNO, IDCLOSE};
+
+        match id {
+            IDOK => TaskDialogButton::Ok,
+            IDCANCEL => TaskDialogButton::Cancel,
+            IDRETRY => TaskDialogButton::Retry,
+            IDYES => TaskDialogButton::Yes,
+            IDNO => TaskDialogButton::No,
+            IDCLOSE => TaskDialogButton::Close,
+            id => TaskDialogButton::Custom(id),
+        }
+    }
+
+}
+
+/// The icons of a task dialog
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TaskDialogIcon {
+    None,
+    Warning,
+    Error,
+    Information,
+    Shield,
+}
+
+impl TaskDialogIcon {
+
+    /// The system icons are passed as `MAKEINTRESOURCE` values
+    fn resource(&self) -> LPCWSTR {
+        let id: u16 = match self {
+            TaskDialogIcon::None => { return ptr::null(); },
+            TaskDialogIcon::Warning => 0xFFFF,
+            TaskDialogIcon::Error => 0xFFFE,
+            TaskDialogIcon::Information => 0xFFFD,
+            TaskDialogIcon::Shield => 0xFFFC,
+        };
+
+        id as usize as LPCWSTR
+    }
+
+}
+
+/// The notifications sent to the callback of a task dialog. See `TaskDialogBuilder::callback`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TaskDialogEvent {
+    /// The dialog was created and is about to be displayed
+    Created,
+
+    /// A button was clicked. Call `TaskDialogControl::keep_open` to prevent the dialog from closing.
+    ButtonClicked(TaskDialogButton),
+
+    /// A radio button was selected. Holds the radio button id.
+    RadioClicked(i32),
+
+    /// The verification checkbox was clicked. Holds the new checked state.
+    VerificationClicked(bool),
+
+    /// A hyperlink was clicked. Holds the `href` value of the link.
+    HyperlinkClicked(String),
+
+    /// Sent about every 200 milliseconds if `timer` is enabled. Holds the milliseconds elapsed since the dialog was created.
+    Timer(u32),
+
+    /// The details were expanded (true) or collapsed (false)
+    Expanded(bool),
+
+    /// The dialog is being destroyed
+    Destroyed,
+}
+
+/// Updates a task dialog from its callback. See `TaskDialogBuilder::callback`.
+pub struct TaskDialogControl {
+    hwnd: HWND,
+    keep_open: Cell<bool>,
+}
+
+impl TaskDialogControl {
+
+    /// Prevents the dialog from closing after a `ButtonClicked` event. Does nothing for the other events.
+    pub fn keep_open(&self) {
+        self.keep_open.set(true);
+    }
+
+    /// Sets the position of the progress bar. The progress bar must be enabled with `progress_bar`.
+    pub fn set_progress(&self, position: u32) {
+        use winapi::um::commctrl::TDM_SET_PROGRESS_BAR_POS;
+        wh::send_message(self.hwnd, TDM_SET_PROGRESS_BAR_POS, position as WPARAM, 0);
+    }
+
+    /// Sets the range of the progress bar. The default range is 0 to 100.
+    pub fn set_progress_range(&self, min: u16, max: u16) {
+        use winapi::um::commctrl::TDM_SET_PROGRESS_BAR_RANGE;
+        let range = ((max as u32) << 16) | (min as u32);
+        wh::send_message(self.hwnd, TDM_SET_PROGRESS_BAR_RANGE, 0, range as LPARAM);
+    }
+
+    /// Switches the progress bar between a marquee (true) and a regular progress bar (false)
+    pub fn set_marquee(&self, marquee: bool) {
+        use winapi::um::commctrl::{TDM_SET_MARQUEE_PROGRESS_BAR, TDM_SET_PROGRESS_BAR_MARQUEE};
+        wh::send_message(self.hwnd, TDM_SET_MARQUEE_PROGRESS_BAR, marquee as WPARAM, 0);
+        wh::send_message(self.hwnd, TDM_SET_PROGRESS_BAR_MARQUEE, marquee as WPARAM, 0);
+    }
+
+    /// Sets the main instruction of the dialog
+    pub fn set_instruction<'a>(&self, text: &'a str) {
+        use winapi::um::commctrl::TDE_MAIN_INSTRUCTION;
+        self.set_element_text(TDE_MAIN_INSTRUCTION, text);
+    }
+
+    /// Sets the content of the dialog
+    pub fn set_content<'a>(&self, text: &'a str) {
+        use winapi::um::commctrl::TDE_CONTENT;
+        self.set_element_text(TDE_CONTENT, text);
+    }
+
+    /// Sets the footer of the dialog. The dialog must have been created with a footer.
+    pub fn set_footer<'a>(&self, text: &'a str) {
+        use winapi::um::commctrl::TDE_FOOTER;
+        self.set_element_text(TDE_FOOTER, text);
+    }
+
+    /// Enables or disables a button
+    pub fn enable_button(&self, button: TaskDialogButton, enabled: bool) {
+        use winapi::um::commctrl::TDM_ENABLE_BUTTON;
+        wh::send_message(self.hwnd, TDM_ENABLE_BUTTON, button.id() as WPARAM, enabled as LPARAM);
+    }
+
+    /// Enables or disables a radio button
+    pub fn enable_radio(&self, id: i32, enabled: bool) {
+        use winapi::um::commctrl::TDM_ENABLE_RADIO_BUTTON;
+        wh::send_message(self.hwnd, TDM_ENABLE_RADIO_BUTTON, id as WPARAM, enabled as LPARAM);
+    }
+
+    /// Simulates a click on a button. Clicking a button closes the dialog unless the callback calls `keep_open`.
+    pub fn click_button(&self, button: TaskDialogButton) {
+        use winapi::um::commctrl::TDM_CLICK_BUTTON;
+        wh::send_message(self.hwnd, TDM_CLICK_BUTTON, button.id() as WPARAM, 0);
+    }
+
+    fn set_element_text(&self, element: u32, text: &str) {
+        use winapi::um::commctrl::TDM_SET_ELEMENT_TEXT;
+        let text = to_utf16(text);
+        wh::send_message(self.hwnd, TDM_SET_ELEMENT_TEXT, element as WPARAM, text.as_ptr() as LPARAM);
+    }
+
+}
+
+/// The result of a task dialog
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TaskDialogResult {
+    /// The button that closed the dialog. If the dialog was cancelled, this is `TaskDialogButton::Cancel`.
+    pub button: TaskDialogButton,
+
+    /// The id of the selected radio button. `None` if the dialog has no radio buttons.
+    pub radio: Option<i32>,
+
+    /// The state of the verification checkbox. `false` if the dialog has no checkbox.
+    pub verification_checked: bool,
+}
+
+type TaskDialogCallback = Box<dyn Fn(&TaskDialogControl, TaskDialogEvent)>;
+
+/**
+    A task dialog is a modern message box. Compared to the `message` functions, it supports custom button labels,
+    command links, radio buttons, a verification checkbox ("don't ask again"), expandable details, a footer,
+    hyperlinks, a progress bar and timer callbacks.
+
+    Task dialogs require the version 6 of the common controls, so the visual styles must be enabled (see `enable_visual_styles`).
+    Otherwise, `run` returns an error.
+
+    Requires the `task-dialog` feature.
+
+    **Builder parameters:**
+      * `title`:               The title of the dialog window
+      * `instruction`:         The main instruction, displayed in large text above the content
+      * `content`:             The content of the dialog
+      * `footer`:              The text displayed at the bottom of the dialog
+      * `details`:             Additional text displayed when the user expands the dialog
+      * `expanded_label`:      The label of the expand button when the details are visible
+      * `collapsed_label`:     The label of the expand button when the details are hidden
+      * `expanded`:            If the details are visible by default
+      * `icon`:                The main icon
+      * `footer_icon`:         The footer icon
+      * `common_buttons`:      The common buttons (`TaskDialogButtons`)
+      * `button`:              Add a custom button with its id. Ids 1 to 8 are reserved for the common buttons.
+      * `command_links`:       Display the custom buttons as command links. Text after a newline is displayed as a note.
+      * `radio`:               Add a radio button with its id
+      * `default_button`:      The button focused by default
+      * `default_radio`:       The radio button selected by default. The first radio button by default.
+      * `verification`:        The label of the verification checkbox
+      * `verification_checked`: If the verification checkbox is checked by default
+      * `hyperlinks`:          Enable the `<a href="...">` links in the content, the details and the footer
+      * `progress_bar`:        Show a progress bar
+      * `marquee`:             Show a marquee progress bar
+      * `timer`:               Send a `Timer` event to the callback about every 200 milliseconds
+      * `allow_cancel`:        Allow the dialog to be closed with Esc or the close button even without a Cancel button
+      * `callback`:            A callback that receives the dialog notifications (`TaskDialogEvent`)
+
+```rust
+use native_windows_gui as nwg;
+
+const SAVE: i32 = 100;
+const DISCARD: i32 = 101;
+
+fn ask_save(window: &nwg::Window) -> Result<bool, nwg::NwgError> {
+    let mut dialog = nwg::TaskDialog::default();
+    nwg::TaskDialog::builder()
+        .title("Editor")
+        .instruction("Do you want to save your changes?")
+        .icon(nwg::TaskDialogIcon::Warning)
+        .button(SAVE, "Save")
+        .button(DISCARD, "Don't save")
+        .common_buttons(nwg::TaskDialogButtons::CANCEL)
+        .verification("Don't ask me again")
+        .build(&mut dialog)?;
+
+    let result = dialog.run(Some(window))?;
+    Ok(result.button == nwg::TaskDialogButton::Custom(SAVE))
+}
+```
+*/
+pub struct TaskDialog {
+    title: Option<Vec<u16>>,
+    instruction: Option<Vec<u16>>,
+    content: Option<Vec<u16>>,
+    footer: Option<Vec<u16>>,
+    details: Option<Vec<u16>>,
+    expanded_label: Option<Vec<u16>>,
+    collapsed_label: Option<Vec<u16>>,
+    verification: Option<Vec<u16>>,
+    icon: TaskDialogIcon,
+    footer_icon: TaskDialogIcon,
+    common_buttons: TaskDialogButtons,
+    buttons: Vec<(i32, Vec<u16>)>,
+    radios: Vec<(i32, Vec<u16>)>,
+    default_button: Option<TaskDialogButton>,
+    default_radio: Option<i32>,
+    flags: u32,
+    callback: Option<TaskDialogCallback>,
+}
+
+impl TaskDialog {
+
+    pub fn builder() -> TaskDialogBuilder {
+        TaskDialogBuilder {
+            title: None,
+            instruction: None,
+            content: None,
+            footer: None,
+            details: None,
+            expanded_label: None,
+            collapsed_label: None,
+            expanded: false,
+            verification: None,
+            verification_checked: false,
+            icon: TaskDialogIcon::None,
+            footer_icon: TaskDialogIcon::None,
+            common_buttons: TaskDialogButtons::NONE,
+            buttons: Vec::new(),
+            command_links: false,
+            radios: Vec::new(),
+            default_button: None,
+            default_radio: None,
+            hyperlinks: false,
+            progress_bar: false,
+            marquee: false,
+            timer: false,
+            allow_cancel: false,
+            callback: None,
+        }
+    }
+
+    /**
+        Display the task dialog. Blocks the current thread until the dialog is closed (similar to `dispatch_thread_events`).
+
+        Returns `InvalidHandle` if the owner is not a window control.
+    */
+    pub fn run<C: Into<ControlHandle>>(&self, owner: Option<C>) -> Result<TaskDialogResult, NwgError> {
+        use winapi::um::commctrl::{TASKDIALOGCONFIG_u1, TASKDIALOGCONFIG_u2};
+        use winapi::shared::winerror::S_OK;
+
+        let owner = match owner {
+            Some(o) => o.into().hwnd().ok_or_else(|| NwgError::invalid_handle("Task dialog owner must be a window control"))?,
+            None => ptr::null_mut()
+        };
+
+        let task_dialog_indirect = unsafe { task_dialog_indirect()? };
+
+        let buttons: Vec<TASKDIALOG_BUTTON> = self.buttons.iter()
+            .map(|(id, text)| TASKDIALOG_BUTTON { nButtonID: *id, pszButtonText: text.as_ptr() })
+            .collect();
+
+        let radios: Vec<TASKDIALOG_BUTTON> = self.radios.iter()
+            .map(|(id, text)| TASKDIALOG_BUTTON { nButtonID: *id, pszButtonText: text.as_ptr() })
+            .collect();
+
+        let text = |t: &Option<Vec<u16>>| t.as_ref().map(|t| t.as_ptr()).unwrap_or(ptr::null());
+
+        let (mut button, mut radio, mut checked) = (0, 0, 0);
+        let hr = unsafe {
+            // The config is packed, so the icons unions are built outside of it
+            let mut main_icon: TASKDIALOGCONFIG_u1 = mem::zeroed();
+            *main_icon.pszMainIcon_mut() = self.icon.resource();
+
+            let mut footer_icon: TASKDIALOGCONFIG_u2 = mem::zeroed();
+            *footer_icon.pszFooterIcon_mut() = self.footer_icon.resource();
+
+            let mut config: TASKDIALOGCONFIG = mem::zeroed();
+            config.cbSize = mem::size_of::<TASKDIALOGCONFIG>() as UINT;
+            config.hwndParent = owner;
+            config.dwFlags = self.flags;
+            config.dwCommonButtons = self.common_buttons.bits();
+            config.pszWindowTitle = text(&self.title);
+            config.u1 = main_icon;
+            config.pszMainInstruction = text(&self.instruction);
+            config.pszContent = text(&self.content);
+            config.cButtons = buttons.len() as UINT;
+            config.pButtons = buttons.as_ptr();
+            config.nDefaultButton = self.default_button.map(|b| b.id()).unwrap_or(0);
+            config.cRadioButtons = radios.len() as UINT;
+            config.pRadioButtons = radios.as_ptr();
+            config.nDefaultRadioButton = self.default_radio.unwrap_or(0);
+            config.pszVerificationText = text(&self.verification);
+            config.pszExpandedInformation = text(&self.details);
+            config.pszExpandedControlText = text(&self.expanded_label);
+            config.pszCollapsedControlText = text(&self.collapsed_label);
+            config.u2 = footer_icon;
+            config.pszFooter = text(&self.footer);
+            config.pfCallback = Some(task_dialog_proc);
+            config.lpCallbackData = self as *const TaskDialog as LONG_PTR;
+
+            task_dialog_indirect(&config, &mut button, &mut radio, &mut checked)
+        };
+
+        if hr != S_OK {
+            return Err(NwgError::resource_create("Failed to display the task dialog").with_hresult("TaskDialogIndirect", hr));
+        }
+
+        Ok(TaskDialogResult {
+            button: TaskDialogButton::from_id(button),
+            radio: match self.radios.is_empty() {
+                true => None,
+                false => Some(radio)
+            },
+            verification_checked: checked != 0,
+        })
+    }
+
+    /**
+        Display the task dialog from the message loop. Same as `run`, but returns a future that resolves when the dialog is closed.
+        See `ModalFuture` for the re-entrancy guarantees.
+
+        Requires the `async` feature.
+    */
+    #[cfg(feature = "async")]
+    pub fn run_async<'a, C: Into<ControlHandle>>(&'a self, owner: Option<C>) -> ModalFuture<'a, Result<TaskDialogResult, NwgError>> {
+        let owner: Option<ControlHandle> = owner.map(|o| o.into());
+        modal_future(move || self.run(owner))
+    }
+
+}
+
+impl Default for TaskDialog {
+
+    fn default() -> TaskDialog {
+        TaskDialog {
+            title: None,
+            instruction: None,
+            content: None,
+            footer: None,
+            details: None,
+            expanded_label: None,
+            collapsed_label: None,
+            verification: None,
+            icon: TaskDialogIcon::None,
+            footer_icon: TaskDialogIcon::None,
+            common_buttons: TaskDialogButtons::NONE,
+            buttons: Vec::new(),
+            radios: Vec::new(),
+            default_button: None,
+            default_radio: None,
+            flags: 0,
+            callback: None,
+        }
+    }
+
+}
+
+impl fmt::Debug for TaskDialog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = |t: &Option<Vec<u16>>| t.as_ref().map(|t| from_utf16(t));
+        write!(f, "TaskDialog {{ title: {:?}, instruction: {:?}, buttons: {} }}", text(&self.title), text(&self.instruction), self.buttons.len())
+    }
+}
+
+
+type TaskDialogIndirectFn = unsafe extern "system" fn(*const TASKDIALOGCONFIG, *mut c_int, *mut c_int, *mut BOOL) -> HRESULT;
+
+/// `TaskDialogIndirect` is loaded at runtime because the version 5 of comctl32 does not export it.
+/// Linking it statically would prevent applications without the visual styles from starting.
+/// comctl32 is always loaded because NWG links to it, so the module handle does not need to be freed
+unsafe fn task_dialog_indirect() -> Result<TaskDialogIndirectFn, NwgError> {
+    use winapi::um::libloaderapi::{GetModuleHandleW, GetProcAddress};
+
+    let lib = to_utf16("comctl32.dll");
+    let module = GetModuleHandleW(lib.as_ptr());
+    if module.is_null() {
+        return Err(NwgError::resource_create("comctl32.dll is not loaded").with_last_error("GetModuleHandleW"));
+    }
+
+    let proc = GetProcAddress(module, "TaskDialogIndirect\0".as_ptr() as *const i8);
+    if proc.is_null() {
+        let msg = "TaskDialogIndirect is not available. The visual styles must be enabled to use task dialogs";
+        return Err(NwgError::resource_create(msg).with_last_error("GetProcAddress"));
+    }
+
+    Ok(mem::transmute(proc))
+}
+
+unsafe extern "system" fn task_dialog_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, data: LONG_PTR) -> HRESULT {
+    use winapi::um::commctrl::{TDN_CREATED, TDN_BUTTON_CLICKED, TDN_HYPERLINK_CLICKED, TDN_TIMER, TDN_DESTROYED,
+        TDN_RADIO_BUTTON_CLICKED, TDN_VERIFICATION_CLICKED, TDN_EXPANDO_BUTTON_CLICKED};
+    use winapi::shared::winerror::{S_OK, S_FALSE};
+
+    let dialog = &*(data as *const TaskDialog);
+    let callback = match dialog.callback.as_ref() {
+        Some(callback) => callback,
+        None => { return S_OK; }
+    };
+
+    let event = match msg {
+        TDN_CREATED => TaskDialogEvent::Created,
+        TDN_BUTTON_CLICKED => TaskDialogEvent::ButtonClicked(TaskDialogButton::from_id(w as i32)),
+        TDN_RADIO_BUTTON_CLICKED => TaskDialogEvent::RadioClicked(w as i32),
+        TDN_VERIFICATION_CLICKED => TaskDialogEvent::VerificationClicked(w != 0),
+        TDN_HYPERLINK_CLICKED => TaskDialogEvent::HyperlinkClicked(from_wide_ptr(l as *mut u16, None)),
+        TDN_TIMER => TaskDialogEvent::Timer(w as u32),
+        TDN_EXPANDO_BUTTON_CLICKED => TaskDialogEvent::Expanded(w != 0),
+        TDN_DESTROYED => TaskDialogEvent::Destroyed,
+        _ => { return S_OK; }
+    };
+
+    let control = TaskDialogControl { hwnd, keep_open: Cell::new(false) };
+    callback(&control, event);
+
+    match msg == TDN_BUTTON_CLICKED && control.keep_open.get() {
+        true => S_FALSE,
+        false => S_OK
+    }
+}
+
+/// The builder for a `TaskDialog` object. Use `TaskDialog::builder` to create one.
+pub struct TaskDialogBuilder {
+    title: Option<String>,
+    instruction: Option<String>,
+    content: Option<String>,
+    footer: Option<String>,
+    details: Option<String>,
+    expanded_label: Option<String>,
+    collapsed_label: Option<String>,
+    expanded: bool,
+    verification: Option<String>,
+    verification_checked: bool,
+    icon: TaskDialogIcon,
+    footer_icon: TaskDialogIcon,
+    common_buttons: TaskDialogButtons,
+    buttons: Vec<(i32, String)>,
+    command_links: bool,
+    radios: Vec<(i32, String)>,
+    default_button: Option<TaskDialogButton>,
+    default_radio: Option<i32>,
+    hyperlinks: bool,
+    progress_bar: bool,
+    marquee: bool,
+    timer: bool,
+    allow_cancel: bool,
+    callback: Option<TaskDialogCallback>,
+}
+
+impl TaskDialogBuilder {
+
+    pub fn title<S: Into<String>>(mut self, t: S) -> TaskDialogBuilder {
+        self.title = Some(t.into());
+        self
+    }
+
+    pub fn instruction<S: Into<String>>(mut self, t: S) -> TaskDialogBuilder {
+        self.instruction = Some(t.into());
+        self
+    }
+
+    pub fn content<S: Into<String>>(mut self, t: S) -> TaskDialogBuilder {
+        self.content = Some(t.into());
+        self
+    }
+
+    pub fn footer<S: Into<String>>(mut self, t: S) -> TaskDialogBuilder {
+        self.footer = Some(t.into());
+        self
+    }
+
+    pub fn details<S: Into<String>>(mut self, t: S) -> TaskDialogBuilder {
+        self.details = Some(t.into());
+        self
+    }
+
+    pub fn expanded_label<S: Into<String>>(mut self, t: S) -> TaskDialogBuilder {
+        self.expanded_label = Some(t.into());
+        self
+    }
+
+    pub fn collapsed_label<S: Into<String>>(mut self, t: S) -> TaskDialogBuilder {
+        self.collapsed_label = Some(t.into());
+        self
+    }
+
+    pub fn expanded(mut self, e: bool) -> TaskDialogBuilder {
+        self.expanded = e;
+        self
+    }
+
+    pub fn verification<S: Into<String>>(mut self, t: S) -> TaskDialogBuilder {
+        self.verification = Some(t.into());
+        self
+    }
+
+    pub fn verification_checked(mut self, c: bool) -> TaskDialogBuilder {
+        self.verification_checked = c;
+        self
+    }
+
+    pub fn icon(mut self, i: TaskDialogIcon) -> TaskDialogBuilder {
+        self.icon = i;
+        self
+    }
+
+    pub fn footer_icon(mut self, i: TaskDialogIcon) -> TaskDialogBuilder {
+        self.footer_icon = i;
+        self
+    }
+
+    pub fn common_buttons(mut self, b: TaskDialogButtons) -> TaskDialogBuilder {
+        self.common_buttons = b;
+        self
+    }
+
+    pub fn button<S: Into<String>>(mut self, id: i32, text: S) -> TaskDialogBuilder {
+        self.buttons.push((id, text.into()));
+        self
+    }
+
+    pub fn command_links(mut self, c: bool) -> TaskDialogBuilder {
+        self.command_links = c;
+        self
+    }
+
+    pub fn radio<S: Into<String>>(mut self, id: i32, text: S) -> TaskDialogBuilder {
+        self.radios.push((id, text.into()));
+        self
+    }
+
+    pub fn default_button(mut self, b: TaskDialogButton) -> TaskDialogBuilder {
+        self.default_button = Some(b);
+        self
+    }
+
+    pub fn default_radio(mut self, id: i32) -> TaskDialogBuilder {
+        self.default_radio = Some(id);
+        self
+    }
+
+    pub fn hyperlinks(mut self, h: bool) -> TaskDialogBuilder {
+        self.hyperlinks = h;
+        self
+    }
+
+    pub fn progress_bar(mut self, p: bool) -> TaskDialogBuilder {
+        self.progress_bar = p;
+        self
+    }
+
+    pub fn marquee(mut self, m: bool) -> TaskDialogBuilder {
+        self.marquee = m;
+        self
+    }
+
+    pub fn timer(mut self, t: bool) -> TaskDialogBuilder {
+        self.timer = t;
+        self
+    }
+
+    pub fn allow_cancel(mut self, a: bool) -> TaskDialogBuilder {
+        self.allow_cancel = a;
+        self
+    }
+
+    pub fn callback<F>(mut self, f: F) -> TaskDialogBuilder
+        where F: Fn(&TaskDialogControl, TaskDialogEvent) + 'static
+    {
+        self.callback = Some(Box::new(f));
+        self
+    }
+
+    pub fn build(self, out: &mut TaskDialog) -> Result<(), NwgError> {
+        use winapi::um::commctrl::{TDF_ENABLE_HYPERLINKS, TDF_USE_COMMAND_LINKS, TDF_EXPANDED_BY_DEFAULT, TDF_VERIFICATION_FLAG_CHECKED,
+            TDF_SHOW_PROGRESS_BAR, TDF_SHOW_MARQUEE_PROGRESS_BAR, TDF_CALLBACK_TIMER, TDF_ALLOW_DIALOG_CANCELLATION, TDF_NO_DEFAULT_RADIO_BUTTON};
+
+        for (id, text) in self.buttons.iter() {
+            if (1..=8).contains(id) {
+                return Err(NwgError::resource_create(format!("The id {} of the task dialog button {:?} is reserved for the common buttons", id, text)));
+            }
+        }
+
+        if let Some(id) = self.default_radio {
+            if !self.radios.iter().any(|(radio_id, _)| *radio_id == id) {
+                return Err(NwgError::resource_create(format!("The default radio button {} of the task dialog does not exist", id)));
+            }
+        }
+
+        let options = [
+            (self.hyperlinks, TDF_ENABLE_HYPERLINKS),
+            (self.command_links && !self.buttons.is_empty(), TDF_USE_COMMAND_LINKS),
+            (self.expanded, TDF_EXPANDED_BY_DEFAULT),
+            (self.verification_checked, TDF_VERIFICATION_FLAG_CHECKED),
+            (self.progress_bar, TDF_SHOW_PROGRESS_BAR),
+            (self.marquee, TDF_SHOW_MARQUEE_PROGRESS_BAR),
+            (self.timer, TDF_CALLBACK_TIMER),
+            (self.allow_cancel, TDF_ALLOW_DIALOG_CANCELLATION),
+            (self.radios.is_empty(), TDF_NO_DEFAULT_RADIO_BUTTON),
+        ];
+
+        let mut flags: u32 = 0;
+        for &(enabled, flag) in options.iter() {
+            if enabled { flags |= flag; }
+        }
+
+        let text = |t: Option<String>| t.map(|t| to_utf16(&t));
+        let labels = |items: Vec<(i32, String)>| -> Vec<(i32, Vec<u16>)> { items.into_iter().map(|(id, t)| (id, to_utf16(&t))).collect() };
+
+        *out = TaskDialog {
+            title: text(self.title),
+            instruction: text(self.instruction),
+            content: text(self.content),
+            footer: text(self.footer),
+            details: text(self.details),
+            expanded_label: text(self.expanded_label),
+            collapsed_label: text(self.collapsed_label),
+            verification: text(self.verification),
+            icon: self.icon,
+            footer_icon: self.footer_icon,
+            common_buttons: self.common_buttons,
+            buttons: labels(self.buttons),
+            radios: labels(self.radios),
+            default_button: self.default_button,
+            default_radio: self.default_radio,
+            flags,
+            callback: self.callback,
+        };
+
+        Ok(())
+    }
+
+}
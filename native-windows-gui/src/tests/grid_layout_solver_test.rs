@@ -0,0 +1,228 @@
+/*!
+    Property tests for the grid layout solver. The solver does not use any window, so those tests are not interactive.
+    The layouts are generated from a fixed seed to keep the failures reproducible.
+*/
+use crate::{GridLayoutSolver, GridLayoutCell, GridLayoutRect, GridLayoutSize};
+
+const ITERATIONS: usize = 2000;
+
+/// Xorshift generator. Good enough to explore the layout properties.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn range(&mut self, min: u32, max: u32) -> u32 {
+        min + (self.next() % ((max - min + 1) as u64)) as u32
+    }
+}
+
+fn random_solver(rng: &mut Rng) -> GridLayoutSolver {
+    let min_size = [rng.range(0, 300), rng.range(0, 300)];
+    let max_size = match rng.range(0, 1) {
+        0 => [u32::max_value(), u32::max_value()],
+        _ => [min_size[0] + rng.range(0, 600), min_size[1] + rng.range(0, 600)],
+    };
+
+    GridLayoutSolver {
+        margins: [rng.range(0, 20), rng.range(0, 20), rng.range(0, 20), rng.range(0, 20)],
+        spacing: rng.range(0, 10),
+        min_size,
+        max_size,
+        ..Default::default()
+    }
+}
+
+/// Cells placed on a grid without overlapping each other
+fn random_cells(rng: &mut Rng) -> (Vec<GridLayoutCell>, u32, u32) {
+    let columns = rng.range(1, 6);
+    let rows = rng.range(1, 6);
+    let mut used = vec![false; (columns * rows) as usize];
+    let mut cells = Vec::new();
+
+    for _ in 0..rng.range(1, 10) {
+        let col = rng.range(0, columns - 1);
+        let row = rng.range(0, rows - 1);
+        let col_span = rng.range(1, columns - col);
+        let row_span = rng.range(1, rows - row);
+
+        let free = (col..col+col_span).all(|c| (row..row+row_span).all(|r| !used[(r * columns + c) as usize]));
+        if free {
+            for c in col..col+col_span {
+                for r in row..row+row_span {
+                    used[(r * columns + c) as usize] = true;
+                }
+            }
+            cells.push(GridLayoutCell::new(col, row, col_span, row_span));
+        }
+    }
+
+    (cells, columns, rows)
+}
+
+fn clamped_size(solver: &GridLayoutSolver, width: u32, height: u32) -> (u32, u32) {
+    let width = width.max(solver.min_size[0]).min(solver.max_size[0]);
+    let height = height.max(solver.min_size[1]).min(solver.max_size[1]);
+    (width, height)
+}
+
+#[test]
+fn grid_solver_cells_stay_inside_margins() {
+    let mut rng = Rng(0x2545F4914F6CDD1D);
+
+    for _ in 0..ITERATIONS {
+        let solver = random_solver(&mut rng);
+        let (cells, _, _) = random_cells(&mut rng);
+        let (width, height) = (rng.range(0, 1000), rng.range(0, 1000));
+        let (clamped_w, clamped_h) = clamped_size(&solver, width, height);
+        let [m_top, m_right, m_bottom, m_left] = solver.margins;
+
+        for rect in solver.solve(&cells, width, height) {
+            assert!(rect.x >= m_left + solver.spacing, "{:?} {:?}", solver, rect);
+            assert!(rect.y >= m_top + solver.spacing, "{:?} {:?}", solver, rect);
+            assert!(rect.x + rect.width + solver.spacing + m_right <= clamped_w, "{:?} {:?}", solver, rect);
+            assert!(rect.y + rect.height + solver.spacing + m_bottom <= clamped_h, "{:?} {:?}", solver, rect);
+        }
+    }
+}
+
+#[test]
+fn grid_solver_cells_do_not_overlap() {
+    let mut rng = Rng(0x9E3779B97F4A7C15);
+
+    for _ in 0..ITERATIONS {
+        let solver = random_solver(&mut rng);
+        let (cells, _, _) = random_cells(&mut rng);
+        let rects = solver.solve(&cells, rng.range(0, 1000), rng.range(0, 1000));
+
+        for (i, a) in rects.iter().enumerate() {
+            for b in rects[i+1..].iter() {
+                if a.width > 0 && a.height > 0 && b.width > 0 && b.height > 0 {
+                    assert!(!a.intersects(b), "{:?} overlaps {:?}", a, b);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn grid_solver_spans_cover_their_cells() {
+    let mut rng = Rng(0xD1B54A32D192ED03);
+
+    for _ in 0..ITERATIONS {
+        let solver = GridLayoutSolver { spacing: rng.range(0, 10), margins: [0, 0, 0, 0], ..Default::default() };
+        let (cells, columns, rows) = random_cells(&mut rng);
+        let solver = GridLayoutSolver { column_count: Some(columns), row_count: Some(rows), ..solver };
+
+        // One single-cell child for every grid position, to compare against the spanning cells
+        let mut singles = Vec::new();
+        for r in 0..rows {
+            for c in 0..columns {
+                singles.push(GridLayoutCell::new(c, r, 1, 1));
+            }
+        }
+
+        let (width, height) = (rng.range(200, 1000), rng.range(200, 1000));
+        let rects = solver.solve(&cells, width, height);
+        let single_rects = solver.solve(&singles, width, height);
+
+        for (cell, rect) in cells.iter().zip(rects.iter()) {
+            let first = single_rects[(cell.row * columns + cell.col) as usize];
+            let last = single_rects[((cell.row + cell.row_span - 1) * columns + (cell.col + cell.col_span - 1)) as usize];
+
+            assert_eq!(rect.x, first.x);
+            assert_eq!(rect.y, first.y);
+            assert_eq!(rect.x + rect.width, last.x + last.width);
+            assert_eq!(rect.y + rect.height, last.y + last.height);
+        }
+    }
+}
+
+#[test]
+fn grid_solver_clamping() {
+    let cells = [GridLayoutCell::new(0, 0, 1, 1)];
+    let solver = GridLayoutSolver { margins: [0, 0, 0, 0], spacing: 0, min_size: [100, 50], max_size: [200, 150], ..Default::default() };
+
+    assert_eq!(solver.solve(&cells, 10, 10), vec![GridLayoutRect { x: 0, y: 0, width: 100, height: 50 }]);
+    assert_eq!(solver.solve(&cells, 1000, 1000), vec![GridLayoutRect { x: 0, y: 0, width: 200, height: 150 }]);
+
+    // Not enough space for the margins
+    let solver = GridLayoutSolver { margins: [10, 10, 10, 10], ..Default::default() };
+    assert_eq!(solver.solve(&cells, 15, 100), vec![]);
+
+    // Spans going over a fixed column count are clamped
+    let solver = GridLayoutSolver { margins: [0, 0, 0, 0], spacing: 0, column_count: Some(2), row_count: Some(1), ..Default::default() };
+    let rects = solver.solve(&[GridLayoutCell::new(1, 0, 5, 3)], 100, 100);
+    assert_eq!(rects, vec![GridLayoutRect { x: 50, y: 0, width: 50, height: 100 }]);
+}
+
+#[test]
+fn grid_solver_line_sizes() {
+    use GridLayoutSize::*;
+
+    let base = GridLayoutSolver { margins: [0, 0, 0, 0], spacing: 0, ..Default::default() };
+    let mut label = GridLayoutCell::new(0, 0, 1, 1);
+    label.min_size = [80, 20];
+    let input = GridLayoutCell::new(1, 0, 1, 1);
+    let button = GridLayoutCell::new(2, 0, 1, 1);
+
+    // Fixed label column, stretched input column
+    let solver = GridLayoutSolver { column_sizes: vec![Fixed(100), Stretch(1)], ..base.clone() };
+    let rects = solver.solve(&[label, input], 400, 30);
+    assert_eq!(rects[0], GridLayoutRect { x: 0, y: 0, width: 100, height: 30 });
+    assert_eq!(rects[1], GridLayoutRect { x: 100, y: 0, width: 300, height: 30 });
+
+    // Auto column from the child minimum size
+    let solver = GridLayoutSolver { column_sizes: vec![Auto, Stretch(1)], ..base.clone() };
+    let rects = solver.solve(&[label, input], 400, 30);
+    assert_eq!(rects[0].width, 80);
+    assert_eq!(rects[1], GridLayoutRect { x: 80, y: 0, width: 320, height: 30 });
+
+    // Stretch factors
+    let solver = GridLayoutSolver { column_sizes: vec![Stretch(1), Stretch(2), Stretch(1)], ..base.clone() };
+    let rects = solver.solve(&[label, input, button], 400, 30);
+    assert_eq!(rects.iter().map(|r| r.width).collect::<Vec<_>>(), vec![100, 200, 100]);
+
+    // Policies define the lines even if they have no children
+    let solver = GridLayoutSolver { row_sizes: vec![Stretch(1), Fixed(10)], ..base.clone() };
+    let rects = solver.solve(&[label], 100, 110);
+    assert_eq!(rects[0].height, 100);
+
+    // Stretch lines share every pixel left by the other lines
+    let mut rng = Rng(0xA0761D6478BD642F);
+    for _ in 0..ITERATIONS {
+        let sizes: Vec<GridLayoutSize> = (0..rng.range(1, 6)).map(|_| match rng.range(0, 2) {
+            0 => Fixed(rng.range(0, 50)),
+            1 => Auto,
+            _ => Stretch(rng.range(1, 5)),
+        }).collect();
+
+        let cells: Vec<GridLayoutCell> = (0..sizes.len() as u32).map(|c| {
+            let mut cell = GridLayoutCell::new(c, 0, 1, 1);
+            cell.min_size = [rng.range(0, 50), 0];
+            cell
+        }).collect();
+
+        let solver = GridLayoutSolver { column_sizes: sizes.clone(), ..base.clone() };
+        let width = rng.range(300, 1000);
+        let rects = solver.solve(&cells, width, 100);
+        let total: u32 = rects.iter().map(|r| r.width).sum();
+
+        if sizes.iter().any(|s| match s { Stretch(_) => true, _ => false }) {
+            assert_eq!(total, width, "{:?} {:?}", sizes, rects);
+        }
+
+        for ((size, cell), rect) in sizes.iter().zip(cells.iter()).zip(rects.iter()) {
+            match size {
+                Fixed(w) => assert_eq!(rect.width, *w),
+                Auto => assert_eq!(rect.width, cell.min_size[0]),
+                Stretch(_) => {}
+            }
+        }
+    }
+}
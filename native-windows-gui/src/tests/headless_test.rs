@@ -0,0 +1,1530 @@
+/*!
+    Tests running on the in-memory window backend. Run with `cargo test --features "all headless"`.
+
+    The backend (see `win32::headless`) stands in for user32 and comctl32. These tests verify the NWG side:
+    the builders, the wrappers around the control messages, the event dispatch and the state kept by the controls.
+    They do not verify how the real common controls behave. For the list views and the tree views, the notifications
+    that comctl32 sends on its own (ex: `LVN_GETDISPINFOW`, `LVN_ODFINDITEMW`, `TVN_GETDISPINFOW` for the lazy children,
+    the custom draw stages, the label edit box) are either emulated with the documented behaviour or sent by the test.
+    Whether comctl32 really sends them that way is not covered by these tests.
+*/
+use crate::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+
+#[derive(Default)]
+pub struct HeadlessApp {
+    window: Window,
+    layout: GridLayout,
+    button: Button,
+    input: TextInput,
+}
+
+fn build_app() -> HeadlessApp {
+    let mut app = HeadlessApp::default();
+
+    Window::builder()
+        .size((300, 200))
+        .position((100, 100))
+        .title("Headless")
+        .build(&mut app.window)
+        .expect("Failed to build window");
+
+    Button::builder()
+        .text("Click me")
+        .parent(&app.window)
+        .build(&mut app.button)
+        .expect("Failed to build button");
+
+    TextInput::builder()
+        .text("Hello")
+        .parent(&app.window)
+        .build(&mut app.input)
+        .expect("Failed to build text input");
+
+    GridLayout::builder()
+        .parent(&app.window)
+        .spacing(0)
+        .margin([0, 0, 0, 0])
+        .child(0, 0, &app.button)
+        .child(1, 0, &app.input)
+        .build(&app.layout)
+        .expect("Failed to build layout");
+
+    app
+}
+
+#[test]
+fn headless_controls() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+
+    assert_eq!(app.window.text(), "Headless");
+    assert_eq!(app.window.size(), (300, 200));
+    assert_eq!(app.button.text(), "Click me");
+    assert_eq!(app.input.text(), "Hello");
+
+    app.input.set_text("World");
+    assert_eq!(app.input.text(), "World");
+
+    assert_eq!(app.button.handle.hwnd().map(|h| unsafe { crate::win32::window_helper::get_window_parent(h) }), app.window.handle.hwnd());
+
+    app.window.set_visible(false);
+    assert!(!app.button.visible());
+    app.window.set_visible(true);
+    assert!(app.button.visible());
+
+    // The grid layout splits the window in two columns
+    assert_eq!(app.button.size(), (150, 200));
+    assert_eq!(app.input.position(), (150, 0));
+
+    app.window.set_size(400, 100);
+    assert_eq!(app.button.size(), (200, 100));
+    assert_eq!(app.input.position(), (200, 0));
+}
+
+#[test]
+fn headless_events() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let events_ref = events.clone();
+    let button_handle = app.button.handle;
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, _evt_data, handle| {
+        if handle == button_handle {
+            events_ref.borrow_mut().push(evt);
+        }
+    });
+
+    app.button.click();
+    assert_eq!(&*events.borrow(), &[Event::OnButtonClick]);
+
+    // Events sent from the message queue are dispatched by `dispatch_thread_events`
+    let notice_events = Rc::new(RefCell::new(0));
+    let mut notice = Notice::default();
+    Notice::builder().parent(&app.window).build(&mut notice).expect("Failed to build notice");
+
+    let notice_ref = notice_events.clone();
+    let notice_handle = notice.handle;
+    let notice_handler = full_bind_event_handler(&app.window.handle, move |evt, _evt_data, handle| {
+        if evt == Event::OnNotice && handle == notice_handle {
+            *notice_ref.borrow_mut() += 1;
+        }
+    });
+
+    let sender = notice.sender();
+    ::std::thread::spawn(move || sender.notice()).join().unwrap();
+    dispatch_thread_events();
+
+    assert_eq!(*notice_events.borrow(), 1);
+
+    unbind_event_handler(&notice_handler);
+    unbind_event_handler(&handler);
+}
+
+#[test]
+fn headless_event_injection() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = Rc::new(build_app());
+
+    let app_ref = Rc::downgrade(&app);
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, evt_data, handle| {
+        if let Some(app) = app_ref.upgrade() {
+            match evt {
+                Event::OnButtonDoubleClick if handle == app.button.handle => app.button.set_text("Double click"),
+                Event::OnKeyPress if handle == app.input.handle => app.input.set_text(&format!("{}", evt_data.on_key())),
+                Event::OnWindowClose if handle == app.window.handle => {
+                    if let EventData::OnWindowClose(data) = &evt_data {
+                        data.close(false);
+                    }
+                },
+                _ => {}
+            }
+        }
+    });
+
+    inject_event(&app.button.handle, Event::OnButtonDoubleClick, || EventData::NoData);
+    assert_eq!(app.button.text(), "Double click");
+
+    inject_event(&app.input.handle, Event::OnKeyPress, || EventData::OnKey(KeyData { key: Key::A, modifiers: Modifiers::NONE, repeat_count: 1, repeated: false, extended: false }));
+    assert_eq!(app.input.text(), format!("{}", keys::_A));
+
+    assert!(!inject_window_close(&app.window.handle));
+
+    unbind_event_handler(&handler);
+    assert!(inject_window_close(&app.window.handle));
+}
+
+#[test]
+fn headless_nested_layouts() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+    let mut button2 = Button::default();
+    Button::builder().parent(&app.window).build(&mut button2).expect("Failed to build button");
+
+    // The right column holds a grid layout with two rows
+    let inner = GridLayout::default();
+    GridLayout::builder()
+        .parent(&app.window)
+        .spacing(0)
+        .margin([0, 0, 0, 0])
+        .child(0, 0, &app.input)
+        .child(0, 1, &button2)
+        .build(&inner)
+        .expect("Failed to build layout");
+
+    GridLayout::builder()
+        .parent(&app.window)
+        .spacing(0)
+        .margin([0, 0, 0, 0])
+        .child(0, 0, &app.button)
+        .child_layout(1, 0, &inner)
+        .build(&app.layout)
+        .expect("Failed to build layout");
+
+    assert_eq!(app.button.size(), (150, 200));
+    assert_eq!(app.input.position(), (150, 0));
+    assert_eq!(app.input.size(), (150, 100));
+    assert_eq!(button2.position(), (150, 100));
+
+    // Only the top level layout handles the resize
+    app.window.set_size(400, 100);
+    assert_eq!(app.input.size(), (200, 50));
+    assert_eq!(button2.position(), (200, 50));
+}
+
+#[test]
+fn headless_dock_layout() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut window = Window::default();
+    Window::builder().size((400, 300)).title("Dock").build(&mut window).expect("Failed to build window");
+
+    let mut controls: Vec<Button> = Vec::new();
+    for _ in 0..5 {
+        let mut button = Button::default();
+        Button::builder().size((50, 50)).parent(&window).build(&mut button).expect("Failed to build button");
+        controls.push(button);
+    }
+
+    let [toolbar, status, tree, side, content] = [&controls[0], &controls[1], &controls[2], &controls[3], &controls[4]];
+
+    let layout = DockLayout::default();
+    DockLayout::builder()
+        .parent(&window)
+        .child(DockStyle::Top, toolbar)
+        .child_size(30)
+        .child(DockStyle::Bottom, status)
+        .child_size(20)
+        .child(DockStyle::Left, tree)
+        .child_size(100)
+        .child(DockStyle::Right, side)
+        .child_min_size([80, 0])
+        .child(DockStyle::Fill, content)
+        .build(&layout)
+        .expect("Failed to build layout");
+
+    assert_eq!((toolbar.position(), toolbar.size()), ((0, 0), (400, 30)));
+    assert_eq!((status.position(), status.size()), ((0, 280), (400, 20)));
+    assert_eq!((tree.position(), tree.size()), ((0, 30), (100, 250)));
+    assert_eq!((side.position(), side.size()), ((320, 30), (80, 250)));
+    assert_eq!((content.position(), content.size()), ((100, 30), (220, 250)));
+
+    // Children that do not fit keep their minimum size
+    window.set_size(150, 100);
+    assert_eq!(tree.size(), (100, 50));
+    assert_eq!(side.size(), (80, 50));
+    assert_eq!(content.size(), (0, 50));
+
+    assert!(layout.has_child(content));
+    layout.remove_child(side);
+    window.set_size(400, 300);
+    assert_eq!((content.position(), content.size()), ((100, 30), (300, 250)));
+}
+
+#[cfg(feature = "splitter")]
+#[test]
+fn headless_splitter() {
+    use crate::win32::window_helper as wh;
+    use winapi::um::winuser::{WM_LBUTTONDOWN, WM_MOUSEMOVE, WM_LBUTTONUP, WM_KEYDOWN, VK_LEFT, VK_RIGHT};
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut window = Window::default();
+    Window::builder().size((400, 300)).title("Splitter").build(&mut window).expect("Failed to build window");
+
+    let mut splitter = Splitter::default();
+    Splitter::builder()
+        .size((400, 300))
+        .split_position(100)
+        .min_sizes([50, 100])
+        .parent(&window)
+        .build(&mut splitter)
+        .expect("Failed to build splitter");
+
+    let mut tree = Button::default();
+    let mut list = Button::default();
+    Button::builder().parent(&splitter).build(&mut tree).expect("Failed to build button");
+    Button::builder().parent(&splitter).build(&mut list).expect("Failed to build button");
+    splitter.set_panes(&tree, &list);
+
+    assert_eq!((tree.position(), tree.size()), ((0, 0), (100, 300)));
+    assert_eq!((list.position(), list.size()), ((105, 0), (295, 300)));
+
+    let moved = Rc::new(RefCell::new(0));
+    let moved_ref = moved.clone();
+    let splitter_handle = splitter.handle;
+    let handler = full_bind_event_handler(&window.handle, move |evt, _evt_data, handle| {
+        if evt == Event::OnSplitterMoved && handle == splitter_handle {
+            *moved_ref.borrow_mut() += 1;
+        }
+    });
+
+    // Drag the bar with the mouse
+    let hwnd = splitter.handle.hwnd().unwrap();
+    wh::send_message(hwnd, WM_LBUTTONDOWN, 0, 102);
+    wh::send_message(hwnd, WM_MOUSEMOVE, 0, 202);
+    wh::send_message(hwnd, WM_LBUTTONUP, 0, 202);
+    wh::send_message(hwnd, WM_MOUSEMOVE, 0, 300);
+
+    assert_eq!(splitter.split_position(), 200);
+    assert_eq!(tree.size(), (200, 300));
+    assert_eq!((list.position(), list.size()), ((205, 0), (195, 300)));
+    assert_eq!(*moved.borrow(), 1);
+
+    // Keyboard and minimum sizes
+    wh::send_message(hwnd, WM_KEYDOWN, VK_LEFT as _, 0);
+    assert_eq!(splitter.split_position(), 190);
+
+    splitter.set_split_position(1000);
+    assert_eq!(splitter.split_position(), 295);
+    splitter.set_split_position(0);
+    assert_eq!(splitter.split_position(), 50);
+    assert_eq!(*moved.borrow(), 2);
+
+    // Collapse and restore
+    splitter.collapse(Some(SplitterPane::First));
+    assert!(!tree.visible());
+    assert_eq!((list.position(), list.size()), ((5, 0), (395, 300)));
+    assert_eq!(splitter.split_position(), 50);
+
+    wh::send_message(hwnd, WM_KEYDOWN, VK_RIGHT as _, 0);
+    assert_eq!(splitter.collapsed(), None);
+    assert!(tree.visible());
+    assert_eq!(splitter.split_position(), 50);
+    assert_eq!(*moved.borrow(), 3);
+
+    unbind_event_handler(&handler);
+}
+
+#[cfg(feature = "scroll-panel")]
+#[test]
+fn headless_scroll_panel() {
+    use crate::win32::window_helper as wh;
+    use winapi::um::winuser::{WM_VSCROLL, WM_MOUSEWHEEL, SB_TOP, SB_LINEDOWN};
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut window = Window::default();
+    Window::builder().size((400, 300)).title("ScrollPanel").build(&mut window).expect("Failed to build window");
+
+    let mut panel = ScrollPanel::default();
+    ScrollPanel::builder()
+        .size((200, 100))
+        .parent(&window)
+        .build(&mut panel)
+        .expect("Failed to build scroll panel");
+
+    // Content size from the children
+    let mut first = Button::default();
+    let mut last = Button::default();
+    Button::builder().size((100, 50)).position((0, 0)).parent(&panel).build(&mut first).expect("Failed to build button");
+    Button::builder().size((100, 50)).position((150, 300)).parent(&panel).build(&mut last).expect("Failed to build button");
+    panel.fit_content();
+
+    assert_eq!(panel.content_size(), (250, 350));
+
+    panel.scroll_to(1000, 1000);
+    assert_eq!(panel.scroll_position(), (50, 250));
+    assert_eq!(first.position(), (-50, -250));
+    assert_eq!(last.position(), (100, 50));
+
+    // Scrollbar and mouse wheel
+    let hwnd = panel.handle.hwnd().unwrap();
+    wh::send_message(hwnd, WM_VSCROLL, SB_TOP as _, 0);
+    assert_eq!(panel.scroll_position(), (50, 0));
+
+    wh::send_message(hwnd, WM_VSCROLL, SB_LINEDOWN as _, 0);
+    assert_eq!(panel.scroll_position(), (50, 20));
+
+    let wheel_down = ((-120i16 as u16 as usize) << 16) as _;
+    wh::send_message(hwnd, WM_MOUSEWHEEL, wheel_down, 0);
+    assert_eq!(panel.scroll_position(), (50, 80));
+
+    // Ensure visible
+    panel.ensure_visible(&first);
+    assert_eq!(panel.scroll_position(), (0, 0));
+    assert_eq!(first.position(), (0, 0));
+
+    panel.ensure_visible(&last);
+    assert_eq!(panel.scroll_position(), (50, 250));
+
+    // Content size from a layout
+    let mut layout_panel = ScrollPanel::default();
+    ScrollPanel::builder()
+        .size((200, 100))
+        .parent(&window)
+        .build(&mut layout_panel)
+        .expect("Failed to build scroll panel");
+
+    let mut content = Button::default();
+    Button::builder().parent(&layout_panel).build(&mut content).expect("Failed to build button");
+
+    let layout = GridLayout::default();
+    GridLayout::builder()
+        .parent(&layout_panel)
+        .spacing(0)
+        .margin([0, 0, 0, 0])
+        .min_size([200, 400])
+        .child(0, 0, &content)
+        .build(&layout)
+        .expect("Failed to build layout");
+
+    layout_panel.set_layout(&layout);
+    assert_eq!(layout_panel.content_size(), (200, 400));
+    assert_eq!((content.position(), content.size()), ((0, 0), (200, 400)));
+
+    layout_panel.scroll_to(0, 1000);
+    assert_eq!(layout_panel.scroll_position(), (0, 300));
+    assert_eq!(content.position(), (0, -300));
+}
+
+#[test]
+fn headless_fallible_accessors() {
+    use crate::win32::window_helper as wh;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let button = Button::default();
+    match button.try_text() {
+        Err(NwgError::ControlNotBound(_)) => {},
+        r => panic!("Expected ControlNotBound, got {:?}", r)
+    }
+
+    let layout = GridLayout::default();
+    match layout.try_fit() {
+        Err(NwgError::ControlNotBound(_)) => {},
+        r => panic!("Expected ControlNotBound, got {:?}", r)
+    }
+
+    let app = build_app();
+    assert_eq!(app.button.try_text().unwrap(), "Click me");
+    assert_eq!(app.layout.try_has_child(&app.button).unwrap(), true);
+
+    match app.layout.try_remove_child(&app.window) {
+        Err(NwgError::ChildNotFound(_)) => {},
+        r => panic!("Expected ChildNotFound, got {:?}", r)
+    }
+
+    // The window was freed behind the control back
+    wh::destroy_window(app.input.handle.hwnd().unwrap());
+    match app.input.try_set_text("Hello") {
+        Err(NwgError::InvalidHandle(_)) => {},
+        r => panic!("Expected InvalidHandle, got {:?}", r)
+    }
+
+    match app.input.try_with(|input| input.selection()) {
+        Err(NwgError::InvalidHandle(_)) => {},
+        r => panic!("Expected InvalidHandle, got {:?}", r)
+    }
+
+    assert_eq!(app.button.try_with(|button| button.text()).unwrap(), "Click me");
+}
+
+#[cfg(all(feature = "list-view", feature = "timer", feature = "notice"))]
+#[test]
+fn headless_fallible_other_controls() {
+    use crate::win32::window_helper as wh;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let list = ListView::default();
+    match list.try_with(|list| list.len()) {
+        Err(NwgError::ControlNotBound(_)) => {},
+        r => panic!("Expected ControlNotBound, got {:?}", r)
+    }
+
+    let timer = Timer::default();
+    match timer.try_start() {
+        Err(NwgError::ControlNotBound(_)) => {},
+        r => panic!("Expected ControlNotBound, got {:?}", r)
+    }
+
+    let app = build_app();
+
+    let mut timer = Timer::default();
+    Timer::builder().parent(&app.window).build(&mut timer).expect("Failed to build timer");
+    let mut notice = Notice::default();
+    Notice::builder().parent(&app.window).build(&mut notice).expect("Failed to build notice");
+
+    assert!(timer.try_start().is_ok());
+    assert!(notice.try_sender().is_ok());
+
+    // Timers and notices are unusable once their parent window is destroyed
+    wh::destroy_window(app.window.handle.hwnd().unwrap());
+
+    match timer.try_stop() {
+        Err(NwgError::InvalidHandle(_)) => {},
+        r => panic!("Expected InvalidHandle, got {:?}", r)
+    }
+
+    match notice.try_sender().map(|_| ()) {
+        Err(NwgError::InvalidHandle(_)) => {},
+        r => panic!("Expected InvalidHandle, got {:?}", r)
+    }
+}
+
+#[test]
+fn headless_structured_errors() {
+    use crate::win32::window_helper as wh;
+    use std::error::Error;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut window = Window::default();
+    Window::builder().title("Errors").build(&mut window).expect("Failed to build window");
+    wh::destroy_window(window.handle.hwnd().unwrap());
+
+    // Creating a control in a freed window keeps the system error
+    let mut button = Button::default();
+    let error = Button::builder().parent(&window).build(&mut button).unwrap_err();
+    assert_eq!(error.operation(), Some("CreateWindowExW"));
+    assert_eq!(error.class(), Some("BUTTON"));
+    assert_eq!(error.code(), Some(SystemErrorCode::Win32(1400)));
+    assert!(error.source().is_none());
+
+    // Error chaining
+    let cancelled = NwgError::initialization("Dialog closed").with_hresult("IFileDialog::Show", 0x800704C7u32 as i32);
+    let error = NwgError::control_create("Could not pick a file").with_source(cancelled);
+    assert!(error.is_cancelled());
+    assert_eq!(error.source().map(|e| e.to_string()), Some("Failed to initialize NWG: \"Dialog closed\" (IFileDialog::Show failed with HRESULT 0x800704C7)".to_string()));
+    assert!(!NwgError::no_parent("Button").is_cancelled());
+}
+
+#[test]
+fn headless_typed_event_data() {
+    use crate::win32::window_helper as wh;
+    use winapi::um::winuser::{WM_LBUTTONDOWN, WM_KEYDOWN, WM_KEYUP, WM_SIZE, WM_HSCROLL, MK_LBUTTON, MK_SHIFT, SIZE_MAXIMIZED, SB_THUMBTRACK, VK_CONTROL, VK_SHIFT, VK_RIGHT};
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let events_ref = events.clone();
+    let window_handle = app.window.handle;
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, evt_data, handle| {
+        if handle == window_handle || evt == Event::OnHorizontalScroll {
+            events_ref.borrow_mut().push((evt, evt_data));
+        }
+    });
+
+    let hwnd = app.window.handle.hwnd().unwrap();
+
+    // Negative coordinates are sent when the mouse is captured outside of the client area
+    let position = (((-5i16) as u16 as u32) << 16) | 20;
+    wh::send_message(hwnd, WM_LBUTTONDOWN, MK_LBUTTON | MK_SHIFT, position as _);
+    wh::send_message(hwnd, WM_SIZE, SIZE_MAXIMIZED, (480 << 16) | 640);
+    wh::send_message(hwnd, WM_KEYDOWN, VK_CONTROL as _, 1);
+    wh::send_message(hwnd, WM_KEYDOWN, keys::_S as _, 3);
+    wh::send_message(hwnd, WM_KEYDOWN, VK_SHIFT as _, 1);
+    wh::send_message(hwnd, WM_KEYDOWN, VK_RIGHT as _, 1 | (1 << 24) | (1 << 30));
+    wh::send_message(hwnd, WM_KEYUP, VK_SHIFT as _, 1);
+    wh::send_message(hwnd, WM_KEYUP, VK_CONTROL as _, 1);
+    wh::send_message(hwnd, WM_HSCROLL, (35 << 16) | SB_THUMBTRACK as usize, 0);
+
+    let events = events.borrow();
+
+    let mouse = events.iter().find(|(e, _)| *e == Event::OnMousePress(MousePressEvent::MousePressLeftDown)).unwrap();
+    assert_eq!(mouse.1.as_mouse(), Some(&MouseData { position: (20, -5), buttons: MouseButtons::LEFT, modifiers: Modifiers::SHIFT }));
+    assert!(mouse.1.as_key().is_none());
+
+    let resize = events.iter().find(|(e, _)| *e == Event::OnWindowMaximize).unwrap();
+    assert_eq!(resize.1.as_resize(), Some(&ResizeData { size: (640, 480), kind: ResizeKind::Maximized }));
+
+    let key = events.iter().find(|(e, d)| *e == Event::OnKeyPress && d.on_key() == keys::_S).unwrap();
+    assert_eq!(key.1.as_key(), Some(&KeyData { key: Key::S, modifiers: Modifiers::CONTROL, repeat_count: 3, repeated: false, extended: false }));
+
+    let select_word: KeyCombo = "Ctrl+Shift+Right".parse().unwrap();
+    let key = events.iter().find(|(e, d)| *e == Event::OnKeyPress && d.on_key() == keys::RIGHT).unwrap();
+    let key = key.1.as_key().unwrap();
+    assert!(key.matches(&select_word));
+    assert!(key.repeated && key.extended);
+
+    let scroll = events.iter().find(|(e, _)| *e == Event::OnHorizontalScroll).unwrap();
+    assert_eq!(scroll.1.as_scroll(), Some(&ScrollData { code: ScrollCode::ThumbTrack, position: 35 }));
+
+    unbind_event_handler(&handler);
+}
+
+#[test]
+fn headless_extended_mouse_events() {
+    use crate::win32::window_helper as wh;
+    use crate::win32::backend::TrackMouseEvent;
+    use winapi::um::winuser::{TRACKMOUSEEVENT, TME_QUERY, TME_CANCEL, TME_HOVER, TME_LEAVE};
+    use std::mem;
+    use winapi::um::winuser::{WM_MBUTTONDOWN, WM_XBUTTONUP, WM_LBUTTONDBLCLK, WM_XBUTTONDBLCLK, WM_MOUSEMOVE, WM_MOUSEHWHEEL,
+        WM_MOUSEHOVER, WM_MOUSELEAVE, MK_MBUTTON, MK_LBUTTON, MK_CONTROL, XBUTTON1, XBUTTON2, WHEEL_DELTA};
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let events_ref = events.clone();
+    let window_handle = app.window.handle;
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, evt_data, handle| {
+        if handle == window_handle {
+            events_ref.borrow_mut().push((evt, evt_data));
+        }
+    });
+
+    let hwnd = app.window.handle.hwnd().unwrap();
+    let at = |x: u32, y: u32| ((y << 16) | x) as isize;
+
+    // The wheel messages are in screen coordinates, the window is at (100, 100)
+    wh::send_message(hwnd, WM_MBUTTONDOWN, MK_MBUTTON, at(10, 20));
+    wh::send_message(hwnd, WM_XBUTTONUP, (XBUTTON2 as usize) << 16, at(11, 21));
+    wh::send_message(hwnd, WM_XBUTTONDBLCLK, (XBUTTON1 as usize) << 16, at(12, 22));
+    wh::send_message(hwnd, WM_LBUTTONDBLCLK, MK_LBUTTON, at(13, 23));
+    wh::send_message(hwnd, WM_MOUSEHWHEEL, ((-WHEEL_DELTA) as u16 as usize) << 16 | MK_CONTROL, at(130, 140));
+    wh::send_message(hwnd, WM_MOUSEMOVE, 0, at(5, 6));
+    wh::send_message(hwnd, WM_MOUSEHOVER, 0, at(7, 8));
+    wh::send_message(hwnd, WM_MOUSELEAVE, 0, 0);
+
+    // The tracking is requested by the first move after a hover or a leave, not by every move
+    let tracking = |flags| {
+        let mut track = TRACKMOUSEEVENT { cbSize: mem::size_of::<TRACKMOUSEEVENT>() as u32, dwFlags: flags, hwndTrack: hwnd, dwHoverTime: 0 };
+        unsafe { TrackMouseEvent(&mut track); }
+        track.dwFlags
+    };
+
+    assert_eq!(tracking(TME_QUERY), 0);
+    wh::send_message(hwnd, WM_MOUSEMOVE, 0, at(5, 6));
+    assert_eq!(tracking(TME_QUERY), TME_HOVER | TME_LEAVE);
+
+    tracking(TME_CANCEL | TME_HOVER | TME_LEAVE);
+    wh::send_message(hwnd, WM_MOUSEMOVE, 0, at(5, 6));
+    assert_eq!(tracking(TME_QUERY), 0);
+
+    wh::send_message(hwnd, WM_MOUSEHOVER, 0, at(5, 6));
+    wh::send_message(hwnd, WM_MOUSEMOVE, 0, at(5, 6));
+    assert_eq!(tracking(TME_QUERY), TME_HOVER | TME_LEAVE);
+
+    let events = events.borrow();
+    let find = |evt: Event| events.iter().find(|(e, _)| *e == evt).map(|(_, d)| d.as_mouse().cloned()).unwrap();
+    let mouse = |position, buttons, modifiers| Some(MouseData { position, buttons, modifiers });
+
+    assert_eq!(find(Event::OnMousePress(MousePressEvent::MousePressMiddleDown)), mouse((10, 20), MouseButtons::MIDDLE, Modifiers::NONE));
+    assert_eq!(find(Event::OnMousePress(MousePressEvent::MousePressX2Up)), mouse((11, 21), MouseButtons::NONE, Modifiers::NONE));
+    assert_eq!(find(Event::OnMousePress(MousePressEvent::MousePressX1DoubleClick)), mouse((12, 22), MouseButtons::NONE, Modifiers::NONE));
+    assert_eq!(find(Event::OnMousePress(MousePressEvent::MousePressLeftDoubleClick)), mouse((13, 23), MouseButtons::LEFT, Modifiers::NONE));
+
+    // The second click of a double click also raises the `Down` event of the button
+    let presses: Vec<Event> = events.iter().map(|(e, _)| *e).filter(|e| match e { Event::OnMousePress(_) => true, _ => false }).collect();
+    assert_eq!(&presses[2..], &[
+        Event::OnMousePress(MousePressEvent::MousePressX1Down),
+        Event::OnMousePress(MousePressEvent::MousePressX1DoubleClick),
+        Event::OnMousePress(MousePressEvent::MousePressLeftDown),
+        Event::OnMousePress(MousePressEvent::MousePressLeftDoubleClick),
+    ]);
+    assert_eq!(find(Event::OnMouseHorizontalWheel), mouse((30, 40), MouseButtons::NONE, Modifiers::CONTROL));
+    assert_eq!(find(Event::OnMouseHover), mouse((7, 8), MouseButtons::NONE, Modifiers::NONE));
+
+    // The position of `OnMouseLeave` is the last known cursor position
+    assert_eq!(find(Event::OnMouseLeave), mouse((7, 8), MouseButtons::NONE, Modifiers::NONE));
+
+    let wheel = events.iter().find(|(e, _)| *e == Event::OnMouseHorizontalWheel).unwrap();
+    assert_eq!(wheel.1.as_mouse_wheel(), Some(-WHEEL_DELTA as i32));
+
+    unbind_event_handler(&handler);
+}
+
+#[test]
+fn headless_accelerators_and_hotkeys() {
+    use crate::win32::window_helper as wh;
+    use winapi::um::winuser::{WM_KEYDOWN, WM_KEYUP, WM_HOTKEY, VK_CONTROL};
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let mut accels = Accelerators::default();
+    Accelerators::builder()
+        .parent(&app.window)
+        .accelerator("Ctrl+S".parse().unwrap(), 1)
+        .accelerator("F5".parse().unwrap(), 2)
+        .build(&mut accels)
+        .expect("Failed to build accelerators");
+
+    assert!(Accelerators::builder().parent(&app.window).accelerator("Win+S".parse().unwrap(), 3).build(&mut Accelerators::default()).is_err());
+
+    let mut hotkey = GlobalHotkey::default();
+    GlobalHotkey::builder()
+        .parent(&app.window)
+        .combo("Ctrl+Alt+Shift+F11".parse().unwrap())
+        .build(&mut hotkey)
+        .expect("Failed to build hotkey");
+
+    // A combination can only be registered once
+    let mut other_hotkey = GlobalHotkey::default();
+    let error = GlobalHotkey::builder().parent(&app.window).combo(hotkey.combo().unwrap()).build(&mut other_hotkey).unwrap_err();
+    assert_eq!(error.code(), Some(SystemErrorCode::Win32(1409)));
+
+    let events_ref = events.clone();
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, evt_data, handle| {
+        match evt {
+            Event::OnAccelerator | Event::OnHotkey => events_ref.borrow_mut().push((evt, evt_data.as_accelerator(), handle)),
+            _ => {}
+        }
+    });
+
+    // Accelerators are translated by the dispatch loop, even if a child control has the focus
+    let button = app.button.handle.hwnd().unwrap();
+    let window = app.window.handle.hwnd().unwrap();
+    wh::post_message(button, WM_KEYDOWN, keys::_S as _, 1);
+    wh::post_message(button, WM_KEYDOWN, VK_CONTROL as _, 1);
+    wh::post_message(button, WM_KEYDOWN, keys::_S as _, 1);
+    wh::post_message(button, WM_KEYUP, VK_CONTROL as _, 1);
+    wh::post_message(button, WM_KEYDOWN, keys::F5 as _, 1);
+    wh::post_message(window, WM_HOTKEY, hotkey.handle.hotkey().unwrap().1 as _, 0);
+    dispatch_thread_events();
+
+    assert_eq!(&*events.borrow(), &[
+        (Event::OnAccelerator, Some(1), app.window.handle),
+        (Event::OnAccelerator, Some(2), app.window.handle),
+        (Event::OnHotkey, None, hotkey.handle),
+    ]);
+
+    // Freed accelerators are not translated anymore
+    drop(accels);
+    wh::post_message(button, WM_KEYDOWN, keys::F5 as _, 1);
+    dispatch_thread_events();
+    assert_eq!(events.borrow().len(), 3);
+
+    unbind_event_handler(&handler);
+}
+
+#[test]
+fn headless_async_tasks() {
+    use std::cell::Cell;
+    use std::time::{Duration, Instant};
+    use std::thread;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = Rc::new(build_app());
+
+    fn run_until<F: Fn() -> bool>(done: F) {
+        let start = Instant::now();
+        while !done() {
+            assert!(start.elapsed() < Duration::from_secs(5), "Task did not finish");
+            dispatch_thread_events();
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    // A task can await a background thread and then touch the controls
+    let app_ref = app.clone();
+    let task = spawn_local(async move {
+        let value = spawn_background(|| 40 + 2).await.unwrap();
+        app_ref.input.set_text(&value.to_string());
+    }).expect("Failed to spawn task");
+
+    // Tasks are never polled by `spawn_local`
+    assert!(!task.finished());
+    assert_eq!(app.input.text(), "Hello");
+
+    run_until(|| task.finished());
+    assert_eq!(app.input.text(), "42");
+
+    // A task can await another task. A panic in a background thread is returned to the awaiting task.
+    let result = Rc::new(Cell::new(0));
+    let result_ref = result.clone();
+    let inner = spawn_local(async { 10 }).unwrap();
+    let outer = spawn_local(async move {
+        let panicked = spawn_background(|| -> u32 { panic!("background panic") }).await.is_err();
+        result_ref.set(inner.await + panicked as u32);
+    }).unwrap();
+
+    run_until(|| outer.finished());
+    assert_eq!(result.get(), 11);
+
+    // Cancelled tasks are dropped without being polled
+    let polled = Rc::new(Cell::new(false));
+    let polled_ref = polled.clone();
+    let cancelled = spawn_local(async move { polled_ref.set(true); }).unwrap();
+    cancelled.cancel();
+    dispatch_thread_events();
+    assert!(!polled.get());
+}
+
+#[test]
+fn headless_executor_modal_loop() {
+    use crate::win32::executor::modal_future;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    // `a` yields once, then `b` queues `c` behind it. When `a` opens its modal loop, `c` is still waiting
+    // and must be polled by the nested loop instead of after the dialog is closed.
+    let log_a = log.clone();
+    let a = spawn_local(async move {
+        modal_future(|| {
+            log_a.borrow_mut().push("modal:open");
+            dispatch_thread_events();
+            log_a.borrow_mut().push("modal:close");
+        }).await;
+    }).unwrap();
+
+    let log_b = log.clone();
+    let b = spawn_local(async move {
+        log_b.borrow_mut().push("b");
+        let log_c = log_b.clone();
+        spawn_local(async move { log_c.borrow_mut().push("c"); }).unwrap();
+    }).unwrap();
+
+    dispatch_thread_events();
+
+    assert!(a.finished() && b.finished());
+    assert_eq!(*log.borrow(), vec!["b", "modal:open", "c", "modal:close"]);
+}
+
+#[test]
+fn headless_notice_channel() {
+    use std::thread;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+    let channel: NoticeChannel<u32> = NoticeChannel::create(&app.window).expect("Failed to build notice channel");
+    let received = Rc::new(RefCell::new(Vec::new()));
+
+    let received_ref = received.clone();
+    let channel_handle = channel.handle;
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, evt_data, handle| {
+        if evt == Event::OnNotice && handle == channel_handle {
+            let notice = evt_data.as_notice().expect("Notice channel without data");
+            assert!(notice.drain::<String>().is_empty());
+            received_ref.borrow_mut().extend(notice.drain::<u32>());
+        }
+    });
+
+    let senders: Vec<_> = (0..3).map(|_| channel.sender()).collect();
+    let threads: Vec<_> = senders.into_iter().enumerate()
+        .map(|(i, sender)| thread::spawn(move || sender.send(i as u32 * 10)))
+        .collect();
+
+    for t in threads {
+        assert_eq!(t.join().unwrap(), Ok(()));
+    }
+
+    dispatch_thread_events();
+
+    let mut values = received.borrow().clone();
+    values.sort();
+    assert_eq!(values, vec![0, 10, 20]);
+    unbind_event_handler(&handler);
+
+    // Values sent before the parent is destroyed can still be drained, then the senders are closed
+    let mut window = Window::default();
+    Window::builder().title("Channel parent").build(&mut window).unwrap();
+    let channel: NoticeChannel<&'static str> = NoticeChannel::create(&window).unwrap();
+    let sender = channel.sender();
+
+    assert_eq!(sender.send("before"), Ok(()));
+    drop(window);
+
+    assert!(!channel.valid());
+    assert_eq!(sender.send("after"), Err("after"));
+    assert_eq!(channel.drain(), vec!["before"]);
+
+    drop(channel);
+    let sender = NoticeChannel::<u32>::create(&app.window).unwrap().sender();
+    assert_eq!(sender.send(1), Err(1));
+}
+
+#[test]
+fn headless_modal_future() {
+    use crate::win32::executor::modal_future;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    // The modal call is made by a later poll, so the second task runs before it
+    let log_a = log.clone();
+    let a = spawn_local(async move {
+        log_a.borrow_mut().push("a:start");
+        let value = modal_future(|| { log_a.borrow_mut().push("modal"); 7 }).await;
+        log_a.borrow_mut().push("a:end");
+        value
+    }).unwrap();
+
+    let log_b = log.clone();
+    let b = spawn_local(async move { log_b.borrow_mut().push("b"); }).unwrap();
+
+    dispatch_thread_events();
+
+    assert!(a.finished() && b.finished());
+    assert_eq!(*log.borrow(), vec!["a:start", "b", "modal", "a:end"]);
+}
+
+#[test]
+fn headless_modal_dialog() {
+    use crate::win32::window_helper as wh;
+    use winapi::um::winuser::{BM_CLICK, WM_KEYDOWN, VK_RETURN, VK_ESCAPE};
+    use std::cell::Cell;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+
+    let mut dialog: Dialog<u32> = Dialog::default();
+    Dialog::builder()
+        .title("Dialog")
+        .parent(Some(&app.window))
+        .build(&mut dialog)
+        .expect("Failed to build dialog");
+
+    let mut ok = Button::default();
+    Button::builder().text("OK").parent(&dialog).build(&mut ok).expect("Failed to build button");
+
+    let mut cancel = Button::default();
+    Button::builder().text("Cancel").parent(&dialog).build(&mut cancel).expect("Failed to build button");
+
+    let dialog = Rc::new(dialog);
+    dialog.set_default_button(Some(&ok));
+    dialog.set_cancel_button(Some(&cancel));
+    assert!(!dialog.visible());
+
+    let window = app.window.handle.hwnd().unwrap();
+    let owner_enabled = Rc::new(Cell::new(true));
+
+    let dialog_ref = Rc::downgrade(&dialog);
+    let owner_enabled_ref = owner_enabled.clone();
+    let (ok_handle, cancel_handle) = (ok.handle, cancel.handle);
+    let handler = full_bind_event_handler(&dialog.handle, move |evt, _evt_data, handle| {
+        if evt != Event::OnButtonClick {
+            return;
+        }
+
+        if let Some(dialog) = dialog_ref.upgrade() {
+            assert!(dialog.running() && dialog.visible());
+            owner_enabled_ref.set(unsafe { wh::get_window_enabled(window) });
+
+            if handle == ok_handle {
+                dialog.end_dialog(1);
+            } else if handle == cancel_handle {
+                dialog.end_dialog(0);
+            }
+        }
+    });
+
+    // The owner is disabled while the dialog runs
+    wh::post_message(ok.handle.hwnd().unwrap(), BM_CLICK, 0, 0);
+    assert_eq!(dialog.run_modal(), Some(1));
+    assert!(!owner_enabled.get());
+    assert!(app.window.enabled() && !dialog.visible() && !dialog.running());
+
+    // Enter clicks the default button and Escape clicks the cancel button
+    wh::post_message(cancel.handle.hwnd().unwrap(), WM_KEYDOWN, VK_RETURN as _, 1);
+    assert_eq!(dialog.run_modal(), Some(1));
+
+    wh::post_message(ok.handle.hwnd().unwrap(), WM_KEYDOWN, VK_ESCAPE as _, 1);
+    assert_eq!(dialog.run_modal(), Some(0));
+
+    // Without a cancel button, Escape closes the dialog without a result
+    dialog.set_cancel_button(None::<&Button>);
+    wh::post_message(ok.handle.hwnd().unwrap(), WM_KEYDOWN, VK_ESCAPE as _, 1);
+    assert_eq!(dialog.run_modal(), None);
+    assert!(app.window.enabled() && !dialog.visible());
+
+    unbind_event_handler(&handler);
+}
+
+#[cfg(feature = "wizard")]
+#[test]
+fn headless_wizard() {
+    use crate::win32::window_helper as wh;
+    use winapi::um::winuser::{WM_KEYDOWN, VK_RETURN, VK_ESCAPE};
+    use std::cell::Cell;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+
+    let mut wizard = Wizard::default();
+    Wizard::builder()
+        .title("Wizard")
+        .parent(Some(&app.window))
+        .build(&mut wizard)
+        .expect("Failed to build wizard");
+
+    let mut pages: [WizardPage; 3] = Default::default();
+    for (i, page) in pages.iter_mut().enumerate() {
+        WizardPage::builder()
+            .title(&format!("Page {}", i))
+            .subtitle("Subtitle")
+            .parent(&wizard)
+            .build(page)
+            .expect("Failed to build wizard page");
+    }
+
+    let mut label = Label::default();
+    Label::builder().text("Name").parent(&pages[1]).build(&mut label).expect("Failed to build label");
+
+    assert_eq!(wizard.page_count(), 3);
+    assert_eq!(pages[2].index(), Some(2));
+    assert_eq!(wizard.current_page(), None);
+
+    let veto = Rc::new(Cell::new(true));
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let veto_ref = veto.clone();
+    let log_ref = log.clone();
+    let handler = full_bind_event_handler(&wizard.handle, move |evt, evt_data, _handle| {
+        match evt {
+            Event::OnWizardNavigate => {
+                let nav = evt_data.as_wizard_navigate().unwrap();
+                log_ref.borrow_mut().push((nav.action(), nav.page()));
+                if nav.action() == WizardAction::Next && nav.page() == Some(1) && veto_ref.get() {
+                    nav.allow(false);
+                }
+            },
+            _ => {}
+        }
+    });
+
+    let hwnd = wizard.handle.hwnd().unwrap();
+    let key = |k: i32| { wh::post_message(hwnd, WM_KEYDOWN, k as _, 1); };
+
+    // Next on the second page is vetoed and Escape cancels the wizard
+    key(VK_RETURN); key(VK_RETURN); key(VK_ESCAPE);
+    assert_eq!(wizard.run_modal(), DialogResult::Cancel);
+    assert_eq!(*log.borrow(), vec![
+        (WizardAction::Next, Some(0)),
+        (WizardAction::Next, Some(1)),
+        (WizardAction::Cancel, Some(1)),
+    ]);
+    assert_eq!(wizard.current_page(), Some(1));
+    assert!(pages[1].visible() && !pages[0].visible() && !wizard.visible());
+    assert!(app.window.enabled());
+
+    // The wizard restarts on the first page and Finish replaces Next on the last page
+    veto.set(false);
+    log.borrow_mut().clear();
+    key(VK_RETURN); key(VK_RETURN); key(VK_RETURN);
+    assert_eq!(wizard.run_modal(), DialogResult::Ok);
+    assert_eq!(*log.borrow(), vec![
+        (WizardAction::Next, Some(0)),
+        (WizardAction::Next, Some(1)),
+        (WizardAction::Finish, Some(2)),
+    ]);
+    assert!(pages[2].visible());
+
+    unbind_event_handler(&handler);
+
+    // Property sheet: the Apply button is enabled by the application
+    let mut sheet = Wizard::default();
+    Wizard::builder()
+        .style(WizardStyle::PropertySheet)
+        .parent(Some(&app.window))
+        .build(&mut sheet)
+        .expect("Failed to build property sheet");
+
+    let mut sheet_page = WizardPage::default();
+    WizardPage::builder().title("General").parent(&sheet).build(&mut sheet_page).expect("Failed to build wizard page");
+
+    assert_eq!(sheet.style(), WizardStyle::PropertySheet);
+    assert!(!sheet.button_enabled(WizardAction::Apply));
+    sheet.set_changed(true);
+    assert!(sheet.button_enabled(WizardAction::Apply));
+
+    wh::post_message(sheet.handle.hwnd().unwrap(), WM_KEYDOWN, VK_RETURN as _, 1);
+    assert_eq!(sheet.run_modal(), DialogResult::Ok);
+}
+
+/// Verifies that the virtual list view forwards the queries to its data source. The cache hints and the keyboard searches are sent by the test.
+#[cfg(feature = "list-view")]
+#[test]
+fn headless_virtual_list_view() {
+    use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+    use winapi::um::commctrl::{LVN_ODCACHEHINT, LVN_ODFINDITEMW, NMLVCACHEHINT, NMLVFINDITEMW, LVFI_STRING, LVFI_PARTIAL};
+    use crate::win32::window_helper as wh;
+    use std::cell::Cell;
+
+    struct Rows {
+        hint: Cell<(usize, usize)>,
+    }
+
+    impl ListViewDataSource for Rows {
+        fn len(&self) -> usize { 1_000_000 }
+        fn text(&self, row: usize, column: usize) -> String { format!("Row {} {}", row, column) }
+        fn image(&self, row: usize, _column: usize) -> Option<i32> { Some((row % 3) as i32) }
+        fn cache_hint(&self, from: usize, to: usize) { self.hint.set((from, to)); }
+        fn find_item(&self, text: &str, start: usize, partial: bool) -> Option<usize> {
+            match (partial, text.strip_prefix("Row ").and_then(|t| t.parse::<usize>().ok())) {
+                (false, Some(row)) if row >= start && row < self.len() => Some(row),
+                _ => None
+            }
+        }
+    }
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+    let rows = Rc::new(Rows { hint: Cell::new((0, 0)) });
+
+    let mut list = ListView::default();
+    ListView::builder()
+        .list_style(ListViewStyle::Detailed)
+        .data_source(rows.clone())
+        .parent(&app.window)
+        .build(&mut list)
+        .expect("Failed to build list view");
+
+    assert!(list.is_virtual());
+    assert_eq!(list.len(), 1_000_000);
+    assert!(list.has_item(999_999, 0) && !list.has_item(1_000_000, 0));
+
+    // The items are queried from the data source
+    let item = list.item(123_456, 0, 50).expect("Missing item");
+    assert_eq!(&item.text, "Row 123456 0");
+    assert!(!item.selected);
+
+    // The selection is tracked by index
+    list.select_item(10, true);
+    list.select_item(500_000, true);
+    assert_eq!(list.selected_items(), vec![10, 500_000]);
+    assert_eq!(list.selected_item(), Some(10));
+    assert!(list.item(500_000, 0, 50).unwrap().selected);
+
+    list.set_item_count(100);
+    assert_eq!(list.selected_items(), vec![10]);
+
+    // The items cannot be modified or sorted
+    list.insert_items_row(None, &["New"]);
+    list.update_item(5, "Changed");
+    assert!(!list.remove_item(5));
+    list.clear();
+    list.sort_items(|a, b| b.cmp(&a));
+    assert_eq!(list.len(), 100);
+    assert_eq!(&list.item(5, 0, 50).unwrap().text, "Row 5 0");
+
+    // Cache hints and keyboard search are forwarded to the data source
+    let list_hwnd = list.handle.hwnd().unwrap();
+    let window = app.window.handle.hwnd().unwrap();
+    let header = |code| NMHDR { hwndFrom: list_hwnd, idFrom: 0, code };
+
+    let mut hint: NMLVCACHEHINT = unsafe { std::mem::zeroed() };
+    hint.hdr = header(LVN_ODCACHEHINT);
+    hint.iFrom = 40;
+    hint.iTo = 80;
+    wh::send_message(window, WM_NOTIFY, 0, &mut hint as *mut NMLVCACHEHINT as _);
+    assert_eq!(rows.hint.get(), (40, 80));
+
+    let text = crate::win32::base_helper::to_utf16("Row 42");
+    let mut find: NMLVFINDITEMW = unsafe { std::mem::zeroed() };
+    find.hdr = header(LVN_ODFINDITEMW);
+    find.lvfi.flags = LVFI_STRING;
+    find.lvfi.psz = text.as_ptr();
+    assert_eq!(wh::send_message(window, WM_NOTIFY, 0, &mut find as *mut NMLVFINDITEMW as _), 42);
+
+    find.lvfi.flags = LVFI_PARTIAL;
+    assert_eq!(wh::send_message(window, WM_NOTIFY, 0, &mut find as *mut NMLVFINDITEMW as _), -1);
+}
+
+#[cfg(feature = "list-view")]
+#[test]
+fn headless_list_view_column_click() {
+    use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+    use winapi::um::commctrl::{LVN_COLUMNCLICK, NMLISTVIEW};
+    use crate::win32::window_helper as wh;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+
+    // The header is removed by default
+    let mut no_header = ListView::default();
+    ListView::builder()
+        .list_style(ListViewStyle::Detailed)
+        .parent(&app.window)
+        .build(&mut no_header)
+        .expect("Failed to build list view");
+
+    let no_header_hwnd = no_header.handle.hwnd().unwrap();
+    assert_eq!(wh::get_style(no_header_hwnd) & ListViewFlags::NO_HEADER.bits(), ListViewFlags::NO_HEADER.bits());
+
+    let mut list = ListView::default();
+    ListView::builder()
+        .list_style(ListViewStyle::Detailed)
+        .column_header(true)
+        .parent(&app.window)
+        .build(&mut list)
+        .expect("Failed to build list view");
+
+    let list_hwnd = list.handle.hwnd().unwrap();
+    assert_eq!(wh::get_style(list_hwnd) & ListViewFlags::NO_HEADER.bits(), 0);
+
+    let clicks = Rc::new(RefCell::new(Vec::new()));
+    let clicks_ref = clicks.clone();
+    let list_handle = list.handle;
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, evt_data, handle| {
+        if evt == Event::OnListViewColumnClick && handle == list_handle {
+            clicks_ref.borrow_mut().push(evt_data.as_list_view_column_index());
+        }
+    });
+
+    let mut notif: NMLISTVIEW = unsafe { std::mem::zeroed() };
+    notif.hdr = NMHDR { hwndFrom: list_hwnd, idFrom: 0, code: LVN_COLUMNCLICK };
+    notif.iItem = -1;
+    notif.iSubItem = 2;
+    wh::send_message(app.window.handle.hwnd().unwrap(), WM_NOTIFY, 0, &mut notif as *mut NMLISTVIEW as _);
+
+    assert_eq!(*clicks.borrow(), vec![Some(2)]);
+
+    // Without a header control, the sort arrows are ignored
+    list.set_column_sort_arrow(0, Some(ListViewColumnSortArrow::Ascending));
+    assert_eq!(list.column_sort_arrow(0), None);
+
+    unbind_event_handler(&handler);
+}
+
+/// Verifies the cell editors of `edit_item` and the edit events. The native label edit box of `edit_label` is not emulated.
+#[cfg(feature = "list-view")]
+#[test]
+fn headless_list_view_editing() {
+    use winapi::um::winuser::{WM_KEYDOWN, VK_RETURN, VK_ESCAPE};
+    use crate::win32::window_helper as wh;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+
+    let mut list = ListView::default();
+    ListView::builder()
+        .list_style(ListViewStyle::Detailed)
+        .flags(ListViewFlags::VISIBLE | ListViewFlags::EDIT_LABELS)
+        .parent(&app.window)
+        .build(&mut list)
+        .expect("Failed to build list view");
+
+    list.insert_column("Name");
+    list.insert_column("Price");
+    list.insert_items_row(None, &["Apple", "1.00"]);
+    list.insert_items_row(None, &["Pear", "2.00"]);
+
+    let edits = Rc::new(RefCell::new(Vec::new()));
+    let edits_ref = edits.clone();
+    let list_handle = list.handle;
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, evt_data, handle| {
+        if handle != list_handle {
+            return;
+        }
+
+        match evt {
+            Event::OnListViewBeginEdit => {
+                let edit = evt_data.as_list_view_edit().unwrap();
+                edit.allow(edit.row_index() != 0);
+            },
+            Event::OnListViewEndEdit => {
+                let edit = evt_data.as_list_view_edit().unwrap();
+                edits_ref.borrow_mut().push((edit.row_index(), edit.column_index(), edit.text().map(|t| t.to_string())));
+                if edit.text() == Some("bad") {
+                    edit.set_text("0.00");
+                }
+            },
+            _ => {}
+        }
+    });
+
+    let commit = |text: &str, key: i32| {
+        let editor = unsafe { crate::win32::backend::GetFocus() };
+        assert_ne!(Some(editor), list.handle.hwnd());
+        unsafe { wh::set_window_text(editor, text); }
+        wh::post_message(editor, WM_KEYDOWN, key as _, 0);
+        dispatch_thread_events();
+    };
+
+    // The first row cannot be edited
+    assert!(!list.edit_item(0, 1, ListViewEditor::TextInput));
+    assert_eq!(list.editing(), None);
+
+    // Enter saves the text
+    assert!(list.edit_item(1, 1, ListViewEditor::TextInput));
+    assert_eq!(list.editing(), Some((1, 1)));
+    commit("2.50", VK_RETURN);
+    assert_eq!(list.editing(), None);
+    assert_eq!(list.item(1, 1, 10).map(|i| i.text), Some("2.50".to_string()));
+
+    // Escape cancels the edit
+    assert!(list.edit_item(1, 1, ListViewEditor::TextInput));
+    commit("3.00", VK_ESCAPE);
+    assert_eq!(list.item(1, 1, 10).map(|i| i.text), Some("2.50".to_string()));
+
+    // The text can be replaced
+    assert!(list.edit_item(1, 0, ListViewEditor::TextInput));
+    commit("bad", VK_RETURN);
+    assert_eq!(list.item(1, 0, 10).map(|i| i.text), Some("0.00".to_string()));
+
+    // Ending the edit from code
+    assert!(list.edit_item(1, 1, ListViewEditor::TextInput));
+    list.end_edit(true);
+    assert_eq!(list.editing(), None);
+
+    assert_eq!(*edits.borrow(), vec![
+        (1, 1, Some("2.50".to_string())),
+        (1, 1, None),
+        (1, 0, Some("bad".to_string())),
+        (1, 1, Some("2.50".to_string())),
+    ]);
+
+    unbind_event_handler(&handler);
+}
+
+/// Verifies the check box, group and color wrappers. The custom draw notifications are sent by the test, the groups are never drawn.
+#[cfg(feature = "list-view")]
+#[test]
+fn headless_list_view_checks_groups_and_colors() {
+    use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+    use winapi::um::commctrl::{NM_CUSTOMDRAW, NMLVCUSTOMDRAW, CDDS_PREPAINT, CDDS_ITEMPREPAINT, CDDS_SUBITEM, CDRF_NOTIFYITEMDRAW,
+        CDRF_NOTIFYSUBITEMDRAW, CDRF_NEWFONT, CLR_DEFAULT, LVN_GETINFOTIPW, NMLVGETINFOTIPW, LVM_GETITEMW, LVITEMW, LVIF_PARAM};
+    use winapi::um::wingdi::RGB;
+    use winapi::shared::minwindef::LRESULT;
+    use crate::win32::window_helper as wh;
+
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+
+    let mut list = ListView::default();
+    ListView::builder()
+        .list_style(ListViewStyle::Detailed)
+        .ex_flags(ListViewExFlags::CHECK_BOXES | ListViewExFlags::INFO_TIP)
+        .parent(&app.window)
+        .build(&mut list)
+        .expect("Failed to build list view");
+
+    list.insert_column("Server");
+    list.insert_column("Status");
+    list.insert_items_row(None, &["A", "Running"]);
+    list.insert_items_row(None, &["B", "Stopped"]);
+    list.insert_items_row(None, &["C", "Running"]);
+
+    let checks = Rc::new(RefCell::new(Vec::new()));
+    let checks_ref = checks.clone();
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let changes_ref = changes.clone();
+    let list_handle = list.handle;
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, evt_data, handle| {
+        if handle != list_handle {
+            return;
+        }
+
+        match evt {
+            Event::OnListViewItemChanged => changes_ref.borrow_mut().push(evt_data.as_list_view_item_changed().unwrap().0),
+            Event::OnListViewItemChecked => checks_ref.borrow_mut().push(evt_data.as_list_view_item_checked().unwrap()),
+            Event::OnListViewInfoTip => {
+                let tip = evt_data.as_list_view_info_tip().unwrap();
+                if tip.row_index() == 1 {
+                    tip.set_text("Stopped since monday");
+                }
+            },
+            _ => {}
+        }
+    });
+
+    // Check boxes
+    assert_eq!(list.checked_items(), Vec::<usize>::new());
+    list.check_item(1, true);
+    assert!(list.item_checked(1));
+    assert_eq!(list.checked_items(), vec![1]);
+    list.check_item(1, false);
+    list.check_item(5, true);
+    assert_eq!(*checks.borrow(), vec![(1, true), (1, false)]);
+
+    // The check box toggles are also reported as item changes
+    assert_eq!(*changes.borrow(), vec![1, 1]);
+
+    // Groups
+    list.set_groups_enabled(true);
+    assert!(list.groups_enabled());
+    list.insert_group(InsertListViewGroup { id: 10, header: "Running".into(), collapsible: true, ..Default::default() });
+    list.insert_group(InsertListViewGroup { id: 20, header: "Stopped".into(), ..Default::default() });
+    list.insert_group(InsertListViewGroup { id: 20, header: "Duplicate".into(), ..Default::default() });
+    assert_eq!(list.group_len(), 2);
+    assert!(list.has_group(20));
+
+    list.set_item_group(0, 10);
+    list.set_item_group(1, 20);
+    assert_eq!(list.item_group(0), Some(10));
+    assert_eq!(list.item_group(2), None);
+
+    assert!(!list.group_collapsed(10));
+    list.set_group_collapsed(10, true);
+    assert!(list.group_collapsed(10));
+
+    list.remove_group(20);
+    assert!(!list.has_group(20));
+    assert_eq!(list.item_group(1), None);
+
+    // Colors follow the items when they are sorted
+    let red = ListViewItemColors { text: Some([255, 0, 0]), background: None };
+    let blue = ListViewItemColors { text: Some([0, 0, 255]), background: Some([230, 230, 255]) };
+    list.set_item_colors(0, None, Some(red));
+    list.set_item_colors(2, Some(1), Some(blue));
+    list.sort_items(|a, b| b.cmp(&a));
+
+    assert_eq!(list.item_colors(2, None), Some(red));
+    assert_eq!(list.item_colors(0, Some(1)), Some(blue));
+    assert_eq!(list.item_colors(0, None), None);
+    assert_eq!(list.item_colors(1, None), None);
+
+    let window = app.window.handle.hwnd().unwrap();
+    let list_hwnd = list.handle.hwnd().unwrap();
+    let item_param = |row: usize| {
+        let mut item: LVITEMW = unsafe { std::mem::zeroed() };
+        item.mask = LVIF_PARAM;
+        item.iItem = row as _;
+        wh::send_message(list_hwnd, LVM_GETITEMW, 0, &mut item as *mut LVITEMW as _);
+        item.lParam
+    };
+
+    let draw = |stage: u32, row: usize, column: usize| {
+        let mut custom: NMLVCUSTOMDRAW = unsafe { std::mem::zeroed() };
+        custom.nmcd.hdr = NMHDR { hwndFrom: list_hwnd, idFrom: 0, code: NM_CUSTOMDRAW };
+        custom.nmcd.dwDrawStage = stage;
+        custom.nmcd.dwItemSpec = row;
+        custom.nmcd.lItemlParam = item_param(row);
+        custom.iSubItem = column as _;
+        let result = wh::send_message(window, WM_NOTIFY, 0, &mut custom as *mut NMLVCUSTOMDRAW as _);
+        (result, custom.clrText, custom.clrTextBk)
+    };
+
+    assert_eq!(draw(CDDS_PREPAINT, 0, 0).0, CDRF_NOTIFYITEMDRAW as LRESULT);
+    assert_eq!(draw(CDDS_ITEMPREPAINT, 2, 0), (CDRF_NEWFONT as LRESULT, RGB(255, 0, 0), CLR_DEFAULT));
+    assert_eq!(draw(CDDS_ITEMPREPAINT, 0, 0).0, CDRF_NOTIFYSUBITEMDRAW as LRESULT);
+    assert_eq!(draw(CDDS_ITEMPREPAINT | CDDS_SUBITEM, 0, 0), (CDRF_NEWFONT as LRESULT, CLR_DEFAULT, CLR_DEFAULT));
+    assert_eq!(draw(CDDS_ITEMPREPAINT | CDDS_SUBITEM, 0, 1), (CDRF_NEWFONT as LRESULT, RGB(0, 0, 255), RGB(230, 230, 255)));
+
+    // Info tips
+    let info_tip = |row: usize| {
+        let mut buffer = [0u16; 80];
+        let mut tip: NMLVGETINFOTIPW = unsafe { std::mem::zeroed() };
+        tip.hdr = NMHDR { hwndFrom: list_hwnd, idFrom: 0, code: LVN_GETINFOTIPW };
+        tip.pszText = buffer.as_mut_ptr();
+        tip.cchTextMax = buffer.len() as _;
+        tip.iItem = row as _;
+        wh::send_message(window, WM_NOTIFY, 0, &mut tip as *mut NMLVGETINFOTIPW as _);
+
+        let end = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        String::from_utf16_lossy(&buffer[..end])
+    };
+
+    assert_eq!(info_tip(1), "Stopped since monday");
+    assert_eq!(info_tip(0), "");
+
+    // The colors are removed with the items
+    list.remove_item(0);
+    assert_eq!(list.item_colors(0, Some(1)), None);
+    assert_eq!(list.item_colors(1, None), Some(red));
+
+    unbind_event_handler(&handler);
+}
+
+/// Verifies the item data and the lazy children. `TVN_GETDISPINFOW` is sent by the emulated tree view like comctl32 documents it.
+#[cfg(feature = "tree-view")]
+#[test]
+fn headless_tree_view_item_data() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let app = build_app();
+
+    let mut tree = TreeView::default();
+    TreeView::builder()
+        .parent(&app.window)
+        .build(&mut tree)
+        .expect("Failed to build tree view");
+
+    let folder = Rc::new(String::from("C:\\data"));
+    let root = tree.insert_item_with_data("data", None, TreeInsert::Root, folder.clone());
+    let locked = tree.insert_item_with_data("locked", None, TreeInsert::Root, 42u32);
+    let empty = tree.insert_item("empty", None, TreeInsert::Root);
+
+    // Values are typed
+    assert_eq!(tree.item_data::<Rc<String>>(&root).map(|p| (*p).clone()), Some(folder.clone()));
+    assert_eq!(tree.item_data::<u32>(&locked).map(|v| *v), Some(42));
+    assert!(tree.item_data::<String>(&locked).is_none());
+    assert!(tree.item_data::<u32>(&empty).is_none());
+
+    tree.set_item_data(&locked, 7u32);
+    assert_eq!(tree.item_data::<u32>(&locked).map(|v| *v), Some(7));
+    tree.remove_item_data(&locked);
+    assert!(tree.item_data::<u32>(&locked).is_none());
+
+    // Lazy children
+    for item in [&root, &locked, &empty].iter() {
+        tree.set_lazy_children(item, true);
+        assert_eq!(tree.item_has_children(item), Some(true));
+    }
+
+    let expanding = Rc::new(RefCell::new(Vec::new()));
+    let expanding_ref = expanding.clone();
+    let tree_handle = tree.handle;
+    let root_handle = root.handle;
+    let locked_handle = locked.handle;
+    let handler = full_bind_event_handler(&app.window.handle, move |evt, evt_data, handle| {
+        if handle != tree_handle || evt != Event::OnTreeItemExpanding {
+            return;
+        }
+
+        let data = evt_data.as_tree_item_expanding().unwrap();
+        expanding_ref.borrow_mut().push((data.item().handle, data.action()));
+        if data.item().handle == locked_handle {
+            data.allow(false);
+        }
+    });
+
+    tree.set_expand_state(&root, ExpandState::Expand);
+    tree.set_expand_state(&locked, ExpandState::Expand);
+    tree.set_expand_state(&empty, ExpandState::Expand);
+    assert_eq!(expanding.borrow().len(), 3);
+    assert_eq!(expanding.borrow()[0], (root_handle, ExpandState::Expand));
+    assert_eq!(expanding.borrow()[1], (locked_handle, ExpandState::Expand));
+
+    assert!(tree.item_state(&root).unwrap().contains(TreeItemState::EXPANDED));
+    assert!(!tree.item_state(&locked).unwrap().contains(TreeItemState::EXPANDED));
+    assert_eq!(tree.item_has_children(&locked), Some(true));
+    assert_eq!(tree.item_has_children(&empty), Some(false));
+
+    let child = tree.insert_item_with_data("child", Some(&root), TreeInsert::Last, folder.clone());
+    assert_eq!(tree.item_has_children(&root), Some(true));
+    assert_eq!(Rc::strong_count(&folder), 3);
+
+    // Removing an item drops the values of the item and its children
+    tree.remove_item(&root);
+    assert_eq!(Rc::strong_count(&folder), 1);
+    assert!(tree.item_data::<Rc<String>>(&child).is_none());
+
+    unbind_event_handler(&handler);
+}
@@ -0,0 +1,52 @@
+/*!
+    Tests for the key combinations parser. Those tests do not use any window.
+*/
+use crate::{Key, KeyCombo, Modifiers, NwgError, keys};
+
+
+#[test]
+fn parse_key_combos() {
+    let combo: KeyCombo = "Ctrl+Shift+S".parse().unwrap();
+    assert_eq!(combo, KeyCombo::new(Modifiers::CONTROL | Modifiers::SHIFT, Key::S));
+
+    let combo: KeyCombo = " alt + f4 ".parse().unwrap();
+    assert_eq!(combo, KeyCombo::new(Modifiers::ALT, Key::F4));
+
+    let combo: KeyCombo = "Win+Del".parse().unwrap();
+    assert_eq!(combo, KeyCombo::new(Modifiers::WIN, Key::Delete));
+
+    let combo: KeyCombo = "Ctrl++".parse().unwrap();
+    assert_eq!(combo, KeyCombo::new(Modifiers::CONTROL, Key::Plus));
+
+    let combo: KeyCombo = "Escape".parse().unwrap();
+    assert_eq!(combo, KeyCombo::new(Modifiers::NONE, Key::Escape));
+
+    match "Ctrl+Hyper+S".parse::<KeyCombo>() {
+        Err(NwgError::BadKeyCombo(_)) => {},
+        r => panic!("Expected BadKeyCombo, got {:?}", r)
+    }
+
+    assert!("Ctrl+".parse::<KeyCombo>().is_err());
+    assert!("".parse::<KeyCombo>().is_err());
+}
+
+#[test]
+fn display_key_combos() {
+    let combo = KeyCombo::new(Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT, Key::Num1);
+    assert_eq!(combo.to_string(), "Ctrl+Shift+Alt+1");
+
+    for text in ["Ctrl+S", "Shift+F10", "Ctrl+Alt+Delete", "Win+Left", "Ctrl+Plus"].iter() {
+        assert_eq!(text.parse::<KeyCombo>().unwrap().to_string(), *text);
+    }
+
+    assert_eq!(Key::Other(0xFF).to_string(), "0xFF");
+}
+
+#[test]
+fn virtual_key_codes() {
+    assert_eq!(Key::from(keys::_A), Key::A);
+    assert_eq!(Key::from(keys::RETURN), Key::Return);
+    assert_eq!(Key::from(keys::OEM_8), Key::Other(keys::OEM_8));
+    assert_eq!(u32::from(Key::F12), keys::F12);
+    assert_eq!(Key::Other(0xFF).code(), 0xFF);
+}
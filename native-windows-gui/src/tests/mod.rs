@@ -1,18 +1,40 @@
+// Only the solver tests are platform independent. The other tests need the `all` feature and a Windows target.
+mod grid_layout_solver_test;
+
+#[cfg(all(windows, feature = "all"))]
 use crate::*;
+#[cfg(all(windows, feature = "all"))]
 use std::cell::RefCell;
 
+#[cfg(all(windows, feature = "all"))]
 mod control_test;
+#[cfg(all(windows, feature = "all"))]
 use control_test::*;
 
+#[cfg(all(windows, feature = "all"))]
 mod thread_test;
+#[cfg(all(windows, feature = "all"))]
 use thread_test::*;
 
+#[cfg(all(windows, feature = "all"))]
 mod freeing_test;
+#[cfg(all(windows, feature = "all"))]
 use freeing_test::*;
 
+#[cfg(all(windows, feature = "all"))]
 mod other;
 
+#[cfg(all(windows, feature = "all"))]
+mod key_combo_test;
+
+#[cfg(all(windows, feature = "all"))]
+mod task_dialog_test;
+
+#[cfg(all(windows, feature = "all", feature = "headless"))]
+mod headless_test;
+
 
+#[cfg(all(windows, feature = "all"))]
 #[derive(Default)]
 pub struct TestControlPanel {
     window: Window,
@@ -28,6 +50,7 @@ pub struct TestControlPanel {
     freeing_tests: FreeingTest,
 }
 
+#[cfg(all(windows, feature = "all"))]
 mod test_control_panel_ui {
     use super::*;
     use crate::{NativeUi, NwgError};
@@ -156,22 +179,26 @@ mod test_control_panel_ui {
 
 }
 
+#[cfg(all(windows, feature = "all"))]
 fn show_control_test(app: &TestControlPanel) {
     app.controls_tests.window.set_visible(true);
     app.controls_tests.panel.set_visible(true);
     app.controls_tests.window.set_focus();
 }
 
+#[cfg(all(windows, feature = "all"))]
 fn show_thread_test(app: &TestControlPanel) {
     app.thread_tests.window.set_visible(true);
     app.thread_tests.window.set_focus();
 }
 
+#[cfg(all(windows, feature = "all"))]
 fn show_freeing_test(app: &TestControlPanel) {
     app.freeing_tests.window.set_visible(true);
     app.freeing_tests.window.set_focus();
 }
 
+#[cfg(all(windows, feature = "all"))]
 fn show(app: &TestControlPanel) {
     let text = "Hello World from Native windows GUI!";
     Clipboard::set_data_text(&app.window, text);
@@ -180,11 +207,14 @@ fn show(app: &TestControlPanel) {
     app.window.set_visible(true);
 }
 
+#[cfg(all(windows, feature = "all"))]
 fn close() {
     stop_thread_dispatch();
 }
 
+#[cfg(all(windows, feature = "all"))]
 #[test]
+#[cfg(not(feature = "headless"))]
 fn everything() {
     #[cfg(feature = "high-dpi")]
     {
@@ -95,6 +95,7 @@ fn test_stuff(_t: &OtherTests) {
 
 /// Just some scaffolding if I ever want to add new specific tests
 #[test]
+#[cfg(not(feature = "headless"))]
 #[allow(unused)]
 fn other_tests() {
     init().expect("Failed to init Native Windows GUI");
@@ -0,0 +1,54 @@
+/*!
+    Tests for the task dialog builder. Those tests do not display any dialog.
+*/
+use crate::{TaskDialog, TaskDialogButton, TaskDialogButtons, NwgError};
+
+
+#[test]
+fn task_dialog_button_ids() {
+    let buttons = [
+        TaskDialogButton::Ok,
+        TaskDialogButton::Cancel,
+        TaskDialogButton::Retry,
+        TaskDialogButton::Yes,
+        TaskDialogButton::No,
+        TaskDialogButton::Close,
+        TaskDialogButton::Custom(100),
+    ];
+
+    for &button in buttons.iter() {
+        assert_eq!(TaskDialogButton::from_id(button.id()), button);
+    }
+}
+
+#[test]
+fn task_dialog_builder() {
+    let mut dialog = TaskDialog::default();
+    TaskDialog::builder()
+        .title("Test")
+        .instruction("Save the changes?")
+        .button(100, "Save")
+        .button(101, "Discard")
+        .common_buttons(TaskDialogButtons::CANCEL)
+        .radio(200, "Now")
+        .radio(201, "Later")
+        .default_radio(201)
+        .build(&mut dialog)
+        .expect("Failed to build the task dialog");
+
+    let reserved = TaskDialog::builder()
+        .button(TaskDialogButton::Yes.id(), "Yes")
+        .build(&mut dialog);
+
+    match reserved {
+        Err(NwgError::ResourceCreationError(_)) => {},
+        r => panic!("Expected ResourceCreationError, got {:?}", r)
+    }
+
+    let missing_radio = TaskDialog::builder()
+        .radio(200, "Now")
+        .default_radio(300)
+        .build(&mut dialog);
+
+    assert!(missing_radio.is_err());
+}
@@ -0,0 +1,96 @@
+/*!
+    Keyboard accelerators tables. The tables are registered per thread and translated by the dispatch loops
+    (`dispatch_thread_events` and `dispatch_thread_events_with_callback`) before the dialog messages.
+*/
+use winapi::shared::windef::{HWND, HMENU, HACCEL};
+use winapi::um::winuser::{ACCEL, MSG, FVIRTKEY, FSHIFT, FCONTROL, FALT, GA_ROOT};
+use winapi::ctypes::c_int;
+use super::backend::{CreateAcceleratorTableW, DestroyAcceleratorTable, TranslateAcceleratorW, GetAncestor};
+use super::base_helper::CUSTOM_ID_BEGIN;
+use crate::{KeyCombo, Modifiers, NwgError};
+use std::cell::RefCell;
+
+
+/// An accelerator table created by an `Accelerators` resource
+struct RegisteredTable {
+    window: HWND,
+    table: HACCEL,
+
+    /// The parent menu of the menu items triggered by the table
+    menu_items: Vec<(u16, HMENU)>,
+}
+
+thread_local! {
+    static TABLES: RefCell<Vec<RegisteredTable>> = RefCell::new(Vec::new());
+}
+
+
+/**
+    Create an accelerator table and register it for `window`. `entries` are the key combinations and their command id.
+    Command ids over `CUSTOM_ID_BEGIN` must be menu items listed in `menu_items`.
+*/
+pub unsafe fn build_accelerators(window: HWND, entries: &[(KeyCombo, u16)], menu_items: Vec<(u16, HMENU)>) -> Result<HACCEL, NwgError> {
+    let mut accels: Vec<ACCEL> = Vec::with_capacity(entries.len());
+    for &(combo, cmd) in entries.iter() {
+        if combo.modifiers.contains(Modifiers::WIN) {
+            return Err(NwgError::bad_key_combo(format!("Accelerators do not support the Win modifier ({})", combo)));
+        }
+
+        let mut flags = FVIRTKEY;
+        if combo.modifiers.contains(Modifiers::SHIFT) { flags |= FSHIFT; }
+        if combo.modifiers.contains(Modifiers::CONTROL) { flags |= FCONTROL; }
+        if combo.modifiers.contains(Modifiers::ALT) { flags |= FALT; }
+
+        accels.push(ACCEL { fVirt: flags, key: combo.key.code() as u16, cmd });
+    }
+
+    let table = CreateAcceleratorTableW(accels.as_mut_ptr(), accels.len() as c_int);
+    if table.is_null() {
+        return Err(NwgError::resource_create("Failed to create the accelerator table").with_last_error("CreateAcceleratorTableW"));
+    }
+
+    TABLES.with(|tables| tables.borrow_mut().push(RegisteredTable { window, table, menu_items }));
+
+    Ok(table)
+}
+
+/// Unregister and free an accelerator table created with `build_accelerators`
+pub unsafe fn destroy_accelerators(table: HACCEL) {
+    TABLES.with(|tables| tables.borrow_mut().retain(|t| t.table != table));
+    DestroyAcceleratorTable(table);
+}
+
+/**
+    Translate a key message into an accelerator command if one of the tables of its top level window matches.
+    Returns true if the message was translated, in which case it must not be dispatched.
+*/
+pub unsafe fn translate_accelerators(msg: &mut MSG) -> bool {
+    if msg.hwnd.is_null() {
+        return false;
+    }
+
+    // The tables are copied because the command is sent right away and the callback may create or free accelerators
+    let root = GetAncestor(msg.hwnd, GA_ROOT);
+    let tables: Vec<(HWND, HACCEL)> = TABLES.with(|tables| {
+        tables.borrow().iter()
+            .filter(|t| t.window == root)
+            .map(|t| (t.window, t.table))
+            .collect()
+    });
+
+    tables.into_iter().any(|(window, table)| TranslateAcceleratorW(window, table, msg) != 0)
+}
+
+/// Return the parent menu of the menu item `id` triggered by an accelerator
+pub fn accelerator_menu_item(id: u16) -> Option<HMENU> {
+    if (id as u32) < CUSTOM_ID_BEGIN {
+        return None;
+    }
+
+    TABLES.with(|tables| {
+        tables.borrow().iter()
+            .flat_map(|t| t.menu_items.iter())
+            .find(|(item_id, _)| *item_id == id)
+            .map(|(_, parent)| *parent)
+    })
+}
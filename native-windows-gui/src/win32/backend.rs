@@ -0,0 +1,59 @@
+/*!
+    Selects the implementation of the window management functions used by the NWG windowing base.
+
+    By default, everything is forwarded to user32/comctl32. With the `headless` feature, the same functions
+    are implemented by an in-memory window manager (see `win32::headless`).
+
+    Only the functions used by `window_helper`, `window` and the dispatch loops should be imported from here.
+    Control specific messages still go through `send_message`, so they reach the backend as well.
+*/
+
+#[cfg(not(feature = "headless"))]
+pub use winapi::um::winuser::{
+    CreateWindowExW, DestroyWindow, DefWindowProcW, RegisterClassExW, LoadCursorW, AdjustWindowRectEx,
+    SetWindowTextW, GetWindowTextW, GetWindowTextLengthW, GetClassNameW,
+    SetWindowPos, GetWindowRect, GetClientRect, ScreenToClient, ShowWindow, IsWindowVisible, IsWindow,
+    GetParent, SetParent, GetAncestor, EnumChildWindows, SetFocus, GetFocus, InvalidateRect, UpdateWindow,
+    SendMessageW, PostMessageW, SendNotifyMessageW, SetTimer, KillTimer,
+    GetMessageW, PeekMessageW, TranslateMessage, DispatchMessageW, IsDialogMessageW,
+    SetCapture, ReleaseCapture, SetCursor, SetScrollInfo, GetScrollInfo, GetKeyState,
+    GetCursorPos, TrackMouseEvent,
+    CreateAcceleratorTableW, DestroyAcceleratorTable, TranslateAcceleratorW, RegisterHotKey, UnregisterHotKey,
+};
+
+#[cfg(all(not(feature = "headless"), target_arch = "x86_64"))]
+pub use winapi::um::winuser::{GetWindowLongPtrW, SetWindowLongPtrW};
+
+#[cfg(all(not(feature = "headless"), target_arch = "x86"))]
+pub use winapi::um::winuser::{GetWindowLongW, SetWindowLongW};
+
+#[cfg(not(feature = "headless"))]
+pub use winapi::um::commctrl::{SetWindowSubclass, GetWindowSubclass, RemoveWindowSubclass, DefSubclassProc};
+
+#[cfg(not(feature = "headless"))]
+pub use winapi::um::libloaderapi::GetModuleHandleW;
+
+#[cfg(not(feature = "headless"))]
+pub use winapi::um::errhandlingapi::GetLastError;
+
+
+#[cfg(feature = "headless")]
+pub use super::headless::{
+    CreateWindowExW, DestroyWindow, DefWindowProcW, RegisterClassExW, LoadCursorW, AdjustWindowRectEx,
+    SetWindowTextW, GetWindowTextW, GetWindowTextLengthW, GetClassNameW,
+    SetWindowPos, GetWindowRect, GetClientRect, ScreenToClient, ShowWindow, IsWindowVisible, IsWindow,
+    GetParent, SetParent, GetAncestor, EnumChildWindows, SetFocus, GetFocus, InvalidateRect, UpdateWindow,
+    SendMessageW, PostMessageW, SendNotifyMessageW, SetTimer, KillTimer,
+    GetMessageW, PeekMessageW, TranslateMessage, DispatchMessageW, IsDialogMessageW,
+    SetCapture, ReleaseCapture, SetCursor, SetScrollInfo, GetScrollInfo, GetKeyState,
+    GetCursorPos, TrackMouseEvent,
+    CreateAcceleratorTableW, DestroyAcceleratorTable, TranslateAcceleratorW, RegisterHotKey, UnregisterHotKey,
+    SetWindowSubclass, GetWindowSubclass, RemoveWindowSubclass, DefSubclassProc,
+    GetModuleHandleW, GetLastError,
+};
+
+#[cfg(all(feature = "headless", target_arch = "x86_64"))]
+pub use super::headless::{GetWindowLongPtrW, SetWindowLongPtrW};
+
+#[cfg(all(feature = "headless", target_arch = "x86"))]
+pub use super::headless::{GetWindowLongW, SetWindowLongW};
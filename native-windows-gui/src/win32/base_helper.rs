@@ -1,30 +1,37 @@
 use std::ptr;
 use winapi::shared::windef::HWND;
 use winapi::shared::minwindef::DWORD;
-use crate::ControlHandle;
+use crate::{ControlHandle, NwgError};
 
 pub const CUSTOM_ID_BEGIN: u32 = 10000;
 
 
 pub fn check_hwnd(handle: &ControlHandle, not_bound: &str, bad_handle: &str) -> HWND {
-    use winapi::um::winuser::IsWindow;
+    match try_check_hwnd(handle, not_bound, bad_handle) {
+        Ok(hwnd) => hwnd,
+        Err(e) => panic!("{}", e)
+    }
+}
+
+/**
+    Same as `check_hwnd`, but returns an error instead of panicking.
+    `ControlNotBound` if the control was not built, `InvalidHandle` if the handle is not a HWND or if the window was destroyed.
+*/
+pub fn try_check_hwnd(handle: &ControlHandle, not_bound: &str, bad_handle: &str) -> Result<HWND, NwgError> {
+    use super::backend::IsWindow;
 
-    if handle.blank() { panic!("{}", not_bound); }
+    if handle.blank() { return Err(NwgError::not_bound(not_bound)); }
     match handle.hwnd() {
         Some(hwnd) => match unsafe { IsWindow(hwnd) } {
-            0 => { panic!("The window handle is no longer valid. This usually means the control was freed by the OS"); },
-            _ => hwnd
+            0 => Err(NwgError::invalid_handle("The window handle is no longer valid. This usually means the control was freed by the OS")),
+            _ => Ok(hwnd)
         },
-        None => { panic!("{}", bad_handle); }
+        None => Err(NwgError::invalid_handle(bad_handle))
     }
 }
 
 pub fn to_utf16<'a>(s: &'a str) -> Vec<u16> {
-    use std::ffi::OsStr;
-    use std::os::windows::ffi::OsStrExt;
-
-    OsStr::new(s)
-      .encode_wide()
+    s.encode_utf16()
       .chain(Some(0u16).into_iter())
       .collect()
 }
@@ -33,19 +40,14 @@ pub fn to_utf16<'a>(s: &'a str) -> Vec<u16> {
     Decode a raw utf16 string. Should be null terminated.
 */
 pub fn from_utf16(s: &[u16]) -> String {
-    use std::ffi::OsString;
-    use std::os::windows::ffi::OsStringExt;
-
     let null_index = s.iter().position(|&i| i==0).unwrap_or(s.len());
-    let os_string = OsString::from_wide(&s[0..null_index]);
-
-    os_string.into_string().unwrap_or("Decoding error".to_string())
+    String::from_utf16(&s[0..null_index]).unwrap_or("Decoding error".to_string())
 }
 
 /**
     Read a string from a wide char pointer. Undefined behaviour if [ptr] is not null terminated.
 */
-#[cfg(any(feature = "file-dialog", feature = "winnls"))]
+#[cfg(any(feature = "file-dialog", feature = "winnls", feature = "task-dialog", feature = "list-view"))]
 pub unsafe fn from_wide_ptr(ptr: *mut u16, length: Option<usize>) -> String {
     use std::slice::from_raw_parts;
 
@@ -72,7 +74,7 @@ pub unsafe fn from_wide_ptr(ptr: *mut u16, length: Option<usize>) -> String {
 */
 #[allow(unused)]
 pub unsafe fn get_system_error() -> (DWORD, String) { 
-    use winapi::um::errhandlingapi::GetLastError;
+    use super::backend::GetLastError;
     use winapi::um::winbase::{FormatMessageW, FORMAT_MESSAGE_FROM_SYSTEM};
     use winapi::um::winnt::{MAKELANGID, LANG_NEUTRAL, SUBLANG_DEFAULT};
     use std::ffi::OsString;
@@ -0,0 +1,92 @@
+/*!
+    Programmatic event injection. Used to drive a built UI from code in automated tests.
+
+    Injected events go through the same event handlers that were bound with `full_bind_event_handler`
+    or `bind_event_handler`. The window system is never involved, so the controls state is not modified
+    by the injection itself, only by what the event handlers do.
+*/
+use winapi::shared::minwindef::LPARAM;
+use winapi::shared::windef::HWND;
+use super::window_helper::{self as wh, NWG_INJECT_EVENT};
+use crate::controls::ControlHandle;
+use crate::{Event, EventData, WindowCloseData};
+
+
+/**
+    The event sent to `process_events` with the `NWG_INJECT_EVENT` message.
+
+    Each bound event handler takes ownership of its event data, so the data is built once per handler.
+*/
+pub(crate) struct InjectedEvent<'a> {
+    pub event: Event,
+    pub data: &'a dyn Fn() -> EventData,
+    pub handle: ControlHandle,
+}
+
+/**
+    Raise an event for a control. Every event handler bound to the control window receives the event with
+    `handle` as the source control. `data` is called once for every handler that receives the event.
+
+    For window-like controls, the event is sent to the control itself. For controls that live in a window (notice, timer, tray),
+    the event is sent to their parent window.
+
+    This function will panic if the control is not bound or if its handle is a menu. Use `inject_event_to` for menus.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    fn type_text(input: &nwg::TextInput) {
+        input.set_text("Hello");
+        nwg::inject_event(&input.handle, nwg::Event::OnTextInput, || nwg::EventData::NoData);
+    }
+    ```
+*/
+pub fn inject_event<F>(handle: &ControlHandle, event: Event, data: F)
+    where F: Fn() -> EventData
+{
+    let hwnd = match handle {
+        &ControlHandle::Hwnd(h) => h,
+        &ControlHandle::Notice(h, _) => h,
+        &ControlHandle::Timer(h, _) => h,
+        &ControlHandle::SystemTray(h) => h,
+        &ControlHandle::Hotkey(h, _) => h,
+        &ControlHandle::NoHandle => panic!("Cannot inject events on a control that is not bound"),
+        htype => panic!("Cannot find the window of a control with an handle of type {:?}. Use `inject_event_to`.", htype)
+    };
+
+    inject(hwnd, *handle, event, &data);
+}
+
+/**
+    Raise an event for `handle` in the event handlers bound to `window`.
+    Used when the source control does not have a window, like a menu item.
+
+    This function will panic if `window` is not a window handle.
+*/
+pub fn inject_event_to<F>(window: &ControlHandle, handle: ControlHandle, event: Event, data: F)
+    where F: Fn() -> EventData
+{
+    let hwnd = window.hwnd().expect("Cannot inject events in a control that is not a window");
+    inject(hwnd, handle, event, &data);
+}
+
+/**
+    Raise a `OnWindowClose` event for a window. Returns `true` if the window would close after the event
+    or `false` if one of the event handlers cancelled it with `WindowCloseData::close(false)`.
+
+    The window is not closed by this function.
+*/
+pub fn inject_window_close(window: &ControlHandle) -> bool {
+    let hwnd = window.hwnd().expect("Cannot inject events in a control that is not a window");
+
+    let mut should_exit = true;
+    let should_exit_ptr = &mut should_exit as *mut bool;
+    inject(hwnd, *window, Event::OnWindowClose, &|| EventData::OnWindowClose(WindowCloseData { data: should_exit_ptr }));
+
+    should_exit
+}
+
+fn inject(hwnd: HWND, handle: ControlHandle, event: Event, data: &dyn Fn() -> EventData) {
+    let injected = InjectedEvent { event, data, handle };
+    wh::send_message(hwnd, NWG_INJECT_EVENT, 0, &injected as *const InjectedEvent as LPARAM);
+}
@@ -0,0 +1,438 @@
+/*!
+    A single threaded executor running futures on the GUI thread.
+
+    Each GUI thread lazily creates a message only window with a notice the first time `spawn_local` is called.
+    Waking a task queues its id and posts the notice message to the window. The tasks are then polled when
+    the message is dispatched, so they run from `dispatch_thread_events`, `dispatch_thread_events_with_callback`
+    or any modal loop (message boxes, dialogs) that dispatches the thread messages.
+
+    Because tasks are polled from the message loop, a future can freely access the controls of its thread.
+    Long blocking work should be moved to another thread with `spawn_background` and awaited.
+
+```rust
+use native_windows_gui as nwg;
+use std::rc::Rc;
+
+fn load(label: Rc<nwg::Label>) -> Result<(), nwg::NwgError> {
+    nwg::spawn_local(async move {
+        label.set_text("Loading...");
+        match nwg::spawn_background(|| std::fs::read_to_string("data.txt")).await {
+            Ok(Ok(data)) => label.set_text(&data),
+            _ => label.set_text("Failed to load the data"),
+        }
+    })?;
+
+    Ok(())
+}
+```
+*/
+use winapi::shared::windef::HWND;
+use super::window::{create_message_window, build_notice, full_bind_event_handler, EventHandler};
+use super::window_helper::NOTICE_MESSAGE;
+use crate::{Event, NwgError};
+use std::future::Future;
+use std::task::{Context, Poll, Waker, RawWaker, RawWakerVTable};
+use std::pin::Pin;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::{mem, panic, thread};
+
+
+/// The ids of the tasks to poll. Shared with the wakers, which can be used from any thread.
+struct ReadyQueue {
+    tasks: Mutex<VecDeque<usize>>,
+    hwnd: usize,
+    notice: u32,
+}
+
+impl ReadyQueue {
+
+    /// Queue a task. The notice is only posted if the queue was empty, because `next` posts a new one while tasks are left.
+    fn schedule(&self, task: usize) {
+        let first = {
+            let mut tasks = self.tasks.lock().unwrap();
+            if tasks.contains(&task) { return; }
+            tasks.push_back(task);
+            tasks.len() == 1
+        };
+
+        if first {
+            self.post();
+        }
+    }
+
+    /// Remove the next task from the queue. If other tasks are waiting, a new notice is posted for them,
+    /// so a modal loop opened by the task still polls the rest of the queue.
+    fn next(&self) -> Option<usize> {
+        let (task, more) = {
+            let mut tasks = self.tasks.lock().unwrap();
+            let task = tasks.pop_front();
+            (task, !tasks.is_empty())
+        };
+
+        if more {
+            self.post();
+        }
+
+        task
+    }
+
+    fn post(&self) {
+        use super::backend::PostMessageW;
+        use winapi::shared::minwindef::{WPARAM, LPARAM};
+
+        // Unlike `SendNotifyMessageW`, `PostMessageW` never calls the window procedure right away when the
+        // task is woken from the GUI thread. This prevents tasks from being polled while another one is running.
+        unsafe { PostMessageW(self.hwnd as HWND, NOTICE_MESSAGE, self.notice as WPARAM, self.hwnd as LPARAM); }
+    }
+
+}
+
+struct TaskWaker {
+    task: usize,
+    queue: Arc<ReadyQueue>,
+}
+
+static WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(clone_waker, wake, wake_by_ref, drop_waker);
+
+unsafe fn clone_waker(data: *const ()) -> RawWaker {
+    let waker = Arc::from_raw(data as *const TaskWaker);
+    let cloned = waker.clone();
+    mem::forget(waker);
+    RawWaker::new(Arc::into_raw(cloned) as *const (), &WAKER_VTABLE)
+}
+
+unsafe fn wake(data: *const ()) {
+    let waker = Arc::from_raw(data as *const TaskWaker);
+    waker.queue.schedule(waker.task);
+}
+
+unsafe fn wake_by_ref(data: *const ()) {
+    let waker = &*(data as *const TaskWaker);
+    waker.queue.schedule(waker.task);
+}
+
+unsafe fn drop_waker(data: *const ()) {
+    drop(Arc::from_raw(data as *const TaskWaker));
+}
+
+fn task_waker(task: usize, queue: &Arc<ReadyQueue>) -> Waker {
+    let waker = Arc::new(TaskWaker { task, queue: queue.clone() });
+    unsafe { Waker::from_raw(RawWaker::new(Arc::into_raw(waker) as *const (), &WAKER_VTABLE)) }
+}
+
+
+enum Slot {
+    Idle(Pin<Box<dyn Future<Output=()>>>),
+
+    /// The future is being polled. `woken` is set if the task was woken in a nested message loop while it was running.
+    Running { woken: bool },
+}
+
+struct Executor {
+    queue: Arc<ReadyQueue>,
+    tasks: RefCell<HashMap<usize, Slot>>,
+    next_id: Cell<usize>,
+
+    // The message window is destroyed by the system when the thread exits
+    _handler: EventHandler,
+}
+
+thread_local! {
+    static EXECUTOR: RefCell<Option<Rc<Executor>>> = RefCell::new(None);
+}
+
+impl Executor {
+
+    fn build() -> Result<Executor, NwgError> {
+        let window = create_message_window()?;
+        let hwnd = window.hwnd().unwrap();
+        let notice = build_notice(hwnd);
+        let (_, notice_id) = notice.notice().unwrap();
+
+        let handler = full_bind_event_handler(&window, move |evt, _evt_data, handle| {
+            if evt == Event::OnNotice && handle == notice {
+                run_ready_tasks();
+            }
+        });
+
+        Ok(Executor {
+            queue: Arc::new(ReadyQueue { tasks: Mutex::new(VecDeque::new()), hwnd: hwnd as usize, notice: notice_id }),
+            tasks: RefCell::new(HashMap::new()),
+            next_id: Cell::new(0),
+            _handler: handler,
+        })
+    }
+
+    fn spawn(&self, future: Pin<Box<dyn Future<Output=()>>>) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        self.tasks.borrow_mut().insert(id, Slot::Idle(future));
+        self.queue.schedule(id);
+
+        id
+    }
+
+    fn poll_task(&self, id: usize) {
+        let slot = {
+            let mut tasks = self.tasks.borrow_mut();
+            match tasks.get_mut(&id) {
+                Some(Slot::Running { woken }) => { *woken = true; return; },
+                Some(slot) => mem::replace(slot, Slot::Running { woken: false }),
+                None => { return; }
+            }
+        };
+
+        let mut future = match slot {
+            Slot::Idle(future) => future,
+            Slot::Running { .. } => unreachable!()
+        };
+
+        // The tasks are not borrowed while the future runs, so it can spawn or cancel other tasks
+        let waker = task_waker(id, &self.queue);
+        let mut context = Context::from_waker(&waker);
+        if future.as_mut().poll(&mut context).is_ready() {
+            self.tasks.borrow_mut().remove(&id);
+            return;
+        }
+
+        let mut tasks = self.tasks.borrow_mut();
+        if let Some(slot) = tasks.get_mut(&id) {
+            let woken = match slot { Slot::Running { woken } => *woken, Slot::Idle(_) => false };
+            *slot = Slot::Idle(future);
+            if woken {
+                self.queue.schedule(id);
+            }
+        }
+    }
+
+    fn cancel(&self, id: usize) {
+        let slot = self.tasks.borrow_mut().remove(&id);
+        drop(slot);
+    }
+
+}
+
+/// Return the executor of the current thread. Creates it if this is the first task of the thread.
+fn executor() -> Result<Rc<Executor>, NwgError> {
+    if let Some(executor) = EXECUTOR.with(|e| e.borrow().clone()) {
+        return Ok(executor);
+    }
+
+    let executor = Rc::new(Executor::build()?);
+    EXECUTOR.with(|e| *e.borrow_mut() = Some(executor.clone()));
+
+    Ok(executor)
+}
+
+/// Poll the next ready task. Each notice polls a single task, so the other messages of the thread are dispatched between the tasks.
+fn run_ready_tasks() {
+    let executor = match EXECUTOR.with(|e| e.borrow().clone()) {
+        Some(executor) => executor,
+        None => { return; }
+    };
+
+    if let Some(id) = executor.queue.next() {
+        executor.poll_task(id);
+    }
+}
+
+
+struct TaskOutput<T> {
+    value: Option<T>,
+    finished: bool,
+    waker: Option<Waker>,
+}
+
+/**
+    A task spawned with `spawn_local`. Awaiting the task returns the output of its future.
+
+    Dropping a `LocalTask` does not stop the task, use `cancel` for that.
+*/
+pub struct LocalTask<T> {
+    id: usize,
+    output: Rc<RefCell<TaskOutput<T>>>,
+}
+
+impl<T> LocalTask<T> {
+
+    /// Returns `true` if the future of the task completed
+    pub fn finished(&self) -> bool {
+        self.output.borrow().finished
+    }
+
+    /// Drop the future of the task. Does nothing if the task is already finished.
+    pub fn cancel(self) {
+        if let Some(executor) = EXECUTOR.with(|e| e.borrow().clone()) {
+            executor.cancel(self.id);
+        }
+    }
+
+}
+
+impl<T> Future for LocalTask<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<T> {
+        let mut output = self.output.borrow_mut();
+        match output.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                output.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/**
+    Run a future on the executor of the current thread. The future is first polled the next time the thread
+    dispatches its messages, never from `spawn_local` itself.
+
+    The executor is created by the first call on a thread. This fails if NWG was not initialized with `nwg::init`.
+
+    Requires the `async` feature.
+*/
+pub fn spawn_local<F>(future: F) -> Result<LocalTask<F::Output>, NwgError>
+    where F: Future + 'static
+{
+    let executor = executor()?;
+    let output = Rc::new(RefCell::new(TaskOutput { value: None, finished: false, waker: None }));
+
+    let task_output = output.clone();
+    let id = executor.spawn(Box::pin(async move {
+        let value = future.await;
+        let waker = {
+            let mut output = task_output.borrow_mut();
+            output.value = Some(value);
+            output.finished = true;
+            output.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }));
+
+    Ok(LocalTask { id, output })
+}
+
+
+struct BackgroundOutput<T> {
+    value: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/**
+    A closure running on another thread, created with `spawn_background`.
+    Awaiting the task returns the value of the closure, or the panic payload if the closure panicked (like `JoinHandle::join`).
+*/
+pub struct BackgroundTask<T> {
+    output: Arc<Mutex<BackgroundOutput<T>>>,
+}
+
+impl<T> BackgroundTask<T> {
+
+    /// Returns `true` if the closure returned or panicked
+    pub fn finished(&self) -> bool {
+        self.output.lock().unwrap().value.is_some()
+    }
+
+}
+
+impl<T> Future for BackgroundTask<T> {
+    type Output = thread::Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<thread::Result<T>> {
+        let mut output = self.output.lock().unwrap();
+        match output.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                output.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/**
+    Run a closure on a new thread. The returned task can be awaited from a future running on the GUI thread.
+
+    Requires the `async` feature.
+*/
+pub fn spawn_background<T, F>(f: F) -> BackgroundTask<T>
+    where F: FnOnce() -> T + Send + 'static,
+          T: Send + 'static
+{
+    let output = Arc::new(Mutex::new(BackgroundOutput { value: None, waker: None }));
+
+    let thread_output = output.clone();
+    thread::spawn(move || {
+        let value = panic::catch_unwind(panic::AssertUnwindSafe(f));
+        let waker = {
+            let mut output = thread_output.lock().unwrap();
+            output.value = Some(value);
+            output.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    });
+
+    BackgroundTask { output }
+}
+
+
+/**
+    A future running a blocking modal call (ex: a dialog) from the message loop. Returned by the `run_async` methods of the dialogs
+    and by the async message boxes.
+
+    The dialogs are still modal. The call runs a nested message loop, and the poll of the task awaiting the future does not
+    return until the dialog is closed. The future only changes where that loop runs: from the executor instead of from
+    the event handler that wants the dialog.
+
+    Re-entrancy guarantees:
+      * The modal call is never made by the method that returns the future, nor by the first poll of the future.
+        The first poll wakes the task and the call is made by the next poll. With `spawn_local`, this means that
+        the dialog is opened from the message loop, after the event handler that spawned the task has returned.
+      * While the dialog is open, its modal loop dispatches the messages of the thread. Other event handlers, timers and
+        the other tasks keep running, including the tasks that were ready when the dialog was opened.
+      * The task awaiting the dialog is blocked for the lifetime of the dialog. Code running from the modal loop must not
+        wait on it (ex: awaiting its `LocalTask`), or it will only resume once the dialog is closed.
+      * The future resolves with the result of the dialog as soon as it is closed.
+
+    Requires the `async` feature.
+*/
+pub struct ModalFuture<'a, T> {
+    call: Option<Box<dyn FnOnce() -> T + 'a>>,
+    yielded: bool,
+}
+
+impl<'a, T> Future for ModalFuture<'a, T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<T> {
+        if !self.yielded {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let call = self.call.take().expect("ModalFuture polled after completion");
+        Poll::Ready(call())
+    }
+}
+
+/// Wrap a blocking modal call in a `ModalFuture`
+pub(crate) fn modal_future<'a, T, F>(call: F) -> ModalFuture<'a, T>
+    where F: FnOnce() -> T + 'a
+{
+    ModalFuture {
+        call: Some(Box::new(call)),
+        yielded: false,
+    }
+}
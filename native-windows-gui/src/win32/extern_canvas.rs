@@ -12,15 +12,15 @@ pub const EXT_CANVAS_CLASS_ID: &'static str = "NWG_EXTERN_CANVAS";
 
 /// Create the NWG tab classes
 pub fn create_extern_canvas_classes() -> Result<(), NwgError>  {
-    use winapi::um::libloaderapi::GetModuleHandleW;
+    use super::backend::GetModuleHandleW;
     use winapi::shared::windef::HBRUSH;
-    use winapi::um::winuser::{CS_HREDRAW, CS_VREDRAW, CS_OWNDC};
+    use winapi::um::winuser::{CS_HREDRAW, CS_VREDRAW, CS_OWNDC, CS_DBLCLKS};
 
     let hmod = unsafe { GetModuleHandleW(ptr::null_mut()) };
-    if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed")); }
+    if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed").with_last_error("GetModuleHandleW")); }
 
     unsafe { 
-        build_sysclass(hmod, EXT_CANVAS_CLASS_ID, Some(extern_canvas_proc), Some(0 as HBRUSH), Some(CS_OWNDC|CS_VREDRAW|CS_HREDRAW))?;
+        build_sysclass(hmod, EXT_CANVAS_CLASS_ID, Some(extern_canvas_proc), Some(0 as HBRUSH), Some(CS_OWNDC|CS_VREDRAW|CS_HREDRAW|CS_DBLCLKS))?;
     }
 
     Ok(())
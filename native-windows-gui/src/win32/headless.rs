@@ -0,0 +1,1812 @@
+/*!
+In-memory implementation of the window management functions used by NWG. Enabled by the `headless` feature.
+
+The functions in this module have the same names and signatures as their user32/comctl32 counterparts
+and are selected in `win32::backend`. Windows created here never reach the desktop: text, styles, position,
+size, visibility, parent/children relationships, focus, subclasses and the message queue are all kept in memory.
+
+Limitations:
+  * A Windows target is still required, because the rest of NWG uses the winapi types and the
+    GDI/kernel32 functions directly. The backend only removes the need for a desktop session.
+  * Windows are thread local. Messages posted to a window are only dispatched by the thread that created it.
+  * `GetMessageW` never waits. When the queue is empty, it behaves as if `WM_QUIT` was received,
+    so `dispatch_thread_events` returns once every pending message was processed.
+  * Only the default behaviour of the "Button", "Edit" and "Static" classes is emulated. Messages specific to
+    other common controls are accepted and return 0.
+  * List views only emulate the text of the items, the columns, the selection, the state images, the groups and the sorting.
+    Every row is 20 pixels high and the groups are never drawn. Only the state image changes send `LVN_ITEMCHANGED`.
+    Virtual list views (`LVS_OWNERDATA`) query their items from their parent (`LVN_GETDISPINFOW`).
+  * Tree views only emulate the items (text, lParam, children and state), the selection and the expansion.
+    `TVM_EXPAND` sends `TVN_ITEMEXPANDINGW` and `TVN_ITEMEXPANDEDW`. Items with `I_CHILDRENCALLBACK` query their parent (`TVN_GETDISPINFOW`).
+  * Timers are registered but never fire on their own.
+  * The keyboard state returned by `GetKeyState` is updated by the key messages sent to the windows.
+  * Global hotkeys are registered but never fire on their own. Post a `WM_HOTKEY` message to simulate them.
+  * The cursor position returned by `GetCursorPos` is updated by the client mouse messages sent to the windows.
+    `TrackMouseEvent` records the tracking requests, but `WM_MOUSEHOVER` and `WM_MOUSELEAVE` must be sent manually.
+    Sending them ends the tracking, like on Windows.
+*/
+#![allow(non_snake_case)]
+
+use winapi::shared::minwindef::{UINT, DWORD, BOOL, WPARAM, LPARAM, LRESULT, ATOM, HMODULE, HINSTANCE, LPVOID};
+use winapi::shared::windef::{HWND, HMENU, HCURSOR, HACCEL, RECT, POINT};
+use winapi::shared::basetsd::{UINT_PTR, DWORD_PTR, LONG_PTR};
+use winapi::shared::ntdef::{LPCWSTR, LPWSTR, SHORT};
+#[cfg(target_arch = "x86")] use winapi::shared::ntdef::LONG;
+use winapi::um::winuser::{WNDPROC, WNDENUMPROC, TIMERPROC, WNDCLASSEXW, MSG, SCROLLINFO, ACCEL, TRACKMOUSEEVENT};
+use winapi::um::commctrl::SUBCLASSPROC;
+use winapi::ctypes::c_int;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::cell::RefCell;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::{self, ThreadId};
+use std::{ptr, mem};
+
+
+/// A subclass installed with `SetWindowSubclass`
+#[derive(Clone, Copy)]
+struct Subclass {
+    proc_: SUBCLASSPROC,
+    id: UINT_PTR,
+    data: DWORD_PTR,
+}
+
+/// The in-memory state of a window
+struct HeadlessWindow {
+    class_name: String,
+    text: Vec<u16>,
+    style: DWORD,
+    ex_style: DWORD,
+    position: (i32, i32),
+    size: (i32, i32),
+    parent: usize,
+    id: usize,
+    check: usize,
+    font: WPARAM,
+    icon: LPARAM,
+    longs: HashMap<c_int, LONG_PTR>,
+    subclasses: Vec<Subclass>,
+
+    /// The min, max, page and position of the horizontal and vertical window scrollbars
+    scroll: [(c_int, c_int, UINT, c_int); 2],
+
+    /// The items of a list view
+    list: HeadlessList,
+
+    /// The items of a tree view
+    tree: HeadlessTree,
+}
+
+/// The in-memory state of a list view
+#[derive(Default)]
+struct HeadlessList {
+    /// The item count of a virtual list view
+    count: usize,
+
+    /// The items of a regular list view
+    rows: Vec<HeadlessListRow>,
+
+    /// The width of the columns
+    columns: Vec<c_int>,
+
+    selected: BTreeSet<usize>,
+
+    /// The groups (id, state) and if the items are displayed in groups
+    groups: Vec<(c_int, UINT)>,
+    group_view: bool,
+
+    ex_style: DWORD,
+}
+
+/// An item of a regular list view
+struct HeadlessListRow {
+    /// The text of the cells, by column
+    cells: Vec<Vec<u16>>,
+
+    /// The state image bits of the item (`LVIS_STATEIMAGEMASK`)
+    state: UINT,
+
+    param: LPARAM,
+    group: c_int,
+}
+
+impl HeadlessList {
+
+    fn len(&self, owner_data: bool) -> usize {
+        match owner_data {
+            true => self.count,
+            false => self.rows.len()
+        }
+    }
+
+}
+
+/// The in-memory state of a tree view. The item handles are the keys of `items`.
+#[derive(Default)]
+struct HeadlessTree {
+    next: usize,
+    items: BTreeMap<usize, HeadlessTreeItem>,
+
+    /// The root items, in order
+    roots: Vec<usize>,
+    selected: usize,
+}
+
+struct HeadlessTreeItem {
+    parent: usize,
+    children: Vec<usize>,
+    text: Vec<u16>,
+    param: LPARAM,
+
+    /// The `cChildren` value set by the application. `None` if the children are counted.
+    children_count: Option<c_int>,
+    state: UINT,
+}
+
+impl HeadlessTree {
+
+    fn siblings(&self, parent: usize) -> &Vec<usize> {
+        match self.items.get(&parent) {
+            Some(item) => &item.children,
+            None => &self.roots
+        }
+    }
+
+    fn siblings_mut(&mut self, parent: usize) -> &mut Vec<usize> {
+        match self.items.get_mut(&parent) {
+            Some(item) => &mut item.children,
+            None => &mut self.roots
+        }
+    }
+
+    /// The item and all its descendants, parents first
+    fn subtree(&self, item: usize) -> Vec<usize> {
+        let mut items = vec![item];
+        let mut i = 0;
+        while i < items.len() {
+            if let Some(tree_item) = self.items.get(&items[i]) {
+                items.extend(tree_item.children.iter().cloned());
+            }
+            i += 1;
+        }
+
+        items
+    }
+
+}
+
+#[derive(Default)]
+struct HeadlessState {
+    focus: usize,
+    capture: usize,
+    last_error: DWORD,
+    windows: BTreeMap<usize, HeadlessWindow>,
+    classes: HashMap<String, WNDPROC>,
+    timers: HashMap<(usize, UINT_PTR), UINT>,
+    keys: HashSet<c_int>,
+    cursor: (i32, i32),
+
+    /// The `TME_HOVER`/`TME_LEAVE` flags requested with `TrackMouseEvent` for each window
+    mouse_tracking: HashMap<usize, DWORD>,
+    accelerators: HashMap<usize, Vec<ACCEL>>,
+}
+
+thread_local! {
+    static STATE: RefCell<HeadlessState> = RefCell::new(Default::default());
+
+    /// Stack of the subclasses currently being executed. Used by `DefSubclassProc` to find the next procedure in the chain.
+    static CHAIN: RefCell<Vec<(usize, usize)>> = RefCell::new(Vec::new());
+}
+
+/// Window handles are unique across threads so that posted messages can be routed to the right thread
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+lazy_static! {
+    /// Messages posted with `PostMessageW`/`SendNotifyMessageW`. Can be filled from any thread.
+    static ref QUEUE: Mutex<VecDeque<(ThreadId, usize, UINT, WPARAM, LPARAM)>> = Mutex::new(VecDeque::new());
+
+    /// The thread that created each window
+    static ref OWNERS: Mutex<HashMap<usize, ThreadId>> = Mutex::new(HashMap::new());
+
+    /// The global hotkeys (window, id) => (modifiers, key). Hotkeys are system wide.
+    static ref HOTKEYS: Mutex<HashMap<(usize, c_int), (UINT, UINT)>> = Mutex::new(HashMap::new());
+}
+
+const HWND_MESSAGE: isize = -3;
+const BUTTON_CLASS: &'static str = "Button";
+const EDIT_CLASS: &'static str = "Edit";
+const STATIC_CLASS: &'static str = "Static";
+const LIST_VIEW_CLASS: &'static str = "SysListView32";
+const TREE_VIEW_CLASS: &'static str = "SysTreeView32";
+const LIST_ROW_HEIGHT: c_int = 20;
+const LIST_COLUMN_WIDTH: c_int = 100;
+
+
+fn with_window<T, F: FnOnce(&mut HeadlessWindow) -> T>(hwnd: HWND, f: F) -> Option<T> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.windows.get_mut(&(hwnd as usize)).map(f)
+    })
+}
+
+fn window_exists(hwnd: HWND) -> bool {
+    STATE.with(|state| state.borrow().windows.contains_key(&(hwnd as usize)))
+}
+
+/// System classes are case insensitive and `GetClassNameW` returns their canonical name
+fn canonical_class_name(name: String) -> String {
+    match &name.to_uppercase() as &str {
+        "BUTTON" => BUTTON_CLASS.to_string(),
+        "EDIT" => EDIT_CLASS.to_string(),
+        "STATIC" => STATIC_CLASS.to_string(),
+        "COMBOBOX" => "ComboBox".to_string(),
+        "LISTBOX" => "ListBox".to_string(),
+        "SCROLLBAR" => "ScrollBar".to_string(),
+        _ => name
+    }
+}
+
+unsafe fn read_wide(s: LPCWSTR) -> Vec<u16> {
+    if s.is_null() {
+        return Vec::new();
+    }
+
+    let mut length = 0;
+    while *s.offset(length) != 0 {
+        length += 1;
+    }
+
+    ::std::slice::from_raw_parts(s, length as usize).to_vec()
+}
+
+unsafe fn write_wide(text: &[u16], buffer: LPWSTR, max_count: c_int) -> c_int {
+    if buffer.is_null() || max_count <= 0 {
+        return 0;
+    }
+
+    let count = ::std::cmp::min(text.len(), (max_count - 1) as usize);
+    ptr::copy_nonoverlapping(text.as_ptr(), buffer, count);
+    *buffer.offset(count as isize) = 0;
+
+    count as c_int
+}
+
+fn make_lparam(low: i32, high: i32) -> LPARAM {
+    (((high as u32 & 0xFFFF) << 16) | (low as u32 & 0xFFFF)) as LPARAM
+}
+
+/// Position of the window client area relative to the "screen"
+fn absolute_position(hwnd: usize) -> (i32, i32) {
+    STATE.with(|state| {
+        let state = state.borrow();
+        let (mut x, mut y) = (0, 0);
+        let mut current = hwnd;
+        while let Some(window) = state.windows.get(&current) {
+            x += window.position.0;
+            y += window.position.1;
+            current = window.parent;
+        }
+
+        (x, y)
+    })
+}
+
+/// Returns every descendant of a window in creation order
+fn descendants(hwnd: usize) -> Vec<usize> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        let mut found = Vec::new();
+        let mut parents = vec![hwnd];
+        while let Some(parent) = parents.pop() {
+            for (&handle, window) in state.windows.iter() {
+                if window.parent == parent {
+                    found.push(handle);
+                    parents.push(handle);
+                }
+            }
+        }
+
+        found
+    })
+}
+
+/// Calls the subclass at `level-1`. If `level` is 0, calls the window procedure.
+unsafe fn call_chain(hwnd: HWND, level: usize, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    if level == 0 {
+        return call_window_proc(hwnd, msg, w, l);
+    }
+
+    let subclass = with_window(hwnd, |window| window.subclasses.get(level - 1).copied()).and_then(|s| s);
+    match subclass.and_then(|s| s.proc_.map(|p| (p, s))) {
+        Some((proc_, subclass)) => {
+            CHAIN.with(|chain| chain.borrow_mut().push((hwnd as usize, level - 1)));
+            let result = proc_(hwnd, msg, w, l, subclass.id, subclass.data);
+            CHAIN.with(|chain| chain.borrow_mut().pop());
+            result
+        },
+        None => call_window_proc(hwnd, msg, w, l)
+    }
+}
+
+unsafe fn call_window_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    let class_name = match with_window(hwnd, |window| window.class_name.clone()) {
+        Some(name) => name,
+        None => { return 0; }
+    };
+
+    let class_proc = STATE.with(|state| state.borrow().classes.get(&class_name).cloned());
+    match class_proc {
+        Some(Some(proc_)) => proc_(hwnd, msg, w, l),
+        _ => system_class_proc(hwnd, &class_name, msg, w, l)
+    }
+}
+
+/// Emulate the parts of the system classes that NWG relies on
+unsafe fn system_class_proc(hwnd: HWND, class_name: &str, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    use winapi::um::winuser::{WM_SETTEXT, BM_CLICK, BM_GETCHECK, BM_SETCHECK, BN_CLICKED, EN_CHANGE, BST_CHECKED, BST_UNCHECKED,
+        BS_AUTOCHECKBOX, BS_AUTORADIOBUTTON, BS_AUTO3STATE, BS_TYPEMASK};
+
+    match (class_name, msg) {
+        (BUTTON_CLASS, BM_CLICK) => {
+            let style = with_window(hwnd, |window| window.style).unwrap_or(0);
+            match style & BS_TYPEMASK {
+                BS_AUTOCHECKBOX | BS_AUTO3STATE => {
+                    with_window(hwnd, |window| window.check = if window.check == BST_UNCHECKED { BST_CHECKED } else { BST_UNCHECKED });
+                },
+                BS_AUTORADIOBUTTON => {
+                    with_window(hwnd, |window| window.check = BST_CHECKED);
+                },
+                _ => {}
+            }
+
+            notify_parent(hwnd, BN_CLICKED);
+            0
+        },
+        (BUTTON_CLASS, BM_GETCHECK) => with_window(hwnd, |window| window.check as LRESULT).unwrap_or(0),
+        (BUTTON_CLASS, BM_SETCHECK) => {
+            with_window(hwnd, |window| window.check = w);
+            0
+        },
+        (EDIT_CLASS, WM_SETTEXT) => {
+            let result = DefWindowProcW(hwnd, msg, w, l);
+            notify_parent(hwnd, EN_CHANGE);
+            result
+        },
+        (LIST_VIEW_CLASS, _) => list_view_proc(hwnd, msg, w, l),
+        (TREE_VIEW_CLASS, _) => tree_view_proc(hwnd, msg, w, l),
+        _ => DefWindowProcW(hwnd, msg, w, l)
+    }
+}
+
+/// Emulate the items, the columns and the selection of a list view. Virtual list views query their items from the parent.
+unsafe fn list_view_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    use winapi::um::commctrl::{LVS_OWNERDATA, LVM_SETITEMCOUNT, LVM_GETITEMCOUNT, LVM_SETITEMSTATE, LVM_GETITEMSTATE, LVM_GETNEXTITEMINDEX,
+        LVM_GETITEMW, LVM_SETITEMW, LVM_SETITEMTEXTW, LVM_INSERTITEMW, LVM_DELETEITEM, LVM_DELETEALLITEMS, LVM_INSERTCOLUMNW, LVM_DELETECOLUMN,
+        LVM_GETCOLUMNWIDTH, LVM_GETSELECTEDCOUNT, LVM_SORTITEMSEX, LVM_GETSUBITEMRECT, LVITEMW, LVITEMINDEX, LVCOLUMNW, PFNLVCOMPARE,
+        LVIF_TEXT, LVIF_STATE, LVIF_PARAM, LVIF_GROUPID, LVCF_WIDTH, LVIS_SELECTED, LVIS_STATEIMAGEMASK, LVNI_SELECTED, LVN_GETDISPINFOW,
+        LVN_ITEMCHANGED, LVN_DELETEITEM, LVN_DELETEALLITEMS, NMLVDISPINFOW, I_GROUPIDNONE, LVM_ENABLEGROUPVIEW, LVM_ISGROUPVIEWENABLED,
+        LVM_INSERTGROUP, LVM_REMOVEGROUP, LVM_HASGROUP, LVM_GETGROUPCOUNT, LVM_SETGROUPINFO, LVM_GETGROUPSTATE, LVM_GETGROUPINFOBYINDEX,
+        LVGROUP, LVGF_GROUPID, LVGF_STATE, LVM_SETEXTENDEDLISTVIEWSTYLE, LVM_GETEXTENDEDLISTVIEWSTYLE, LVS_EX_CHECKBOXES};
+    use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+
+    let (owner_data, parent, id, len) = match with_window(hwnd, |window| {
+        let owner_data = window.style & LVS_OWNERDATA == LVS_OWNERDATA;
+        (owner_data, window.parent, window.id, window.list.len(owner_data))
+    }) {
+        Some(values) => values,
+        None => { return 0; }
+    };
+
+    match msg {
+        LVM_INSERTCOLUMNW => {
+            let column = &*(l as *const LVCOLUMNW);
+            let width = if column.mask & LVCF_WIDTH == LVCF_WIDTH { column.cx } else { LIST_COLUMN_WIDTH };
+            with_window(hwnd, |window| {
+                let index = w.min(window.list.columns.len());
+                window.list.columns.insert(index, width);
+                index as LRESULT
+            }).unwrap_or(-1)
+        },
+        LVM_DELETECOLUMN => with_window(hwnd, |window| {
+            if w >= window.list.columns.len() {
+                return 0;
+            }
+
+            window.list.columns.remove(w);
+            for row in window.list.rows.iter_mut().filter(|row| w < row.cells.len()) {
+                row.cells.remove(w);
+            }
+
+            1
+        }).unwrap_or(0),
+        LVM_GETCOLUMNWIDTH => with_window(hwnd, |window| window.list.columns.get(w).cloned().unwrap_or(0) as LRESULT).unwrap_or(0),
+        LVM_SETITEMCOUNT => {
+            if owner_data {
+                with_window(hwnd, |window| {
+                    window.list.count = w;
+                    window.list.selected = window.list.selected.iter().cloned().filter(|&i| i < w).collect();
+                });
+            }
+            1
+        },
+        LVM_GETITEMCOUNT => len as LRESULT,
+        LVM_INSERTITEMW if !owner_data => {
+            let item = &*(l as *const LVITEMW);
+            let text = if item.mask & LVIF_TEXT == LVIF_TEXT { read_wide(item.pszText) } else { Vec::new() };
+            let check_boxes = with_window(hwnd, |window| window.list.ex_style & LVS_EX_CHECKBOXES != 0).unwrap_or(false);
+            let mut row = HeadlessListRow {
+                cells: vec![text],
+                state: if item.mask & LVIF_STATE == LVIF_STATE { item.state & item.stateMask & LVIS_STATEIMAGEMASK } else { 0 },
+                param: if item.mask & LVIF_PARAM == LVIF_PARAM { item.lParam } else { 0 },
+                group: if item.mask & LVIF_GROUPID == LVIF_GROUPID { item.iGroupId } else { I_GROUPIDNONE },
+            };
+
+            // The new items are unchecked
+            if check_boxes && row.state == 0 {
+                row.state = 1 << 12;
+            }
+
+            with_window(hwnd, |window| {
+                let index = (item.iItem.max(0) as usize).min(window.list.rows.len());
+                window.list.rows.insert(index, row);
+                window.list.selected = window.list.selected.iter().map(|&i| if i >= index { i + 1 } else { i }).collect();
+                index as LRESULT
+            }).unwrap_or(-1)
+        },
+        LVM_SETITEMW | LVM_SETITEMTEXTW if !owner_data => {
+            let item = &*(l as *const LVITEMW);
+            let row = if msg == LVM_SETITEMW { item.iItem } else { w as c_int };
+            if row < 0 || row as usize >= len {
+                return 0;
+            }
+
+            if msg == LVM_SETITEMTEXTW || item.mask & LVIF_TEXT == LVIF_TEXT {
+                let (column, text) = (item.iSubItem.max(0) as usize, read_wide(item.pszText));
+                with_window(hwnd, |window| {
+                    let cells = &mut window.list.rows[row as usize].cells;
+                    if cells.len() <= column {
+                        cells.resize(column + 1, Vec::new());
+                    }
+                    cells[column] = text;
+                });
+            }
+
+            if msg == LVM_SETITEMW {
+                with_window(hwnd, |window| {
+                    let row = &mut window.list.rows[row as usize];
+                    if item.mask & LVIF_PARAM == LVIF_PARAM { row.param = item.lParam; }
+                    if item.mask & LVIF_GROUPID == LVIF_GROUPID { row.group = item.iGroupId; }
+                });
+            }
+
+            1
+        },
+        LVM_DELETEITEM if !owner_data => {
+            let param = match with_window(hwnd, |window| window.list.rows.get(w).map(|row| row.param)).unwrap_or(None) {
+                Some(param) => param,
+                None => { return 0; }
+            };
+
+            notify_list_parent(hwnd, LVN_DELETEITEM, w as c_int, 0, 0, param);
+
+            with_window(hwnd, |window| {
+                window.list.rows.remove(w);
+                window.list.selected = window.list.selected.iter().filter(|&&i| i != w).map(|&i| if i > w { i - 1 } else { i }).collect();
+            });
+
+            1
+        },
+        LVM_DELETEALLITEMS => {
+            // The parent returns TRUE to skip the LVN_DELETEITEM notifications
+            if notify_list_parent(hwnd, LVN_DELETEALLITEMS, -1, 0, 0, 0) == 0 {
+                let params: Vec<LPARAM> = with_window(hwnd, |window| window.list.rows.iter().map(|row| row.param).collect()).unwrap_or_default();
+                for (i, param) in params.into_iter().enumerate() {
+                    notify_list_parent(hwnd, LVN_DELETEITEM, i as c_int, 0, 0, param);
+                }
+            }
+
+            with_window(hwnd, |window| {
+                window.list.rows.clear();
+                window.list.count = 0;
+                window.list.selected.clear();
+            });
+            1
+        },
+        LVM_SETITEMSTATE => {
+            let item = &*(l as *const LVITEMW);
+            if item.stateMask & LVIS_SELECTED == LVIS_SELECTED {
+                let selected = item.state & LVIS_SELECTED == LVIS_SELECTED;
+                let indices: Vec<usize> = match w as isize {
+                    -1 => (0..len).collect(),
+                    i if (i as usize) < len => vec![i as usize],
+                    _ => Vec::new()
+                };
+
+                with_window(hwnd, |window| for i in indices {
+                    match selected {
+                        true => window.list.selected.insert(i),
+                        false => window.list.selected.remove(&i)
+                    };
+                });
+            }
+
+            if item.stateMask & LVIS_STATEIMAGEMASK != 0 && !owner_data {
+                let image = item.state & item.stateMask & LVIS_STATEIMAGEMASK;
+                let indices: Vec<usize> = match w as isize {
+                    -1 => (0..len).collect(),
+                    i if (i as usize) < len => vec![i as usize],
+                    _ => Vec::new()
+                };
+
+                for i in indices {
+                    let changed = with_window(hwnd, |window| {
+                        let row = &mut window.list.rows[i];
+                        let old = row.state;
+                        row.state = image;
+                        (old, row.param)
+                    });
+
+                    match changed {
+                        Some((old, param)) if old != image => { notify_list_parent(hwnd, LVN_ITEMCHANGED, i as c_int, old, image, param); },
+                        _ => {}
+                    }
+                }
+            }
+
+            1
+        },
+        LVM_GETITEMSTATE => {
+            let state = with_window(hwnd, |window| {
+                let selected = if window.list.selected.contains(&w) { LVIS_SELECTED } else { 0 };
+                let image = window.list.rows.get(w).map(|row| row.state).unwrap_or(0);
+                selected | image
+            }).unwrap_or(0);
+
+            (state & (l as UINT)) as LRESULT
+        },
+        LVM_GETSELECTEDCOUNT => with_window(hwnd, |window| window.list.selected.len() as LRESULT).unwrap_or(0),
+        LVM_GETNEXTITEMINDEX => {
+            let index = &mut *(w as *mut LVITEMINDEX);
+            if l & (LVNI_SELECTED as LPARAM) == 0 {
+                return 0;
+            }
+
+            let start = (index.iItem + 1).max(0) as usize;
+            match with_window(hwnd, |window| window.list.selected.range(start..).next().cloned()).unwrap_or(None) {
+                Some(next) => {
+                    index.iItem = next as c_int;
+                    1
+                },
+                None => 0
+            }
+        },
+        LVM_GETITEMW => {
+            let item = &mut *(l as *mut LVITEMW);
+            if item.iItem < 0 || item.iItem as usize >= len {
+                return 0;
+            }
+
+            let (row, column) = (item.iItem as usize, item.iSubItem.max(0) as usize);
+            let (selected, columns, text, state, param, group) = match with_window(hwnd, |window| {
+                let text = window.list.rows.get(row).and_then(|r| r.cells.get(column)).cloned().unwrap_or_default();
+                let (state, param, group) = window.list.rows.get(row).map(|r| (r.state, r.param, r.group)).unwrap_or((0, 0, I_GROUPIDNONE));
+                (window.list.selected.contains(&row), window.list.columns.len(), text, state, param, group)
+            }) {
+                Some(values) => values,
+                None => { return 0; }
+            };
+
+            if column >= columns.max(1) {
+                return 0;
+            }
+
+            if item.mask & LVIF_STATE == LVIF_STATE {
+                let selected = if selected { LVIS_SELECTED } else { 0 };
+                item.state = (selected | state) & item.stateMask;
+            }
+
+            if item.mask & LVIF_PARAM == LVIF_PARAM {
+                item.lParam = param;
+            }
+
+            if item.mask & LVIF_GROUPID == LVIF_GROUPID {
+                item.iGroupId = group;
+            }
+
+            if owner_data {
+                // The virtual list view asks its parent for the content of the item
+                let mut info = NMLVDISPINFOW {
+                    hdr: NMHDR { hwndFrom: hwnd, idFrom: id, code: LVN_GETDISPINFOW },
+                    item: *item,
+                };
+
+                SendMessageW(parent as HWND, WM_NOTIFY, id, &mut info as *mut NMLVDISPINFOW as LPARAM);
+                *item = info.item;
+            } else if item.mask & LVIF_TEXT == LVIF_TEXT {
+                write_wide(&text, item.pszText, item.cchTextMax);
+            }
+
+            1
+        },
+        LVM_SORTITEMSEX if !owner_data => {
+            let compare: PFNLVCOMPARE = mem::transmute(l);
+            let compare = match compare {
+                Some(c) => c,
+                None => { return 0; }
+            };
+
+            // The comparator receives the indices of the items before the sort
+            let mut order: Vec<usize> = (0..len).collect();
+            order.sort_by(|&a, &b| compare(a as LPARAM, b as LPARAM, w as LPARAM).cmp(&0));
+
+            with_window(hwnd, |window| {
+                let mut old_rows: Vec<Option<HeadlessListRow>> = window.list.rows.drain(..).map(Some).collect();
+                window.list.rows = order.iter().filter_map(|&i| old_rows[i].take()).collect();
+                window.list.selected = order.iter().enumerate().filter(|(_, i)| window.list.selected.contains(*i)).map(|(new, _)| new).collect();
+            });
+
+            1
+        },
+        LVM_SETEXTENDEDLISTVIEWSTYLE => with_window(hwnd, |window| {
+            let mask = if w == 0 { !0 } else { w as DWORD };
+            let old = window.list.ex_style;
+            window.list.ex_style = (old & !mask) | (l as DWORD & mask);
+
+            // Enabling the check boxes unchecks every item
+            if old & LVS_EX_CHECKBOXES == 0 && window.list.ex_style & LVS_EX_CHECKBOXES != 0 {
+                for row in window.list.rows.iter_mut() {
+                    row.state = 1 << 12;
+                }
+            }
+
+            old as LRESULT
+        }).unwrap_or(0),
+        LVM_GETEXTENDEDLISTVIEWSTYLE => with_window(hwnd, |window| window.list.ex_style as LRESULT).unwrap_or(0),
+        LVM_ENABLEGROUPVIEW => with_window(hwnd, |window| {
+            let enabled = w != 0;
+            (mem::replace(&mut window.list.group_view, enabled) != enabled) as LRESULT
+        }).unwrap_or(-1),
+        LVM_ISGROUPVIEWENABLED => with_window(hwnd, |window| window.list.group_view as LRESULT).unwrap_or(0),
+        LVM_INSERTGROUP => {
+            let group = &*(l as *const LVGROUP);
+            let state = if group.mask & LVGF_STATE == LVGF_STATE { group.state & group.stateMask } else { 0 };
+            with_window(hwnd, |window| {
+                if window.list.groups.iter().any(|&(id, _)| id == group.iGroupId) {
+                    return -1;
+                }
+
+                let index = match w as isize {
+                    i if i < 0 => window.list.groups.len(),
+                    i => (i as usize).min(window.list.groups.len())
+                };
+
+                window.list.groups.insert(index, (group.iGroupId, state));
+                index as LRESULT
+            }).unwrap_or(-1)
+        },
+        LVM_REMOVEGROUP => with_window(hwnd, |window| {
+            let index = match window.list.groups.iter().position(|&(id, _)| id == w as c_int) {
+                Some(index) => index,
+                None => { return -1; }
+            };
+
+            window.list.groups.remove(index);
+            for row in window.list.rows.iter_mut().filter(|row| row.group == w as c_int) {
+                row.group = I_GROUPIDNONE;
+            }
+
+            index as LRESULT
+        }).unwrap_or(-1),
+        LVM_HASGROUP => with_window(hwnd, |window| window.list.groups.iter().any(|&(id, _)| id == w as c_int) as LRESULT).unwrap_or(0),
+        LVM_GETGROUPCOUNT => with_window(hwnd, |window| window.list.groups.len() as LRESULT).unwrap_or(0),
+        LVM_SETGROUPINFO => {
+            let group = &*(l as *const LVGROUP);
+            with_window(hwnd, |window| {
+                match window.list.groups.iter_mut().find(|(id, _)| *id == w as c_int) {
+                    Some((id, state)) => {
+                        if group.mask & LVGF_STATE == LVGF_STATE {
+                            *state = (*state & !group.stateMask) | (group.state & group.stateMask);
+                        }
+                        *id as LRESULT
+                    },
+                    None => -1
+                }
+            }).unwrap_or(-1)
+        },
+        LVM_GETGROUPSTATE => with_window(hwnd, |window| {
+            window.list.groups.iter().find(|&&(id, _)| id == w as c_int).map(|&(_, state)| (state & l as UINT) as LRESULT).unwrap_or(0)
+        }).unwrap_or(0),
+        LVM_GETGROUPINFOBYINDEX => {
+            let group = &mut *(l as *mut LVGROUP);
+            match with_window(hwnd, |window| window.list.groups.get(w).cloned()).unwrap_or(None) {
+                Some((id, state)) => {
+                    if group.mask & LVGF_GROUPID == LVGF_GROUPID { group.iGroupId = id; }
+                    if group.mask & LVGF_STATE == LVGF_STATE { group.state = state & group.stateMask; }
+                    1
+                },
+                None => 0
+            }
+        },
+        LVM_GETSUBITEMRECT => {
+            let rect = &mut *(l as *mut RECT);
+            let (row, column) = (w, rect.top.max(0) as usize);
+            if row >= len {
+                return 0;
+            }
+
+            let (columns, client_width) = match with_window(hwnd, |window| (window.list.columns.clone(), window.size.0)) {
+                Some(values) => values,
+                None => { return 0; }
+            };
+
+            let (left, width) = match columns.is_empty() {
+                true => (0, client_width),
+                false if column < columns.len() => (columns[..column].iter().sum(), columns[column]),
+                false => { return 0; }
+            };
+
+            let top = row as c_int * LIST_ROW_HEIGHT;
+            *rect = RECT { left, top, right: left + width, bottom: top + LIST_ROW_HEIGHT };
+
+            1
+        },
+        _ => DefWindowProcW(hwnd, msg, w, l)
+    }
+}
+
+/// Emulate the items, the selection and the expansion of a tree view
+unsafe fn tree_view_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    use winapi::um::commctrl::{TVM_INSERTITEMW, TVM_DELETEITEM, TVM_GETNEXTITEM, TVM_GETITEMW, TVM_SETITEMW, TVM_GETITEMSTATE, TVM_EXPAND,
+        TVM_GETCOUNT, TVINSERTSTRUCTW, TVITEMW, HTREEITEM, TVI_ROOT, TVI_FIRST, TVI_LAST, TVI_SORT, TVIF_TEXT, TVIF_PARAM, TVIF_CHILDREN,
+        TVIF_STATE, TVIS_SELECTED, TVIS_EXPANDED, TVIS_EXPANDEDONCE, TVGN_ROOT, TVGN_NEXT, TVGN_PREVIOUS, TVGN_PARENT, TVGN_CHILD, TVGN_CARET,
+        TVE_COLLAPSE, TVE_EXPAND, TVE_TOGGLE, TVN_DELETEITEMW, TVN_ITEMEXPANDINGW, TVN_ITEMEXPANDEDW};
+
+    match msg {
+        TVM_INSERTITEMW => {
+            let insert = &*(l as *const TVINSERTSTRUCTW);
+            let item = insert.u.item();
+            let parent = match insert.hParent {
+                p if p.is_null() || p == TVI_ROOT => 0,
+                p => p as usize
+            };
+
+            let new_item = HeadlessTreeItem {
+                parent,
+                children: Vec::new(),
+                text: if item.mask & TVIF_TEXT == TVIF_TEXT { read_wide(item.pszText) } else { Vec::new() },
+                param: if item.mask & TVIF_PARAM == TVIF_PARAM { item.lParam } else { 0 },
+                children_count: if item.mask & TVIF_CHILDREN == TVIF_CHILDREN { Some(item.cChildren) } else { None },
+                state: 0,
+            };
+
+            with_window(hwnd, |window| {
+                let tree = &mut window.tree;
+                if parent != 0 && !tree.items.contains_key(&parent) {
+                    return 0;
+                }
+
+                let index = match insert.hInsertAfter {
+                    after if after == TVI_FIRST => 0,
+                    after if after == TVI_LAST || after == TVI_ROOT => tree.siblings(parent).len(),
+                    after if after == TVI_SORT => {
+                        let siblings = tree.siblings(parent);
+                        siblings.iter().position(|i| tree.items[i].text > new_item.text).unwrap_or(siblings.len())
+                    },
+                    after => {
+                        let siblings = tree.siblings(parent);
+                        siblings.iter().position(|&i| i == after as usize).map(|i| i + 1).unwrap_or(siblings.len())
+                    }
+                };
+
+                tree.next += 1;
+                let handle = tree.next;
+                tree.items.insert(handle, new_item);
+                tree.siblings_mut(parent).insert(index, handle);
+
+                handle as LRESULT
+            }).unwrap_or(0)
+        },
+        TVM_DELETEITEM => {
+            let items: Vec<(usize, LPARAM)> = with_window(hwnd, |window| {
+                let tree = &window.tree;
+                let roots: Vec<usize> = match l as HTREEITEM {
+                    i if i.is_null() || i == TVI_ROOT => tree.roots.clone(),
+                    i if tree.items.contains_key(&(i as usize)) => vec![i as usize],
+                    _ => Vec::new()
+                };
+
+                roots.iter().flat_map(|&root| tree.subtree(root)).map(|i| (i, tree.items[&i].param)).collect()
+            }).unwrap_or_default();
+
+            if items.is_empty() {
+                return 0;
+            }
+
+            for &(item, param) in items.iter() {
+                notify_tree_parent(hwnd, TVN_DELETEITEMW, 0, item, 0, param);
+            }
+
+            with_window(hwnd, |window| {
+                let tree = &mut window.tree;
+                for &(item, _) in items.iter() {
+                    if let Some(removed) = tree.items.remove(&item) {
+                        tree.siblings_mut(removed.parent).retain(|&i| i != item);
+                    }
+                }
+
+                if !tree.items.contains_key(&tree.selected) {
+                    tree.selected = 0;
+                }
+            });
+
+            1
+        },
+        TVM_GETNEXTITEM => with_window(hwnd, |window| {
+            let tree = &window.tree;
+            let item = l as usize;
+            let parent = tree.items.get(&item).map(|i| i.parent).unwrap_or(0);
+            let sibling = |offset: isize| {
+                let siblings = tree.siblings(parent);
+                let index = siblings.iter().position(|&i| i == item)? as isize + offset;
+                match index < 0 {
+                    true => None,
+                    false => siblings.get(index as usize).cloned()
+                }
+            };
+
+            let next = match w {
+                TVGN_ROOT => tree.roots.first().cloned(),
+                TVGN_NEXT => sibling(1),
+                TVGN_PREVIOUS => sibling(-1),
+                TVGN_PARENT => Some(parent).filter(|&p| p != 0),
+                TVGN_CHILD => tree.items.get(&item).and_then(|i| i.children.first().cloned()),
+                TVGN_CARET => Some(tree.selected).filter(|&s| s != 0),
+                _ => None
+            };
+
+            next.unwrap_or(0) as LRESULT
+        }).unwrap_or(0),
+        TVM_GETITEMW => {
+            let item = &mut *(l as *mut TVITEMW);
+            let handle = item.hItem as usize;
+            let values = with_window(hwnd, |window| {
+                let selected = window.tree.selected == handle;
+                window.tree.items.get(&handle).map(|i| {
+                    let state = i.state | if selected { TVIS_SELECTED } else { 0 };
+                    (i.text.clone(), i.param, i.children_count, i.children.len(), state)
+                })
+            }).unwrap_or(None);
+
+            let (text, param, children_count, children, state) = match values {
+                Some(values) => values,
+                None => { return 0; }
+            };
+
+            if item.mask & TVIF_TEXT == TVIF_TEXT {
+                write_wide(&text, item.pszText, item.cchTextMax);
+            }
+
+            if item.mask & TVIF_PARAM == TVIF_PARAM {
+                item.lParam = param;
+            }
+
+            if item.mask & TVIF_STATE == TVIF_STATE {
+                item.state = state & item.stateMask;
+            }
+
+            if item.mask & TVIF_CHILDREN == TVIF_CHILDREN {
+                item.cChildren = tree_item_children(hwnd, handle, param, children_count, children);
+            }
+
+            1
+        },
+        TVM_SETITEMW => {
+            let item = &*(l as *const TVITEMW);
+            let handle = item.hItem as usize;
+            with_window(hwnd, |window| {
+                let tree = &mut window.tree;
+                let tree_item = match tree.items.get_mut(&handle) {
+                    Some(i) => i,
+                    None => { return 0; }
+                };
+
+                if item.mask & TVIF_TEXT == TVIF_TEXT { tree_item.text = read_wide(item.pszText); }
+                if item.mask & TVIF_PARAM == TVIF_PARAM { tree_item.param = item.lParam; }
+                if item.mask & TVIF_CHILDREN == TVIF_CHILDREN { tree_item.children_count = Some(item.cChildren); }
+                if item.mask & TVIF_STATE == TVIF_STATE {
+                    tree_item.state = (tree_item.state & !item.stateMask) | (item.state & item.stateMask & !TVIS_SELECTED);
+                    if item.stateMask & TVIS_SELECTED == TVIS_SELECTED {
+                        let selected = item.state & TVIS_SELECTED == TVIS_SELECTED;
+                        if selected {
+                            tree.selected = handle;
+                        } else if tree.selected == handle {
+                            tree.selected = 0;
+                        }
+                    }
+                }
+
+                1
+            }).unwrap_or(0)
+        },
+        TVM_GETITEMSTATE => with_window(hwnd, |window| {
+            let selected = if window.tree.selected == w { TVIS_SELECTED } else { 0 };
+            window.tree.items.get(&w).map(|i| ((i.state | selected) & l as UINT) as LRESULT).unwrap_or(0)
+        }).unwrap_or(0),
+        TVM_EXPAND => {
+            let handle = l as usize;
+            let values = with_window(hwnd, |window| {
+                window.tree.items.get(&handle).map(|i| (i.state, i.param, i.children_count, i.children.len()))
+            }).unwrap_or(None);
+
+            let (state, param, children_count, children) = match values {
+                Some(values) => values,
+                None => { return 0; }
+            };
+
+            let expanded = state & TVIS_EXPANDED == TVIS_EXPANDED;
+            let expand = match w & (TVE_COLLAPSE | TVE_EXPAND | TVE_TOGGLE) {
+                TVE_EXPAND => true,
+                TVE_COLLAPSE => false,
+                _ => !expanded
+            };
+
+            if expand == expanded || (expand && tree_item_children(hwnd, handle, param, children_count, children) == 0) {
+                return 0;
+            }
+
+            let action = if expand { TVE_EXPAND } else { TVE_COLLAPSE };
+            if notify_tree_parent(hwnd, TVN_ITEMEXPANDINGW, action as UINT, handle, state, param) != 0 {
+                return 0;
+            }
+
+            let new_state = match expand {
+                true => state | TVIS_EXPANDED | TVIS_EXPANDEDONCE,
+                false => state & !TVIS_EXPANDED
+            };
+
+            with_window(hwnd, |window| {
+                if let Some(i) = window.tree.items.get_mut(&handle) {
+                    i.state = new_state;
+                }
+            });
+
+            notify_tree_parent(hwnd, TVN_ITEMEXPANDEDW, action as UINT, handle, new_state, param);
+
+            1
+        },
+        TVM_GETCOUNT => with_window(hwnd, |window| window.tree.items.len() as LRESULT).unwrap_or(0),
+        _ => DefWindowProcW(hwnd, msg, w, l)
+    }
+}
+
+/// Returns the `cChildren` value of a tree item. Items with `I_CHILDRENCALLBACK` ask their parent.
+unsafe fn tree_item_children(hwnd: HWND, item: usize, param: LPARAM, children_count: Option<c_int>, children: usize) -> c_int {
+    use winapi::um::commctrl::{NMTVDISPINFOW, TVN_GETDISPINFOW, TVIF_CHILDREN, I_CHILDRENCALLBACK};
+    use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+
+    match children_count {
+        None => (children > 0) as c_int,
+        Some(I_CHILDRENCALLBACK) => {
+            let (parent, id) = match with_window(hwnd, |window| (window.parent, window.id)) {
+                Some(values) => values,
+                None => { return 0; }
+            };
+
+            let mut info: NMTVDISPINFOW = mem::zeroed();
+            info.hdr = NMHDR { hwndFrom: hwnd, idFrom: id, code: TVN_GETDISPINFOW };
+            info.item.mask = TVIF_CHILDREN;
+            info.item.hItem = item as _;
+            info.item.lParam = param;
+
+            SendMessageW(parent as HWND, WM_NOTIFY, id, &mut info as *mut NMTVDISPINFOW as LPARAM);
+            info.item.cChildren
+        },
+        Some(count) => count
+    }
+}
+
+/// Sends a `NMTREEVIEWW` notification to the parent of a tree view. `TVN_DELETEITEMW` sends the item in `itemOld`.
+unsafe fn notify_tree_parent(hwnd: HWND, code: UINT, action: UINT, item: usize, state: UINT, param: LPARAM) -> LRESULT {
+    use winapi::um::commctrl::{NMTREEVIEWW, TVN_DELETEITEMW};
+    use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+
+    let (parent, id) = match with_window(hwnd, |window| (window.parent, window.id)) {
+        Some(values) => values,
+        None => { return 0; }
+    };
+
+    let mut notif: NMTREEVIEWW = mem::zeroed();
+    notif.hdr = NMHDR { hwndFrom: hwnd, idFrom: id, code };
+    notif.action = action;
+
+    let tree_item = match code {
+        TVN_DELETEITEMW => &mut notif.itemOld,
+        _ => &mut notif.itemNew
+    };
+
+    tree_item.hItem = item as _;
+    tree_item.state = state;
+    tree_item.lParam = param;
+
+    SendMessageW(parent as HWND, WM_NOTIFY, id, &mut notif as *mut NMTREEVIEWW as LPARAM)
+}
+
+/// Sends a `NMLISTVIEW` notification to the parent of a list view
+unsafe fn notify_list_parent(hwnd: HWND, code: UINT, row: c_int, old_state: UINT, new_state: UINT, param: LPARAM) -> LRESULT {
+    use winapi::um::commctrl::{NMLISTVIEW, LVIF_STATE};
+    use winapi::um::winuser::{NMHDR, WM_NOTIFY};
+
+    let (parent, id) = match with_window(hwnd, |window| (window.parent, window.id)) {
+        Some(values) => values,
+        None => { return 0; }
+    };
+
+    let mut notif: NMLISTVIEW = mem::zeroed();
+    notif.hdr = NMHDR { hwndFrom: hwnd, idFrom: id, code };
+    notif.iItem = row;
+    notif.uOldState = old_state;
+    notif.uNewState = new_state;
+    notif.uChanged = if old_state != new_state { LVIF_STATE } else { 0 };
+    notif.lParam = param;
+
+    SendMessageW(parent as HWND, WM_NOTIFY, id, &mut notif as *mut NMLISTVIEW as LPARAM)
+}
+
+/// Sends a `WM_COMMAND` notification to the parent of a control
+unsafe fn notify_parent(hwnd: HWND, code: u16) {
+    use winapi::um::winuser::WM_COMMAND;
+
+    if let Some((parent, id)) = with_window(hwnd, |window| (window.parent, window.id)) {
+        if parent != 0 {
+            let w = ((code as usize) << 16) | (id & 0xFFFF);
+            SendMessageW(parent as HWND, WM_COMMAND, w, hwnd as LPARAM);
+        }
+    }
+}
+
+
+//
+// Window creation
+//
+
+pub unsafe fn GetModuleHandleW(_name: LPCWSTR) -> HMODULE {
+    // Any non null value will do, the module is never used
+    1 as HMODULE
+}
+
+pub unsafe fn GetLastError() -> DWORD {
+    STATE.with(|state| state.borrow().last_error)
+}
+
+fn set_last_error(code: DWORD) {
+    STATE.with(|state| state.borrow_mut().last_error = code);
+}
+
+pub unsafe fn LoadCursorW(_instance: HINSTANCE, _name: LPCWSTR) -> HCURSOR {
+    ptr::null_mut()
+}
+
+pub unsafe fn RegisterClassExW(class: *const WNDCLASSEXW) -> ATOM {
+    let class = &*class;
+    let name = String::from_utf16_lossy(&read_wide(class.lpszClassName));
+    STATE.with(|state| state.borrow_mut().classes.insert(name, class.lpfnWndProc));
+    1
+}
+
+pub unsafe fn AdjustWindowRectEx(_rect: *mut RECT, _style: DWORD, _menu: BOOL, _ex_style: DWORD) -> BOOL {
+    // Headless windows do not have a non client area
+    1
+}
+
+pub unsafe fn CreateWindowExW(
+    ex_style: DWORD,
+    class_name: LPCWSTR,
+    window_name: LPCWSTR,
+    style: DWORD,
+    x: c_int, y: c_int,
+    width: c_int, height: c_int,
+    parent: HWND,
+    menu: HMENU,
+    _instance: HINSTANCE,
+    _param: LPVOID
+) -> HWND {
+    use winapi::um::winuser::{WM_CREATE, WS_CHILD};
+
+    let class_name = canonical_class_name(String::from_utf16_lossy(&read_wide(class_name)));
+    let parent = match parent as isize {
+        HWND_MESSAGE => 0,
+        p => p as usize
+    };
+
+    if parent != 0 && !window_exists(parent as HWND) {
+        set_last_error(winapi::shared::winerror::ERROR_INVALID_WINDOW_HANDLE);
+        return ptr::null_mut();
+    }
+
+    let hwnd = NEXT_ID.fetch_add(1, Ordering::SeqCst) << 4;
+    OWNERS.lock().unwrap().insert(hwnd, thread::current().id());
+
+    let hwnd = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let window = HeadlessWindow {
+            class_name,
+            text: read_wide(window_name),
+            style,
+            ex_style,
+            position: (x, y),
+            size: (width, height),
+            parent,
+            id: if style & WS_CHILD == WS_CHILD { menu as usize } else { 0 },
+            check: 0,
+            font: 0,
+            icon: 0,
+            longs: HashMap::new(),
+            subclasses: Vec::new(),
+            scroll: [(0, 0, 0, 0); 2],
+            list: Default::default(),
+            tree: Default::default(),
+        };
+
+        state.windows.insert(hwnd, window);
+        hwnd as HWND
+    });
+
+    SendMessageW(hwnd, WM_CREATE, 0, 0);
+
+    hwnd
+}
+
+pub unsafe fn DestroyWindow(hwnd: HWND) -> BOOL {
+    use winapi::um::winuser::WM_DESTROY;
+
+    if !window_exists(hwnd) {
+        return 0;
+    }
+
+    // Children are destroyed before their parent
+    for child in descendants(hwnd as usize).into_iter().rev() {
+        SendMessageW(child as HWND, WM_DESTROY, 0, 0);
+        STATE.with(|state| state.borrow_mut().windows.remove(&child));
+        OWNERS.lock().unwrap().remove(&child);
+    }
+
+    SendMessageW(hwnd, WM_DESTROY, 0, 0);
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.windows.remove(&(hwnd as usize));
+        if state.focus == hwnd as usize {
+            state.focus = 0;
+        }
+        if state.capture == hwnd as usize {
+            state.capture = 0;
+        }
+    });
+    OWNERS.lock().unwrap().remove(&(hwnd as usize));
+
+    1
+}
+
+pub unsafe fn IsWindow(hwnd: HWND) -> BOOL {
+    window_exists(hwnd) as BOOL
+}
+
+pub unsafe fn DefWindowProcW(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    use winapi::um::winuser::{WM_SETTEXT, WM_GETTEXT, WM_GETTEXTLENGTH, WM_SETFONT, WM_GETFONT, WM_SETICON, WM_GETICON, WM_CLOSE};
+
+    match msg {
+        WM_SETTEXT => {
+            let text = read_wide(l as LPCWSTR);
+            with_window(hwnd, |window| window.text = text);
+            1
+        },
+        WM_GETTEXT => {
+            let text = with_window(hwnd, |window| window.text.clone()).unwrap_or_default();
+            write_wide(&text, l as LPWSTR, w as c_int) as LRESULT
+        },
+        WM_GETTEXTLENGTH => with_window(hwnd, |window| window.text.len() as LRESULT).unwrap_or(0),
+        WM_SETFONT => {
+            with_window(hwnd, |window| window.font = w);
+            0
+        },
+        WM_GETFONT => with_window(hwnd, |window| window.font as LRESULT).unwrap_or(0),
+        WM_SETICON => with_window(hwnd, |window| mem::replace(&mut window.icon, l)).unwrap_or(0),
+        WM_GETICON => with_window(hwnd, |window| window.icon).unwrap_or(0),
+        WM_CLOSE => {
+            DestroyWindow(hwnd);
+            0
+        },
+        _ => 0
+    }
+}
+
+
+//
+// Window properties
+//
+
+pub unsafe fn SetWindowTextW(hwnd: HWND, text: LPCWSTR) -> BOOL {
+    use winapi::um::winuser::WM_SETTEXT;
+
+    if !window_exists(hwnd) {
+        return 0;
+    }
+
+    SendMessageW(hwnd, WM_SETTEXT, 0, text as LPARAM) as BOOL
+}
+
+pub unsafe fn GetWindowTextW(hwnd: HWND, buffer: LPWSTR, max_count: c_int) -> c_int {
+    match with_window(hwnd, |window| window.text.clone()) {
+        Some(text) => write_wide(&text, buffer, max_count),
+        None => 0
+    }
+}
+
+pub unsafe fn GetWindowTextLengthW(hwnd: HWND) -> c_int {
+    with_window(hwnd, |window| window.text.len() as c_int).unwrap_or(0)
+}
+
+pub unsafe fn GetClassNameW(hwnd: HWND, buffer: LPWSTR, max_count: c_int) -> c_int {
+    match with_window(hwnd, |window| window.class_name.encode_utf16().collect::<Vec<u16>>()) {
+        Some(name) => write_wide(&name, buffer, max_count),
+        None => 0
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+pub unsafe fn GetWindowLongPtrW(hwnd: HWND, index: c_int) -> LONG_PTR {
+    get_window_long(hwnd, index)
+}
+
+#[cfg(target_arch = "x86_64")]
+pub unsafe fn SetWindowLongPtrW(hwnd: HWND, index: c_int, value: LONG_PTR) -> LONG_PTR {
+    set_window_long(hwnd, index, value)
+}
+
+#[cfg(target_arch = "x86")]
+pub unsafe fn GetWindowLongW(hwnd: HWND, index: c_int) -> LONG {
+    get_window_long(hwnd, index) as LONG
+}
+
+#[cfg(target_arch = "x86")]
+pub unsafe fn SetWindowLongW(hwnd: HWND, index: c_int, value: LONG) -> LONG {
+    set_window_long(hwnd, index, value as LONG_PTR) as LONG
+}
+
+fn get_window_long(hwnd: HWND, index: c_int) -> LONG_PTR {
+    use winapi::um::winuser::{GWL_STYLE, GWL_EXSTYLE};
+
+    with_window(hwnd, |window| match index {
+        GWL_STYLE => window.style as LONG_PTR,
+        GWL_EXSTYLE => window.ex_style as LONG_PTR,
+        i => window.longs.get(&i).cloned().unwrap_or(0)
+    }).unwrap_or(0)
+}
+
+fn set_window_long(hwnd: HWND, index: c_int, value: LONG_PTR) -> LONG_PTR {
+    use winapi::um::winuser::{GWL_STYLE, GWL_EXSTYLE};
+
+    with_window(hwnd, |window| match index {
+        GWL_STYLE => mem::replace(&mut window.style, value as DWORD) as LONG_PTR,
+        GWL_EXSTYLE => mem::replace(&mut window.ex_style, value as DWORD) as LONG_PTR,
+        i => window.longs.insert(i, value).unwrap_or(0)
+    }).unwrap_or(0)
+}
+
+pub unsafe fn SetWindowPos(hwnd: HWND, _after: HWND, x: c_int, y: c_int, cx: c_int, cy: c_int, flags: UINT) -> BOOL {
+    use winapi::um::winuser::{SWP_NOMOVE, SWP_NOSIZE, WM_MOVE, WM_SIZE, SIZE_RESTORED};
+
+    if !window_exists(hwnd) {
+        return 0;
+    }
+
+    if flags & SWP_NOMOVE == 0 {
+        with_window(hwnd, |window| window.position = (x, y));
+        SendMessageW(hwnd, WM_MOVE, 0, make_lparam(x, y));
+    }
+
+    if flags & SWP_NOSIZE == 0 {
+        with_window(hwnd, |window| window.size = (cx, cy));
+        SendMessageW(hwnd, WM_SIZE, SIZE_RESTORED, make_lparam(cx, cy));
+    }
+
+    1
+}
+
+pub unsafe fn GetWindowRect(hwnd: HWND, rect: *mut RECT) -> BOOL {
+    let size = match with_window(hwnd, |window| window.size) {
+        Some(size) => size,
+        None => { return 0; }
+    };
+
+    let (x, y) = absolute_position(hwnd as usize);
+    *rect = RECT { left: x, top: y, right: x + size.0, bottom: y + size.1 };
+
+    1
+}
+
+pub unsafe fn GetClientRect(hwnd: HWND, rect: *mut RECT) -> BOOL {
+    match with_window(hwnd, |window| window.size) {
+        Some((w, h)) => {
+            *rect = RECT { left: 0, top: 0, right: w, bottom: h };
+            1
+        },
+        None => 0
+    }
+}
+
+pub unsafe fn ScreenToClient(hwnd: HWND, point: *mut POINT) -> BOOL {
+    if !window_exists(hwnd) {
+        return 0;
+    }
+
+    let (x, y) = absolute_position(hwnd as usize);
+    let point = &mut *point;
+    point.x -= x;
+    point.y -= y;
+
+    1
+}
+
+pub unsafe fn ShowWindow(hwnd: HWND, cmd: c_int) -> BOOL {
+    use winapi::um::winuser::{SW_HIDE, WS_VISIBLE};
+
+    with_window(hwnd, |window| {
+        let was_visible = window.style & WS_VISIBLE == WS_VISIBLE;
+        match cmd {
+            SW_HIDE => { window.style &= !WS_VISIBLE; },
+            _ => { window.style |= WS_VISIBLE; }
+        }
+
+        was_visible as BOOL
+    }).unwrap_or(0)
+}
+
+/// A window is only visible if all its parents are also visible
+pub unsafe fn IsWindowVisible(hwnd: HWND) -> BOOL {
+    use winapi::um::winuser::WS_VISIBLE;
+
+    let mut current = hwnd as usize;
+    loop {
+        match with_window(current as HWND, |window| (window.style & WS_VISIBLE == WS_VISIBLE, window.parent)) {
+            Some((true, 0)) => { return 1; },
+            Some((true, parent)) => { current = parent; },
+            _ => { return 0; }
+        }
+    }
+}
+
+pub unsafe fn InvalidateRect(hwnd: HWND, _rect: *const RECT, _erase: BOOL) -> BOOL {
+    window_exists(hwnd) as BOOL
+}
+
+pub unsafe fn UpdateWindow(hwnd: HWND) -> BOOL {
+    window_exists(hwnd) as BOOL
+}
+
+pub unsafe fn SetFocus(hwnd: HWND) -> HWND {
+    if !window_exists(hwnd) {
+        return ptr::null_mut();
+    }
+
+    STATE.with(|state| mem::replace(&mut state.borrow_mut().focus, hwnd as usize) as HWND)
+}
+
+pub unsafe fn GetFocus() -> HWND {
+    STATE.with(|state| state.borrow().focus as HWND)
+}
+
+pub unsafe fn SetCapture(hwnd: HWND) -> HWND {
+    STATE.with(|state| mem::replace(&mut state.borrow_mut().capture, hwnd as usize) as HWND)
+}
+
+pub unsafe fn ReleaseCapture() -> BOOL {
+    use winapi::um::winuser::WM_CAPTURECHANGED;
+
+    let previous = STATE.with(|state| mem::replace(&mut state.borrow_mut().capture, 0));
+    if previous != 0 {
+        SendMessageW(previous as HWND, WM_CAPTURECHANGED, 0, 0);
+    }
+
+    1
+}
+
+pub unsafe fn SetScrollInfo(hwnd: HWND, bar: c_int, info: *const SCROLLINFO, _redraw: BOOL) -> c_int {
+    use winapi::um::winuser::{SIF_RANGE, SIF_PAGE, SIF_POS};
+
+    let info = &*info;
+    let index = (bar & 1) as usize;
+    with_window(hwnd, |window| {
+        let (mut min, mut max, mut page, mut pos) = window.scroll[index];
+        if info.fMask & SIF_RANGE == SIF_RANGE { min = info.nMin; max = info.nMax; }
+        if info.fMask & SIF_PAGE == SIF_PAGE { page = info.nPage; }
+        if info.fMask & SIF_POS == SIF_POS { pos = info.nPos; }
+
+        // Same clamping as the system scrollbars
+        let max_pos = (max - (page.max(1) as c_int) + 1).max(min);
+        pos = pos.max(min).min(max_pos);
+
+        window.scroll[index] = (min, max, page, pos);
+        pos
+    }).unwrap_or(0)
+}
+
+pub unsafe fn GetScrollInfo(hwnd: HWND, bar: c_int, info: *mut SCROLLINFO) -> BOOL {
+    let info = &mut *info;
+    let index = (bar & 1) as usize;
+    match with_window(hwnd, |window| window.scroll[index]) {
+        Some((min, max, page, pos)) => {
+            info.nMin = min;
+            info.nMax = max;
+            info.nPage = page;
+            info.nPos = pos;
+            info.nTrackPos = pos;
+            1
+        },
+        None => 0
+    }
+}
+
+pub unsafe fn SetCursor(_cursor: HCURSOR) -> HCURSOR {
+    // There is no mouse cursor in the headless backend
+    ptr::null_mut()
+}
+
+
+//
+// Keyboard
+//
+
+/// Keep track of the keys held down. There is no keyboard in the headless backend, so this is done when a key message is sent.
+fn update_key_state(msg: UINT, w: WPARAM) {
+    use winapi::um::winuser::{WM_KEYDOWN, WM_KEYUP, WM_SYSKEYDOWN, WM_SYSKEYUP};
+
+    match msg {
+        WM_KEYDOWN | WM_SYSKEYDOWN => STATE.with(|state| state.borrow_mut().keys.insert(w as c_int)),
+        WM_KEYUP | WM_SYSKEYUP => STATE.with(|state| state.borrow_mut().keys.remove(&(w as c_int))),
+        _ => false
+    };
+}
+
+/// Move the cursor to the position of a client mouse message. The wheel messages are already in screen coordinates.
+fn update_cursor(hwnd: HWND, msg: UINT, l: LPARAM) {
+    use winapi::um::winuser::{WM_MOUSEFIRST, WM_MOUSELAST, WM_MOUSEWHEEL, WM_MOUSEHWHEEL, WM_MOUSEHOVER};
+    use winapi::shared::minwindef::{LOWORD, HIWORD};
+
+    let (x, y) = (LOWORD(l as u32) as i16 as i32, HIWORD(l as u32) as i16 as i32);
+    let cursor = match msg {
+        WM_MOUSEWHEEL | WM_MOUSEHWHEEL => (x, y),
+        WM_MOUSEFIRST ..= WM_MOUSELAST | WM_MOUSEHOVER => {
+            let (wx, wy) = absolute_position(hwnd as usize);
+            (wx + x, wy + y)
+        },
+        _ => { return; }
+    };
+
+    STATE.with(|state| state.borrow_mut().cursor = cursor);
+}
+
+pub unsafe fn GetCursorPos(point: *mut POINT) -> BOOL {
+    let (x, y) = STATE.with(|state| state.borrow().cursor);
+    *point = POINT { x, y };
+    1
+}
+
+pub unsafe fn TrackMouseEvent(track: *mut TRACKMOUSEEVENT) -> BOOL {
+    use winapi::um::winuser::{TME_CANCEL, TME_QUERY, TME_HOVER, TME_LEAVE};
+
+    let track = &mut *track;
+    let flags = track.dwFlags & (TME_HOVER | TME_LEAVE);
+    let hwnd = track.hwndTrack as usize;
+
+    STATE.with(|state| {
+        let tracking = &mut state.borrow_mut().mouse_tracking;
+        if track.dwFlags & TME_QUERY == TME_QUERY {
+            track.dwFlags = tracking.get(&hwnd).cloned().unwrap_or(0);
+        } else if track.dwFlags & TME_CANCEL == TME_CANCEL {
+            let remaining = tracking.get(&hwnd).cloned().unwrap_or(0) & !flags;
+            tracking.insert(hwnd, remaining);
+        } else {
+            *tracking.entry(hwnd).or_insert(0) |= flags;
+        }
+    });
+
+    1
+}
+
+/// `WM_MOUSEHOVER` ends the hover tracking of a window and `WM_MOUSELEAVE` ends every tracking
+fn update_mouse_tracking(hwnd: HWND, msg: UINT) {
+    use winapi::um::winuser::{WM_MOUSEHOVER, WM_MOUSELEAVE, TME_HOVER};
+
+    let ended = match msg {
+        WM_MOUSEHOVER => TME_HOVER,
+        WM_MOUSELEAVE => !0,
+        _ => { return; }
+    };
+
+    STATE.with(|state| {
+        if let Some(flags) = state.borrow_mut().mouse_tracking.get_mut(&(hwnd as usize)) {
+            *flags &= !ended;
+        }
+    });
+}
+
+pub unsafe fn GetKeyState(key: c_int) -> SHORT {
+    match STATE.with(|state| state.borrow().keys.contains(&key)) {
+        true => 0x8000u16 as SHORT,
+        false => 0
+    }
+}
+
+pub unsafe fn CreateAcceleratorTableW(accels: *mut ACCEL, count: c_int) -> HACCEL {
+    let accels = ::std::slice::from_raw_parts(accels, count.max(0) as usize).to_vec();
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    STATE.with(|state| state.borrow_mut().accelerators.insert(id, accels));
+    id as HACCEL
+}
+
+pub unsafe fn DestroyAcceleratorTable(table: HACCEL) -> BOOL {
+    STATE.with(|state| state.borrow_mut().accelerators.remove(&(table as usize)).is_some()) as BOOL
+}
+
+/// Only virtual key accelerators are supported. The modifiers are read from `GetKeyState`.
+pub unsafe fn TranslateAcceleratorW(hwnd: HWND, table: HACCEL, msg: *mut MSG) -> c_int {
+    use winapi::um::winuser::{WM_KEYDOWN, WM_SYSKEYDOWN, WM_COMMAND, FSHIFT, FCONTROL, FALT, VK_SHIFT, VK_CONTROL, VK_MENU};
+
+    let msg = &*msg;
+    if msg.message != WM_KEYDOWN && msg.message != WM_SYSKEYDOWN {
+        return 0;
+    }
+
+    let mut flags = 0;
+    if GetKeyState(VK_SHIFT) < 0 { flags |= FSHIFT; }
+    if GetKeyState(VK_CONTROL) < 0 { flags |= FCONTROL; }
+    if GetKeyState(VK_MENU) < 0 { flags |= FALT; }
+
+    let cmd = STATE.with(|state| {
+        state.borrow().accelerators.get(&(table as usize)).and_then(|accels| {
+            accels.iter()
+                .find(|a| a.key as WPARAM == msg.wParam && a.fVirt & (FSHIFT|FCONTROL|FALT) == flags)
+                .map(|a| a.cmd)
+        })
+    });
+
+    match cmd {
+        Some(cmd) => {
+            SendMessageW(hwnd, WM_COMMAND, (1 << 16) | (cmd as WPARAM), 0);
+            1
+        },
+        None => 0
+    }
+}
+
+pub unsafe fn RegisterHotKey(hwnd: HWND, id: c_int, modifiers: UINT, key: UINT) -> BOOL {
+    use winapi::um::winuser::MOD_NOREPEAT;
+    use winapi::shared::winerror::ERROR_HOTKEY_ALREADY_REGISTERED;
+
+    let modifiers = modifiers & !(MOD_NOREPEAT as UINT);
+    let mut hotkeys = HOTKEYS.lock().unwrap();
+    if hotkeys.iter().any(|(k, v)| *k != (hwnd as usize, id) && *v == (modifiers, key)) {
+        set_last_error(ERROR_HOTKEY_ALREADY_REGISTERED);
+        return 0;
+    }
+
+    hotkeys.insert((hwnd as usize, id), (modifiers, key));
+    1
+}
+
+pub unsafe fn UnregisterHotKey(hwnd: HWND, id: c_int) -> BOOL {
+    HOTKEYS.lock().unwrap().remove(&(hwnd as usize, id)).is_some() as BOOL
+}
+
+
+//
+// Parent / children
+//
+
+pub unsafe fn GetParent(hwnd: HWND) -> HWND {
+    with_window(hwnd, |window| window.parent as HWND).unwrap_or(ptr::null_mut())
+}
+
+pub unsafe fn SetParent(hwnd: HWND, parent: HWND) -> HWND {
+    if !parent.is_null() && !window_exists(parent) {
+        return ptr::null_mut();
+    }
+
+    with_window(hwnd, |window| mem::replace(&mut window.parent, parent as usize) as HWND).unwrap_or(ptr::null_mut())
+}
+
+pub unsafe fn GetAncestor(hwnd: HWND, _flags: UINT) -> HWND {
+    use winapi::um::winuser::WS_CHILD;
+
+    // NWG only ever asks for GA_ROOT. Like in user32, owned top level windows (ex: dialogs) are roots.
+    let mut current = hwnd;
+    loop {
+        match with_window(current, |window| (window.parent, window.style & WS_CHILD)) {
+            Some((0, _)) | Some((_, 0)) => { return current; },
+            Some((parent, _)) => { current = parent as HWND; },
+            None => { return ptr::null_mut(); }
+        }
+    }
+}
+
+pub unsafe fn EnumChildWindows(hwnd: HWND, callback: WNDENUMPROC, l: LPARAM) -> BOOL {
+    let callback = match callback {
+        Some(cb) => cb,
+        None => { return 0; }
+    };
+
+    for child in descendants(hwnd as usize) {
+        if window_exists(child as HWND) && callback(child as HWND, l) == 0 {
+            break;
+        }
+    }
+
+    1
+}
+
+
+//
+// Subclasses
+//
+
+pub unsafe fn SetWindowSubclass(hwnd: HWND, proc_: SUBCLASSPROC, id: UINT_PTR, data: DWORD_PTR) -> BOOL {
+    let key = proc_.map(|p| p as usize);
+    with_window(hwnd, |window| {
+        let existing = window.subclasses.iter_mut().find(|s| s.proc_.map(|p| p as usize) == key && s.id == id);
+        match existing {
+            Some(subclass) => { subclass.data = data; },
+            None => { window.subclasses.push(Subclass { proc_, id, data }); }
+        }
+
+        1
+    }).unwrap_or(0)
+}
+
+pub unsafe fn GetWindowSubclass(hwnd: HWND, proc_: SUBCLASSPROC, id: UINT_PTR, data: *mut DWORD_PTR) -> BOOL {
+    let key = proc_.map(|p| p as usize);
+    let subclass = with_window(hwnd, |window| {
+        window.subclasses.iter().find(|s| s.proc_.map(|p| p as usize) == key && s.id == id).copied()
+    });
+
+    match subclass {
+        Some(Some(subclass)) => {
+            if !data.is_null() {
+                *data = subclass.data;
+            }
+            1
+        },
+        _ => 0
+    }
+}
+
+pub unsafe fn RemoveWindowSubclass(hwnd: HWND, proc_: SUBCLASSPROC, id: UINT_PTR) -> BOOL {
+    let key = proc_.map(|p| p as usize);
+    with_window(hwnd, |window| {
+        let index = window.subclasses.iter().position(|s| s.proc_.map(|p| p as usize) == key && s.id == id);
+        match index {
+            Some(i) => { window.subclasses.remove(i); 1 },
+            None => 0
+        }
+    }).unwrap_or(0)
+}
+
+pub unsafe fn DefSubclassProc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    let level = CHAIN.with(|chain| {
+        chain.borrow().iter().rev()
+            .find(|(handle, _)| *handle == hwnd as usize)
+            .map(|(_, level)| *level)
+    });
+
+    call_chain(hwnd, level.unwrap_or(0), msg, w, l)
+}
+
+
+//
+// Messages
+//
+
+pub unsafe fn SendMessageW(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    update_key_state(msg, w);
+    update_cursor(hwnd, msg, l);
+    update_mouse_tracking(hwnd, msg);
+
+    match with_window(hwnd, |window| window.subclasses.len()) {
+        Some(level) => call_chain(hwnd, level, msg, w, l),
+        None => 0
+    }
+}
+
+pub unsafe fn PostMessageW(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> BOOL {
+    post(hwnd, msg, w, l)
+}
+
+/// Unlike `PostMessageW`, this can be called from any thread, so the handle is validated against the owners list.
+pub unsafe fn SendNotifyMessageW(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> BOOL {
+    post(hwnd, msg, w, l)
+}
+
+/// Queue a message for the thread that owns `hwnd`. Thread messages (`hwnd` is null) go to the current thread.
+fn post(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> BOOL {
+    let owner = match hwnd.is_null() {
+        true => Some(thread::current().id()),
+        false => OWNERS.lock().unwrap().get(&(hwnd as usize)).cloned()
+    };
+
+    match owner {
+        Some(owner) => {
+            QUEUE.lock().unwrap().push_back((owner, hwnd as usize, msg, w, l));
+            1
+        },
+        None => 0
+    }
+}
+
+fn next_message(msg: &mut MSG) -> bool {
+    use winapi::um::winuser::WM_QUIT;
+
+    let current = thread::current().id();
+    let mut queue = QUEUE.lock().unwrap();
+    let next = queue.iter().position(|m| m.0 == current).and_then(|i| queue.remove(i));
+
+    match next {
+        Some((_, hwnd, message, w, l)) => {
+            msg.hwnd = hwnd as HWND;
+            msg.message = message;
+            msg.wParam = w;
+            msg.lParam = l;
+            true
+        },
+        None => {
+            msg.hwnd = ptr::null_mut();
+            msg.message = WM_QUIT;
+            msg.wParam = 0;
+            msg.lParam = 0;
+            false
+        }
+    }
+}
+
+pub unsafe fn GetMessageW(msg: *mut MSG, _hwnd: HWND, _min: UINT, _max: UINT) -> BOOL {
+    use winapi::um::winuser::WM_QUIT;
+
+    let msg = &mut *msg;
+    match next_message(msg) {
+        true => (msg.message != WM_QUIT) as BOOL,
+        false => 0
+    }
+}
+
+pub unsafe fn PeekMessageW(msg: *mut MSG, _hwnd: HWND, _min: UINT, _max: UINT, _remove: UINT) -> BOOL {
+    let msg = &mut *msg;
+    let mut next: MSG = mem::zeroed();
+    match next_message(&mut next) {
+        true => { *msg = next; 1 },
+        false => 0
+    }
+}
+
+pub unsafe fn TranslateMessage(_msg: *const MSG) -> BOOL {
+    0
+}
+
+pub unsafe fn DispatchMessageW(msg: *const MSG) -> LRESULT {
+    let msg = &*msg;
+    if msg.hwnd.is_null() {
+        return 0;
+    }
+
+    SendMessageW(msg.hwnd, msg.message, msg.wParam, msg.lParam)
+}
+
+pub unsafe fn IsDialogMessageW(_hwnd: HWND, _msg: *mut MSG) -> BOOL {
+    0
+}
+
+pub unsafe fn SetTimer(hwnd: HWND, id: UINT_PTR, interval: UINT, _callback: TIMERPROC) -> UINT_PTR {
+    STATE.with(|state| state.borrow_mut().timers.insert((hwnd as usize, id), interval));
+    id
+}
+
+pub unsafe fn KillTimer(hwnd: HWND, id: UINT_PTR) -> BOOL {
+    STATE.with(|state| state.borrow_mut().timers.remove(&(hwnd as usize, id)).is_some() as BOOL)
+}
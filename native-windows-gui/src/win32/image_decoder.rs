@@ -21,7 +21,7 @@ pub unsafe fn create_image_factory() -> Result<*mut IWICImagingFactory, NwgError
     );
 
     if result != S_OK {
-        return Err(NwgError::resource_create("Failed to create a image factory"));
+        return Err(NwgError::resource_create("Failed to create a image factory").with_hresult("CoCreateInstance", result));
     }
 
     Ok(image_factory)
@@ -44,7 +44,7 @@ pub unsafe fn create_decoder_from_file<'a>(fact: &IWICImagingFactory, path: &'a
     );
 
     if result != S_OK {
-        return Err(NwgError::resource_create("Failed to create a bitmap decoder"));
+        return Err(NwgError::resource_create("Failed to create a bitmap decoder").with_hresult("IWICImagingFactory::CreateDecoderFromFilename", result));
     }
 
     Ok(decoder)
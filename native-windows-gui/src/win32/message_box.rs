@@ -3,6 +3,9 @@ use crate::controls::ControlHandle;
 use winapi::shared::windef::HWND;
 use std::ptr;
 
+#[cfg(feature = "async")]
+use super::executor::{ModalFuture, modal_future};
+
 
 /**
     Enum of message box buttons (to use with `MessageParams` )
@@ -165,6 +168,48 @@ pub fn modal_message<'a, P: Into<ControlHandle>>(parent: P, params: &MessagePara
     inner_message(hwnd, params)
 }
 
+/**
+    Create an application wide message box from the message loop. Same as `message`, but returns a future that resolves
+    with the button clicked by the user. See `ModalFuture` for the re-entrancy guarantees.
+
+    Requires the `async` feature.
+
+    ```rust
+    use native_windows_gui as nwg;
+    async fn confirm() -> bool {
+        let p = nwg::MessageParams {
+            title: "Hey",
+            content: "Do you like cats?",
+            buttons: nwg::MessageButtons::YesNo,
+            icons: nwg::MessageIcons::Question
+        };
+
+        nwg::message_async(&p).await == nwg::MessageChoice::Yes
+    }
+    ```
+*/
+#[cfg(feature = "async")]
+pub fn message_async<'a>(params: &MessageParams<'a>) -> ModalFuture<'a, MessageChoice> {
+    let params = params.clone();
+    modal_future(move || message(&params))
+}
+
+
+/**
+    Create a message box for a selected window from the message loop. Same as `modal_message`, but returns a future that resolves
+    with the button clicked by the user. See `ModalFuture` for the re-entrancy guarantees.
+
+    The future panics if a non window control is used as parent (ex: a menu)
+
+    Requires the `async` feature.
+*/
+#[cfg(feature = "async")]
+pub fn modal_message_async<'a, P: Into<ControlHandle>>(parent: P, params: &MessageParams<'a>) -> ModalFuture<'a, MessageChoice> {
+    let parent = parent.into();
+    let params = params.clone();
+    modal_future(move || modal_message(parent, &params))
+}
+
 /**
     Display a message box and then panic. The message box has for style `MessageButtons::Ok` and `MessageIcons::Error` .
     It is recommended to use `modal_fatal_message` because it locks the window that creates the message box.
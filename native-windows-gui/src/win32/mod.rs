@@ -4,6 +4,10 @@ pub(crate) mod resources_helper;
 pub(crate) mod window;
 pub(crate) mod message_box;
 pub(crate) mod high_dpi;
+pub(crate) mod backend;
+
+#[cfg(feature = "headless")]
+pub(crate) mod headless;
 
 #[cfg(feature = "menu")]
 pub(crate) mod menu;
@@ -23,22 +27,43 @@ pub(crate) mod extern_canvas;
 #[cfg(feature = "image-decoder")]
 pub(crate) mod image_decoder;
 
+#[cfg(feature = "splitter")]
+pub(crate) mod splitter;
+
+#[cfg(feature = "scroll-panel")]
+pub(crate) mod scroll_panel;
+
+#[cfg(feature = "event-injection")]
+pub(crate) mod event_injection;
+
+#[cfg(feature = "accelerators")]
+pub(crate) mod accelerators;
+
+#[cfg(feature = "async")]
+pub(crate) mod executor;
+
 use std::{mem, ptr};
 use crate::errors::NwgError;
 
 
-use winapi::um::winuser::{IsDialogMessageW, GetAncestor, TranslateMessage, DispatchMessageW, GA_ROOT};
+use winapi::um::winuser::GA_ROOT;
+use backend::{IsDialogMessageW, GetAncestor, TranslateMessage, DispatchMessageW};
 
 /**
     Dispatch system events in the current thread. This method will pause the thread until there are events to process.
+    With the `async` feature, the tasks spawned with `spawn_local` are polled from this loop.
 */
 pub fn dispatch_thread_events() {
     use winapi::um::winuser::MSG;
-    use winapi::um::winuser::GetMessageW;
+    use backend::GetMessageW;
 
     unsafe {
         let mut msg: MSG = mem::zeroed();
         while GetMessageW(&mut msg, ptr::null_mut(), 0, 0) != 0 {
+            if translate_accelerators(&mut msg) {
+                continue;
+            }
+
             if IsDialogMessageW(GetAncestor(msg.hwnd, GA_ROOT), &mut msg) == 0 {
                 TranslateMessage(&msg); 
                 DispatchMessageW(&msg); 
@@ -56,13 +81,14 @@ pub fn dispatch_thread_events_with_callback<F>(mut cb: F)
     where F: FnMut() -> () + 'static
 {
     use winapi::um::winuser::MSG;
-    use winapi::um::winuser::{PeekMessageW, PM_REMOVE, WM_QUIT};
+    use winapi::um::winuser::{PM_REMOVE, WM_QUIT};
+    use backend::PeekMessageW;
 
     unsafe {
         let mut msg: MSG = mem::zeroed();
         while msg.message != WM_QUIT {
             let has_message = PeekMessageW(&mut msg, ptr::null_mut(), 0, 0, PM_REMOVE) != 0;
-            if has_message {
+            if has_message && !translate_accelerators(&mut msg) {
                 if IsDialogMessageW(GetAncestor(msg.hwnd, GA_ROOT), &mut msg) == 0 {
                     TranslateMessage(&msg); 
                     DispatchMessageW(&msg); 
@@ -74,11 +100,52 @@ pub fn dispatch_thread_events_with_callback<F>(mut cb: F)
     }
 }
 
+/**
+    Dispatch system events in the current thread until `done` returns true. Used by the modal dialogs.
+    `filter` is called for every message before it is dispatched. If it returns true, the message is not dispatched.
+
+    Returns false if the loop was stopped by `WM_QUIT`. The quit message is posted again so that the outer loop stops as well.
+*/
+#[cfg(feature = "dialog")]
+pub(crate) fn dispatch_modal_events<F1, F2>(mut filter: F1, done: F2) -> bool
+    where F1: FnMut(&winapi::um::winuser::MSG) -> bool, F2: Fn() -> bool
+{
+    use winapi::um::winuser::{MSG, WM_QUIT};
+    use backend::{GetMessageW, PostMessageW};
+
+    unsafe {
+        let mut msg: MSG = mem::zeroed();
+        while !done() {
+            if GetMessageW(&mut msg, ptr::null_mut(), 0, 0) == 0 {
+                PostMessageW(ptr::null_mut(), WM_QUIT, msg.wParam, 0);
+                return false;
+            }
+
+            if translate_accelerators(&mut msg) || filter(&msg) {
+                continue;
+            }
+
+            if IsDialogMessageW(GetAncestor(msg.hwnd, GA_ROOT), &mut msg) == 0 {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(feature = "accelerators")]
+unsafe fn translate_accelerators(msg: &mut winapi::um::winuser::MSG) -> bool { accelerators::translate_accelerators(msg) }
+
+#[cfg(not(feature = "accelerators"))]
+unsafe fn translate_accelerators(_msg: &mut winapi::um::winuser::MSG) -> bool { false }
+
 /**
     Break the events loop running on the current thread
 */
 pub fn stop_thread_dispatch() {
-  use winapi::um::winuser::PostMessageW;
+  use backend::PostMessageW;
   use winapi::um::winuser::WM_QUIT;
 
   unsafe { PostMessageW(ptr::null_mut(), WM_QUIT, 0, 0) };
@@ -88,6 +155,7 @@ pub fn stop_thread_dispatch() {
 /**
   Enable the Windows visual style in the application without having to use a manifest
 */
+#[cfg(not(feature = "headless"))]
 pub fn enable_visual_styles() {
     use winapi::shared::minwindef::{ULONG, DWORD, MAX_PATH};
     use winapi::shared::basetsd::ULONG_PTR;
@@ -125,10 +193,16 @@ pub fn enable_visual_styles() {
     }
 }
 
+/// The headless backend does not draw anything
+#[cfg(feature = "headless")]
+pub fn enable_visual_styles() {
+}
+
 /**
     Ensure that the dll containing the winapi controls is loaded.
     Also register the custom classes used by NWG
 */
+#[cfg(not(feature = "headless"))]
 pub fn init_common_controls() -> Result<(), NwgError> {
     use winapi::um::objbase::CoInitialize;
     use winapi::um::libloaderapi::LoadLibraryW;
@@ -177,13 +251,30 @@ pub fn init_common_controls() -> Result<(), NwgError> {
     tabs_init()?;
     extern_canvas_init()?;
     frame_init()?;
+    splitter_init()?;
+    scroll_panel_init()?;
     
     match unsafe { CoInitialize(ptr::null_mut()) } {
         S_OK | S_FALSE => Ok(()),
-        _ => Err(NwgError::initialization("CoInitialize failed"))
+        hr => Err(NwgError::initialization("CoInitialize failed").with_hresult("CoInitialize", hr))
     }
 }
 
+/**
+    Register the custom classes used by NWG in the headless backend.
+    The system classes are always available in the headless backend.
+*/
+#[cfg(feature = "headless")]
+pub fn init_common_controls() -> Result<(), NwgError> {
+    window::init_window_class()?;
+    tabs_init()?;
+    extern_canvas_init()?;
+    frame_init()?;
+    splitter_init()?;
+    scroll_panel_init()?;
+    Ok(())
+}
+
 #[cfg(feature = "tabs")]
 fn tabs_init() -> Result<(), NwgError> { tabs::create_tab_classes() }
 
@@ -202,3 +293,15 @@ fn frame_init() -> Result<(), NwgError> { window::create_frame_classes() }
 #[cfg(not(feature = "frame"))]
 fn frame_init() -> Result<(), NwgError> { Ok(()) }
 
+#[cfg(feature = "splitter")]
+fn splitter_init() -> Result<(), NwgError> { splitter::create_splitter_classes() }
+
+#[cfg(not(feature = "splitter"))]
+fn splitter_init() -> Result<(), NwgError> { Ok(()) }
+
+#[cfg(feature = "scroll-panel")]
+fn scroll_panel_init() -> Result<(), NwgError> { scroll_panel::create_scroll_panel_classes() }
+
+#[cfg(not(feature = "scroll-panel"))]
+fn scroll_panel_init() -> Result<(), NwgError> { Ok(()) }
+
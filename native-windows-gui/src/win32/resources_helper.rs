@@ -63,7 +63,7 @@ pub unsafe fn build_font(
     drop(fam);
 
     if handle.is_null() {
-        Err( NwgError::resource_create("Failed to create font") )
+        Err( NwgError::resource_create("Failed to create font").with_operation("CreateFontW") )
     } else {
         Ok( handle )
     }
@@ -108,7 +108,7 @@ pub unsafe fn build_image<'a>(
     }
 
     if handle.is_null() {
-        Err( NwgError::resource_create(format!("Failed to create image from source '{}' ", source)))
+        Err( NwgError::resource_create(format!("Failed to create image from source '{}' ", source)).with_last_error("LoadImageW"))
     } else {
         Ok(handle)
     }
@@ -196,7 +196,7 @@ pub unsafe fn bitmap_from_memory(source: &[u8]) -> Result<HANDLE, NwgError> {
     let header_size = fheader_size + iheader_size;
     if source.len() < header_size {
         let msg = format!("Invalid source. The source size ({} bytes) is smaller than the required headers size ({} bytes).", source.len(), header_size);
-        return Err(NwgError::ResourceCreationError(msg));
+        return Err(NwgError::resource_create(msg));
     }
 
     // Read the bitmap file header
@@ -232,8 +232,7 @@ pub unsafe fn bitmap_from_memory(source: &[u8]) -> Result<HANDLE, NwgError> {
 
     let data_ptr = source.as_ptr().offset(fheader.bfOffBits as isize) as *const c_void;
     if 0 == SetDIBits(hdc, bitmap, 0, h as u32, data_ptr, &info, DIB_RGB_COLORS) {
-        let msg = "SetDIBits failed.".to_string();
-        return Err(NwgError::ResourceCreationError(msg));
+        return Err(NwgError::resource_create("SetDIBits failed.").with_operation("SetDIBits"));
     }
 
     return Ok(bitmap as HANDLE);
@@ -265,23 +264,25 @@ pub unsafe fn create_file_dialog<'a, 'b>(
     let mut handle: *mut IFileDialog = ptr::null_mut();
     let r = CoCreateInstance(&clsid, ptr::null_mut(), CLSCTX_INPROC_SERVER, &uuid, mem::transmute(&mut handle) );
     if r != S_OK {
-        return Err(NwgError::file_dialog("Filedialog creation failed"));
+        return Err(NwgError::file_dialog("Filedialog creation failed").with_hresult("CoCreateInstance", r));
     }
 
     let file_dialog = &mut *handle;
     let mut flags = 0;
 
     // Set dialog options
-    if file_dialog.GetOptions(&mut flags) != S_OK {
+    let r = file_dialog.GetOptions(&mut flags);
+    if r != S_OK {
         file_dialog.Release(); 
-        return Err(NwgError::file_dialog("Filedialog creation failed"));
+        return Err(NwgError::file_dialog("Filedialog creation failed").with_hresult("IFileDialog::GetOptions", r));
     }
  
     let use_dir = if action == FileDialogAction::OpenDirectory { FOS_PICKFOLDERS } else { 0 };
     let multiselect = if multiselect { FOS_ALLOWMULTISELECT } else { 0 };
-    if file_dialog.SetOptions(flags | FOS_FORCEFILESYSTEM | use_dir | multiselect) != S_OK {
+    let r = file_dialog.SetOptions(flags | FOS_FORCEFILESYSTEM | use_dir | multiselect);
+    if r != S_OK {
         file_dialog.Release();
-        return Err(NwgError::file_dialog("Filedialog creation failed"));
+        return Err(NwgError::file_dialog("Filedialog creation failed").with_hresult("IFileDialog::SetOptions", r));
     }
 
     
@@ -325,8 +326,9 @@ pub unsafe fn file_dialog_set_default_folder<'a>(dialog: &mut IFileDialog, folde
     let mut shellitem: *mut IShellItem = ptr::null_mut();
     let path = to_utf16(&folder_name);
 
-    if SHCreateItemFromParsingName(path.as_ptr(), ptr::null_mut(), &IShellItem::uuidof(), mem::transmute(&mut shellitem) ) != S_OK {
-        return Err(NwgError::file_dialog("Failed to set default folder"));
+    let r = SHCreateItemFromParsingName(path.as_ptr(), ptr::null_mut(), &IShellItem::uuidof(), mem::transmute(&mut shellitem) );
+    if r != S_OK {
+        return Err(NwgError::file_dialog("Failed to set default folder").with_hresult("SHCreateItemFromParsingName", r));
     }
 
     let shellitem = &mut *shellitem;
@@ -336,17 +338,18 @@ pub unsafe fn file_dialog_set_default_folder<'a>(dialog: &mut IFileDialog, folde
 
     if results != S_OK && results != S_FALSE {
         shellitem.Release();
-        return Err(NwgError::file_dialog("Failed to set default folder"));
+        return Err(NwgError::file_dialog("Failed to set default folder").with_hresult("IShellItem::GetAttributes", results));
     }
 
     if file_properties & SFGAO_FOLDER != SFGAO_FOLDER {
         shellitem.Release();
-        return Err(NwgError::file_dialog("Failed to set default folder"));
+        return Err(NwgError::file_dialog(format!("Failed to set default folder: {:?} is not a folder", folder_name)));
     }
 
-    if dialog.SetDefaultFolder(shellitem) != S_OK {
+    let r = dialog.SetDefaultFolder(shellitem);
+    if r != S_OK {
         shellitem.Release();
-        return Err(NwgError::file_dialog("Failed to set default folder"));
+        return Err(NwgError::file_dialog("Failed to set default folder").with_hresult("IFileDialog::SetDefaultFolder", r));
     }
 
     shellitem.Release();
@@ -379,11 +382,12 @@ pub unsafe fn file_dialog_set_filters<'a>(dialog: &mut IFileDialog, filters: &'a
     }
 
     let filters_count = raw_filters.len() as UINT;
-    if dialog.SetFileTypes(filters_count, raw_filters.as_ptr()) == S_OK {
+    let r = dialog.SetFileTypes(filters_count, raw_filters.as_ptr());
+    if r == S_OK {
         Ok(())
     } else {
         let err = format!("Failed to set the filters using {:?}", filters);
-        return Err(NwgError::file_dialog(&err));
+        return Err(NwgError::file_dialog(&err).with_hresult("IFileDialog::SetFileTypes", r));
     }
 }
 
@@ -393,8 +397,9 @@ pub unsafe fn filedialog_get_item(dialog: &mut IFileDialog) -> Result<String, Nw
     
     let mut _item: *mut IShellItem = ptr::null_mut();
 
-    if dialog.GetResult(&mut _item) != S_OK {
-        return Err(NwgError::file_dialog("Failed to get dialog item"));
+    let r = dialog.GetResult(&mut _item);
+    if r != S_OK {
+        return Err(NwgError::file_dialog("Failed to get dialog item").with_hresult("IFileDialog::GetResult", r));
     }
 
     let text = get_ishellitem_path(&mut *_item);
@@ -411,8 +416,9 @@ pub unsafe fn filedialog_get_items(dialog: &mut IFileOpenDialog) -> Result<Vec<S
     let mut _item: *mut IShellItem = ptr::null_mut();
     let mut _items: *mut IShellItemArray = ptr::null_mut();
 
-    if dialog.GetResults( mem::transmute(&mut _items) ) != S_OK {
-        return Err(NwgError::file_dialog("Failed to get dialog items"));
+    let r = dialog.GetResults( mem::transmute(&mut _items) );
+    if r != S_OK {
+        return Err(NwgError::file_dialog("Failed to get dialog items").with_hresult("IFileOpenDialog::GetResults", r));
     }
 
     let items = &mut *_items;
@@ -441,8 +447,9 @@ unsafe fn get_ishellitem_path(item: &mut IShellItem) -> Result<String, NwgError>
     use super::base_helper::from_wide_ptr;
 
     let mut item_path: PWSTR = ptr::null_mut();
-    if item.GetDisplayName(SIGDN_FILESYSPATH, &mut item_path) != S_OK {
-        return Err(NwgError::file_dialog("Failed to get file name"));
+    let r = item.GetDisplayName(SIGDN_FILESYSPATH, &mut item_path);
+    if r != S_OK {
+        return Err(NwgError::file_dialog("Failed to get file name").with_hresult("IShellItem::GetDisplayName", r));
     }
 
     let text = from_wide_ptr(item_path, None);
@@ -457,8 +464,9 @@ pub unsafe fn file_dialog_options(dialog: &mut IFileDialog) -> Result<u32, NwgEr
     use winapi::shared::winerror::S_OK;
 
     let mut flags = 0;
-    if dialog.GetOptions(&mut flags) != S_OK {
-        return Err(NwgError::file_dialog("Failed to get the file dialog options"));
+    let r = dialog.GetOptions(&mut flags);
+    if r != S_OK {
+        return Err(NwgError::file_dialog("Failed to get the file dialog options").with_hresult("IFileDialog::GetOptions", r));
     }
 
     Ok(flags)
@@ -474,8 +482,9 @@ pub unsafe fn toggle_dialog_flags(dialog: &mut IFileDialog, flag: u32, enabled:
         false => flags & (!flag)
     };
 
-    if dialog.SetOptions(flags) != S_OK {
-        return Err(NwgError::file_dialog("Failed to set the file dialog options"));
+    let r = dialog.SetOptions(flags);
+    if r != S_OK {
+        return Err(NwgError::file_dialog("Failed to set the file dialog options").with_hresult("IFileDialog::SetOptions", r));
     } else {
         Ok(())
     }
@@ -0,0 +1,42 @@
+/*!
+    Low level scroll panel utility
+*/
+use winapi::shared::minwindef::{UINT, WPARAM, LPARAM, LRESULT};
+use winapi::shared::windef::{HWND};
+use super::window::build_sysclass;
+use crate::NwgError;
+use std::ptr;
+
+pub const SCROLL_PANEL_CLASS_ID: &'static str = "NWG_SCROLL_PANEL";
+
+
+/// Create the NWG scroll panel classes
+pub fn create_scroll_panel_classes() -> Result<(), NwgError>  {
+    use super::backend::GetModuleHandleW;
+
+    let hmod = unsafe { GetModuleHandleW(ptr::null_mut()) };
+    if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed").with_last_error("GetModuleHandleW")); }
+
+    unsafe {
+        build_sysclass(hmod, SCROLL_PANEL_CLASS_ID, Some(scroll_panel_proc), None, None)?;
+    }
+
+    Ok(())
+}
+
+
+unsafe extern "system" fn scroll_panel_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    use winapi::um::winuser::{WM_CREATE};
+    use super::backend::DefWindowProcW;
+
+    let handled = match msg {
+        WM_CREATE => Some(0),
+        _ => None
+    };
+
+    if let Some(result) = handled {
+        result
+    } else {
+        DefWindowProcW(hwnd, msg, w, l)
+    }
+}
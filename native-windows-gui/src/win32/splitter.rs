@@ -0,0 +1,44 @@
+/*!
+    Low level splitter utility
+*/
+use winapi::shared::minwindef::{UINT, WPARAM, LPARAM, LRESULT};
+use winapi::shared::windef::{HWND};
+use super::window::build_sysclass;
+use crate::NwgError;
+use std::ptr;
+
+pub const SPLITTER_CLASS_ID: &'static str = "NWG_SPLITTER";
+
+
+/// Create the NWG splitter classes
+pub fn create_splitter_classes() -> Result<(), NwgError>  {
+    use super::backend::GetModuleHandleW;
+    use winapi::shared::windef::HBRUSH;
+    use winapi::um::winuser::COLOR_BTNFACE;
+
+    let hmod = unsafe { GetModuleHandleW(ptr::null_mut()) };
+    if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed").with_last_error("GetModuleHandleW")); }
+
+    unsafe {
+        build_sysclass(hmod, SPLITTER_CLASS_ID, Some(splitter_proc), Some((COLOR_BTNFACE + 1) as HBRUSH), None)?;
+    }
+
+    Ok(())
+}
+
+
+unsafe extern "system" fn splitter_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    use winapi::um::winuser::{WM_CREATE};
+    use super::backend::DefWindowProcW;
+
+    let handled = match msg {
+        WM_CREATE => Some(0),
+        _ => None
+    };
+
+    if let Some(result) = handled {
+        result
+    } else {
+        DefWindowProcW(hwnd, msg, w, l)
+    }
+}
@@ -12,12 +12,12 @@ pub const TAB_CLASS_ID: &'static str = "NWG_TAB";
 
 /// Create the NWG tab classes
 pub fn create_tab_classes() -> Result<(), NwgError>  {
-    use winapi::um::libloaderapi::GetModuleHandleW;
+    use super::backend::GetModuleHandleW;
     use winapi::shared::windef::HBRUSH;
     use winapi::um::winuser::COLOR_BTNFACE;
 
     let hmod = unsafe { GetModuleHandleW(ptr::null_mut()) };
-    if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed")); }
+    if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed").with_last_error("GetModuleHandleW")); }
 
     unsafe { 
         build_sysclass(hmod, TAB_CLASS_ID, Some(tab_proc), Some(COLOR_BTNFACE as HBRUSH), None)?;
@@ -9,18 +9,30 @@ use winapi::shared::basetsd::{DWORD_PTR, UINT_PTR};
 use winapi::um::winuser::{WNDPROC, NMHDR};
 use winapi::um::commctrl::{NMTTDISPINFOW, SUBCLASSPROC};
 use super::base_helper::{CUSTOM_ID_BEGIN, to_utf16};
-use super::window_helper::{NOTICE_MESSAGE, NWG_INIT, NWG_TRAY};
+use super::window_helper::{NOTICE_MESSAGE, NWG_INIT, NWG_TRAY, NWG_INJECT_EVENT, NWG_SPLITTER_MOVED, NWG_WIZARD_NAVIGATE, NWG_WIZARD_PAGE_CHANGED,
+    NWG_LIST_VIEW_BEGIN_EDIT, NWG_LIST_VIEW_END_EDIT, NWG_LIST_VIEW_INFO_TIP, NWG_LIST_VIEW_GROUP_CLICK,
+    NWG_TREE_ITEM_EXPANDING};
 use super::high_dpi;
 use crate::controls::ControlHandle;
-use crate::{Event, EventData, NwgError};
+use crate::{Event, EventData, NwgError, SystemErrorCode};
+use crate::{Key, KeyCombo, Modifiers};
+use crate::events::{KeyData, MouseButtons, MouseData, MousePressEvent, WheelData, ResizeData, ResizeKind, ScrollCode, ScrollData};
 use std::{ptr, mem};
 use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashSet;
 
 
 static mut TIMER_ID: u32 = 1; 
 static mut NOTICE_ID: u32 = 1; 
+static mut HOTKEY_ID: u32 = 1; 
 static mut EVENT_HANDLER_ID: UINT_PTR = 1;
 
+thread_local! {
+    /// The windows that will receive the next `WM_MOUSEHOVER` and `WM_MOUSELEAVE`. See `track_mouse`.
+    static TRACKED_WINDOWS: RefCell<HashSet<usize>> = RefCell::new(HashSet::new());
+}
+
 const NO_DATA: EventData = EventData::NoData;
 
 type RawCallback = dyn Fn(HWND, UINT, WPARAM, LPARAM) -> Option<LRESULT>;
@@ -59,8 +71,114 @@ pub fn build_notice(parent: HWND) -> ControlHandle {
     ControlHandle::Notice(parent, id)
 }
 
+#[cfg(feature = "notice")]
+thread_local! {
+    /// The queues of the notice channels, by notice handle. Used to fill the data of the `OnNotice` events.
+    static NOTICE_QUEUES: std::cell::RefCell<std::collections::HashMap<(usize, u32), NoticeQueueRef>> = Default::default();
+}
+
+#[cfg(feature = "notice")]
+type NoticeQueueRef = std::sync::Arc<dyn std::any::Any + Send + Sync>;
+
+/// Attach the queue of a notice channel to a notice. Its values are sent with the `OnNotice` events of the notice.
+#[cfg(feature = "notice")]
+pub(crate) fn register_notice_queue(parent: HWND, id: u32, queue: NoticeQueueRef) {
+    NOTICE_QUEUES.with(|queues| queues.borrow_mut().insert((parent as usize, id), queue));
+}
+
+#[cfg(feature = "notice")]
+pub(crate) fn unregister_notice_queue(parent: HWND, id: u32) {
+    NOTICE_QUEUES.with(|queues| queues.borrow_mut().remove(&(parent as usize, id)));
+}
+
+#[cfg(feature = "notice")]
+fn notice_data(parent: HWND, id: u32) -> EventData {
+    let queue = NOTICE_QUEUES.with(|queues| queues.borrow().get(&(parent as usize, id)).cloned());
+    match queue {
+        Some(queue) => EventData::OnNotice(crate::NoticeData { queue }),
+        None => NO_DATA
+    }
+}
+
+#[cfg(not(feature = "notice"))]
+fn notice_data(_parent: HWND, _id: u32) -> EventData {
+    NO_DATA
+}
+
+/// `l` points to the navigation data of the wizard. Each event handler receives its own copy.
+#[cfg(feature = "wizard")]
+unsafe fn wizard_navigate_data(l: LPARAM) -> EventData {
+    use crate::WizardNavigateData;
+
+    let data = &*(l as *const WizardNavigateData);
+    EventData::OnWizardNavigate(WizardNavigateData { action: data.action, page: data.page, data: data.data })
+}
+
+#[cfg(not(feature = "wizard"))]
+unsafe fn wizard_navigate_data(_l: LPARAM) -> EventData {
+    NO_DATA
+}
+
+/// `l` points to the edit data of the list view. Each event handler receives its own copy.
+#[cfg(feature = "list-view")]
+unsafe fn list_view_edit_data(l: LPARAM) -> EventData {
+    use crate::ListViewEditData;
+
+    let data = &*(l as *const ListViewEditData);
+    EventData::OnListViewEdit(ListViewEditData {
+        row_index: data.row_index,
+        column_index: data.column_index,
+        text: data.text.clone(),
+        allow: data.allow,
+        replace: data.replace,
+    })
+}
+
+#[cfg(not(feature = "list-view"))]
+unsafe fn list_view_edit_data(_l: LPARAM) -> EventData {
+    NO_DATA
+}
+
+/// `l` points to the info tip data of the list view. Each event handler receives its own copy.
+#[cfg(feature = "list-view")]
+unsafe fn list_view_info_tip_data(l: LPARAM) -> EventData {
+    use crate::ListViewInfoTipData;
+
+    let data = &*(l as *const ListViewInfoTipData);
+    EventData::OnListViewInfoTip(ListViewInfoTipData { row_index: data.row_index, text: data.text })
+}
+
+#[cfg(not(feature = "list-view"))]
+unsafe fn list_view_info_tip_data(_l: LPARAM) -> EventData {
+    NO_DATA
+}
+
+/// `l` points to the expansion data of the tree view. Each event handler receives its own copy.
+#[cfg(feature = "tree-view")]
+unsafe fn tree_item_expanding_data(l: LPARAM) -> EventData {
+    use crate::TreeItemExpandingData;
+
+    let data = &*(l as *const TreeItemExpandingData);
+    EventData::OnTreeItemExpanding(TreeItemExpandingData { item: data.item, action: data.action, allow: data.allow })
+}
+
+#[cfg(not(feature = "tree-view"))]
+unsafe fn tree_item_expanding_data(_l: LPARAM) -> EventData {
+    NO_DATA
+}
+
+#[cfg(feature = "list-view")]
+fn list_view_group_data(w: WPARAM) -> EventData {
+    EventData::OnListViewGroupId { group_id: w as i32 }
+}
+
+#[cfg(not(feature = "list-view"))]
+fn list_view_group_data(_w: WPARAM) -> EventData {
+    NO_DATA
+}
+
 pub unsafe fn build_timer(parent: HWND, interval: u32, stopped: bool) -> ControlHandle {
-    use winapi::um::winuser::SetTimer;
+    use super::backend::SetTimer;
     
     let id = TIMER_ID;
     TIMER_ID += 1;
@@ -72,6 +190,40 @@ pub unsafe fn build_timer(parent: HWND, interval: u32, stopped: bool) -> Control
     ControlHandle::Timer(parent, id)
 }
 
+/**
+    Register a system wide hotkey that sends `WM_HOTKEY` to `parent`. Hotkey ids are unique in the application.
+*/
+pub unsafe fn build_hotkey(parent: HWND, combo: KeyCombo, repeat: bool) -> Result<ControlHandle, NwgError> {
+    let id = HOTKEY_ID;
+    HOTKEY_ID += 1;
+
+    register_hotkey(parent, id, combo, repeat)?;
+
+    Ok(ControlHandle::Hotkey(parent, id))
+}
+
+pub unsafe fn register_hotkey(parent: HWND, id: u32, combo: KeyCombo, repeat: bool) -> Result<(), NwgError> {
+    use super::backend::RegisterHotKey;
+    use winapi::um::winuser::{MOD_ALT, MOD_CONTROL, MOD_SHIFT, MOD_WIN, MOD_NOREPEAT};
+
+    let mut flags = 0;
+    if combo.modifiers.contains(Modifiers::ALT) { flags |= MOD_ALT; }
+    if combo.modifiers.contains(Modifiers::CONTROL) { flags |= MOD_CONTROL; }
+    if combo.modifiers.contains(Modifiers::SHIFT) { flags |= MOD_SHIFT; }
+    if combo.modifiers.contains(Modifiers::WIN) { flags |= MOD_WIN; }
+    if !repeat { flags |= MOD_NOREPEAT; }
+
+    match RegisterHotKey(parent, id as _, flags as UINT, combo.key.code()) {
+        0 => Err(NwgError::control_create(format!("Failed to register the hotkey {}", combo)).with_last_error("RegisterHotKey")),
+        _ => Ok(())
+    }
+}
+
+pub unsafe fn unregister_hotkey(parent: HWND, id: u32) {
+    use super::backend::UnregisterHotKey;
+    UnregisterHotKey(parent, id as _);
+}
+
 /**
     Hook the window subclass with the default event dispatcher.
     The hook is applied to the window and all it's children (recursively).
@@ -83,8 +235,7 @@ pub unsafe fn build_timer(parent: HWND, interval: u32, stopped: bool) -> Control
 pub fn full_bind_event_handler<F>(handle: &ControlHandle, f: F) -> EventHandler
     where F: Fn(Event, EventData, ControlHandle) -> () + 'static
 {
-    use winapi::um::commctrl::{SetWindowSubclass};
-    use winapi::um::winuser::EnumChildWindows;
+    use super::backend::{SetWindowSubclass, EnumChildWindows};
 
     struct SetSubclassParam {
         callback_ptr: *mut *const Callback,
@@ -168,7 +319,7 @@ Returns a `EventHandler` that can be passed to `unbind_event_handler` to remove
 pub fn bind_event_handler<F>(handle: &ControlHandle, parent_handle: &ControlHandle, f: F) -> EventHandler
     where F: Fn(Event, EventData, ControlHandle) -> () + 'static
 {
-    use winapi::um::commctrl::{SetWindowSubclass};
+    use super::backend::SetWindowSubclass;
 
     let hwnd = handle.hwnd().expect("Cannot bind control with an handle of type");
     let parent_hwnd = parent_handle.hwnd().expect("Cannot bind control with an handle of type");
@@ -207,7 +358,7 @@ pub fn bind_event_handler<F>(handle: &ControlHandle, parent_handle: &ControlHand
 */
 pub fn unbind_event_handler(handler: &EventHandler)
 {
-    use winapi::um::commctrl::{RemoveWindowSubclass, GetWindowSubclass};
+    use super::backend::{RemoveWindowSubclass, GetWindowSubclass};
 
     let id = handler.id;
     let subclass_id = handler.subclass_id;
@@ -238,7 +389,7 @@ pub fn unbind_event_handler(handler: &EventHandler)
 pub(crate) fn bind_raw_event_handler_inner<F>(handle: &ControlHandle, handler_id: UINT_PTR, f: F) -> Result<RawEventHandler, NwgError>
     where F: Fn(HWND, UINT, WPARAM, LPARAM) -> Option<LRESULT> + 'static
 {
-    use winapi::um::commctrl::{GetWindowSubclass, SetWindowSubclass};
+    use super::backend::{GetWindowSubclass, SetWindowSubclass};
 
     let handler_id = handler_id;
     let subclass_proc: SUBCLASSPROC = Some(process_raw_events);
@@ -319,7 +470,7 @@ where F: Fn(HWND, UINT, WPARAM, LPARAM) -> Option<LRESULT> + 'static
     This function will panic if the handle parameter is not a window control.
 */
 pub fn has_raw_handler(handle: &ControlHandle, handler_id: UINT_PTR) -> bool {
-    use winapi::um::commctrl::{GetWindowSubclass};
+    use super::backend::GetWindowSubclass;
 
     let handle = handle.hwnd().expect("This type of control cannot have a raw handler.");
     let subclass_proc: SUBCLASSPROC = Some(process_raw_events);
@@ -333,7 +484,7 @@ pub fn has_raw_handler(handle: &ControlHandle, handler_id: UINT_PTR) -> bool {
 */
 pub fn unbind_raw_event_handler(handler: &RawEventHandler) -> Result<(), NwgError>
 {
-    use winapi::um::commctrl::{RemoveWindowSubclass, GetWindowSubclass};
+    use super::backend::{RemoveWindowSubclass, GetWindowSubclass};
 
     let subclass_proc = handler.subclass_proc;
     let handler_id = handler.handler_id;
@@ -348,7 +499,7 @@ pub fn unbind_raw_event_handler(handler: &RawEventHandler) -> Result<(), NwgErro
                 "this can happen if the control ({:?}) was freed or",
                 "this raw event handler was already freed"
             ), handler_id, handle);
-            return Err(NwgError::EventsBinding(err));
+            return Err(NwgError::events_binding(err).with_operation("GetWindowSubclass"));
         }
 
         let callback_wrapper_ptr = callback_value as *mut *mut RawCallback;
@@ -376,13 +527,14 @@ pub(crate) unsafe fn build_hwnd_control<'a>(
 ) -> Result<ControlHandle, NwgError> 
 {
     use winapi::um::winuser::{WS_EX_COMPOSITED, WS_OVERLAPPEDWINDOW, WS_VISIBLE, WS_CLIPCHILDREN, /*WS_EX_LAYERED*/};
-    use winapi::um::winuser::{CreateWindowExW, AdjustWindowRectEx};
+    use super::backend::{CreateWindowExW, AdjustWindowRectEx};
     use winapi::shared::windef::RECT;
-    use winapi::um::libloaderapi::GetModuleHandleW;
+    use super::backend::GetModuleHandleW;
 
     let hmod = GetModuleHandleW(ptr::null_mut());
-    if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed")); }
+    if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed").with_last_error("GetModuleHandleW")); }
 
+    let class = class_name;
     let class_name = to_utf16(class_name);
     let window_title = to_utf16(window_title.unwrap_or("New Window"));
     let ex_flags = ex_flags.unwrap_or(WS_EX_COMPOSITED);
@@ -417,7 +569,7 @@ pub(crate) unsafe fn build_hwnd_control<'a>(
     );
 
     if handle.is_null() {
-        Err(NwgError::initialization("Window creation failed"))
+        Err(NwgError::initialization("Window creation failed").with_last_error("CreateWindowExW").with_class(class))
     } else {
         Ok(ControlHandle::Hwnd(handle))
     }
@@ -431,16 +583,17 @@ pub(crate) unsafe fn build_sysclass<'a>(
     style: Option<UINT>
 ) -> Result<(), NwgError> 
 {
-    use winapi::um::winuser::{LoadCursorW, RegisterClassExW};
-    use winapi::um::winuser::{CS_HREDRAW, CS_VREDRAW, COLOR_WINDOW, IDC_ARROW, WNDCLASSEXW};
-    use winapi::um::errhandlingapi::GetLastError;
+    use super::backend::{LoadCursorW, RegisterClassExW};
+    use winapi::um::winuser::{CS_HREDRAW, CS_VREDRAW, CS_DBLCLKS, COLOR_WINDOW, IDC_ARROW, WNDCLASSEXW};
+    use super::backend::GetLastError;
     use winapi::shared::winerror::ERROR_CLASS_ALREADY_EXISTS;
 
+    let class = class_name;
     let class_name = to_utf16(class_name);
     let background: HBRUSH = background.unwrap_or(mem::transmute(COLOR_WINDOW as usize));
-    let style: UINT = style.unwrap_or(CS_HREDRAW | CS_VREDRAW);
+    let style: UINT = style.unwrap_or(CS_HREDRAW | CS_VREDRAW | CS_DBLCLKS);
 
-    let class =
+    let class_info =
     WNDCLASSEXW {
         cbSize: mem::size_of::<WNDCLASSEXW>() as UINT,
         style,
@@ -456,9 +609,15 @@ pub(crate) unsafe fn build_sysclass<'a>(
         hIconSm: ptr::null_mut()
     };
 
-    let class_token = RegisterClassExW(&class);
-    if class_token == 0 && GetLastError() != ERROR_CLASS_ALREADY_EXISTS { 
-        Err(NwgError::initialization("System class creation failed"))
+    let class_token = RegisterClassExW(&class_info);
+    if class_token == 0 {
+        match GetLastError() {
+            ERROR_CLASS_ALREADY_EXISTS => Ok(()),
+            code => Err(NwgError::initialization("System class creation failed")
+                .with_operation("RegisterClassExW")
+                .with_code(SystemErrorCode::Win32(code))
+                .with_class(class))
+        }
     } else {
         Ok(())
     }
@@ -466,11 +625,11 @@ pub(crate) unsafe fn build_sysclass<'a>(
 
 /// Create the window class for the base nwg window
 pub(crate) fn init_window_class() -> Result<(), NwgError> {
-    use winapi::um::libloaderapi::GetModuleHandleW;
+    use super::backend::GetModuleHandleW;
     
     unsafe {
         let hmod = GetModuleHandleW(ptr::null_mut());
-        if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed")); }
+        if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed").with_last_error("GetModuleHandleW")); }
 
         build_sysclass(hmod, "NativeWindowsGuiWindow", Some(blank_window_proc), None, None)?;
     }
@@ -482,11 +641,11 @@ pub(crate) fn init_window_class() -> Result<(), NwgError> {
 #[cfg(feature = "frame")]
 /// Create the window class for the frame control
 pub(crate) fn create_frame_classes() -> Result<(), NwgError> {
-    use winapi::um::libloaderapi::GetModuleHandleW;
+    use super::backend::GetModuleHandleW;
     
     unsafe {
         let hmod = GetModuleHandleW(ptr::null_mut());
-        if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed")); }
+        if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed").with_last_error("GetModuleHandleW")); }
 
         build_sysclass(hmod, "NWG_FRAME", Some(blank_window_proc), None, None)?;
     }
@@ -494,12 +653,11 @@ pub(crate) fn create_frame_classes() -> Result<(), NwgError> {
     Ok(())
 }
 
-#[cfg(feature = "message-window")]
-/// Create a message only window. Used with the `MessageWindow` control
+#[cfg(any(feature = "message-window", feature = "async"))]
+/// Create a message only window. Used with the `MessageWindow` control and by the async executor
 pub(crate) fn create_message_window() -> Result<ControlHandle, NwgError> {
     use winapi::um::winuser::HWND_MESSAGE;
-    use winapi::um::winuser::CreateWindowExW;
-    use winapi::um::libloaderapi::GetModuleHandleW;
+    use super::backend::{CreateWindowExW, GetModuleHandleW};
 
 
     let class_name = to_utf16("NativeWindowsGuiWindow");
@@ -507,7 +665,7 @@ pub(crate) fn create_message_window() -> Result<ControlHandle, NwgError> {
 
     unsafe {
         let hmod = GetModuleHandleW(ptr::null_mut());
-        if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed")); }
+        if hmod.is_null() { return Err(NwgError::initialization("GetModuleHandleW failed").with_last_error("GetModuleHandleW")); }
         
         let handle = CreateWindowExW (
             0,
@@ -523,7 +681,7 @@ pub(crate) fn create_message_window() -> Result<ControlHandle, NwgError> {
         );
 
         if handle.is_null() {
-            Err(NwgError::initialization("Message only window creation failed"))
+            Err(NwgError::initialization("Message only window creation failed").with_last_error("CreateWindowExW").with_class("NativeWindowsGuiWindow"))
         } else {
             Ok(ControlHandle::Hwnd(handle))
         }
@@ -536,7 +694,7 @@ pub(crate) fn create_message_window() -> Result<ControlHandle, NwgError> {
 */
 unsafe extern "system" fn blank_window_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
     use winapi::um::winuser::{WM_CREATE, WM_CLOSE, SW_HIDE};
-    use winapi::um::winuser::{DefWindowProcW, PostMessageW, ShowWindow};
+    use super::backend::{DefWindowProcW, PostMessageW, ShowWindow};
 
     let handled = match msg {
         WM_CREATE => {
@@ -562,16 +720,17 @@ unsafe extern "system" fn blank_window_proc(hwnd: HWND, msg: UINT, w: WPARAM, l:
 */
 #[allow(unused_variables)]
 unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, id: UINT_PTR, data: DWORD_PTR) -> LRESULT {
-    use std::os::windows::ffi::OsStringExt;
-    use std::ffi::OsString;
     use std::{char};
     use crate::events::*;
 
-    use winapi::um::commctrl::{DefSubclassProc, TTN_GETDISPINFOW};
-    use winapi::um::winuser::{GetClassNameW, GetMenuItemID, GetSubMenu};
+    use winapi::um::commctrl::TTN_GETDISPINFOW;
+    use winapi::um::winuser::{GetMenuItemID, GetSubMenu};
+    use super::backend::{DefSubclassProc, GetClassNameW};
     use winapi::um::winuser::{WM_CLOSE, WM_COMMAND, WM_MENUCOMMAND, WM_TIMER, WM_NOTIFY, WM_HSCROLL, WM_VSCROLL, WM_LBUTTONDOWN, WM_LBUTTONUP,
       WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SIZE, WM_MOVE, WM_PAINT, WM_MOUSEMOVE, WM_CONTEXTMENU, WM_INITMENUPOPUP, WM_MENUSELECT, WM_EXITSIZEMOVE,
-      WM_ENTERSIZEMOVE, SIZE_MAXIMIZED, SIZE_MINIMIZED, WM_KEYDOWN, WM_KEYUP, WM_CHAR, WM_MOUSEWHEEL, WM_DROPFILES, GET_WHEEL_DELTA_WPARAM};
+      WM_ENTERSIZEMOVE, SIZE_MAXIMIZED, SIZE_MINIMIZED, WM_KEYDOWN, WM_KEYUP, WM_SYSKEYDOWN, WM_SYSKEYUP, WM_CHAR, WM_HOTKEY, WM_MOUSEWHEEL, WM_MOUSEHWHEEL, WM_DROPFILES,
+      WM_MBUTTONDOWN, WM_MBUTTONUP, WM_XBUTTONDOWN, WM_XBUTTONUP, WM_LBUTTONDBLCLK, WM_RBUTTONDBLCLK, WM_MBUTTONDBLCLK, WM_XBUTTONDBLCLK,
+      WM_MOUSEHOVER, WM_MOUSELEAVE, WM_DESTROY};
     use winapi::um::shellapi::{NIN_BALLOONSHOW, NIN_BALLOONHIDE, NIN_BALLOONTIMEOUT, NIN_BALLOONUSERCLICK};
     use winapi::um::winnt::WCHAR;
     use winapi::shared::minwindef::{HIWORD, LOWORD};
@@ -581,14 +740,14 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
     let base_handle = ControlHandle::Hwnd(hwnd);
 
     match msg {
-        WM_KEYDOWN | WM_KEYUP => {
-            let evt = match msg == WM_KEYDOWN { 
+        WM_KEYDOWN | WM_KEYUP | WM_SYSKEYDOWN | WM_SYSKEYUP => {
+            // Keys pressed while ALT is held down (and F10) are sent as system keys
+            let evt = match msg == WM_KEYDOWN || msg == WM_SYSKEYDOWN { 
                 true => Event::OnKeyPress,
                 false => Event::OnKeyRelease
             };
 
-            let keycode = w as u32;
-            let data = EventData::OnKey(keycode);
+            let data = EventData::OnKey(key_data(w, l));
             callback(evt, data, base_handle);
         },
         WM_NOTIFY => {
@@ -611,9 +770,8 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
         WM_INITMENUPOPUP => {
             callback(Event::OnMenuOpen, NO_DATA, ControlHandle::Menu(ptr::null_mut(), w as HMENU));
         }
-        WM_MOUSEWHEEL => {
-            callback(Event::OnMouseWheel, EventData::OnMouseWheel(GET_WHEEL_DELTA_WPARAM(w) as i32), base_handle);
-        },
+        WM_MOUSEWHEEL => callback(Event::OnMouseWheel, EventData::OnMouseWheel(wheel_data(hwnd, w, l)), base_handle),
+        WM_MOUSEHWHEEL => callback(Event::OnMouseHorizontalWheel, EventData::OnMouseWheel(wheel_data(hwnd, w, l)), base_handle),
         WM_MENUSELECT => {
             let index = LOWORD(w as u32) as u32;
             let parent = l as HMENU;
@@ -625,6 +783,9 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
                 callback(Event::OnMenuHover, NO_DATA, ControlHandle::MenuItem(parent, index));
             }
         },
+        WM_COMMAND if l == 0 && HIWORD(w as u32) == 1 => {
+            handle_accelerator_command(hwnd, LOWORD(w as u32), callback);
+        },
         WM_COMMAND => {
             let child_handle: HWND = l as HWND;
             let message = HIWORD(w as u32) as u16;
@@ -634,7 +795,7 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
             // It might be a good idea to just compare the class_name_raw
             let mut class_name_raw: Vec<WCHAR> = Vec::with_capacity(100);  class_name_raw.set_len(100);
             let count = GetClassNameW(child_handle, class_name_raw.as_mut_ptr(), 100) as usize;
-            let class_name = OsString::from_wide(&class_name_raw[..count]).into_string().unwrap_or("".to_string());
+            let class_name = String::from_utf16(&class_name_raw[..count]).unwrap_or("".to_string());
 
             match &class_name as &str {
                 "Button" => callback(button_commands(message), NO_DATA, handle),
@@ -671,10 +832,11 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
             }
         },
         WM_SIZE => {
+            let data = resize_data(w, l);
             match w {
-                SIZE_MAXIMIZED => callback(Event::OnWindowMaximize, NO_DATA, base_handle),
-                SIZE_MINIMIZED => callback(Event::OnWindowMinimize, NO_DATA, base_handle),
-                _ => callback(Event::OnResize, NO_DATA, base_handle)
+                SIZE_MAXIMIZED => callback(Event::OnWindowMaximize, EventData::OnResize(data), base_handle),
+                SIZE_MINIMIZED => callback(Event::OnWindowMinimize, EventData::OnResize(data), base_handle),
+                _ => callback(Event::OnResize, EventData::OnResize(data), base_handle)
             }
         },
         WM_PAINT => {
@@ -690,15 +852,47 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
         WM_ENTERSIZEMOVE => callback(Event::OnResizeBegin, NO_DATA, base_handle),
         WM_TIMER => callback(Event::OnTimerTick, NO_DATA, ControlHandle::Timer(hwnd, w as u32)),
         WM_MOVE => callback(Event::OnMove, NO_DATA, base_handle),
-        WM_HSCROLL => callback(Event::OnHorizontalScroll, NO_DATA, ControlHandle::Hwnd(l as HWND)),
-        WM_VSCROLL => callback(Event::OnVerticalScroll, NO_DATA, ControlHandle::Hwnd(l as HWND)),
-        WM_MOUSEMOVE => callback(Event::OnMouseMove, NO_DATA, base_handle), 
-        WM_LBUTTONUP => callback(Event::OnMousePress(MousePressEvent::MousePressLeftUp), NO_DATA,  base_handle), 
-        WM_LBUTTONDOWN => callback(Event::OnMousePress(MousePressEvent::MousePressLeftDown), NO_DATA, base_handle), 
-        WM_RBUTTONUP => callback(Event::OnMousePress(MousePressEvent::MousePressRightUp), NO_DATA, base_handle), 
-        WM_RBUTTONDOWN => callback(Event::OnMousePress(MousePressEvent::MousePressRightDown), NO_DATA, base_handle),
-        NOTICE_MESSAGE => callback(Event::OnNotice, NO_DATA, ControlHandle::Notice(hwnd, w as u32)),
+        WM_HSCROLL => callback(Event::OnHorizontalScroll, EventData::OnScroll(scroll_data(w)), ControlHandle::Hwnd(l as HWND)),
+        WM_VSCROLL => callback(Event::OnVerticalScroll, EventData::OnScroll(scroll_data(w)), ControlHandle::Hwnd(l as HWND)),
+        WM_MOUSEMOVE => {
+            // Hover and leave are only sent after the window asks to track the mouse
+            track_mouse(hwnd);
+            callback(Event::OnMouseMove, EventData::OnMouse(mouse_data(w, l)), base_handle)
+        },
+        WM_MOUSEHOVER => {
+            untrack_mouse(hwnd);
+            callback(Event::OnMouseHover, EventData::OnMouse(mouse_data(w, l)), base_handle)
+        },
+        WM_MOUSELEAVE => {
+            untrack_mouse(hwnd);
+            callback(Event::OnMouseLeave, EventData::OnMouse(cursor_data(hwnd)), base_handle)
+        },
+        WM_DESTROY => untrack_mouse(hwnd),
+        WM_LBUTTONUP | WM_LBUTTONDOWN | WM_LBUTTONDBLCLK | WM_RBUTTONUP | WM_RBUTTONDOWN | WM_RBUTTONDBLCLK |
+        WM_MBUTTONUP | WM_MBUTTONDOWN | WM_MBUTTONDBLCLK | WM_XBUTTONUP | WM_XBUTTONDOWN | WM_XBUTTONDBLCLK => {
+            if let Some(press) = mouse_press_event(msg, w) {
+                let data = mouse_data(w, l);
+
+                // The second click of a double click only sends the double click message
+                if let Some(down) = double_click_down(press) {
+                    callback(Event::OnMousePress(down), EventData::OnMouse(data), base_handle);
+                }
+
+                callback(Event::OnMousePress(press), EventData::OnMouse(data), base_handle);
+            }
+        },
+        NOTICE_MESSAGE => callback(Event::OnNotice, notice_data(hwnd, w as u32), ControlHandle::Notice(hwnd, w as u32)),
+        WM_HOTKEY => callback(Event::OnHotkey, NO_DATA, ControlHandle::Hotkey(hwnd, w as u32)),
         NWG_INIT => callback(Event::OnInit, NO_DATA, base_handle),
+        NWG_INJECT_EVENT => handle_injected_event(l, callback),
+        NWG_SPLITTER_MOVED => callback(Event::OnSplitterMoved, NO_DATA, base_handle),
+        NWG_WIZARD_NAVIGATE => callback(Event::OnWizardNavigate, wizard_navigate_data(l), base_handle),
+        NWG_WIZARD_PAGE_CHANGED => callback(Event::OnWizardPageChanged, NO_DATA, base_handle),
+        NWG_LIST_VIEW_BEGIN_EDIT => callback(Event::OnListViewBeginEdit, list_view_edit_data(l), base_handle),
+        NWG_LIST_VIEW_END_EDIT => callback(Event::OnListViewEndEdit, list_view_edit_data(l), base_handle),
+        NWG_LIST_VIEW_INFO_TIP => callback(Event::OnListViewInfoTip, list_view_info_tip_data(l), base_handle),
+        NWG_LIST_VIEW_GROUP_CLICK => callback(Event::OnListViewGroupClick, list_view_group_data(w), base_handle),
+        NWG_TREE_ITEM_EXPANDING => callback(Event::OnTreeItemExpanding, tree_item_expanding_data(l), base_handle),
         WM_CLOSE => {
             let mut should_exit = true;
             let data = EventData::OnWindowClose(WindowCloseData { data: &mut should_exit as *mut bool });
@@ -727,7 +921,262 @@ unsafe extern "system" fn process_raw_events(hwnd: HWND, msg: UINT, w: WPARAM, l
 
     match result {
         Some(r) => r,
-        None => ::winapi::um::commctrl::DefSubclassProc(hwnd, msg, w, l)
+        None => super::backend::DefSubclassProc(hwnd, msg, w, l)
+    }
+}
+
+#[cfg(feature = "event-injection")]
+unsafe fn handle_injected_event(l: LPARAM, callback: &Callback) {
+    use super::event_injection::InjectedEvent;
+
+    let injected = &*(l as *const InjectedEvent);
+    callback(injected.event, (injected.data)(), injected.handle);
+}
+
+#[cfg(not(feature = "event-injection"))]
+unsafe fn handle_injected_event(_l: LPARAM, _callback: &Callback) {}
+
+/// Commands sent by `TranslateAcceleratorW`. Menu items ids start at `CUSTOM_ID_BEGIN`, custom ids are lower.
+#[cfg(feature = "accelerators")]
+unsafe fn handle_accelerator_command(hwnd: HWND, id: u16, callback: &Callback) {
+    use super::accelerators::accelerator_menu_item;
+
+    match accelerator_menu_item(id) {
+        Some(parent) => {
+            if menu_item_enabled(parent, id as u32) {
+                callback(Event::OnMenuItemSelected, NO_DATA, ControlHandle::MenuItem(parent, id as u32));
+            }
+        },
+        None => callback(Event::OnAccelerator, EventData::OnAccelerator(id), ControlHandle::Hwnd(hwnd))
+    }
+}
+
+#[cfg(not(feature = "accelerators"))]
+unsafe fn handle_accelerator_command(_hwnd: HWND, _id: u16, _callback: &Callback) {}
+
+#[cfg(all(feature = "accelerators", feature = "menu"))]
+unsafe fn menu_item_enabled(parent: HMENU, id: u32) -> bool {
+    super::menu::is_menuitem_enabled(parent, None, Some(id))
+}
+
+#[cfg(all(feature = "accelerators", not(feature = "menu")))]
+unsafe fn menu_item_enabled(_parent: HMENU, _id: u32) -> bool { true }
+
+/// The modifier keys currently held down
+unsafe fn key_modifiers() -> Modifiers {
+    use super::backend::GetKeyState;
+    use winapi::um::winuser::{VK_SHIFT, VK_CONTROL, VK_MENU};
+
+    let mut modifiers = Modifiers::NONE;
+    if GetKeyState(VK_SHIFT) < 0 { modifiers |= Modifiers::SHIFT; }
+    if GetKeyState(VK_CONTROL) < 0 { modifiers |= Modifiers::CONTROL; }
+    if GetKeyState(VK_MENU) < 0 { modifiers |= Modifiers::ALT; }
+    if win_key_down() { modifiers |= Modifiers::WIN; }
+
+    modifiers
+}
+
+unsafe fn win_key_down() -> bool {
+    use super::backend::GetKeyState;
+    use winapi::um::winuser::{VK_LWIN, VK_RWIN};
+
+    GetKeyState(VK_LWIN) < 0 || GetKeyState(VK_RWIN) < 0
+}
+
+/// Key messages pack the repeat count in the bits 0-15 of `l`, the extended flag in the bit 24 and the previous key state in the bit 30
+unsafe fn key_data(w: WPARAM, l: LPARAM) -> KeyData {
+    use winapi::shared::minwindef::LOWORD;
+
+    let l = l as u32;
+    KeyData {
+        key: Key::from(w as u32),
+        modifiers: key_modifiers(),
+        repeat_count: LOWORD(l),
+        repeated: l & (1 << 30) != 0,
+        extended: l & (1 << 24) != 0,
+    }
+}
+
+/// Mouse messages pack the buttons held down in `w` and the signed client coordinates in `l`
+unsafe fn mouse_data(w: WPARAM, l: LPARAM) -> MouseData {
+    use winapi::shared::minwindef::{LOWORD, HIWORD};
+    mouse_data_at(w, LOWORD(l as u32) as i16 as i32, HIWORD(l as u32) as i16 as i32)
+}
+
+/// Mouse data from the `MK_*` flags in the low word of `w` and a physical position in the client area
+unsafe fn mouse_data_at(w: WPARAM, x: i32, y: i32) -> MouseData {
+    use winapi::um::winuser::{MK_LBUTTON, MK_RBUTTON, MK_MBUTTON, MK_XBUTTON1, MK_XBUTTON2, MK_SHIFT, MK_CONTROL, VK_MENU};
+    use super::backend::GetKeyState;
+
+    let flags = w & 0xFFFF;
+    let mut buttons = MouseButtons::NONE;
+    if flags & MK_LBUTTON == MK_LBUTTON { buttons |= MouseButtons::LEFT; }
+    if flags & MK_RBUTTON == MK_RBUTTON { buttons |= MouseButtons::RIGHT; }
+    if flags & MK_MBUTTON == MK_MBUTTON { buttons |= MouseButtons::MIDDLE; }
+    if flags & MK_XBUTTON1 == MK_XBUTTON1 { buttons |= MouseButtons::X1; }
+    if flags & MK_XBUTTON2 == MK_XBUTTON2 { buttons |= MouseButtons::X2; }
+
+    // Alt is not part of the mouse flags
+    let mut modifiers = Modifiers::NONE;
+    if flags & MK_SHIFT == MK_SHIFT { modifiers |= Modifiers::SHIFT; }
+    if flags & MK_CONTROL == MK_CONTROL { modifiers |= Modifiers::CONTROL; }
+    if GetKeyState(VK_MENU) < 0 { modifiers |= Modifiers::ALT; }
+    if win_key_down() { modifiers |= Modifiers::WIN; }
+
+    MouseData {
+        position: high_dpi::physical_to_logical(x, y),
+        buttons,
+        modifiers,
+    }
+}
+
+/// The wheel messages position is in screen coordinates
+unsafe fn wheel_data(hwnd: HWND, w: WPARAM, l: LPARAM) -> WheelData {
+    use winapi::um::winuser::GET_WHEEL_DELTA_WPARAM;
+    use winapi::shared::minwindef::{LOWORD, HIWORD};
+    use winapi::shared::windef::POINT;
+    use super::backend::ScreenToClient;
+
+    let mut point = POINT { x: LOWORD(l as u32) as i16 as i32, y: HIWORD(l as u32) as i16 as i32 };
+    ScreenToClient(hwnd, &mut point);
+
+    WheelData {
+        delta: GET_WHEEL_DELTA_WPARAM(w) as i32,
+        mouse: mouse_data_at(w, point.x, point.y),
+    }
+}
+
+/// `WM_MOUSELEAVE` has no parameters, the mouse data is read from the cursor and the buttons state
+unsafe fn cursor_data(hwnd: HWND) -> MouseData {
+    use winapi::um::winuser::{MK_LBUTTON, MK_RBUTTON, MK_MBUTTON, MK_XBUTTON1, MK_XBUTTON2, MK_SHIFT, MK_CONTROL,
+        VK_LBUTTON, VK_RBUTTON, VK_MBUTTON, VK_XBUTTON1, VK_XBUTTON2, VK_SHIFT, VK_CONTROL};
+    use winapi::shared::windef::POINT;
+    use super::backend::{GetCursorPos, ScreenToClient, GetKeyState};
+
+    let mut point = POINT { x: 0, y: 0 };
+    GetCursorPos(&mut point);
+    ScreenToClient(hwnd, &mut point);
+
+    let keys = [
+        (VK_LBUTTON, MK_LBUTTON), (VK_RBUTTON, MK_RBUTTON), (VK_MBUTTON, MK_MBUTTON),
+        (VK_XBUTTON1, MK_XBUTTON1), (VK_XBUTTON2, MK_XBUTTON2), (VK_SHIFT, MK_SHIFT), (VK_CONTROL, MK_CONTROL),
+    ];
+
+    let mut flags = 0;
+    for &(key, flag) in keys.iter() {
+        if GetKeyState(key) < 0 { flags |= flag; }
+    }
+
+    mouse_data_at(flags, point.x, point.y)
+}
+
+/// Map a mouse button message to a mouse press event. The X button of the `WM_XBUTTON*` messages is in the high word of `w`.
+fn mouse_press_event(msg: UINT, w: WPARAM) -> Option<MousePressEvent> {
+    use winapi::um::winuser::{WM_LBUTTONUP, WM_LBUTTONDOWN, WM_LBUTTONDBLCLK, WM_RBUTTONUP, WM_RBUTTONDOWN, WM_RBUTTONDBLCLK,
+        WM_MBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONDBLCLK, WM_XBUTTONUP, WM_XBUTTONDOWN, WM_XBUTTONDBLCLK, XBUTTON1};
+    use winapi::shared::minwindef::HIWORD;
+    use crate::events::MousePressEvent::*;
+
+    let x1 = HIWORD(w as u32) == XBUTTON1;
+    let press = match msg {
+        WM_LBUTTONUP => MousePressLeftUp,
+        WM_LBUTTONDOWN => MousePressLeftDown,
+        WM_LBUTTONDBLCLK => MousePressLeftDoubleClick,
+        WM_RBUTTONUP => MousePressRightUp,
+        WM_RBUTTONDOWN => MousePressRightDown,
+        WM_RBUTTONDBLCLK => MousePressRightDoubleClick,
+        WM_MBUTTONUP => MousePressMiddleUp,
+        WM_MBUTTONDOWN => MousePressMiddleDown,
+        WM_MBUTTONDBLCLK => MousePressMiddleDoubleClick,
+        WM_XBUTTONUP => if x1 { MousePressX1Up } else { MousePressX2Up },
+        WM_XBUTTONDOWN => if x1 { MousePressX1Down } else { MousePressX2Down },
+        WM_XBUTTONDBLCLK => if x1 { MousePressX1DoubleClick } else { MousePressX2DoubleClick },
+        _ => { return None; }
+    };
+
+    Some(press)
+}
+
+/// The `Down` event of the button of a double click event
+fn double_click_down(press: MousePressEvent) -> Option<MousePressEvent> {
+    use crate::events::MousePressEvent::*;
+
+    match press {
+        MousePressLeftDoubleClick => Some(MousePressLeftDown),
+        MousePressRightDoubleClick => Some(MousePressRightDown),
+        MousePressMiddleDoubleClick => Some(MousePressMiddleDown),
+        MousePressX1DoubleClick => Some(MousePressX1Down),
+        MousePressX2DoubleClick => Some(MousePressX2Down),
+        _ => None
+    }
+}
+
+/// Ask the system to send `WM_MOUSEHOVER` and `WM_MOUSELEAVE` to the window. Tracking stops after each of those messages.
+/// Does nothing if the window is already tracked.
+unsafe fn track_mouse(hwnd: HWND) {
+    use winapi::um::winuser::{TRACKMOUSEEVENT, TME_HOVER, TME_LEAVE, HOVER_DEFAULT};
+    use super::backend::TrackMouseEvent;
+
+    if !TRACKED_WINDOWS.with(|tracked| tracked.borrow_mut().insert(hwnd as usize)) {
+        return;
+    }
+
+    let mut track = TRACKMOUSEEVENT {
+        cbSize: mem::size_of::<TRACKMOUSEEVENT>() as u32,
+        dwFlags: TME_HOVER | TME_LEAVE,
+        hwndTrack: hwnd,
+        dwHoverTime: HOVER_DEFAULT,
+    };
+
+    TrackMouseEvent(&mut track);
+}
+
+/// Called when the tracking started by `track_mouse` stops
+fn untrack_mouse(hwnd: HWND) {
+    TRACKED_WINDOWS.with(|tracked| tracked.borrow_mut().remove(&(hwnd as usize)));
+}
+
+unsafe fn resize_data(w: WPARAM, l: LPARAM) -> ResizeData {
+    use winapi::um::winuser::{SIZE_MAXIMIZED, SIZE_MINIMIZED, SIZE_MAXSHOW, SIZE_MAXHIDE};
+    use winapi::shared::minwindef::{LOWORD, HIWORD};
+
+    let kind = match w {
+        SIZE_MAXIMIZED => ResizeKind::Maximized,
+        SIZE_MINIMIZED => ResizeKind::Minimized,
+        SIZE_MAXSHOW => ResizeKind::MaxShow,
+        SIZE_MAXHIDE => ResizeKind::MaxHide,
+        _ => ResizeKind::Restored
+    };
+
+    let (width, height) = high_dpi::physical_to_logical(LOWORD(l as u32) as i32, HIWORD(l as u32) as i32);
+
+    ResizeData {
+        size: (width as u32, height as u32),
+        kind,
+    }
+}
+
+fn scroll_data(w: WPARAM) -> ScrollData {
+    use winapi::um::winuser::{SB_LINEUP, SB_LINEDOWN, SB_PAGEUP, SB_PAGEDOWN, SB_THUMBPOSITION, SB_THUMBTRACK, SB_TOP, SB_BOTTOM, SB_ENDSCROLL};
+    use winapi::shared::minwindef::{LOWORD, HIWORD};
+
+    let code = LOWORD(w as u32);
+    let code = match code as LPARAM {
+        SB_LINEUP => ScrollCode::LineUp,
+        SB_LINEDOWN => ScrollCode::LineDown,
+        SB_PAGEUP => ScrollCode::PageUp,
+        SB_PAGEDOWN => ScrollCode::PageDown,
+        SB_THUMBPOSITION => ScrollCode::ThumbPosition,
+        SB_THUMBTRACK => ScrollCode::ThumbTrack,
+        SB_TOP => ScrollCode::Top,
+        SB_BOTTOM => ScrollCode::Bottom,
+        SB_ENDSCROLL => ScrollCode::EndScroll,
+        _ => ScrollCode::Other(code)
+    };
+
+    ScrollData {
+        code,
+        position: HIWORD(w as u32) as u32,
     }
 }
 
@@ -807,7 +1256,7 @@ fn tree_commands(m: u32) -> Event {
 
 fn list_view_commands(m: u32) -> Event {
     use winapi::um::commctrl::{NM_KILLFOCUS, NM_SETFOCUS, LVN_DELETEALLITEMS,
-        LVN_DELETEITEM, LVN_INSERTITEM, LVN_ITEMACTIVATE, LVN_ITEMCHANGED};
+        LVN_DELETEITEM, LVN_INSERTITEM, LVN_ITEMACTIVATE, LVN_ITEMCHANGED, LVN_COLUMNCLICK};
 
     match m {
         LVN_DELETEALLITEMS => Event::OnListViewClear,
@@ -815,12 +1264,39 @@ fn list_view_commands(m: u32) -> Event {
         LVN_INSERTITEM => Event::OnListViewItemInsert,
         LVN_ITEMACTIVATE => Event::OnListViewItemActivated,
         LVN_ITEMCHANGED => Event::OnListViewItemChanged,
+        LVN_COLUMNCLICK => Event::OnListViewColumnClick,
         NM_KILLFOCUS => Event::OnListViewFocusLost,
         NM_SETFOCUS => Event::OnListViewFocus,
         _ => Event::Unknown
     }
 }
 
+/// Returns the data of `OnListViewItemChecked` if the check box of an item was toggled. This is raised after `OnListViewItemChanged`.
+/// The state image of the items is initialized when the check boxes are enabled, this is not reported.
+#[cfg(feature="list-view")]
+fn list_view_checked_data(m: u32, notif_raw: *const NMHDR) -> Option<EventData> {
+    use winapi::um::commctrl::{NMLISTVIEW, LVN_ITEMCHANGED, LVIF_STATE, LVIS_STATEIMAGEMASK};
+    use crate::controls::CHECKED_STATE_IMAGE;
+
+    if m != LVN_ITEMCHANGED {
+        return None;
+    }
+
+    let data: &NMLISTVIEW = unsafe { &*(notif_raw as *const NMLISTVIEW) };
+    let old_image = (data.uOldState & LVIS_STATEIMAGEMASK) >> 12;
+    let new_image = (data.uNewState & LVIS_STATEIMAGEMASK) >> 12;
+    if data.uChanged & LVIF_STATE == 0 || old_image == 0 || old_image == new_image {
+        return None;
+    }
+
+    Some(EventData::OnListViewItemChecked { row_index: data.iItem as _, checked: new_image == CHECKED_STATE_IMAGE })
+}
+
+#[cfg(not(feature="list-view"))]
+fn list_view_checked_data(_m: u32, _notif_raw: *const NMHDR) -> Option<EventData> {
+    None
+}
+
 #[cfg(feature="tree-view")]
 fn tree_data(m: u32, notif_raw: *const NMHDR) -> EventData {
     use crate::{TreeItem, TreeItemAction, ExpandState, TreeItemState};
@@ -874,7 +1350,7 @@ fn tree_data(_m: u32, _notif_raw: *const NMHDR) -> EventData {
 #[cfg(feature="list-view")]
 fn list_view_data(m: u32, notif_raw: *const NMHDR) -> EventData {
     use winapi::um::commctrl::{NMLISTVIEW, LVN_DELETEITEM, LVN_ITEMACTIVATE,
-        LVN_INSERTITEM, LVN_ITEMCHANGED, LVIS_SELECTED};
+        LVN_INSERTITEM, LVN_ITEMCHANGED, LVN_COLUMNCLICK, LVIS_SELECTED};
 
     match m {
         LVN_DELETEITEM => {
@@ -906,6 +1382,10 @@ fn list_view_data(m: u32, notif_raw: *const NMHDR) -> EventData {
                 selected: data.uNewState & LVIS_SELECTED == LVIS_SELECTED
             }
         },
+        LVN_COLUMNCLICK => {
+            let data: &NMLISTVIEW = unsafe { &*(notif_raw as *const NMLISTVIEW) };
+            EventData::OnListViewColumnIndex { column_index: data.iSubItem as _ }
+        },
         _ => NO_DATA
     }
 }
@@ -919,7 +1399,7 @@ fn list_view_data(_m: u32, _notif_raw: *const NMHDR) -> EventData {
 
 unsafe fn static_commands(handle: HWND, m: u16) -> Event {
     use winapi::um::winuser::{STN_CLICKED, STN_DBLCLK, STM_GETIMAGE, IMAGE_BITMAP};
-    use winapi::um::winuser::SendMessageW;
+    use super::backend::SendMessageW;
 
     let has_image = SendMessageW(handle, STM_GETIMAGE, IMAGE_BITMAP as usize, 0) != 0;
     if has_image {
@@ -957,17 +1437,15 @@ unsafe fn handle_tooltip_callback<'a>(notif: *mut NMTTDISPINFOW, callback: &Call
 }
 
 unsafe fn handle_default_notify_callback<'a>(notif_raw: *const NMHDR, callback: &Callback){
-    use std::os::windows::ffi::OsStringExt;
-    use std::ffi::OsString;
     use winapi::um::winnt::WCHAR;
-    use winapi::um::winuser::GetClassNameW;
+    use super::backend::GetClassNameW;
 
     let notif = &*notif_raw;
     let handle = ControlHandle::Hwnd(notif.hwndFrom);
 
     let mut class_name_raw: [WCHAR; 100] = mem::zeroed();
     let count = GetClassNameW(notif.hwndFrom, class_name_raw.as_mut_ptr(), 100) as usize;
-    let class_name = OsString::from_wide(&class_name_raw[..count]).into_string().unwrap_or("".to_string());
+    let class_name = String::from_utf16(&class_name_raw[..count]).unwrap_or("".to_string());
 
     let code = notif.code;
 
@@ -976,7 +1454,12 @@ unsafe fn handle_default_notify_callback<'a>(notif_raw: *const NMHDR, callback:
         "SysTabControl32" => callback(tabs_commands(code), NO_DATA, handle),
         "msctls_trackbar32" => callback(track_commands(code), NO_DATA, handle),
         winapi::um::commctrl::WC_TREEVIEW => callback(tree_commands(code), tree_data(code, notif_raw), handle),
-        winapi::um::commctrl::WC_LISTVIEW => callback(list_view_commands(code), list_view_data(code, notif_raw), handle),
+        winapi::um::commctrl::WC_LISTVIEW => {
+            callback(list_view_commands(code), list_view_data(code, notif_raw), handle);
+            if let Some(data) = list_view_checked_data(code, notif_raw) {
+                callback(Event::OnListViewItemChecked, data, handle);
+            }
+        },
         _ => {}
     }
 }
@@ -10,12 +10,22 @@ use std::{ptr, mem};
 pub const NOTICE_MESSAGE: UINT = WM_USER+100;
 pub const NWG_INIT: UINT = WM_USER + 101;
 pub const NWG_TRAY: UINT = WM_USER + 102;
+pub const NWG_INJECT_EVENT: UINT = WM_USER + 103;
+pub const NWG_SPLITTER_MOVED: UINT = WM_USER + 104;
+pub const NWG_WIZARD_NAVIGATE: UINT = WM_USER + 105;
+pub const NWG_WIZARD_PAGE_CHANGED: UINT = WM_USER + 106;
+pub const NWG_LIST_VIEW_BEGIN_EDIT: UINT = WM_USER + 107;
+pub const NWG_LIST_VIEW_END_EDIT: UINT = WM_USER + 108;
+pub const NWG_LIST_VIEW_EDITOR_DONE: UINT = WM_USER + 109;
+pub const NWG_LIST_VIEW_INFO_TIP: UINT = WM_USER + 110;
+pub const NWG_LIST_VIEW_GROUP_CLICK: UINT = WM_USER + 111;
+pub const NWG_TREE_ITEM_EXPANDING: UINT = WM_USER + 112;
 
 
 /// Haha you maybe though that destroying windows would be easy right? WRONG.
 /// The window children must first be destroyed otherwise `DestroyWindow` will free them and the associated rust value will be ~CORRUPTED~
 pub fn destroy_window(hwnd: HWND) { 
-    use winapi::um::winuser::{SetParent, DestroyWindow};
+    use super::backend::{SetParent, DestroyWindow};
 
     // Remove the children from the window
     iterate_window_children(hwnd, |child| {
@@ -32,7 +42,7 @@ pub fn destroy_window(hwnd: HWND) {
 pub fn iterate_window_children<F>(hwnd_parent: HWND, cb: F) 
     where F: FnMut(HWND) -> ()
 {
-    use winapi::um::winuser::EnumChildWindows;
+    use super::backend::EnumChildWindows;
     use winapi::shared::minwindef::BOOL;
 
     struct EnumChildData<F> {
@@ -62,9 +72,9 @@ pub fn iterate_window_children<F>(hwnd_parent: HWND, cb: F)
     }
 }
 
-#[cfg(any(feature="timer", feature="notice"))]
+#[cfg(any(feature="timer", feature="notice", feature="dialog", feature="global-hotkey"))]
 pub fn window_valid(hwnd: HWND) -> bool {
-    use winapi::um::winuser::IsWindow;
+    use super::backend::IsWindow;
 
     unsafe {
         IsWindow(hwnd) != 0
@@ -72,7 +82,7 @@ pub fn window_valid(hwnd: HWND) -> bool {
 }
 
 pub fn get_window_parent(hwnd: HWND) -> HWND {
-    use winapi::um::winuser::GetParent;
+    use super::backend::GetParent;
     unsafe { GetParent(hwnd) }
 }
 
@@ -87,7 +97,7 @@ pub fn get_window_font(handle: HWND) -> HFONT {
 /// Set the font of a window
 pub unsafe fn set_window_font(handle: HWND, font_handle: Option<HFONT>, redraw: bool) {
     use winapi::um::winuser::WM_SETFONT;
-    use winapi::um::winuser::SendMessageW;
+    use super::backend::SendMessageW;
 
     let font_handle = font_handle.unwrap_or(ptr::null_mut());
 
@@ -97,7 +107,7 @@ pub unsafe fn set_window_font(handle: HWND, font_handle: Option<HFONT>, redraw:
 
 #[cfg(feature = "timer")]
 pub fn kill_timer(hwnd: HWND, id: u32) {
-    use winapi::um::winuser::KillTimer;
+    use super::backend::KillTimer;
     use winapi::shared::basetsd::UINT_PTR;
 
     unsafe {
@@ -107,7 +117,7 @@ pub fn kill_timer(hwnd: HWND, id: u32) {
 
 #[cfg(feature = "timer")]
 pub fn start_timer(hwnd: HWND, id: u32, interval: u32) {
-    use winapi::um::winuser::SetTimer;
+    use super::backend::SetTimer;
     use winapi::shared::basetsd::UINT_PTR;
 
     unsafe {
@@ -127,23 +137,70 @@ pub fn set_style(handle: HWND, style: u32) {
 }
 
 pub fn send_message(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
-    unsafe { ::winapi::um::winuser::SendMessageW(hwnd, msg, w, l) }
+    unsafe { super::backend::SendMessageW(hwnd, msg, w, l) }
 }
 
 pub fn post_message(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) {
-    unsafe { ::winapi::um::winuser::PostMessageW(hwnd, msg, w, l) };
+    unsafe { super::backend::PostMessageW(hwnd, msg, w, l) };
 }
 
 pub unsafe fn set_focus(handle: HWND) {
-    ::winapi::um::winuser::SetFocus(handle);
+    super::backend::SetFocus(handle);
 }
 
 pub unsafe fn get_focus(handle: HWND) -> bool {
-    ::winapi::um::winuser::GetFocus() == handle
+    super::backend::GetFocus() == handle
+}
+
+/// Capture the mouse input. Used by controls that can be dragged.
+pub unsafe fn set_capture(handle: HWND) {
+    super::backend::SetCapture(handle);
+}
+
+pub unsafe fn release_capture() {
+    super::backend::ReleaseCapture();
+}
+
+/// Sets the content size, the page size and the position of one of the window scrollbars (`SB_HORZ` or `SB_VERT`).
+/// The scrollbar is hidden if the page is bigger than the content.
+pub unsafe fn set_window_scroll(handle: HWND, bar: UINT, content: u32, page: u32, pos: u32) {
+    use super::backend::SetScrollInfo;
+    use winapi::um::winuser::{SCROLLINFO, SIF_RANGE, SIF_PAGE, SIF_POS};
+
+    let info = SCROLLINFO {
+        cbSize: mem::size_of::<SCROLLINFO>() as UINT,
+        fMask: SIF_RANGE | SIF_PAGE | SIF_POS,
+        nMin: 0,
+        nMax: content.saturating_sub(1) as c_int,
+        nPage: page,
+        nPos: pos as c_int,
+        nTrackPos: 0,
+    };
+
+    SetScrollInfo(handle, bar as c_int, &info, 1);
+}
+
+/// Returns the position of the thumb of one of the window scrollbars (`SB_HORZ` or `SB_VERT`) while it is dragged by the user
+pub unsafe fn get_window_scroll_track(handle: HWND, bar: UINT) -> u32 {
+    use super::backend::GetScrollInfo;
+    use winapi::um::winuser::{SCROLLINFO, SIF_TRACKPOS};
+
+    let mut info: SCROLLINFO = mem::zeroed();
+    info.cbSize = mem::size_of::<SCROLLINFO>() as UINT;
+    info.fMask = SIF_TRACKPOS;
+    GetScrollInfo(handle, bar as c_int, &mut info);
+
+    info.nTrackPos.max(0) as u32
+}
+
+/// Sets the mouse cursor to one of the system cursors (ex: `IDC_SIZEWE`)
+pub unsafe fn set_system_cursor(cursor: winapi::shared::ntdef::LPCWSTR) {
+    use super::backend::{SetCursor, LoadCursorW};
+    SetCursor(LoadCursorW(ptr::null_mut(), cursor));
 }
 
 pub unsafe fn get_window_text(handle: HWND) -> String {
-    use winapi::um::winuser::{GetWindowTextW, GetWindowTextLengthW};
+    use super::backend::{GetWindowTextW, GetWindowTextLengthW};
 
     let mut buffer_size = GetWindowTextLengthW(handle) as usize;
     if buffer_size == 0 { return String::new(); }
@@ -160,14 +217,14 @@ pub unsafe fn get_window_text(handle: HWND) -> String {
 }
 
 pub unsafe fn set_window_text<'a>(handle: HWND, text: &'a str) {
-    use winapi::um::winuser::{SetWindowTextW};
+    use super::backend::SetWindowTextW;
 
     let text = to_utf16(text);
     SetWindowTextW(handle, text.as_ptr());
 }
 
 pub unsafe fn set_window_position(handle: HWND, x: i32, y: i32) {
-    use winapi::um::winuser::SetWindowPos;
+    use super::backend::SetWindowPos;
     use winapi::um::winuser::{SWP_NOZORDER, SWP_NOSIZE, SWP_NOACTIVATE};
 
     let (x, y) = high_dpi::logical_to_physical(x, y);
@@ -175,7 +232,7 @@ pub unsafe fn set_window_position(handle: HWND, x: i32, y: i32) {
 }
 
 pub unsafe fn get_window_position(handle: HWND) -> (i32, i32) {
-    use winapi::um::winuser::{GetWindowRect, ScreenToClient, GetParent};
+    use super::backend::{GetWindowRect, ScreenToClient, GetParent};
     use winapi::shared::windef::{RECT, POINT};
     
     let mut r: RECT = mem::zeroed();
@@ -194,15 +251,15 @@ pub unsafe fn get_window_position(handle: HWND) -> (i32, i32) {
 }
 
 pub unsafe fn set_window_size(handle: HWND, w: u32, h: u32, fix: bool) {
-    use winapi::um::winuser::{SetWindowPos, AdjustWindowRectEx, GetWindowLongW};
+    use super::backend::{SetWindowPos, AdjustWindowRectEx};
     use winapi::um::winuser::{SWP_NOZORDER, SWP_NOMOVE, SWP_NOACTIVATE, SWP_NOCOPYBITS, GWL_STYLE, GWL_EXSTYLE};
     use winapi::shared::windef::RECT;
 
     let (mut w, mut h) = high_dpi::logical_to_physical(w as i32, h as i32);
 
     if fix {
-        let flags = GetWindowLongW(handle, GWL_STYLE) as u32;
-        let ex_flags = GetWindowLongW(handle, GWL_EXSTYLE) as u32;
+        let flags = get_window_long(handle, GWL_STYLE) as u32;
+        let ex_flags = get_window_long(handle, GWL_EXSTYLE) as u32;
         let mut rect = RECT {left: 0, top: 0, right: w, bottom: h};
         AdjustWindowRectEx(&mut rect, flags, 0, ex_flags);
 
@@ -223,7 +280,7 @@ pub unsafe fn get_window_physical_size(handle: HWND) -> (u32, u32) {
 }
 
 unsafe fn get_window_size_impl(handle: HWND, return_physical: bool) -> (u32, u32) {
-    use winapi::um::winuser::GetClientRect;
+    use super::backend::GetClientRect;
     use winapi::shared::windef::RECT;
     
     let mut r: RECT = mem::zeroed();
@@ -239,7 +296,7 @@ unsafe fn get_window_size_impl(handle: HWND, return_physical: bool) -> (u32, u32
 }
 
 pub unsafe fn set_window_visibility(handle: HWND, visible: bool) {
-    use winapi::um::winuser::ShowWindow;
+    use super::backend::ShowWindow;
     use winapi::um::winuser::{SW_HIDE, SW_SHOW};
 
     let visible = if visible { SW_SHOW } else { SW_HIDE };
@@ -247,7 +304,7 @@ pub unsafe fn set_window_visibility(handle: HWND, visible: bool) {
 }
 
 pub unsafe fn get_window_visibility(handle: HWND) -> bool {
-    use winapi::um::winuser::IsWindowVisible;
+    use super::backend::IsWindowVisible;
     IsWindowVisible(handle) != 0
 }
 
@@ -260,7 +317,7 @@ pub unsafe fn get_window_enabled(handle: HWND) -> bool {
 
 pub unsafe fn set_window_enabled(handle: HWND, enabled: bool) {
     use winapi::um::winuser::{GWL_STYLE, WS_DISABLED};
-    use winapi::um::winuser::{UpdateWindow, InvalidateRect};
+    use super::backend::{UpdateWindow, InvalidateRect};
 
     let old_style = get_window_long(handle, GWL_STYLE) as usize;
     if enabled {
@@ -276,17 +333,15 @@ pub unsafe fn set_window_enabled(handle: HWND, enabled: bool) {
 
 #[cfg(feature = "tabs")]
 pub unsafe fn get_window_class_name(handle: HWND) -> String {
-    use std::ffi::OsString;
-    use std::os::windows::ffi::OsStringExt;
     use winapi::shared::ntdef::WCHAR;
-    use winapi::um::winuser::GetClassNameW;
+    use super::backend::GetClassNameW;
 
     let mut class_name_raw: Vec<WCHAR> = Vec::with_capacity(100); 
     class_name_raw.set_len(100);
 
     let count = GetClassNameW(handle, class_name_raw.as_mut_ptr(), 100) as usize;
     
-    OsString::from_wide(&class_name_raw[..count]).into_string().unwrap_or("".to_string())
+    String::from_utf16(&class_name_raw[..count]).unwrap_or("".to_string())
 }
 
 #[cfg(target_arch = "x86")] use winapi::shared::ntdef::LONG;
@@ -295,23 +350,23 @@ pub unsafe fn get_window_class_name(handle: HWND) -> String {
 #[inline(always)]
 #[cfg(target_arch = "x86_64")]
 pub fn get_window_long(handle: HWND, index: c_int) -> LONG_PTR {
-    unsafe{ ::winapi::um::winuser::GetWindowLongPtrW(handle, index) }
+    unsafe{ super::backend::GetWindowLongPtrW(handle, index) }
 }
 
 #[inline(always)]
 #[cfg(target_arch = "x86")]
 pub fn get_window_long(handle: HWND, index: c_int) -> LONG {
-    unsafe { ::winapi::um::winuser::GetWindowLongW(handle, index) }
+    unsafe { super::backend::GetWindowLongW(handle, index) }
 }
 
 #[inline(always)]
 #[cfg(target_arch = "x86_64")]
 pub fn set_window_long(handle: HWND, index: c_int, v: usize) {
-    unsafe{ ::winapi::um::winuser::SetWindowLongPtrW(handle, index, v as LONG_PTR); }
+    unsafe{ super::backend::SetWindowLongPtrW(handle, index, v as LONG_PTR); }
 }
 
 #[inline(always)]
 #[cfg(target_arch = "x86")]
 pub fn set_window_long(handle: HWND, index: c_int, v: usize) {
-    unsafe { ::winapi::um::winuser::SetWindowLongW(handle, index, v as LONG); }
+    unsafe { super::backend::SetWindowLongW(handle, index, v as LONG); }
 }